@@ -1,21 +1,104 @@
 use crate::api::error::AuthError;
-use crate::api::models::{AuthRequest, RegisterRequest, TokenResponse};
+use crate::api::models::{AuthRequest, KdfParams, RegisterRequest, TokenResponse};
+use crate::api::server::api_base_url;
 use reqwest::Client;
 use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
 
-const API_BASE_URL: &str = "https://back.reetui.hackclub.app";
+/// How a password gets turned into what the server sees. Accounts made before the server
+/// supported salts only know the bare SHA-256.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PasswordScheme {
+    LegacySha256,
+    Pbkdf2Sha256 { salt: String, iterations: u32 },
+}
+
+impl PasswordScheme {
+    fn from_params(params: KdfParams) -> Self {
+        match params.algorithm.as_str() {
+            "pbkdf2-sha256" if params.iterations > 0 => PasswordScheme::Pbkdf2Sha256 {
+                salt: params.salt,
+                iterations: params.iterations,
+            },
+            _ => PasswordScheme::LegacySha256,
+        }
+    }
+
+    fn params(&self) -> Option<KdfParams> {
+        match self {
+            PasswordScheme::LegacySha256 => None,
+            PasswordScheme::Pbkdf2Sha256 { salt, iterations } => Some(KdfParams {
+                algorithm: "pbkdf2-sha256".to_string(),
+                salt: salt.clone(),
+                iterations: *iterations,
+            }),
+        }
+    }
+}
+
+/// Hex of the hashed password, the one place register, login and change-password hash from.
+pub fn hash_password(password: &str, scheme: &PasswordScheme) -> String {
+    match scheme {
+        PasswordScheme::LegacySha256 => format!("{:x}", Sha256::digest(password.as_bytes())),
+        PasswordScheme::Pbkdf2Sha256 { salt, iterations } => {
+            let mut key = [0u8; 32];
+            pbkdf2::pbkdf2_hmac::<Sha256>(
+                password.as_bytes(),
+                salt.as_bytes(),
+                *iterations,
+                &mut key,
+            );
+            key.iter().map(|b| format!("{:02x}", b)).collect()
+        }
+    }
+}
+
+/// Asks the server how this user's password is hashed. Only a server without the endpoint
+/// (a 404) gets the legacy hash, any other failure is an error rather than a wrong guess.
+pub async fn fetch_password_scheme(
+    client: &Client,
+    username: &str,
+) -> Result<PasswordScheme, AuthError> {
+    fetch_password_scheme_from(client, &api_base_url(), username).await
+}
+
+pub async fn fetch_password_scheme_from(
+    client: &Client,
+    base_url: &str,
+    username: &str,
+) -> Result<PasswordScheme, AuthError> {
+    let endpoint = format!("{}/auth/kdf", base_url);
+    let response = client
+        .get(&endpoint)
+        .query(&[("username", username)])
+        .send()
+        .await?;
+    let status = response.status();
+    if status == StatusCode::NOT_FOUND {
+        return Ok(PasswordScheme::LegacySha256);
+    }
+    if !status.is_success() {
+        return Err(AuthError::ServerError(status));
+    }
+    let params = response.json::<KdfParams>().await?;
+    Ok(PasswordScheme::from_params(params))
+}
 
+/// Takes the plain password, hashing it the way the server asks for.
 pub async fn register(
     client: &Client,
     username: &str,
     password: &str,
     icon: &str,
 ) -> Result<TokenResponse, AuthError> {
-    let endpoint = format!("{}/auth/register", API_BASE_URL);
+    let scheme = fetch_password_scheme(client, username).await?;
+    let password = &hash_password(password, &scheme);
+    let endpoint = format!("{}/auth/register", api_base_url());
     let payload = RegisterRequest {
         username,
         password,
         icon,
+        kdf: scheme.params(),
     };
     let response = client.post(&endpoint).json(&payload).send().await?;
     let status = response.status();
@@ -32,12 +115,15 @@ pub async fn register(
 }
 // 󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻󱑻
 // noice
+/// Takes the plain password, hashing it the way the server asks for.
 pub async fn login(
     client: &Client,
     username: &str,
     password: &str,
 ) -> Result<TokenResponse, AuthError> {
-    let endpoint = format!("{}/auth/login", API_BASE_URL);
+    let scheme = fetch_password_scheme(client, username).await?;
+    let password = &hash_password(password, &scheme);
+    let endpoint = format!("{}/auth/login", api_base_url());
     let payload = AuthRequest { username, password };
     let response = client.post(&endpoint).json(&payload).send().await?;
     let status = response.status();
@@ -0,0 +1,220 @@
+use crate::api::auth_api::{self, PasswordScheme};
+use crate::api::error::AuthError;
+use crate::api::file_api::{self, ExpectedChecksum, FileApiError};
+use crate::api::link_preview::{self, LinkPreview};
+use crate::api::models::TokenResponse;
+use reqwest::Client;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Errors that say whether trying the same request again could go differently.
+pub trait Transient {
+    /// Connection drops, timeouts and 5xx are worth another go, a 4xx never is.
+    fn is_transient(&self) -> bool;
+}
+
+fn is_transient_reqwest(e: &reqwest::Error) -> bool {
+    e.is_connect()
+        || e.is_timeout()
+        || e.is_request()
+        || e.status().is_some_and(|s| s.is_server_error())
+}
+
+impl Transient for FileApiError {
+    fn is_transient(&self) -> bool {
+        match self {
+            FileApiError::RequestError(e) => is_transient_reqwest(e),
+            FileApiError::RequestFailedStatus(status) => status.is_server_error(),
+            FileApiError::IoError(_)
+            | FileApiError::ChecksumMismatch(_)
+            | FileApiError::Other(_) => false,
+        }
+    }
+}
+
+impl Transient for AuthError {
+    fn is_transient(&self) -> bool {
+        match self {
+            AuthError::RequestFailed(e) => is_transient_reqwest(e),
+            AuthError::ServerError(status) => status.is_server_error(),
+            AuthError::UsernameTaken | AuthError::Unauthorized => false,
+        }
+    }
+}
+
+/// Capped exponential backoff, `max_attempts` counts the first try.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(4),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// How long to wait after the given failed attempt (1 for the first one).
+    pub fn delay_after(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+/// The app's single HTTP client. Cheap to clone: clones share reqwest's connection pool, and
+/// timeouts, headers and retries are set here once. The base URL comes from `api::server`,
+/// fixed at startup.
+#[derive(Clone, Debug)]
+pub struct ApiClient {
+    http: Client,
+    token: Option<String>,
+    retry_policy: RetryPolicy,
+    /// "Retrying download 2/3..." lines, main turns them into notifications
+    retry_notices: Option<mpsc::UnboundedSender<String>>,
+}
+
+impl Default for ApiClient {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl ApiClient {
+    pub fn new(token: Option<String>) -> Self {
+        let http = Client::builder()
+            .user_agent(concat!("ReeTUI/", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self {
+            http,
+            token,
+            retry_policy: RetryPolicy::default(),
+            retry_notices: None,
+        }
+    }
+
+    pub fn set_token(&mut self, token: Option<String>) {
+        self.token = token;
+    }
+
+    pub fn set_retry_policy(&mut self, retry_policy: RetryPolicy) {
+        self.retry_policy = retry_policy;
+    }
+
+    pub fn set_retry_notices(&mut self, retry_notices: mpsc::UnboundedSender<String>) {
+        self.retry_notices = Some(retry_notices);
+    }
+
+    pub fn http(&self) -> &Client {
+        &self.http
+    }
+
+    /// Runs `request` until it works, fails for good, or the policy runs out of attempts.
+    /// Only for requests that are safe to send twice.
+    pub async fn retry<T, E, F, Fut>(&self, what: &str, mut request: F) -> Result<T, E>
+    where
+        E: Transient,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match request().await {
+                Err(e) if e.is_transient() && attempt < self.retry_policy.max_attempts => {
+                    tokio::time::sleep(self.retry_policy.delay_after(attempt)).await;
+                    attempt += 1;
+                    if let Some(notices) = &self.retry_notices {
+                        let _ = notices.send(format!(
+                            "Retrying {} {}/{}...",
+                            what, attempt, self.retry_policy.max_attempts
+                        ));
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // logging in changes nothing on the server, so it may be retried, registering may not
+    pub async fn login(&self, username: &str, password: &str) -> Result<TokenResponse, AuthError> {
+        self.retry("login", || auth_api::login(&self.http, username, password))
+            .await
+    }
+
+    pub async fn register(
+        &self,
+        username: &str,
+        password: &str,
+        icon: &str,
+    ) -> Result<TokenResponse, AuthError> {
+        auth_api::register(&self.http, username, password, icon).await
+    }
+
+    pub async fn password_scheme(&self, username: &str) -> Result<PasswordScheme, AuthError> {
+        auth_api::fetch_password_scheme(&self.http, username).await
+    }
+
+    // not retried, a preview that doesn't come right away isn't worth waiting on. The token is
+    // never sent, these go to whatever site the link points at
+    pub async fn link_preview(&self, url: &str) -> Result<LinkPreview, String> {
+        link_preview::fetch_link_preview(&self.http, url).await
+    }
+
+    pub async fn upload_file(
+        &self,
+        channel_id: &str,
+        file_path: PathBuf,
+        progress_sender: mpsc::UnboundedSender<(String, u8)>,
+    ) -> Result<String, FileApiError> {
+        let token = self.token.as_deref().ok_or_else(|| {
+            FileApiError::Other("Not logged in, no token to upload with".to_string())
+        })?;
+        // shared by the attempts, a retry resumes where the last one got to. Gone once this
+        // returns, uploading the file again starts a new session
+        let session = tokio::sync::Mutex::new(None);
+        let (session, file_path, progress_sender) = (&session, &file_path, &progress_sender);
+        self.retry("upload", move || async move {
+            file_api::upload_file_chunked(
+                &self.http,
+                token,
+                channel_id,
+                file_path.clone(),
+                progress_sender.clone(),
+                &mut *session.lock().await,
+            )
+            .await
+        })
+        .await
+    }
+
+    pub async fn download_file(
+        &self,
+        file_id: &str,
+        file_name: &str,
+        progress_sender: mpsc::UnboundedSender<(String, u8)>,
+        destination: Option<&Path>,
+        expected: &ExpectedChecksum,
+    ) -> Result<PathBuf, FileApiError> {
+        self.retry("download", || {
+            file_api::download_file(
+                &self.http,
+                file_id,
+                file_name,
+                progress_sender.clone(),
+                destination,
+                expected,
+            )
+        })
+        .await
+    }
+}
@@ -1,18 +1,21 @@
+use crate::api::models::BroadcastMessage;
+use crate::api::server::api_base_url;
 use futures_util::StreamExt;
-use reqwest::{multipart, Client};
+use reqwest::{multipart, Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fmt;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs::File;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc;
 
-const API_BASE_URL: &str = "https://back.reetui.hackclub.app";
-
 #[derive(Debug)]
 pub enum FileApiError {
     RequestFailedStatus(reqwest::StatusCode),
     RequestError(reqwest::Error),
     IoError(std::io::Error),
+    ChecksumMismatch(String),
     Other(String),
 }
 
@@ -24,6 +27,7 @@ impl fmt::Display for FileApiError {
             }
             FileApiError::RequestError(e) => write!(f, "Request error: {}\nTell to the owner (Youssef 󰊤 :'YoussefDevPro')\nIn the repo 󰌷 https://github.com/YoussefDevPro/ReeTUI", e),
             FileApiError::IoError(e) => write!(f, "IO error: {}\nTell to the owner (Youssef 󰊤 :'YoussefDevPro')\nIn the repo 󰌷 https://github.com/YoussefDevPro/ReeTUI", e),
+            FileApiError::ChecksumMismatch(e) => write!(f, "The downloaded file doesn't match what the server sent: {}", e),
             FileApiError::Other(e) => write!(f, "Error: {}\nTell to the owner (Youssef 󰊤 :'YoussefDevPro')\nIn the repo 󰌷 https://github.com/YoussefDevPro/ReeTUI", e),
         }
     }
@@ -49,6 +53,26 @@ pub async fn upload_file(
     channel_id: &str,
     file_path: PathBuf,
     progress_sender: mpsc::UnboundedSender<(String, u8)>,
+) -> Result<String, FileApiError> {
+    upload_file_to(
+        client,
+        &api_base_url(),
+        token,
+        channel_id,
+        file_path,
+        progress_sender,
+    )
+    .await
+}
+
+/// `upload_file` against the server at `base_url`.
+pub async fn upload_file_to(
+    client: &Client,
+    base_url: &str,
+    token: &str,
+    channel_id: &str,
+    file_path: PathBuf,
+    progress_sender: mpsc::UnboundedSender<(String, u8)>,
 ) -> Result<String, FileApiError> {
     let file_name = file_path
         .file_name()
@@ -68,7 +92,7 @@ pub async fn upload_file(
         .part("file_extension", multipart::Part::text(file_extension));
 
     let response = client
-        .post(&format!("{}/files/upload/{}", API_BASE_URL, channel_id))
+        .post(format!("{}/files/upload/{}", base_url, channel_id))
         .bearer_auth(token)
         .multipart(form)
         .send()
@@ -85,27 +109,241 @@ pub async fn upload_file(
     }
 }
 
+/// The server side of a chunked upload. Kept across retries so a retry carries on from the
+/// last chunk the server acknowledged instead of sending the file again. Only in memory, an
+/// upload started again after a restart (or a reconnect) begins from the first chunk.
+#[derive(Debug, Clone, Deserialize)]
+pub struct UploadSession {
+    pub upload_id: String,
+    pub chunk_size: u64,
+    /// bytes the server has acknowledged so far
+    pub received: u64,
+}
+
+#[derive(Deserialize)]
+struct UploadStatus {
+    received: u64,
+}
+
+const DEFAULT_CHUNK_SIZE: u64 = 1024 * 1024;
+
+/// Uploads in chunks when the server supports it, otherwise falls back to `upload_file`.
+/// Pass the same `session` on a retry to resume.
+pub async fn upload_file_chunked(
+    client: &Client,
+    token: &str,
+    channel_id: &str,
+    file_path: PathBuf,
+    progress_sender: mpsc::UnboundedSender<(String, u8)>,
+    session: &mut Option<UploadSession>,
+) -> Result<String, FileApiError> {
+    upload_file_chunked_to(
+        client,
+        &api_base_url(),
+        token,
+        channel_id,
+        file_path,
+        progress_sender,
+        session,
+    )
+    .await
+}
+
+/// `upload_file_chunked` against the server at `base_url`.
+pub async fn upload_file_chunked_to(
+    client: &Client,
+    base_url: &str,
+    token: &str,
+    channel_id: &str,
+    file_path: PathBuf,
+    progress_sender: mpsc::UnboundedSender<(String, u8)>,
+    session: &mut Option<UploadSession>,
+) -> Result<String, FileApiError> {
+    let file_name = file_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| FileApiError::Other("Invalid file name ".to_string()))?
+        .to_string();
+    let file_extension = file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+        .to_string();
+    let file = tokio::fs::read(&file_path).await?;
+    let file_size = file.len() as u64;
+
+    if let Some(existing) = session.as_mut() {
+        // what the server really has, the last chunk may have made it even if its answer didn't
+        let url = format!("{}/files/upload/{}", base_url, existing.upload_id);
+        let response = client.get(url).bearer_auth(token).send().await?;
+        match response.status() {
+            status if status.is_success() => {
+                existing.received = response.json::<UploadStatus>().await?.received;
+            }
+            // the server forgot about it, start over
+            StatusCode::NOT_FOUND => *session = None,
+            status => return Err(FileApiError::RequestFailedStatus(status)),
+        }
+    }
+
+    if session.is_none() {
+        let url = format!("{}/files/upload/{}/init", base_url, channel_id);
+        let response = client
+            .post(url)
+            .bearer_auth(token)
+            .json(&serde_json::json!({
+                "file_name": file_name,
+                "file_extension": file_extension,
+                "file_size": file_size,
+                "chunk_size": DEFAULT_CHUNK_SIZE,
+            }))
+            .send()
+            .await?;
+        match response.status() {
+            status if status.is_success() => *session = Some(response.json().await?),
+            StatusCode::NOT_FOUND
+            | StatusCode::METHOD_NOT_ALLOWED
+            | StatusCode::NOT_IMPLEMENTED => {
+                return upload_file_to(
+                    client,
+                    base_url,
+                    token,
+                    channel_id,
+                    file_path,
+                    progress_sender,
+                )
+                .await;
+            }
+            status => return Err(FileApiError::RequestFailedStatus(status)),
+        }
+    }
+
+    let current = session.as_mut().expect("session was just set up");
+    while current.received < file_size {
+        let start = current.received as usize;
+        let end = (start + current.chunk_size.max(1) as usize).min(file.len());
+        let url = format!("{}/files/upload/{}/chunk", base_url, current.upload_id);
+        let response = client
+            .put(url)
+            .query(&[("offset", start)])
+            .bearer_auth(token)
+            .body(file[start..end].to_vec())
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(FileApiError::RequestFailedStatus(response.status()));
+        }
+        let received = response.json::<UploadStatus>().await?.received;
+        if received <= current.received {
+            return Err(FileApiError::Other(
+                "The server didn't take the chunk".to_string(),
+            ));
+        }
+        current.received = received;
+        let progress = ((received as f64 / file_size as f64) * 100.0).min(99.0) as u8;
+        let _ = progress_sender.send((file_name.clone(), progress));
+    }
+
+    let url = format!("{}/files/upload/{}/complete", base_url, current.upload_id);
+    let response = client.post(url).bearer_auth(token).send().await?;
+    if !response.status().is_success() {
+        return Err(FileApiError::RequestFailedStatus(response.status()));
+    }
+    let file_id = response.text().await?;
+    *session = None;
+    let _ = progress_sender.send((file_id.clone(), 100));
+    Ok(file_id)
+}
+
+/// What the server told us about a file, checked once the download is on disk.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExpectedChecksum {
+    pub sha256: Option<String>,
+    pub size: Option<u64>,
+}
+
+impl ExpectedChecksum {
+    pub fn from_message(message: &BroadcastMessage) -> Self {
+        Self {
+            sha256: message.file_sha256.clone(),
+            size: message.file_size_bytes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sha256.is_none() && self.size.is_none()
+    }
+
+    /// Hash first, exact size when there is no hash, `Content-Length` as a last resort.
+    pub fn verify(
+        &self,
+        sha256: &str,
+        size: u64,
+        content_length: Option<u64>,
+    ) -> Result<(), FileApiError> {
+        if let Some(expected) = &self.sha256 {
+            if !expected.eq_ignore_ascii_case(sha256) {
+                return Err(FileApiError::ChecksumMismatch(format!(
+                    "sha256 is {}, expected {}",
+                    sha256, expected
+                )));
+            }
+        }
+        if let Some(expected) = self.size.or(content_length) {
+            if expected != size {
+                return Err(FileApiError::ChecksumMismatch(format!(
+                    "got {} bytes, expected {}",
+                    size, expected
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `downloads/` in the directory the app was started from, where saved files go by default.
+pub fn default_download_dir() -> std::io::Result<PathBuf> {
+    Ok(std::env::current_dir()?.join("downloads"))
+}
+
+/// True when `path` already holds this file: same hash, or same size when there is no hash,
+/// or just the same name when the server told us nothing.
+pub async fn matches_local_file(path: &Path, expected: &ExpectedChecksum) -> bool {
+    let Ok(metadata) = tokio::fs::metadata(path).await else {
+        return false;
+    };
+    if let Some(sha256) = &expected.sha256 {
+        return match tokio::fs::read(path).await {
+            Ok(data) => format!("{:x}", Sha256::digest(&data)).eq_ignore_ascii_case(sha256),
+            Err(_) => false,
+        };
+    }
+    expected.size.is_none_or(|size| size == metadata.len())
+}
+
+/// Saves the file into `destination` (under a fresh name if one is taken), or into the temp
+/// dir when there is no destination.
 pub async fn download_file(
     client: &Client,
     file_id: &str,
     file_name: &str,
     progress_sender: mpsc::UnboundedSender<(String, u8)>,
-    save_to_downloads: bool,
+    destination: Option<&Path>,
+    expected: &ExpectedChecksum,
 ) -> Result<PathBuf, FileApiError> {
     let response = client
-        .get(&format!("{}/files/download/{}", API_BASE_URL, file_id))
+        .get(&format!("{}/files/download/{}", api_base_url(), file_id))
         .send()
         .await?;
 
     if response.status().is_success() {
-        let total_size = response.content_length().unwrap_or(0);
+        let content_length = response.content_length();
+        let total_size = content_length.unwrap_or(0);
         let mut downloaded_size: u64 = 0;
         let mut stream = response.bytes_stream();
 
-        let file_path = if save_to_downloads {
-            let current_dir = std::env::current_dir()?;
-            let downloads_dir = current_dir.join("downloads");
-            tokio::fs::create_dir_all(&downloads_dir).await?;
+        let file_path = if let Some(downloads_dir) = destination {
+            tokio::fs::create_dir_all(downloads_dir).await?;
 
             let mut unique_file_path = downloads_dir.join(file_name);
             let mut counter = 0;
@@ -130,21 +368,38 @@ pub async fn download_file(
             temp_dir.join(file_name)
         };
         let mut file = File::create(&file_path).await?;
+        let mut hasher = Sha256::new();
 
-        while let Some(chunk_result) = stream.next().await {
-            let chunk = chunk_result?;
-            file.write_all(&chunk).await?;
-            downloaded_size += chunk.len() as u64;
-            let progress = (((downloaded_size as f64 / total_size as f64) * 100.0) as u8).min(100);
-            if progress_sender
-                .send((file_id.to_string(), progress))
-                .is_err()
-            {
-                // The receiver has been dropped, so we can stop sending progress updates.
-                break;
+        let streamed: Result<(), FileApiError> = async {
+            while let Some(chunk_result) = stream.next().await {
+                let chunk = chunk_result?;
+                file.write_all(&chunk).await?;
+                hasher.update(&chunk);
+                downloaded_size += chunk.len() as u64;
+                let progress =
+                    (((downloaded_size as f64 / total_size as f64) * 100.0) as u8).min(100);
+                // nobody watching the progress anymore is fine, the file still has to be whole
+                let _ = progress_sender.send((file_id.to_string(), progress));
             }
+            file.flush().await?;
+            Ok(())
         }
+        .await;
         drop(progress_sender);
+
+        let verified = streamed.and_then(|_| {
+            expected.verify(
+                &format!("{:x}", hasher.finalize()),
+                downloaded_size,
+                content_length,
+            )
+        });
+        if let Err(e) = verified {
+            // never leave a half-written or corrupted file lying around
+            drop(file);
+            let _ = tokio::fs::remove_file(&file_path).await;
+            return Err(e);
+        }
         Ok(file_path)
     } else {
         Err(FileApiError::RequestFailedStatus(response.status()))
@@ -0,0 +1,137 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::Client;
+use std::time::Duration;
+
+// pages are read up to here, the <head> with the metadata is always well within it
+const MAX_PREVIEW_BYTES: usize = 256 * 1024;
+const PREVIEW_TIMEOUT: Duration = Duration::from_secs(5);
+
+lazy_static! {
+    static ref URL_REGEX: Regex = Regex::new(r"<?https?://[^\s<>]+>?").unwrap();
+    static ref META_REGEX: Regex = Regex::new(r"(?is)<meta\s[^>]*>").unwrap();
+    static ref ATTRIBUTE_REGEX: Regex =
+        Regex::new(r#"(?is)([a-z:_-]+)\s*=\s*(?:"([^"]*)"|'([^']*)')"#).unwrap();
+    static ref TITLE_REGEX: Regex = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap();
+}
+
+/// The card shown under a message that links somewhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkPreview {
+    pub site: String,
+    pub title: String,
+    pub description: Option<String>,
+}
+
+/// The first link of a message worth a preview. Links wrapped in `<...>` are the sender saying
+/// they don't want one.
+pub fn previewable_url(content: &str) -> Option<String> {
+    URL_REGEX
+        .find_iter(content)
+        .map(|m| m.as_str())
+        .find(|url| !url.starts_with('<'))
+        .map(|url| {
+            url.trim_end_matches('>')
+                .trim_end_matches(['.', ',', ')', '!', '?'])
+                .to_string()
+        })
+}
+
+/// OpenGraph title/description/site name from a page, falling back to its `<title>` and the
+/// host. None when the page has no title at all.
+pub fn parse_link_preview(url: &str, html: &str) -> Option<LinkPreview> {
+    let mut title = None;
+    let mut description = None;
+    let mut site = None;
+    for tag in META_REGEX.find_iter(html) {
+        let mut key = None;
+        let mut content = None;
+        for attribute in ATTRIBUTE_REGEX.captures_iter(tag.as_str()) {
+            let value = attribute
+                .get(2)
+                .or(attribute.get(3))
+                .map_or("", |v| v.as_str());
+            match attribute[1].to_ascii_lowercase().as_str() {
+                "property" | "name" => key = Some(value.to_ascii_lowercase()),
+                "content" => content = Some(decode_entities(value)),
+                _ => {}
+            }
+        }
+        let (Some(key), Some(content)) = (key, content) else {
+            continue;
+        };
+        match key.as_str() {
+            "og:title" => title = title.or(Some(content)),
+            "og:description" | "description" => description = description.or(Some(content)),
+            "og:site_name" => site = site.or(Some(content)),
+            _ => {}
+        }
+    }
+    let title = title
+        .or_else(|| {
+            TITLE_REGEX
+                .captures(html)
+                .map(|c| decode_entities(c[1].trim()))
+        })
+        .filter(|t| !t.is_empty())?;
+    let site = site
+        .or_else(|| {
+            url::Url::parse(url).ok().and_then(|u| {
+                u.host_str()
+                    .map(|h| h.trim_start_matches("www.").to_string())
+            })
+        })
+        .unwrap_or_default();
+    Some(LinkPreview {
+        site,
+        title: squash_whitespace(&title),
+        description: description
+            .map(|d| squash_whitespace(&d))
+            .filter(|d| !d.is_empty()),
+    })
+}
+
+fn squash_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+/// Fetches the start of an HTML page and reads its preview out of it. The error is only for
+/// logging, a failed preview just leaves the raw link.
+pub async fn fetch_link_preview(client: &Client, url: &str) -> Result<LinkPreview, String> {
+    let mut response = client
+        .get(url)
+        .timeout(PREVIEW_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("{} answered {}", url, response.status()));
+    }
+    let is_html = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("text/html"));
+    if !is_html {
+        return Err(format!("{} isn't a web page", url));
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+        body.extend_from_slice(&chunk);
+        if body.len() >= MAX_PREVIEW_BYTES {
+            break;
+        }
+    }
+    parse_link_preview(url, &String::from_utf8_lossy(&body))
+        .ok_or_else(|| format!("{} has no title", url))
+}
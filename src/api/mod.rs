@@ -1,5 +1,12 @@
 pub mod auth_api;
+pub mod client;
 pub mod error;
 pub mod models;
+pub mod server;
+pub mod transport;
 pub mod websocket;
 pub mod file_api;
+pub mod link_preview;
+
+#[cfg(test)]
+mod tests;
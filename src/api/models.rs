@@ -12,6 +12,17 @@ pub struct RegisterRequest<'a> {
     pub username: &'a str,
     pub password: &'a str,
     pub icon: &'a str,
+    /// what the password was hashed with, the server keeps it for the account's logins
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<KdfParams>,
+}
+
+/// How the server wants a user's password hashed, from `GET /auth/kdf`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct KdfParams {
+    pub algorithm: String,
+    pub salt: String,
+    pub iterations: u32,
 }
 
 #[derive(Deserialize)]
@@ -28,6 +39,9 @@ pub struct ChannelCommand<'a> {
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct BroadcastMessage {
+    /// the server's id for the message, what /goto and permalinks refer to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     #[serde(default)]
     pub client_id: Option<String>,
     pub user: String,
@@ -49,6 +63,10 @@ pub struct BroadcastMessage {
     pub file_icon: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub file_size_mb: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_size_bytes: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_sha256: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_image: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -63,6 +81,14 @@ pub struct BroadcastMessage {
     pub gif_frames: Option<Vec<(String, Duration)>>,
 }
 
+impl BroadcastMessage {
+    /// Size of the attached file in bytes, the exact one when the server sent it.
+    pub fn file_size(&self) -> Option<u64> {
+        self.file_size_bytes
+            .or_else(|| self.file_size_mb.map(|mb| (mb * 1024.0 * 1024.0) as u64))
+    }
+}
+
 fn default_message_type() -> String {
     "text".to_string()
 }
@@ -80,6 +106,9 @@ pub struct Channel {
     pub id: String,
     pub name: String,
     pub icon: String,
+    /// created locally and still waiting for the server to confirm it
+    #[serde(skip)]
+    pub pending: bool,
 }
 
 #[derive(Deserialize, Debug, Clone)]
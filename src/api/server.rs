@@ -0,0 +1,48 @@
+use std::sync::{OnceLock, RwLock};
+
+const DEFAULT_API_BASE_URL: &str = "https://back.reetui.hackclub.app";
+const DEFAULT_WS_URL: &str = "wss://isock.reetui.hackclub.app";
+
+static SERVER_OVERRIDE: OnceLock<String> = OnceLock::new();
+static PROFILE_SERVER: RwLock<Option<String>> = RwLock::new(None);
+
+/// Points every request at `url` instead of the hosted server. Set once at startup from
+/// `--server`, later calls are ignored.
+pub fn set_server(url: &str) {
+    let _ = SERVER_OVERRIDE.set(url.trim_end_matches('/').to_string());
+}
+
+/// The server the active profile talks to, None goes back to the hosted one. `--server`
+/// still wins over it.
+pub fn set_profile_server(url: Option<&str>) {
+    if let Ok(mut server) = PROFILE_SERVER.write() {
+        *server = url.map(|url| url.trim_end_matches('/').to_string());
+    }
+}
+
+fn server_override() -> Option<String> {
+    SERVER_OVERRIDE
+        .get()
+        .cloned()
+        .or_else(|| PROFILE_SERVER.read().ok()?.clone())
+}
+
+pub fn api_base_url() -> String {
+    server_override().unwrap_or_else(|| DEFAULT_API_BASE_URL.to_string())
+}
+
+/// A self-hosted server serves the socket on the same host, so only the scheme changes.
+pub fn ws_url() -> String {
+    match server_override() {
+        Some(url) => {
+            if let Some(rest) = url.strip_prefix("https://") {
+                format!("wss://{}", rest)
+            } else if let Some(rest) = url.strip_prefix("http://") {
+                format!("ws://{}", rest)
+            } else {
+                url
+            }
+        }
+        None => DEFAULT_WS_URL.to_string(),
+    }
+}
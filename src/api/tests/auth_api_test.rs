@@ -0,0 +1,87 @@
+use crate::api::auth_api::{fetch_password_scheme_from, hash_password, PasswordScheme};
+use crate::api::error::AuthError;
+use crate::api::tests::mock_server::mock_server_with;
+
+fn pbkdf2(salt: &str, iterations: u32) -> PasswordScheme {
+    PasswordScheme::Pbkdf2Sha256 {
+        salt: salt.to_string(),
+        iterations,
+    }
+}
+
+#[test]
+fn test_legacy_hash_is_bare_sha256() {
+    assert_eq!(
+        hash_password("password", &PasswordScheme::LegacySha256),
+        "5e884898da28047151d0e56f8dc6292773603d0d6aabbdd62a11ef721d1542d8"
+    );
+}
+
+#[test]
+fn test_pbkdf2_matches_known_vector() {
+    // the well-known PBKDF2-HMAC-SHA256 test vector, 32 byte key
+    assert_eq!(
+        hash_password("password", &pbkdf2("salt", 1)),
+        "120fb6cffcf8b32c43e7225256c4f837a86548c92ccc35480805987cb70be17b"
+    );
+}
+
+#[test]
+fn test_pbkdf2_is_stable_for_the_same_salt() {
+    let scheme = pbkdf2("c0ffee", 1000);
+    assert_eq!(
+        hash_password("hunter2", &scheme),
+        hash_password("hunter2", &scheme)
+    );
+}
+
+#[test]
+fn test_pbkdf2_depends_on_salt_and_iterations() {
+    let base = hash_password("hunter2", &pbkdf2("c0ffee", 1000));
+    assert_ne!(base, hash_password("hunter2", &pbkdf2("decaf", 1000)));
+    assert_ne!(base, hash_password("hunter2", &pbkdf2("c0ffee", 1001)));
+    assert_ne!(
+        base,
+        hash_password("hunter2", &PasswordScheme::LegacySha256)
+    );
+}
+
+/// Answers with `status` and a json `body`.
+async fn kdf_server(status: u16, body: &'static str) -> String {
+    let (url, _) = mock_server_with(move |_| (status, body.to_string())).await;
+    url
+}
+
+#[tokio::test]
+async fn test_scheme_comes_from_the_server() {
+    let (url, requests) = mock_server_with(|_| {
+        (
+            200,
+            r#"{"algorithm":"pbkdf2-sha256","salt":"c0ffee","iterations":1000}"#.to_string(),
+        )
+    })
+    .await;
+    let scheme = fetch_password_scheme_from(&reqwest::Client::new(), &url, "ree").await;
+    assert_eq!(scheme.unwrap(), pbkdf2("c0ffee", 1000));
+
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(requests[0].path, "/auth/kdf?username=ree");
+    assert!(requests[0].body.is_empty());
+}
+
+#[tokio::test]
+async fn test_only_a_missing_endpoint_means_legacy() {
+    let url = kdf_server(404, "").await;
+    let scheme = fetch_password_scheme_from(&reqwest::Client::new(), &url, "ree").await;
+    assert_eq!(scheme.unwrap(), PasswordScheme::LegacySha256);
+
+    let url = kdf_server(500, "").await;
+    let scheme = fetch_password_scheme_from(&reqwest::Client::new(), &url, "ree").await;
+    assert!(matches!(scheme, Err(AuthError::ServerError(_))));
+
+    let url = kdf_server(200, "not json").await;
+    let scheme = fetch_password_scheme_from(&reqwest::Client::new(), &url, "ree").await;
+    assert!(matches!(scheme, Err(AuthError::RequestFailed(_))));
+}
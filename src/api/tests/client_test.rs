@@ -0,0 +1,76 @@
+use crate::api::client::{ApiClient, RetryPolicy};
+use crate::api::file_api::FileApiError;
+use crate::api::tests::mock_server::mock_server;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+fn fast_client() -> ApiClient {
+    let mut client = ApiClient::new(None);
+    client.set_retry_policy(RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(1),
+        max_delay: Duration::from_millis(5),
+    });
+    client
+}
+
+async fn get(client: &ApiClient, url: &str) -> Result<String, FileApiError> {
+    let response = client.http().get(url).send().await?;
+    if !response.status().is_success() {
+        return Err(FileApiError::RequestFailedStatus(response.status()));
+    }
+    Ok(response.text().await?)
+}
+
+#[tokio::test]
+async fn test_retries_server_errors_until_success() {
+    let (url, hits) = mock_server(vec![503, 502, 200]).await;
+    let client = fast_client();
+    let result = client.retry("test", || get(&client, &url)).await;
+    assert_eq!(result.unwrap(), "ok");
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_gives_up_after_max_attempts() {
+    let (url, hits) = mock_server(vec![500]).await;
+    let client = fast_client();
+    let result = client.retry("test", || get(&client, &url)).await;
+    assert!(matches!(result, Err(FileApiError::RequestFailedStatus(s)) if s.as_u16() == 500));
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_retried() {
+    let (url, hits) = mock_server(vec![404, 200]).await;
+    let client = fast_client();
+    let result = client.retry("test", || get(&client, &url)).await;
+    assert!(result.is_err());
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_retry_notices_count_attempts() {
+    let (url, _hits) = mock_server(vec![503, 200]).await;
+    let mut client = fast_client();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    client.set_retry_notices(tx);
+    client
+        .retry("download", || get(&client, &url))
+        .await
+        .unwrap();
+    assert_eq!(rx.recv().await.unwrap(), "Retrying download 2/3...");
+}
+
+#[test]
+fn test_backoff_doubles_and_caps() {
+    let policy = RetryPolicy {
+        max_attempts: 5,
+        base_delay: Duration::from_millis(100),
+        max_delay: Duration::from_millis(300),
+    };
+    assert_eq!(policy.delay_after(1), Duration::from_millis(100));
+    assert_eq!(policy.delay_after(2), Duration::from_millis(200));
+    assert_eq!(policy.delay_after(3), Duration::from_millis(300));
+    assert_eq!(policy.delay_after(30), Duration::from_millis(300));
+}
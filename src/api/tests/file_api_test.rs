@@ -0,0 +1,228 @@
+use crate::api::file_api::{
+    matches_local_file, upload_file_chunked_to, ExpectedChecksum, FileApiError, UploadSession,
+};
+use crate::api::tests::mock_server::{mock_server_with, MockRequest};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+#[test]
+fn test_matching_hash_and_size_pass() {
+    let expected = ExpectedChecksum {
+        sha256: Some(HELLO_SHA256.to_uppercase()),
+        size: Some(5),
+    };
+    assert!(expected.verify(HELLO_SHA256, 5, Some(5)).is_ok());
+}
+
+#[test]
+fn test_hash_mismatch_fails() {
+    let expected = ExpectedChecksum {
+        sha256: Some(HELLO_SHA256.to_string()),
+        size: None,
+    };
+    let result = expected.verify(&"0".repeat(64), 5, None);
+    assert!(matches!(result, Err(FileApiError::ChecksumMismatch(_))));
+}
+
+#[test]
+fn test_falls_back_to_size_without_hash() {
+    let expected = ExpectedChecksum {
+        sha256: None,
+        size: Some(10),
+    };
+    assert!(expected.verify(HELLO_SHA256, 5, Some(5)).is_err());
+    assert!(ExpectedChecksum::default()
+        .verify(HELLO_SHA256, 5, Some(5))
+        .is_ok());
+}
+
+#[test]
+fn test_truncated_download_fails_on_content_length() {
+    let result = ExpectedChecksum::default().verify(HELLO_SHA256, 3, Some(5));
+    assert!(matches!(result, Err(FileApiError::ChecksumMismatch(_))));
+}
+
+#[tokio::test]
+async fn test_existing_file_is_matched_by_hash_then_size() {
+    let path = std::env::temp_dir().join(format!("reetui_match_{}", uuid::Uuid::new_v4()));
+    tokio::fs::write(&path, b"hello").await.unwrap();
+    let by_hash = ExpectedChecksum {
+        sha256: Some(HELLO_SHA256.to_string()),
+        size: None,
+    };
+    let wrong_size = ExpectedChecksum {
+        sha256: None,
+        size: Some(4),
+    };
+    let same_hash = matches_local_file(&path, &by_hash).await;
+    let same_size = matches_local_file(&path, &wrong_size).await;
+    let name_only = matches_local_file(&path, &ExpectedChecksum::default()).await;
+    tokio::fs::remove_file(&path).await.unwrap();
+    assert!(same_hash);
+    assert!(!same_size);
+    assert!(name_only);
+    assert!(!matches_local_file(&path, &ExpectedChecksum::default()).await);
+}
+
+const UPLOAD: &[u8] = b"hello world!";
+
+async fn upload_source() -> PathBuf {
+    let path = std::env::temp_dir().join(format!("reetui_upload_{}.txt", uuid::Uuid::new_v4()));
+    tokio::fs::write(&path, UPLOAD).await.unwrap();
+    path
+}
+
+/// Takes chunked uploads 5 bytes at a time into its one session, `u1`. The chunk requests
+/// numbered (from 0) in `failing` get a 500 and aren't kept.
+async fn upload_server(
+    failing: &'static [usize],
+) -> (String, Arc<Mutex<Vec<MockRequest>>>, Arc<Mutex<Vec<u8>>>) {
+    let stored = Arc::new(Mutex::new(Vec::new()));
+    let stored_for_server = stored.clone();
+    let chunks = Arc::new(Mutex::new(0));
+    let (url, requests) = mock_server_with(move |request| {
+        let mut stored = stored_for_server.lock().unwrap();
+        let received = |stored: &Vec<u8>| format!(r#"{{"received":{}}}"#, stored.len());
+        match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/files/upload/home/init") => (
+                200,
+                r#"{"upload_id":"u1","chunk_size":5,"received":0}"#.to_string(),
+            ),
+            ("GET", "/files/upload/u1") => (200, received(&stored)),
+            ("PUT", path) if path.starts_with("/files/upload/u1/chunk?offset=") => {
+                let mut chunks = chunks.lock().unwrap();
+                *chunks += 1;
+                if failing.contains(&(*chunks - 1)) {
+                    return (500, String::new());
+                }
+                let offset: usize = path.rsplit('=').next().unwrap().parse().unwrap();
+                if offset == stored.len() {
+                    stored.extend_from_slice(&request.body);
+                }
+                (200, received(&stored))
+            }
+            ("POST", "/files/upload/u1/complete") => (200, "file-1".to_string()),
+            _ => (404, String::new()),
+        }
+    })
+    .await;
+    (url, requests, stored)
+}
+
+fn chunk_offsets(requests: &[MockRequest]) -> Vec<&str> {
+    requests
+        .iter()
+        .filter(|r| r.method == "PUT")
+        .filter_map(|r| r.path.rsplit('=').next())
+        .collect()
+}
+
+fn inits(requests: &[MockRequest]) -> usize {
+    requests
+        .iter()
+        .filter(|r| r.path.ends_with("/init"))
+        .count()
+}
+
+#[tokio::test]
+async fn test_upload_is_sent_in_the_servers_chunk_size() {
+    let (url, requests, stored) = upload_server(&[]).await;
+    let path = upload_source().await;
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut session = None;
+    let client = reqwest::Client::new();
+    let result =
+        upload_file_chunked_to(&client, &url, "t", "home", path.clone(), tx, &mut session).await;
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    assert_eq!(result.unwrap(), "file-1");
+    assert_eq!(
+        chunk_offsets(&requests.lock().unwrap()),
+        vec!["0", "5", "10"]
+    );
+    assert_eq!(stored.lock().unwrap().as_slice(), UPLOAD);
+    assert!(session.is_none());
+}
+
+#[tokio::test]
+async fn test_upload_resumes_after_a_failed_chunk() {
+    let (url, requests, stored) = upload_server(&[1]).await;
+    let path = upload_source().await;
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut session = None;
+    let client = reqwest::Client::new();
+    let first = upload_file_chunked_to(
+        &client,
+        &url,
+        "t",
+        "home",
+        path.clone(),
+        tx.clone(),
+        &mut session,
+    )
+    .await;
+    assert!(matches!(first, Err(FileApiError::RequestFailedStatus(s)) if s.as_u16() == 500));
+    assert_eq!(session.as_ref().map(|s| s.received), Some(5));
+
+    // what a retry does, the same session again
+    let second =
+        upload_file_chunked_to(&client, &url, "t", "home", path.clone(), tx, &mut session).await;
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    assert_eq!(second.unwrap(), "file-1");
+    let requests = requests.lock().unwrap();
+    assert_eq!(inits(&requests), 1);
+    assert_eq!(chunk_offsets(&requests), vec!["0", "5", "5", "10"]);
+    assert_eq!(stored.lock().unwrap().as_slice(), UPLOAD);
+}
+
+#[tokio::test]
+async fn test_forgotten_session_starts_over() {
+    let (url, requests, stored) = upload_server(&[]).await;
+    let path = upload_source().await;
+    let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut session = Some(UploadSession {
+        upload_id: "gone".to_string(),
+        chunk_size: 5,
+        received: 5,
+    });
+    let client = reqwest::Client::new();
+    let result =
+        upload_file_chunked_to(&client, &url, "t", "home", path.clone(), tx, &mut session).await;
+    tokio::fs::remove_file(&path).await.unwrap();
+
+    assert_eq!(result.unwrap(), "file-1");
+    let requests = requests.lock().unwrap();
+    assert_eq!(requests[0].path, "/files/upload/gone");
+    assert_eq!(inits(&requests), 1);
+    assert_eq!(chunk_offsets(&requests), vec!["0", "5", "10"]);
+    assert_eq!(stored.lock().unwrap().as_slice(), UPLOAD);
+}
+
+#[tokio::test]
+async fn test_servers_without_chunked_uploads_get_the_whole_file() {
+    for status in [404, 405, 501] {
+        let (url, requests) = mock_server_with(move |request| match request.path.as_str() {
+            "/files/upload/home/init" => (status, String::new()),
+            "/files/upload/home" => (200, "file-2".to_string()),
+            _ => (404, String::new()),
+        })
+        .await;
+        let path = upload_source().await;
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut session = None;
+        let client = reqwest::Client::new();
+        let result =
+            upload_file_chunked_to(&client, &url, "t", "home", path.clone(), tx, &mut session)
+                .await;
+        tokio::fs::remove_file(&path).await.unwrap();
+
+        assert_eq!(result.unwrap(), "file-2", "init answered {}", status);
+        let requests = requests.lock().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].method, "POST");
+        assert!(requests[1].body.windows(UPLOAD.len()).any(|w| w == UPLOAD));
+    }
+}
@@ -0,0 +1,50 @@
+use crate::api::link_preview::{parse_link_preview, previewable_url, LinkPreview};
+
+#[test]
+fn test_first_link_is_picked_and_trailing_punctuation_dropped() {
+    assert_eq!(
+        previewable_url("look at https://example.com/a. and https://other.org"),
+        Some("https://example.com/a".to_string())
+    );
+    assert_eq!(previewable_url("no links here"), None);
+}
+
+#[test]
+fn test_links_in_angle_brackets_opt_out() {
+    assert_eq!(previewable_url("quiet <https://example.com>"), None);
+    assert_eq!(
+        previewable_url("<https://example.com> but https://shown.dev"),
+        Some("https://shown.dev".to_string())
+    );
+}
+
+#[test]
+fn test_opengraph_wins_over_the_title_tag() {
+    let html = r#"<html><head>
+        <title>Fallback</title>
+        <meta property="og:title" content="Ree &amp; friends">
+        <meta name="description" content="A   chat
+            client">
+        <meta property="og:site_name" content='GitHub'>
+    </head></html>"#;
+    assert_eq!(
+        parse_link_preview("https://github.com/x", html),
+        Some(LinkPreview {
+            site: "GitHub".to_string(),
+            title: "Ree & friends".to_string(),
+            description: Some("A chat client".to_string()),
+        })
+    );
+}
+
+#[test]
+fn test_title_tag_and_host_as_fallback() {
+    let html = "<html><head><TITLE> Just a page </TITLE></head></html>";
+    let preview = parse_link_preview("https://www.example.com/page", html).unwrap();
+    assert_eq!(preview.title, "Just a page");
+    assert_eq!(preview.site, "example.com");
+    assert_eq!(preview.description, None);
+
+    // nothing to show, the raw link stays on its own
+    assert_eq!(parse_link_preview("https://example.com", "<html></html>"), None);
+}
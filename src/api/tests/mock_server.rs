@@ -0,0 +1,116 @@
+// A tiny HTTP server for the api tests, one canned answer per request.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// What the mock server got.
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    pub method: String,
+    /// path and query, like `/files/upload/u1/chunk?offset=0`
+    pub path: String,
+    pub body: Vec<u8>,
+}
+
+/// Answers every request with whatever `respond` says (a status and a body) and keeps them
+/// all. The url has no trailing slash.
+pub async fn mock_server_with(
+    respond: impl Fn(&MockRequest) -> (u16, String) + Send + Sync + 'static,
+) -> (String, Arc<Mutex<Vec<MockRequest>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let requests_for_server = requests.clone();
+    tokio::spawn(async move {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                break;
+            };
+            let Some(request) = read_request(&mut socket).await else {
+                continue;
+            };
+            let (status, body) = respond(&request);
+            requests_for_server.lock().unwrap().push(request);
+            let response = format!(
+                "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    });
+    (url, requests)
+}
+
+/// Answers every request with the next status in `statuses` (the last one repeats) and counts
+/// the requests it got.
+pub async fn mock_server(statuses: Vec<u16>) -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let hits_for_server = hits.clone();
+    let (url, _) = mock_server_with(move |_| {
+        let n = hits_for_server.fetch_add(1, Ordering::SeqCst);
+        (statuses[n.min(statuses.len() - 1)], "ok".to_string())
+    })
+    .await;
+    (format!("{}/", url), hits)
+}
+
+async fn read_request(socket: &mut TcpStream) -> Option<MockRequest> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    let header_end = loop {
+        if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+        let n = socket.read(&mut buf).await.ok().filter(|&n| n > 0)?;
+        data.extend_from_slice(&buf[..n]);
+    };
+    let head = String::from_utf8_lossy(&data[..header_end]).into_owned();
+    let mut request_line = head.lines().next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let path = request_line.next()?.to_string();
+    let header = |name: &str| {
+        head.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.eq_ignore_ascii_case(name)
+                .then(|| value.trim().to_string())
+        })
+    };
+    let chunked = header("transfer-encoding").is_some_and(|v| v.eq_ignore_ascii_case("chunked"));
+    let length: usize = header("content-length")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
+    let mut body = data[header_end..].to_vec();
+    if chunked {
+        while !body.ends_with(b"0\r\n\r\n") {
+            let n = socket.read(&mut buf).await.ok().filter(|&n| n > 0)?;
+            body.extend_from_slice(&buf[..n]);
+        }
+        body = dechunk(&body);
+    } else {
+        while body.len() < length {
+            let n = socket.read(&mut buf).await.ok().filter(|&n| n > 0)?;
+            body.extend_from_slice(&buf[..n]);
+        }
+    }
+    Some(MockRequest { method, path, body })
+}
+
+fn dechunk(mut raw: &[u8]) -> Vec<u8> {
+    let mut body = Vec::new();
+    while let Some(line_end) = raw.windows(2).position(|w| w == b"\r\n") {
+        let size = usize::from_str_radix(String::from_utf8_lossy(&raw[..line_end]).trim(), 16)
+            .unwrap_or(0);
+        if size == 0 {
+            break;
+        }
+        let start = line_end + 2;
+        body.extend_from_slice(&raw[start..start + size]);
+        raw = &raw[start + size + 2..];
+    }
+    body
+}
@@ -0,0 +1,6 @@
+pub mod auth_api_test;
+pub mod client_test;
+pub mod file_api_test;
+pub mod link_preview_test;
+pub mod mock_server;
+pub mod transport_test;
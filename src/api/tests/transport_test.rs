@@ -0,0 +1,161 @@
+use crate::api::client::ApiClient;
+use crate::api::transport::{ChatTransport, MemoryServer, MemoryTransport, TransportEvent};
+use crate::api::websocket::handle_server_communication;
+use crate::app::AppState;
+use crate::tui::chat::ws_command::WsCommand;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, Mutex};
+use tokio_util::sync::CancellationToken;
+
+struct Connection {
+    app_state: Arc<Mutex<AppState>>,
+    server: MemoryServer,
+    command_tx: mpsc::UnboundedSender<WsCommand>,
+    cancellation_token: CancellationToken,
+    task: tokio::task::JoinHandle<Result<(), Box<dyn std::error::Error + Send + Sync>>>,
+}
+
+fn connect() -> Connection {
+    let (transport, server) = MemoryTransport::pair();
+    let app_state = Arc::new(Mutex::new(AppState::default()));
+    let (command_tx, command_rx) = mpsc::unbounded_channel();
+    let (redraw_tx, _redraw_rx) = mpsc::unbounded_channel();
+    let cancellation_token = CancellationToken::new();
+    let task = tokio::spawn(handle_server_communication(
+        transport,
+        app_state.clone(),
+        command_tx.clone(),
+        command_rx,
+        ApiClient::new(None),
+        redraw_tx,
+        cancellation_token.clone(),
+    ));
+    Connection {
+        app_state,
+        server,
+        command_tx,
+        cancellation_token,
+        task,
+    }
+}
+
+// the connection runs on its own task, give it a moment to get to the message
+async fn eventually(mut check: impl FnMut() -> bool) -> bool {
+    for _ in 0..100 {
+        if check() {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    false
+}
+
+#[tokio::test]
+async fn test_server_messages_end_up_in_the_state() {
+    let connection = connect();
+    assert!(connection.server.send(
+        serde_json::json!({
+            "user": "ree",
+            "icon": "",
+            "content": "hi from the other side",
+            "timestamp": 0,
+            "channel_id": "home",
+        })
+        .to_string()
+    ));
+
+    let app_state = connection.app_state.clone();
+    let arrived = eventually(|| {
+        app_state.try_lock().is_ok_and(|state| {
+            state.messages.get("home").is_some_and(|messages| {
+                messages
+                    .iter()
+                    .any(|m| m.content == "hi from the other side")
+            })
+        })
+    })
+    .await;
+    assert!(arrived);
+    connection.cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn test_commands_go_out_as_channel_commands() {
+    let mut connection = connect();
+    connection
+        .command_tx
+        .send(WsCommand::Message {
+            channel_id: "home".to_string(),
+            content: "/get_history home 0".to_string(),
+        })
+        .unwrap();
+
+    let frame = tokio::time::timeout(Duration::from_secs(1), connection.server.received.recv())
+        .await
+        .expect("the command should go out")
+        .unwrap();
+    let frame: serde_json::Value = serde_json::from_str(&frame).unwrap();
+    assert_eq!(frame["channel_id"], "home");
+    assert_eq!(frame["content"], "/get_history home 0");
+    connection.cancellation_token.cancel();
+}
+
+#[tokio::test]
+async fn test_cancelling_closes_the_transport() {
+    let connection = connect();
+    connection.cancellation_token.cancel();
+    tokio::time::timeout(Duration::from_secs(1), connection.task)
+        .await
+        .expect("cancelling should end the connection")
+        .unwrap()
+        .unwrap();
+    assert!(!connection.server.send("{}"));
+}
+
+#[tokio::test]
+async fn test_memory_transport_answers_pings() {
+    let (mut transport, _server) = MemoryTransport::pair();
+    transport.send_ping().await.unwrap();
+    assert_eq!(transport.next_event().await, Some(TransportEvent::Pong));
+}
+
+#[tokio::test]
+async fn test_only_errors_naming_a_pending_channel_drop_it() {
+    let connection = connect();
+    connection
+        .app_state
+        .lock()
+        .await
+        .add_pending_channel("lounge".to_string(), "#".to_string());
+
+    let toasts = |count: usize| {
+        let app_state = connection.app_state.clone();
+        eventually(move || {
+            app_state
+                .try_lock()
+                .is_ok_and(|state| state.notification_manager.notifications().len() == count)
+        })
+    };
+    let has_pending = || async {
+        let state = connection.app_state.lock().await;
+        state
+            .channels
+            .iter()
+            .any(|c| c.pending && c.name == "lounge")
+    };
+
+    // a rate limit isn't about the channel, it stays until the server says otherwise
+    assert!(connection
+        .server
+        .send(serde_json::json!({ "message": "Slow down" }).to_string()));
+    assert!(toasts(1).await);
+    assert!(has_pending().await);
+
+    assert!(connection
+        .server
+        .send(serde_json::json!({ "message": "Channel lounge already exists" }).to_string()));
+    assert!(toasts(2).await);
+    assert!(!has_pending().await);
+    connection.cancellation_token.cancel();
+}
@@ -0,0 +1,107 @@
+use crate::api::models::ChannelCommand;
+use std::future::Future;
+use tokio::sync::mpsc;
+
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// What the server side of a transport sent us.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransportEvent {
+    /// one JSON server message, see `websocket::ServerMessage`
+    Text(String),
+    /// answer to `send_ping`
+    Pong,
+}
+
+/// How the chat talks to the server. The WebSocket is the real one, the in-memory one is for
+/// tests and anything that wants a server without a network.
+pub trait ChatTransport: Send + 'static {
+    /// Sends `content` to a channel, slash commands included. Hands back the frame that went
+    /// out so it can be logged.
+    fn send_message(
+        &mut self,
+        channel_id: &str,
+        content: &str,
+    ) -> impl Future<Output = Result<String, TransportError>> + Send;
+
+    /// The keepalive, transports that can't drop silently don't need one.
+    fn send_ping(&mut self) -> impl Future<Output = Result<(), TransportError>> + Send {
+        async { Ok(()) }
+    }
+
+    /// The next thing the server sent, None once the connection is gone. Dropping the future
+    /// before it's done must not lose anything, it's raced against the outgoing side.
+    fn next_event(&mut self) -> impl Future<Output = Option<TransportEvent>> + Send;
+
+    fn close(&mut self) -> impl Future<Output = ()> + Send;
+}
+
+/// The frame a chat message (or command) goes out as.
+pub fn channel_command_payload(channel_id: &str, content: &str) -> Result<String, TransportError> {
+    Ok(serde_json::to_string(&ChannelCommand {
+        channel_id,
+        content,
+    })?)
+}
+
+/// A transport that's just a pair of channels, the other end is a `MemoryServer`.
+pub struct MemoryTransport {
+    outbound: mpsc::UnboundedSender<String>,
+    inbound: mpsc::UnboundedReceiver<TransportEvent>,
+    // pings are answered by the transport itself, there's no server to do it
+    pongs: mpsc::UnboundedSender<TransportEvent>,
+}
+
+/// The server side of a `MemoryTransport`.
+pub struct MemoryServer {
+    /// every frame the client sent, in order
+    pub received: mpsc::UnboundedReceiver<String>,
+    sender: mpsc::UnboundedSender<TransportEvent>,
+}
+
+impl MemoryTransport {
+    pub fn pair() -> (MemoryTransport, MemoryServer) {
+        let (outbound, received) = mpsc::unbounded_channel();
+        let (sender, inbound) = mpsc::unbounded_channel();
+        let transport = MemoryTransport {
+            outbound,
+            inbound,
+            pongs: sender.clone(),
+        };
+        (transport, MemoryServer { received, sender })
+    }
+}
+
+impl MemoryServer {
+    /// Sends a server message to the client, false once the client is gone.
+    pub fn send(&self, text: impl Into<String>) -> bool {
+        self.sender.send(TransportEvent::Text(text.into())).is_ok()
+    }
+}
+
+impl ChatTransport for MemoryTransport {
+    async fn send_message(
+        &mut self,
+        channel_id: &str,
+        content: &str,
+    ) -> Result<String, TransportError> {
+        let payload = channel_command_payload(channel_id, content)?;
+        self.outbound
+            .send(payload.clone())
+            .map_err(|_| "the in-memory server is gone")?;
+        Ok(payload)
+    }
+
+    async fn send_ping(&mut self) -> Result<(), TransportError> {
+        let _ = self.pongs.send(TransportEvent::Pong);
+        Ok(())
+    }
+
+    async fn next_event(&mut self) -> Option<TransportEvent> {
+        self.inbound.recv().await
+    }
+
+    async fn close(&mut self) {
+        self.inbound.close();
+    }
+}
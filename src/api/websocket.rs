@@ -1,9 +1,15 @@
-use crate::api::models::{BroadcastMessage, Channel, ChannelCommand};
-use crate::app::app_state::AppState;
+use crate::api::client::ApiClient;
+use crate::api::models::{BroadcastMessage, Channel};
+use crate::api::server::ws_url;
+use crate::api::transport::{
+    channel_command_payload, ChatTransport, TransportError, TransportEvent,
+};
+use crate::app::app_state::{
+    AccountDeletion, AppState, GotoOutcome, PasswordChange, ProfileUpdate, ServerErrorSubject,
+    WsFrameDirection,
+};
 use crate::tui::chat::ws_command::WsCommand;
-use futures_util::stream::{SplitSink, SplitStream};
 use futures_util::{SinkExt, StreamExt};
-use reqwest::Client;
 use rustls::client::danger::{ServerCertVerified, ServerCertVerifier};
 use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
 use rustls::{ClientConfig, RootCertStore, SignatureScheme};
@@ -75,14 +81,85 @@ lazy_static! {
     static ref THEME_KEYWORDS: Vec<&'static str> = vec!["gizzy", "zombi"];
 }
 
-pub type WsWriter = SplitSink<WebSocketStream<MaybeTlsStream<TcpStream>>, Message>;
-pub type WsReader = SplitStream<WebSocketStream<MaybeTlsStream<TcpStream>>>;
+/// The real transport, a WebSocket to the server that was already sent the auth token.
+pub struct WebSocketTransport {
+    stream: WebSocketStream<MaybeTlsStream<TcpStream>>,
+}
+
+impl ChatTransport for WebSocketTransport {
+    async fn send_message(
+        &mut self,
+        channel_id: &str,
+        content: &str,
+    ) -> Result<String, TransportError> {
+        let payload = channel_command_payload(channel_id, content)?;
+        self.stream.send(Message::Text(payload.clone())).await?;
+        Ok(payload)
+    }
+
+    async fn send_ping(&mut self) -> Result<(), TransportError> {
+        self.stream.send(Message::Ping(Vec::new())).await?;
+        Ok(())
+    }
+
+    async fn next_event(&mut self) -> Option<TransportEvent> {
+        loop {
+            match self.stream.next().await? {
+                Ok(Message::Text(text)) => return Some(TransportEvent::Text(text.to_string())),
+                Ok(Message::Pong(_)) => return Some(TransportEvent::Pong),
+                // pings are answered by tungstenite, binary frames aren't part of the protocol
+                Ok(_) => continue,
+                Err(_) => return None,
+            }
+        }
+    }
+
+    async fn close(&mut self) {
+        let _ = self.stream.close(None).await;
+    }
+}
+
+#[derive(Debug)]
+pub enum ConnectError {
+    TimedOut(Duration),
+    Failed(Box<dyn std::error::Error>),
+}
+
+impl ConnectError {
+    /// A slow or unreachable server may be back later, other failures need a change first.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, ConnectError::TimedOut(_))
+    }
+}
+
+impl std::fmt::Display for ConnectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConnectError::TimedOut(timeout) => write!(
+                f,
+                "Connection timed out, the server didn't answer in {}s",
+                timeout.as_secs()
+            ),
+            ConnectError::Failed(e) => write!(f, "Failed to connect: {}", e),
+        }
+    }
+}
 
-const WS_URL: &str = "wss://isock.reetui.hackclub.app";
+impl std::error::Error for ConnectError {}
 
-pub async fn connect(token: &str) -> Result<(WsWriter, WsReader), Box<dyn std::error::Error>> {
-    rustls::crypto::CryptoProvider::install_default(rustls::crypto::ring::default_provider())
-        .expect("Failed to install default crypto provider\nTell to the owner (Youssef 󰊤 :'YoussefDevPro')\nIn the repo 󰌷 https://github.com/YoussefDevPro/ReeTUI");
+pub async fn connect(token: &str, timeout: Duration) -> Result<WebSocketTransport, ConnectError> {
+    match tokio::time::timeout(timeout, connect_without_timeout(token)).await {
+        Ok(connection) => connection.map_err(ConnectError::Failed),
+        Err(_) => Err(ConnectError::TimedOut(timeout)),
+    }
+}
+
+async fn connect_without_timeout(
+    token: &str,
+) -> Result<WebSocketTransport, Box<dyn std::error::Error>> {
+    // already installed when this isn't the first connection of the run, that's fine
+    let _ =
+        rustls::crypto::CryptoProvider::install_default(rustls::crypto::ring::default_provider());
     // Create a custom rustls client config that trusts any certificate
     //
     // THIS IS INSECURE AND SHOULD NOT BE USED IN PRODUCTION
@@ -100,24 +177,10 @@ pub async fn connect(token: &str) -> Result<(WsWriter, WsReader), Box<dyn std::e
 
     let connector = Connector::Rustls(Arc::new(client_config));
 
-    let (ws_stream, _) = connect_async_tls_with_config(WS_URL, None, true, Some(connector)).await?;
-    let (mut writer, reader) = ws_stream.split();
-    writer.send(Message::Text(token.to_string().into())).await?;
-    Ok((writer, reader))
-}
-
-pub async fn send_message(
-    writer: &mut WsWriter,
-    channel_id: &str,
-    content: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let command = ChannelCommand {
-        channel_id,
-        content,
-    };
-    let payload = serde_json::to_string(&command)?;
-    writer.send(Message::Text(payload.into())).await?;
-    Ok(())
+    let (mut stream, _) =
+        connect_async_tls_with_config(ws_url(), None, true, Some(connector)).await?;
+    stream.send(Message::Text(token.to_string())).await?;
+    Ok(WebSocketTransport { stream })
 }
 
 #[derive(serde::Deserialize, Debug)]
@@ -152,6 +215,38 @@ pub struct ChannelUpdateWrapper {
     pub channel: Channel,
 }
 
+#[derive(serde::Deserialize, Debug)]
+pub struct ChannelDeleteWrapper {
+    #[serde(rename = "ChannelDelete")]
+    pub channel_id: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct ProfileData {
+    pub username: String,
+    pub icon: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct ProfileUpdateWrapper {
+    #[serde(rename = "ProfileUpdate")]
+    pub profile: ProfileData,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct PasswordChangedWrapper {
+    #[serde(rename = "PasswordChanged")]
+    #[allow(dead_code)]
+    pub username: String,
+}
+
+#[derive(serde::Deserialize, Debug)]
+pub struct AccountDeletedWrapper {
+    #[serde(rename = "AccountDeleted")]
+    #[allow(dead_code)]
+    pub username: String,
+}
+
 #[derive(serde::Deserialize, Debug)]
 #[serde(untagged)]
 pub enum ServerMessage {
@@ -159,9 +254,12 @@ pub enum ServerMessage {
     ChannelList(ChannelListWrapper),
     UserList(UserListWrapper),
     ChannelUpdate(ChannelUpdateWrapper),
+    ChannelDelete(ChannelDeleteWrapper),
+    ProfileUpdate(ProfileUpdateWrapper),
+    PasswordChanged(PasswordChangedWrapper),
+    AccountDeleted(AccountDeletedWrapper),
     Broadcast(BroadcastMessage),
     Error {
-        #[allow(dead_code)]
         message: String,
     },
     FileDownload {
@@ -178,30 +276,100 @@ pub enum ServerMessage {
         #[allow(dead_code)]
         notification_type: crate::tui::notification::notification::NotificationType,
     },
+    /// Anything newer servers send that this client doesn't know yet. Must stay the last
+    /// variant, untagged enums take the first one that fits.
+    Unknown(serde_json::Value),
+}
+
+// "Foo" for {"Foo": {...}}, which is how the server tags most of its messages
+fn unknown_message_kind(raw: &serde_json::Value) -> String {
+    raw.as_object()
+        .and_then(|object| object.keys().next())
+        .cloned()
+        .unwrap_or_else(|| "untagged".to_string())
 }
 
-pub async fn handle_websocket_communication(
-    mut ws_reader: WsReader,
+/// Runs the connection until it's cancelled: server messages go into AppState, messages
+/// from `command_rx` go out, and a ping every `heartbeat_interval_secs` makes sure the other
+/// end is still there.
+pub async fn handle_server_communication<T: ChatTransport>(
+    mut transport: T,
     app_state: Arc<Mutex<AppState>>,
     command_tx: mpsc::UnboundedSender<WsCommand>,
-    http_client: Client,
+    mut command_rx: mpsc::UnboundedReceiver<WsCommand>,
+    api: ApiClient,
     redraw_tx: mpsc::UnboundedSender<String>,
     cancellation_token: CancellationToken,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let heartbeat_secs = {
+        let mut state = app_state.lock().await;
+        state.last_pong = Some(tokio::time::Instant::now());
+        state.config.heartbeat_interval_secs
+    };
+    let period = Duration::from_secs(heartbeat_secs.max(1));
+    let mut heartbeat = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
     loop {
         tokio::select! {
             _ = cancellation_token.cancelled() => {
 
                 break;
             }
-            Some(Ok(msg)) = ws_reader.next() => {
-                if let Message::Text(text) = msg {
-                    if let Ok(server_msg) = serde_json::from_str::<ServerMessage>(&text) {
-                        let mut state = app_state.lock().await;
+            _ = heartbeat.tick(), if heartbeat_secs > 0 => {
+                let mut state = app_state.lock().await;
+                if state.last_pong.is_some_and(|pong| pong.elapsed() > period * 2) {
+                    state
+                        .notification_manager
+                        .add(
+                            "Connection Lost 󰖪".to_string(),
+                            format!(
+                                "No answer from the server for {}s, reconnecting...",
+                                heartbeat_secs * 2
+                            ),
+                            crate::tui::notification::notification::NotificationType::Warning,
+                            Some(Duration::from_secs(3)),
+                            app_state.clone(),
+                        )
+                        .await;
+                    // opening the chat page again is what connects
+                    state.next_page = Some(crate::app::TuiPage::Chat);
+                    let _ = redraw_tx.send(String::new());
+                    break;
+                }
+                drop(state);
+                if transport.send_ping().await.is_err() {
+                    break;
+                }
+            }
+            command = command_rx.recv() => {
+                let Some(command) = command else {
+                    break;
+                };
+                if let WsCommand::Message { channel_id, content } = command {
+                    let Ok(payload) = transport.send_message(&channel_id, &content).await else {
+                        break;
+                    };
+                    app_state
+                        .lock()
+                        .await
+                        .log_ws_frame(WsFrameDirection::Outbound, &payload, true);
+                }
+            }
+            Some(event) = transport.next_event() => {
+                if let TransportEvent::Pong = event {
+                    // answer to the keepalive ping
+                    app_state.lock().await.last_pong = Some(tokio::time::Instant::now());
+                    continue;
+                }
+                if let TransportEvent::Text(text) = event {
+                    let parsed = serde_json::from_str::<ServerMessage>(&text);
+                    let mut state = app_state.lock().await;
+                    let understood = matches!(&parsed, Ok(msg) if !matches!(msg, ServerMessage::Unknown(_)));
+                    state.log_ws_frame(WsFrameDirection::Inbound, &text, understood);
+                    if let Ok(server_msg) = parsed {
 
                         match server_msg {
                             ServerMessage::ChannelList(wrapper) => {
-                                state.channels = wrapper.channels;
+                                state.set_channels(wrapper.channels);
                                 // Check if the current channel is still in the updated list
                                 let current_channel_id = state.current_channel.as_ref().map(|c| c.id.clone());
                                 if let Some(id) = current_channel_id {
@@ -213,7 +381,7 @@ pub async fn handle_websocket_communication(
 
                                 // If no current channel or it was just removed, set to the first available channel
                                 if state.current_channel.is_none() {
-                                    if let Some(first_channel) = state.channels.get(0).cloned() {
+                                    if let Some(first_channel) = state.channels.iter().find(|c| !c.pending).cloned() {
                                         let channel_id = first_channel.id.clone();
                                         state.set_current_channel(first_channel);
                                         let _ = command_tx.send(WsCommand::Message {
@@ -232,19 +400,20 @@ pub async fn handle_websocket_communication(
                                     }
                                 }
                                 let is_image = message.is_image.unwrap_or(false);
-                                if message.file_id.is_some() {
+                                if let Some(downloadable_file) = crate::app::app_state::DownloadableFile::from_message(&message) {
                                     // This is a file message, add it to downloadable_files
-                                    let downloadable_file = crate::app::app_state::DownloadableFile {
-                                        file_id: message.file_id.clone().unwrap(),
-                                        file_name: message.file_name.clone().unwrap_or_default(),
-                                        file_extension: message.file_extension.clone().unwrap_or_default(),
-                                        file_size: (message.file_size_mb.unwrap_or(0.0) * 1024.0 * 1024.0) as u64,
-                                        sender_username: message.user.clone(),
-                                        sender_icon: message.icon.clone(),
-                                        devicon: message.file_icon.clone().unwrap_or_default(),
-                                    };
                                     state.downloadable_files.insert(downloadable_file.file_id.clone(), downloadable_file);
                                 }
+                                if let Some(notice) = state.hidden_message_notice(&message) {
+                                    state.notification_manager.add(
+                                        "New Message 󰍡".to_string(),
+                                        notice,
+                                        crate::tui::notification::notification::NotificationType::Info,
+                                        Some(Duration::from_secs(4)),
+                                        app_state.clone(),
+                                    ).await;
+                                }
+                                crate::tui::chat::link_preview::request_link_preview(&mut state, &message, &app_state, &api, &redraw_tx);
                                 state.add_message(message.clone());
                                 if THEME_KEYWORDS.iter().any(|&word| message.content.contains(word)) {
                                     let now = tokio::time::Instant::now();
@@ -263,14 +432,14 @@ pub async fn handle_websocket_communication(
                                 }
                                 if is_image {
                                     let app_state_clone = app_state.clone();
-                                    let http_client_clone = http_client.clone();
+                                    let api = api.clone();
                                     let redraw_tx_clone = redraw_tx.clone();
                                     let chat_width = state.chat_width;
                                     tokio::spawn(async move {
                                         crate::tui::chat::image_handler::process_image_message(
                                             app_state_clone,
                                             message,
-                                            &http_client_clone,
+                                            &api,
                                             chat_width,
                                             redraw_tx_clone,
                                         )
@@ -285,36 +454,47 @@ pub async fn handle_websocket_communication(
                                 for message in messages.iter_mut() {
                                     message.client_id = Some(Uuid::new_v4().to_string());
                                 }
-                                 if state.messages.get(&channel_id).map_or(true, |m| m.is_empty()) {
+                                state.history_loaded.insert(channel_id.clone());
+                                 if state.wants_history(&channel_id) {
                                     state.prepend_history(&channel_id, messages.clone());
                                     state
                                         .channel_history_state
                                         .insert(channel_id.clone(), (history.offset as u64, history.has_more, true));
                                     state.update_last_message_count(channel_id.clone(), messages.len());
                                     state.set_initial_load_complete(true);
+                                }
+                                match state.resume_goto(&channel_id) {
+                                    Some(GotoOutcome::Fetch { channel_id, offset }) => {
+                                        let _ = command_tx.send(WsCommand::Message {
+                                            channel_id: channel_id.clone(),
+                                            content: format!("/get_history {} {}", channel_id, offset),
+                                        });
+                                    }
+                                    Some(GotoOutcome::NotFound) => {
+                                        state.notification_manager.add(
+                                            "Message Not Found 󰍉".to_string(),
+                                            "Went through the whole channel, that message isn't in it.".to_string(),
+                                            crate::tui::notification::notification::NotificationType::Warning,
+                                            Some(Duration::from_secs(3)),
+                                            app_state.clone(),
+                                        ).await;
+                                    }
+                                    Some(GotoOutcome::Found) | None => {}
                                 }                                for message in messages {
-                                    if message.file_id.is_some() {
-                                        let downloadable_file = crate::app::app_state::DownloadableFile {
-                                            file_id: message.file_id.clone().unwrap(),
-                                            file_name: message.file_name.clone().unwrap_or_default(),
-                                            file_extension: message.file_extension.clone().unwrap_or_default(),
-                                            file_size: (message.file_size_mb.unwrap_or(0.0) * 1024.0 * 1024.0) as u64,
-                                            sender_username: message.user.clone(),
-                                            sender_icon: message.icon.clone(),
-                                            devicon: message.file_icon.clone().unwrap_or_default(),
-                                        };
+                                    crate::tui::chat::link_preview::request_link_preview(&mut state, &message, &app_state, &api, &redraw_tx);
+                                    if let Some(downloadable_file) = crate::app::app_state::DownloadableFile::from_message(&message) {
                                         state.downloadable_files.insert(downloadable_file.file_id.clone(), downloadable_file);
                                     }
                                     if message.is_image.unwrap_or(false) {
                                         let app_state_clone = app_state.clone();
-                                        let http_client_clone = http_client.clone();
+                                        let api = api.clone();
                                         let redraw_tx_clone = redraw_tx.clone();
                                         let chat_width = state.chat_width;
                                         tokio::spawn(async move {
                                             crate::tui::chat::image_handler::process_image_message(
                                                 app_state_clone,
                                                 message,
-                                                &http_client_clone,
+                                                &api,
                                                 chat_width,
                                                 redraw_tx_clone,
                                             )
@@ -337,6 +517,108 @@ pub async fn handle_websocket_communication(
                                 });
                                 let _ = redraw_tx.send(String::new());
                             }
+                            ServerMessage::ChannelDelete(wrapper) => {
+                                state.remove_channel(&wrapper.channel_id);
+                                if state.current_channel.is_none() {
+                                    if let Some(first_channel) = state.channels.iter().find(|c| !c.pending).cloned() {
+                                        let channel_id = first_channel.id.clone();
+                                        state.set_current_channel(first_channel);
+                                        let _ = command_tx.send(WsCommand::Message {
+                                            channel_id: channel_id.clone(),
+                                            content: format!("/get_history {} 0", channel_id),
+                                        });
+                                    }
+                                }
+                                let _ = redraw_tx.send(String::new());
+                            }
+                            ServerMessage::ProfileUpdate(wrapper) => {
+                                let previous_username = state.username.clone();
+                                let profile = wrapper.profile;
+                                state.apply_profile(profile.username.clone(), profile.icon.clone());
+                                if let Err(e) = crate::config::save_config(&state.config) {
+                                    state.notification_manager.add(
+                                        "Config Not Saved 󰒓".to_string(),
+                                        e.to_string(),
+                                        crate::tui::notification::notification::NotificationType::Warning,
+                                        Some(Duration::from_secs(3)),
+                                        app_state.clone(),
+                                    ).await;
+                                } else if state.config.token_storage == crate::config::TokenStorage::Keyring
+                                    && previous_username != state.username
+                                {
+                                    // the token was just stored under the new name, the old entry is dead weight
+                                    let _ = crate::config::token_store::delete_token(state.config.server.as_deref(), previous_username.as_deref());
+                                }
+                                state.notification_manager.add(
+                                    "Profile Updated 󰀄".to_string(),
+                                    format!("You're {} {} now", profile.icon, profile.username),
+                                    crate::tui::notification::notification::NotificationType::Success,
+                                    Some(Duration::from_secs(3)),
+                                    app_state.clone(),
+                                ).await;
+                                let _ = redraw_tx.send(String::new());
+                            }
+                            ServerMessage::PasswordChanged(_) => {
+                                state.password_change = PasswordChange::Changed;
+                                state.notification_manager.add(
+                                    "Password Changed 󰌾".to_string(),
+                                    "Use the new one next time you log in".to_string(),
+                                    crate::tui::notification::notification::NotificationType::Success,
+                                    Some(Duration::from_secs(3)),
+                                    app_state.clone(),
+                                ).await;
+                                let _ = redraw_tx.send(String::new());
+                            }
+                            ServerMessage::AccountDeleted(_) => {
+                                let _ = crate::config::token_store::delete_token(state.config.server.as_deref(), state.config.username.as_deref());
+                                state.clear_user_auth().await;
+                                state.account_deletion = AccountDeletion::Idle;
+                                state.show_settings = false;
+                                state.config.token = None;
+                                state.config.username = None;
+                                state.config.user_icon = None;
+                                // keep the preferences, only the account is gone
+                                let _ = crate::config::save_config(&state.config);
+                                state.notification_manager.add(
+                                    "Account Deleted 󰆴".to_string(),
+                                    "It's gone, thanks for hanging around".to_string(),
+                                    crate::tui::notification::notification::NotificationType::Info,
+                                    Some(Duration::from_secs(3)),
+                                    app_state.clone(),
+                                ).await;
+                                state.next_page = Some(crate::app::TuiPage::Auth);
+                                let _ = redraw_tx.send(String::new());
+                            }
+                            ServerMessage::Error { message } => {
+                                let (title, subject) = match state.error_subject(&message) {
+                                    Some(ServerErrorSubject::Channel(name)) => {
+                                        state.remove_pending_channel(&format!("pending:{}", name));
+                                        ("Channel Creation Error ", Some(format!("'{}' didn't make it: {}", name, message)))
+                                    }
+                                    Some(ServerErrorSubject::Profile) => {
+                                        state.profile_update = ProfileUpdate::Rejected(message.clone());
+                                        ("Profile Not Updated 󰀄", None)
+                                    }
+                                    Some(ServerErrorSubject::Password) => {
+                                        // most likely the current password was wrong
+                                        state.password_change = PasswordChange::Rejected(message.clone());
+                                        ("Password Not Changed 󰌾", None)
+                                    }
+                                    Some(ServerErrorSubject::AccountDeletion) => {
+                                        state.account_deletion = AccountDeletion::Rejected(message.clone());
+                                        ("Account Not Deleted 󰆴", None)
+                                    }
+                                    None => ("Server Error ", None),
+                                };
+                                state.notification_manager.add(
+                                    title.to_string(),
+                                    subject.unwrap_or(message),
+                                    crate::tui::notification::notification::NotificationType::Error,
+                                    Some(Duration::from_secs(3)),
+                                    app_state.clone(),
+                                ).await;
+                                let _ = redraw_tx.send(String::new());
+                            }
                             ServerMessage::Notification {
                                 title,
                                 message,
@@ -344,6 +626,17 @@ pub async fn handle_websocket_communication(
                             } => {
                                 state.notification_manager.add(title, message, notification_type, Some(Duration::from_secs(3)), app_state.clone()).await;
                             }
+                            // protocol moves faster than the client, only worth a toast when debugging
+                            ServerMessage::Unknown(raw) if state.ws_debug_enabled() => {
+                                state.notification_manager.add(
+                                    "Unknown Server Message 󰋗".to_string(),
+                                    format!("Got a '{}' message this version doesn't know, it's in the WebSocket log", unknown_message_kind(&raw)),
+                                    crate::tui::notification::notification::NotificationType::Info,
+                                    Some(Duration::from_secs(3)),
+                                    app_state.clone(),
+                                ).await;
+                                let _ = redraw_tx.send(String::new());
+                            }
                             _ => {}
                         }
                     }
@@ -351,5 +644,6 @@ pub async fn handle_websocket_communication(
             }
         }
     }
+    transport.close().await;
     Ok(())
 }
@@ -1,11 +1,28 @@
+use crate::api::file_api::ExpectedChecksum;
+use crate::api::link_preview::LinkPreview;
 use crate::api::models::{BroadcastMessage, Channel};
-use crate::app::{PopupState, TuiPage};
-use crate::themes::{Theme, ThemeName, ThemesConfig};
+use crate::app::{PopupState, PopupType, TuiPage};
+use crate::themes::{Rgb, Theme, ThemeName, ThemesConfig};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::Duration;
+use zeroize::Zeroizing;
+use crate::tui::chat::popups::channel_menu::ChannelMenuState;
+use crate::tui::chat::popups::emoji_picker::EmojiPickerState;
+use crate::tui::chat::popups::mentions::{MentionCandidate, RECENT_AUTHORS_LIMIT};
+use crate::tui::chat::popups::profiles::ProfileSwitcherState;
+use crate::tui::keymap::KeybindingsOverlay;
 use crate::tui::help;
-use crate::config::Config;
+use crate::config::{Config, PinnedMessage};
+
+const INPUT_HISTORY_LIMIT: usize = 100;
+const WS_LOG_LIMIT: usize = 200;
+/// commands whose arguments are credentials, blanked out of the websocket log
+const SECRET_COMMANDS: &[&str] = &["/change_password"];
+/// how much of a message the "new message" toast shows while the chat is hidden
+const NOTICE_PREVIEW_CHARS: usize = 60;
+/// how long the message /goto landed on stays highlighted
+pub const GOTO_FLASH: Duration = Duration::from_millis(1500);
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum DebugView {
@@ -23,6 +40,88 @@ pub enum ChatFocusedPane {
     Input,
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WsFrameDirection {
+    Inbound,
+    Outbound,
+}
+
+/// One raw text frame as it went over the socket, kept for the ws_debug log popup.
+#[derive(Debug, Clone)]
+pub struct WsFrame {
+    pub direction: WsFrameDirection,
+    pub time: String,
+    pub text: String,
+    /// false when the client couldn't make sense of an inbound frame (bad JSON or an unknown message)
+    pub parsed: bool,
+}
+
+/// Where a username/icon change from the settings screen is at.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ProfileUpdate {
+    #[default]
+    Idle,
+    /// settings asked for it, the chat loop sends it on its next turn
+    Requested { username: String, icon: String },
+    /// on the wire, waiting for the server to confirm or refuse
+    Sent { username: String, icon: String },
+    /// what the server said when it refused, e.g. the name is taken
+    Rejected(String),
+}
+
+/// Where a password change from the settings screen is at.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum PasswordChange {
+    #[default]
+    Idle,
+    /// still plain, the chat loop hashes them with the server's scheme before sending. Wiped
+    /// from memory when dropped
+    Requested {
+        current: Zeroizing<String>,
+        new: Zeroizing<String>,
+    },
+    Sent,
+    Changed,
+    Rejected(String),
+}
+
+/// Where an account deletion from the settings screen is at.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum AccountDeletion {
+    #[default]
+    Idle,
+    Requested,
+    Sent,
+    Rejected(String),
+}
+
+/// The request waiting on the server that an error is about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ServerErrorSubject {
+    /// a pending channel, by name
+    Channel(String),
+    Profile,
+    Password,
+    AccountDeletion,
+}
+
+/// What `/goto <message_id>` found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GotoOutcome {
+    /// in the buffer, the next draw scrolls to it and flashes it
+    Found,
+    /// not loaded yet, this page of older history might have it
+    Fetch { channel_id: String, offset: usize },
+    NotFound,
+}
+
+/// A /goto waiting for older history of its channel to come in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingGoto {
+    pub channel_id: String,
+    pub message_id: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadableFile {
     pub file_id: String,
@@ -32,6 +131,46 @@ pub struct DownloadableFile {
     pub sender_username: String,
     pub sender_icon: String,
     pub devicon: String, // This will store the devicon character
+    #[serde(default)]
+    pub expected: ExpectedChecksum,
+    /// Set once a download matched the server's hash or size.
+    #[serde(default)]
+    pub verified: bool,
+}
+
+impl DownloadableFile {
+    pub fn from_message(message: &BroadcastMessage) -> Option<Self> {
+        let file_id = message.file_id.clone()?;
+        Some(Self {
+            file_id,
+            file_name: message.file_name.clone().unwrap_or_default(),
+            file_extension: message.file_extension.clone().unwrap_or_default(),
+            file_size: message.file_size().unwrap_or(0),
+            sender_username: message.user.clone(),
+            sender_icon: message.icon.clone(),
+            devicon: message.file_icon.clone().unwrap_or_default(),
+            expected: ExpectedChecksum::from_message(message),
+            verified: false,
+        })
+    }
+}
+
+/// Card under a message with a link, cached by URL.
+#[derive(Debug, Clone)]
+pub enum LinkPreviewState {
+    Loading,
+    Ready(LinkPreview),
+    /// the raw link is all there is
+    Failed,
+}
+
+/// Inline preview of an image attachment, cached by file_id.
+#[derive(Debug, Clone)]
+pub enum Thumbnail {
+    Loading,
+    /// chafa output, ANSI escapes included
+    Ready(String),
+    Failed(String),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -52,8 +191,9 @@ pub struct AppState {
     pub message_scroll_offset: usize,
     pub themes: HashMap<ThemeName, Theme>,
     pub current_theme: Theme,
+    /// theme, no-color mode and timestamp setting the cached message lines were drawn with
     #[serde(skip)]
-    pub last_rendered_theme: Option<ThemeName>,
+    pub last_rendered_theme: Option<(ThemeName, bool, bool)>,
     pub settings_main_selection: usize,
     pub settings_focused_pane: crate::tui::settings::state::FocusedPane,
     pub quit_confirmation_state: crate::tui::settings::state::QuitConfirmationState,
@@ -63,6 +203,10 @@ pub struct AppState {
     pub show_settings: bool,
     pub popup_state: crate::app::PopupState,
     pub active_users: Vec<String>,
+    /// each channel's latest authors, newest first, kept as messages arrive so the mention
+    /// popup doesn't go through every message on each key
+    #[serde(skip)]
+    pub recent_authors: HashMap<String, VecDeque<MentionCandidate>>,
     pub mention_query: String,
     pub selected_mention_index: usize,
     pub emoji_query: String,
@@ -97,6 +241,108 @@ pub struct AppState {
     pub last_message_counts: HashMap<String, usize>,
     #[serde(skip_serializing, skip_deserializing)]
     pub initial_load_complete: bool,
+    /// the channel the delete confirmation popup is asking about
+    #[serde(skip)]
+    pub channel_to_delete: Option<Channel>,
+    /// Some while the channel filter box is open, holds what's typed in it
+    #[serde(skip)]
+    pub channel_filter: Option<String>,
+    #[serde(skip)]
+    pub palette_query: String,
+    #[serde(skip)]
+    pub selected_palette_index: usize,
+    /// sent messages, oldest first, recalled with Up/Down in the input
+    #[serde(skip)]
+    pub input_history: Vec<String>,
+    /// which history entry is in the input right now, None when not browsing
+    #[serde(skip)]
+    pub input_history_index: Option<usize>,
+    /// what was typed before browsing started, given back when walking past the newest entry
+    #[serde(skip)]
+    pub input_history_draft: String,
+    /// last raw websocket frames, newest at the back, only filled in ws_debug mode
+    #[serde(skip)]
+    pub ws_log: VecDeque<WsFrame>,
+    /// when the last message went out, for the flood guard
+    #[serde(skip)]
+    pub last_sent_at: Option<tokio::time::Instant>,
+    /// a send was held back and the user was told, so holding Enter doesn't stack toasts
+    #[serde(skip)]
+    pub send_throttled: bool,
+    /// when the server last answered a keepalive ping, reset on connect
+    #[serde(skip)]
+    pub last_pong: Option<tokio::time::Instant>,
+    /// shared by every HTTP call, keeps the auth token in step with auth_token
+    #[serde(skip)]
+    pub api: crate::api::client::ApiClient,
+    #[serde(skip)]
+    pub profile_update: ProfileUpdate,
+    #[serde(skip)]
+    pub password_change: PasswordChange,
+    #[serde(skip)]
+    pub account_deletion: AccountDeletion,
+    /// file_id of the attachment picked with Left/Right in the messages pane
+    #[serde(skip)]
+    pub selected_attachment: Option<String>,
+    #[serde(skip)]
+    pub thumbnails: HashMap<String, Thumbnail>,
+    #[serde(skip)]
+    pub link_previews: HashMap<String, LinkPreviewState>,
+    #[serde(skip)]
+    pub emoji_picker: EmojiPickerState,
+    #[serde(skip)]
+    pub profile_switcher: ProfileSwitcherState,
+    #[serde(skip)]
+    pub channel_menu: ChannelMenuState,
+    #[serde(skip)]
+    pub keybindings: KeybindingsOverlay,
+    /// set once the server sent the channel list, until then the list shows a skeleton
+    #[serde(skip)]
+    pub channels_loaded: bool,
+    /// channels whose first page of history came in
+    #[serde(skip)]
+    pub history_loaded: HashSet<String>,
+    #[serde(skip)]
+    pub pending_goto: Option<PendingGoto>,
+    /// client_id of the message the next draw scrolls into view
+    #[serde(skip)]
+    pub scroll_to_message: Option<String>,
+    /// client_id of the message /goto landed on and when, drawn highlighted for GOTO_FLASH
+    #[serde(skip)]
+    pub message_flash: Option<(String, tokio::time::Instant)>,
+    /// client_ids of long messages opened past their preview
+    #[serde(skip)]
+    pub expanded_messages: HashSet<String>,
+    /// the lowest long message on screen, what Enter in the messages pane opens or folds
+    #[serde(skip)]
+    pub collapsible_in_view: Option<String>,
+    /// client_ids of messages whose spoilers were revealed
+    #[serde(skip)]
+    pub revealed_spoilers: HashSet<String>,
+    /// the lowest message on screen with a spoiler in it, what `s` reveals or hides
+    #[serde(skip)]
+    pub spoiler_in_view: Option<String>,
+    /// client_id of the message at the bottom edge of the view, what `y` copies
+    #[serde(skip)]
+    pub message_at_bottom: Option<String>,
+    /// whether the pinned strip lists every pin or just the count
+    #[serde(skip)]
+    pub pins_expanded: bool,
+    /// index into the channel's pins of the one last jumped to
+    #[serde(skip)]
+    pub selected_pin: Option<usize>,
+    /// the transcript is drawn as plain text over everything for the terminal's own selection
+    #[serde(skip)]
+    pub copy_mode: bool,
+    /// lines copy mode is scrolled up from the newest one
+    #[serde(skip)]
+    pub copy_mode_scroll: usize,
+    /// the message the inspector popup shows, taken when it opened
+    #[serde(skip)]
+    pub inspected_message: Option<BroadcastMessage>,
+    /// what the terminal supports, detected once at startup
+    #[serde(skip)]
+    pub capabilities: crate::tui::capabilities::TerminalCapabilities,
     pub config: Config,
 }
 
@@ -121,6 +367,7 @@ impl Default for AppState {
             themes: ThemesConfig::get_all_themes().unwrap(),
             last_theme_change_time: tokio::time::Instant::now(),
             active_users: Vec::new(),
+            recent_authors: HashMap::new(),
             selected_mention_index: 0,
             selected_emoji_index: 0,
             help_state: help::state::HelpState::default(),
@@ -158,6 +405,44 @@ impl Default for AppState {
             download_scroll_offset: 0,
             last_message_counts: HashMap::new(),
             initial_load_complete: false,
+            channel_to_delete: None,
+            channel_filter: None,
+            palette_query: String::new(),
+            selected_palette_index: 0,
+            input_history: Vec::new(),
+            input_history_index: None,
+            input_history_draft: String::new(),
+            ws_log: VecDeque::new(),
+            last_sent_at: None,
+            send_throttled: false,
+            last_pong: None,
+            api: crate::api::client::ApiClient::default(),
+            profile_update: ProfileUpdate::Idle,
+            password_change: PasswordChange::Idle,
+            account_deletion: AccountDeletion::Idle,
+            selected_attachment: None,
+            thumbnails: HashMap::new(),
+            link_previews: HashMap::new(),
+            emoji_picker: EmojiPickerState::default(),
+            profile_switcher: ProfileSwitcherState::default(),
+            channel_menu: ChannelMenuState::default(),
+            keybindings: KeybindingsOverlay::default(),
+            channels_loaded: false,
+            history_loaded: HashSet::new(),
+            pending_goto: None,
+            scroll_to_message: None,
+            message_flash: None,
+            expanded_messages: HashSet::new(),
+            collapsible_in_view: None,
+            revealed_spoilers: HashSet::new(),
+            spoiler_in_view: None,
+            message_at_bottom: None,
+            pins_expanded: false,
+            selected_pin: None,
+            copy_mode: false,
+            copy_mode_scroll: 0,
+            inspected_message: None,
+            capabilities: Default::default(),
             config: Config::default(),
         }
     }
@@ -171,6 +456,7 @@ impl AppState {
         let current_theme_name = config.current_theme_name.clone();
 
         let mut app_state = Self {
+            api: crate::api::client::ApiClient::new(auth_token.clone()),
             auth_token,
             username,
             user_icon,
@@ -183,10 +469,71 @@ impl AppState {
             // Fallback to default theme if the saved theme is not found
             app_state.themes.get(&ThemeName::CatppuccinMocha).unwrap()
         }).clone();
+        app_state.sync_do_not_disturb();
 
         app_state
     }
 
+    /// Ticks the toasts with the on-screen count from the config, the pages call it every tick.
+    pub fn update_notifications(&mut self) {
+        self.notification_manager
+            .update(self.config.max_notifications);
+    }
+
+    /// Lets the notification manager know about do not disturb and turns it off once its
+    /// time is up. Cheap, the pages call it every tick.
+    pub fn sync_do_not_disturb(&mut self) {
+        let now = chrono::Utc::now().timestamp();
+        if self.config.do_not_disturb && !self.config.do_not_disturb_active(now) {
+            self.config.set_do_not_disturb(false, now);
+        }
+        self.notification_manager
+            .set_do_not_disturb(self.config.do_not_disturb);
+    }
+
+    /// Title and body of a desktop notification for every message that mentions us since the
+    /// last call, and marks them seen. None while do not disturb is on, those mentions stay
+    /// quiet for good instead of ringing once it ends.
+    pub fn take_mention_bells(&mut self, now: i64) -> Vec<(String, String)> {
+        let Some(username) = self.username.clone() else {
+            self.set_initial_load_complete(true);
+            return Vec::new();
+        };
+        let username_mention = format!("@{}", username);
+        let ring = self.initial_load_complete && !self.config.do_not_disturb_active(now);
+        let mut bells = Vec::new();
+        let mut channels_to_update: Vec<(String, usize)> = Vec::new();
+
+        for (channel_id, messages) in &self.messages {
+            let current_message_count = messages.len();
+            let last_count = *self.last_message_counts.get(channel_id).unwrap_or(&0);
+
+            if ring && current_message_count > last_count {
+                for msg in messages.range(last_count..current_message_count) {
+                    if msg.user != username && msg.content.contains(&username_mention) {
+                        let channel_name = self
+                            .channels
+                            .iter()
+                            .find(|c| c.id == *channel_id)
+                            .map(|c| c.name.as_str())
+                            .unwrap_or("unknown");
+                        bells.push((
+                            format!(":O ⇶ New mention from {} in #{}", msg.user, channel_name),
+                            msg.content.clone(),
+                        ));
+                    }
+                }
+            }
+            channels_to_update.push((channel_id.clone(), current_message_count));
+        }
+
+        for (channel_id, count) in channels_to_update {
+            self.update_last_message_count(channel_id, count);
+        }
+        self.set_initial_load_complete(true);
+        bells
+    }
+
     pub fn update_last_message_count(&mut self, channel_id: String, count: usize) {
         self.last_message_counts.insert(channel_id, count);
     }
@@ -196,16 +543,81 @@ impl AppState {
     }
 
     pub fn set_user_auth(&mut self, token: String, username: String, icon: String) {
+        self.api.set_token(Some(token.clone()));
         self.auth_token = Some(token);
         self.username = Some(username);
         self.user_icon = Some(icon);
     }
 
+    /// Takes on a username/icon the server confirmed, in memory and in the config.
+    pub fn apply_profile(&mut self, username: String, icon: String) {
+        self.config.username = Some(username.clone());
+        self.config.user_icon = Some(icon.clone());
+        self.username = Some(username);
+        self.user_icon = Some(icon);
+        self.profile_update = ProfileUpdate::Idle;
+        // own messages are drawn with the old name and icon otherwise
+        self.rendered_messages.clear();
+    }
+
+    /// Gives up on a profile change the server never answered, false if it answered already.
+    pub fn expire_profile_update(&mut self, username: &str, icon: &str) -> bool {
+        match &self.profile_update {
+            ProfileUpdate::Sent {
+                username: sent_username,
+                icon: sent_icon,
+            } if sent_username == username && sent_icon == icon => {
+                self.profile_update =
+                    ProfileUpdate::Rejected("The server never answered, try again?".to_string());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves over to another saved account: its login and server replace the current ones
+    /// and everything loaded for the old account is dropped. Returns the page to continue on,
+    /// the chat reconnects from scratch when it's the chat again. A `Keyring` error still
+    /// switched, the token just stayed in the plain files.
+    pub async fn switch_profile(
+        &mut self,
+        name: &str,
+    ) -> Result<crate::app::TuiPage, crate::config::ConfigError> {
+        // the keyring blocks, so the config goes over to a blocking thread for the switch
+        let mut config = std::mem::take(&mut self.config);
+        let profile_name = name.to_string();
+        let (config, result) = tokio::task::spawn_blocking(move || {
+            let result = crate::config::profiles::switch_profile(&mut config, &profile_name);
+            (config, result)
+        })
+        .await
+        .unwrap();
+        self.config = config;
+        let keyring_error = match result {
+            Ok(()) => None,
+            Err(e @ crate::config::ConfigError::Keyring(_)) => Some(e),
+            Err(e) => return Err(e),
+        };
+        crate::api::server::set_profile_server(self.config.server.as_deref());
+        // logging out resets the theme, switching accounts shouldn't
+        let theme = self.current_theme.clone();
+        self.clear_user_auth().await;
+        self.current_theme = theme;
+        if let (Some(token), Some(username)) =
+            (self.config.token.clone(), self.config.username.clone())
+        {
+            let icon = self.config.user_icon.clone().unwrap_or_default();
+            self.set_user_auth(token, username, icon);
+        }
+        keyring_error.map_or(Ok(crate::app::TuiPage::start_page(&self.config)), Err)
+    }
+
     pub fn get_current_theme(&self) -> &Theme {
         &self.current_theme
     }
 
     pub async fn clear_user_auth(&mut self) {
+        self.api.set_token(None);
         self.auth_token = None;
         self.username = None;
         self.user_icon = None;
@@ -214,6 +626,9 @@ impl AppState {
         self.messages.clear();
         self.rendered_messages.clear();
         self.channel_history_state.clear();
+        self.channels_loaded = false;
+        self.history_loaded.clear();
+        self.recent_authors.clear();
         self.popup_state = PopupState::default();
         self.next_page = None;
 
@@ -250,6 +665,7 @@ impl AppState {
     pub fn add_message(&mut self, message: BroadcastMessage) {
         let channel_id = message.channel_id.clone();
         let message_id = message.client_id.clone().unwrap();
+        self.note_author(&channel_id, &message, true);
         let channel_messages = self.messages.entry(channel_id.clone()).or_default();
 
         // Check if the previous message needs re-rendering for grouping
@@ -285,6 +701,32 @@ impl AppState {
             .insert(message_id, true);
     }
 
+    // a new message's author moves to the front, older history only fills in behind
+    fn note_author(&mut self, channel_id: &str, message: &BroadcastMessage, newest: bool) {
+        let authors = self
+            .recent_authors
+            .entry(channel_id.to_string())
+            .or_default();
+        match authors.iter().position(|a| a.username == message.user) {
+            Some(_) if !newest => return,
+            Some(i) => {
+                authors.remove(i);
+            }
+            None if !newest && authors.len() >= RECENT_AUTHORS_LIMIT => return,
+            None => {}
+        }
+        let author = MentionCandidate {
+            username: message.user.clone(),
+            icon: Some(message.icon.clone()),
+        };
+        if newest {
+            authors.push_front(author);
+            authors.truncate(RECENT_AUTHORS_LIMIT);
+        } else {
+            authors.push_back(author);
+        }
+    }
+
     #[allow(dead_code)]
     pub fn prepend_history(&mut self, channel_id: &str, history: Vec<BroadcastMessage>) {
         if history.is_empty() {
@@ -294,6 +736,9 @@ impl AppState {
             return;
         }
 
+        for msg in history.iter().rev() {
+            self.note_author(channel_id, msg, false);
+        }
         let channel_messages = self.messages.entry(channel_id.to_string()).or_default();
         let needs_re_render_for_channel = self
             .needs_re_render
@@ -345,13 +790,155 @@ impl AppState {
     pub fn add_or_update_channel(&mut self, new_channel: Channel) {
         if let Some(pos) = self.channels.iter().position(|c| c.id == new_channel.id) {
             self.channels[pos] = new_channel;
+        } else if let Some(pos) = self
+            .channels
+            .iter()
+            .position(|c| c.pending && c.name == new_channel.name)
+        {
+            // the server confirmed a channel we already show, swap it in place
+            self.channels[pos] = new_channel.clone();
+            self.messages.entry(new_channel.id.clone()).or_default();
         } else {
             self.channels.push(new_channel.clone());
             self.messages.entry(new_channel.id.clone()).or_default();
         }
+        self.sort_channels();
+        self.channels_loaded = true;
+    }
+
+    /// Pinned channels first, then the user's order, then whatever order the server sent.
+    pub fn sort_channels(&mut self) {
+        let pinned = &self.config.pinned_channels;
+        let order = &self.config.channel_order;
+        self.channels.sort_by_key(|c| {
+            (
+                !pinned.contains(&c.id),
+                order.iter().position(|id| id == &c.id).unwrap_or(usize::MAX),
+            )
+        });
+    }
+
+    /// Indices into `channels` that match the channel filter (all of them when it's closed).
+    pub fn visible_channel_indices(&self) -> Vec<usize> {
+        let query = self.channel_filter.as_deref().unwrap_or("").to_lowercase();
+        self.channels
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.name.to_lowercase().contains(&query))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    pub fn is_channel_pinned(&self, channel_id: &str) -> bool {
+        self.config.pinned_channels.iter().any(|id| id == channel_id)
+    }
+
+    /// Pins or unpins a channel, returns where it ended up in the list.
+    pub fn toggle_channel_pin(&mut self, channel_id: &str) -> Option<usize> {
+        if let Some(pos) = self.config.pinned_channels.iter().position(|id| id == channel_id) {
+            self.config.pinned_channels.remove(pos);
+        } else {
+            self.config.pinned_channels.push(channel_id.to_string());
+        }
+        self.sort_channels();
+        self.channels.iter().position(|c| c.id == channel_id)
+    }
+
+    /// Swaps a channel with its neighbour (pinned ones only move among pinned ones) and
+    /// remembers the new order, returns its new index.
+    pub fn move_channel(&mut self, index: usize, up: bool) -> Option<usize> {
+        let target = if up { index.checked_sub(1)? } else { index + 1 };
+        let (a, b) = (self.channels.get(index)?, self.channels.get(target)?);
+        if a.pending
+            || b.pending
+            || self.is_channel_pinned(&a.id) != self.is_channel_pinned(&b.id)
+        {
+            return None;
+        }
+        self.channels.swap(index, target);
+        self.config.channel_order = self
+            .channels
+            .iter()
+            .filter(|c| !c.pending)
+            .map(|c| c.id.clone())
+            .collect();
+        Some(target)
+    }
+
+    /// Shows a channel right away while the server is still creating it, returns its temporary id.
+    pub fn add_pending_channel(&mut self, name: String, icon: String) -> String {
+        let id = format!("pending:{}", name);
+        if !self.channels.iter().any(|c| c.id == id) {
+            self.channels.push(Channel {
+                id: id.clone(),
+                name,
+                icon,
+                pending: true,
+            });
+        }
+        id
+    }
+
+    /// Drops a pending channel the server never confirmed, false if it was confirmed already.
+    pub fn remove_pending_channel(&mut self, channel_id: &str) -> bool {
+        let before = self.channels.len();
+        self.channels.retain(|c| !(c.pending && c.id == channel_id));
+        before != self.channels.len()
+    }
+
+    /// Which waiting request a server error answers. Errors carry no request id, so one is only
+    /// pinned on a request when it names what that request was about, a rate limit that comes
+    /// in while a channel is being created leaves the channel alone.
+    pub fn error_subject(&self, message: &str) -> Option<ServerErrorSubject> {
+        let lowercase = message.to_lowercase();
+        let names = |text: &str| !text.is_empty() && lowercase.contains(&text.to_lowercase());
+        if let Some(channel) = self.channels.iter().find(|c| c.pending && names(&c.name)) {
+            return Some(ServerErrorSubject::Channel(channel.name.clone()));
+        }
+        if let ProfileUpdate::Sent { username, .. } = &self.profile_update {
+            if names(username) || names("username") || names("icon") {
+                return Some(ServerErrorSubject::Profile);
+            }
+        }
+        if self.password_change == PasswordChange::Sent && names("password") {
+            return Some(ServerErrorSubject::Password);
+        }
+        if self.account_deletion == AccountDeletion::Sent && names("account") {
+            return Some(ServerErrorSubject::AccountDeletion);
+        }
+        None
+    }
+
+    /// Replaces the channel list with the server's, keeping pending channels it doesn't know yet.
+    pub fn set_channels(&mut self, channels: Vec<Channel>) {
+        let pending: Vec<Channel> = self
+            .channels
+            .drain(..)
+            .filter(|c| c.pending && !channels.iter().any(|s| s.name == c.name))
+            .collect();
+        self.channels = channels;
+        self.sort_channels();
+        self.channels.extend(pending);
+        self.channels_loaded = true;
+    }
+
+    /// Nothing came from the server yet, the channel list shows "Loading channels…".
+    pub fn channels_loading(&self) -> bool {
+        !self.channels_loaded && self.channels.is_empty()
+    }
+
+    /// The open channel has no messages and its history hasn't arrived, before there's a
+    /// channel at all that's the case until the channel list comes in.
+    pub fn messages_loading(&self) -> bool {
+        match &self.current_channel {
+            Some(channel) => {
+                !self.history_loaded.contains(&channel.id)
+                    && self.messages.get(&channel.id).is_none_or(|m| m.is_empty())
+            }
+            None => !self.channels_loaded,
+        }
     }
 
-    #[allow(dead_code)]
     pub fn remove_channel(&mut self, channel_id: &str) {
         self.channels.retain(|c| c.id != channel_id);
         if let Some(current) = &self.current_channel {
@@ -362,9 +949,430 @@ impl AppState {
         self.messages.remove(channel_id);
         self.rendered_messages.remove(channel_id);
         self.channel_history_state.remove(channel_id);
+        self.history_loaded.remove(channel_id);
         self.needs_re_render.remove(channel_id);
     }
 
+    pub fn push_input_history(&mut self, input: &str) {
+        self.input_history_index = None;
+        self.input_history_draft.clear();
+        if input.trim().is_empty() || self.input_history.last().is_some_and(|last| last == input) {
+            return;
+        }
+        self.input_history.push(input.to_string());
+        if self.input_history.len() > INPUT_HISTORY_LIMIT {
+            self.input_history.remove(0);
+        }
+    }
+
+    pub fn is_browsing_input_history(&self) -> bool {
+        self.input_history_index.is_some()
+    }
+
+    /// One step back in history, `current` is kept as the draft when browsing starts.
+    pub fn previous_input(&mut self, current: &str) -> Option<String> {
+        let index = match self.input_history_index {
+            Some(0) => return None,
+            Some(i) => i - 1,
+            None => {
+                self.input_history_draft = current.to_string();
+                self.input_history.len().checked_sub(1)?
+            }
+        };
+        self.input_history_index = Some(index);
+        self.input_history.get(index).cloned()
+    }
+
+    /// One step forward in history, past the newest entry gives the draft back.
+    pub fn next_input(&mut self) -> Option<String> {
+        let index = self.input_history_index? + 1;
+        if index < self.input_history.len() {
+            self.input_history_index = Some(index);
+            self.input_history.get(index).cloned()
+        } else {
+            self.input_history_index = None;
+            Some(std::mem::take(&mut self.input_history_draft))
+        }
+    }
+
+    /// ws_debug from the config, or REETUI_WS_DEBUG set in the environment.
+    pub fn ws_debug_enabled(&self) -> bool {
+        self.config.ws_debug || std::env::var_os("REETUI_WS_DEBUG").is_some()
+    }
+
+    /// Keeps a raw frame for the websocket log, with the auth token and the arguments of
+    /// password commands blanked out.
+    pub fn log_ws_frame(&mut self, direction: WsFrameDirection, text: &str, parsed: bool) {
+        if !self.ws_debug_enabled() {
+            return;
+        }
+        let mut text = match self.auth_token.as_deref() {
+            Some(token) if !token.is_empty() => text.replace(token, "<redacted>"),
+            _ => text.to_string(),
+        };
+        for command in SECRET_COMMANDS {
+            text = redact_command_arguments(&text, command);
+        }
+        if self.ws_log.len() >= WS_LOG_LIMIT {
+            self.ws_log.pop_front();
+        }
+        self.ws_log.push_back(WsFrame {
+            direction,
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            text,
+            parsed,
+        });
+    }
+
+    /// Moves the attachment selection through the file messages of the current channel,
+    /// starting from the newest one. Returns the message that is selected now.
+    pub fn step_attachment_selection(&mut self, forward: bool) -> Option<BroadcastMessage> {
+        let channel_id = self.current_channel.as_ref()?.id.clone();
+        let attachments: Vec<&BroadcastMessage> = self
+            .messages
+            .get(&channel_id)?
+            .iter()
+            .filter(|m| m.file_id.is_some())
+            .collect();
+        let current = attachments
+            .iter()
+            .position(|m| m.file_id == self.selected_attachment);
+        let next = match current {
+            Some(i) if forward => (i + 1).min(attachments.len() - 1),
+            Some(i) => i.saturating_sub(1),
+            None => attachments.len().checked_sub(1)?,
+        };
+        let previous_id = current.and_then(|i| attachments[i].client_id.clone());
+        let selected = attachments[next].clone();
+        self.selected_attachment = selected.file_id.clone();
+
+        // both the old and the new selection look different now
+        let needs_re_render = self.needs_re_render.entry(channel_id).or_default();
+        for message_id in previous_id.into_iter().chain(selected.client_id.clone()) {
+            needs_re_render.insert(message_id, true);
+        }
+        Some(selected)
+    }
+
+    /// Toast text for a message that arrived while settings (or its help screen) hide the chat,
+    /// None when the message is on screen anyway or is our own.
+    pub fn hidden_message_notice(&self, message: &BroadcastMessage) -> Option<String> {
+        if !self.show_settings || self.username.as_deref() == Some(message.user.as_str()) {
+            return None;
+        }
+        let channel = self
+            .channels
+            .iter()
+            .find(|c| c.id == message.channel_id)
+            .map_or(message.channel_name.as_str(), |c| c.name.as_str());
+        let preview = if message.file_id.is_some() {
+            "sent a file".to_string()
+        } else {
+            let mut preview: String = message.content.chars().take(NOTICE_PREVIEW_CHARS).collect();
+            if message.content.chars().count() > NOTICE_PREVIEW_CHARS {
+                preview.push('…');
+            }
+            preview
+        };
+        Some(format!("{} in {}: {}", message.user, channel, preview))
+    }
+
+    /// (file_id, file_name) of every file shared in the current channel, oldest first.
+    pub fn channel_attachments(&self) -> Vec<(String, String)> {
+        let Some(messages) = self
+            .current_channel
+            .as_ref()
+            .and_then(|channel| self.messages.get(&channel.id))
+        else {
+            return Vec::new();
+        };
+        let mut seen = std::collections::HashSet::new();
+        messages
+            .iter()
+            .filter_map(|m| {
+                let file_id = m.file_id.clone()?;
+                seen.insert(file_id.clone()).then(|| {
+                    let file_name = m.file_name.clone().unwrap_or_else(|| file_id.clone());
+                    (file_id, file_name)
+                })
+            })
+            .collect()
+    }
+
+    /// Redraws every message showing this attachment, once its thumbnail changed.
+    pub fn refresh_attachment(&mut self, file_id: &str) {
+        for (channel_id, messages) in &self.messages {
+            for message in messages {
+                if message.file_id.as_deref() == Some(file_id) {
+                    if let Some(message_id) = &message.client_id {
+                        self.needs_re_render
+                            .entry(channel_id.clone())
+                            .or_default()
+                            .insert(message_id.clone(), true);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Looks for a message of the current channel by its server id (or client_id). When it's
+    /// loaded it gets scrolled to and flashed, otherwise the caller fetches the page this
+    /// returns and `resume_goto` tries again once it's in.
+    pub fn goto_message(&mut self, message_id: &str) -> GotoOutcome {
+        self.pending_goto = None;
+        let Some(channel_id) = self.current_channel.as_ref().map(|c| c.id.clone()) else {
+            return GotoOutcome::NotFound;
+        };
+        let messages = self.messages.get(&channel_id);
+        let found = messages.and_then(|messages| {
+            messages.iter().find(|m| {
+                m.id.as_deref() == Some(message_id) || m.client_id.as_deref() == Some(message_id)
+            })
+        });
+        if let Some(client_id) = found.and_then(|m| m.client_id.clone()) {
+            self.scroll_to_message = Some(client_id.clone());
+            self.message_flash = Some((client_id, tokio::time::Instant::now()));
+            self.chat_focused_pane = ChatFocusedPane::Messages;
+            return GotoOutcome::Found;
+        }
+        let offset = messages.map_or(0, |m| m.len());
+        let has_more = self
+            .channel_history_state
+            .get(&channel_id)
+            .is_none_or(|&(_, has_more, _)| has_more);
+        if !has_more {
+            return GotoOutcome::NotFound;
+        }
+        self.pending_goto = Some(PendingGoto {
+            channel_id: channel_id.clone(),
+            message_id: message_id.to_string(),
+        });
+        GotoOutcome::Fetch { channel_id, offset }
+    }
+
+    /// Whether an older page of this channel should be prepended, the first page always is,
+    /// later ones only when a /goto asked for them.
+    pub fn wants_history(&self, channel_id: &str) -> bool {
+        self.messages.get(channel_id).is_none_or(|m| m.is_empty())
+            || self
+                .pending_goto
+                .as_ref()
+                .is_some_and(|goto| goto.channel_id == channel_id)
+    }
+
+    /// Runs the waiting /goto again after history for `channel_id` came in. None when nothing
+    /// was waiting on it or the user moved to another channel meanwhile.
+    pub fn resume_goto(&mut self, channel_id: &str) -> Option<GotoOutcome> {
+        let goto = self
+            .pending_goto
+            .take_if(|goto| goto.channel_id == channel_id)?;
+        if self.current_channel.as_ref().map(|c| c.id.as_str()) != Some(channel_id) {
+            return None;
+        }
+        Some(self.goto_message(&goto.message_id))
+    }
+
+    /// Opens the long message at the bottom of the view past its preview, or folds it back.
+    /// False when there's no long message on screen.
+    pub fn toggle_collapsible_in_view(&mut self) -> bool {
+        let Some(message_id) = self.collapsible_in_view.clone() else {
+            return false;
+        };
+        if !self.expanded_messages.remove(&message_id) {
+            self.expanded_messages.insert(message_id);
+        }
+        true
+    }
+
+    /// Reveals the spoilers of the spoiler message on screen, or hides them again. False when
+    /// there's none in view.
+    pub fn toggle_spoiler_in_view(&mut self) -> bool {
+        let Some(message_id) = self.spoiler_in_view.clone() else {
+            return false;
+        };
+        if !self.revealed_spoilers.remove(&message_id) {
+            self.revealed_spoilers.insert(message_id.clone());
+        }
+        if let Some(channel) = &self.current_channel {
+            self.needs_re_render
+                .entry(channel.id.clone())
+                .or_default()
+                .insert(message_id, true);
+        }
+        true
+    }
+
+    pub fn bottom_message(&self) -> Option<&BroadcastMessage> {
+        let message_id = self.message_at_bottom.as_deref()?;
+        self.messages
+            .get(&self.current_channel.as_ref()?.id)?
+            .iter()
+            .find(|m| m.client_id.as_deref() == Some(message_id))
+    }
+
+    /// The message the details panel describes: the picked attachment's, otherwise the one at
+    /// the bottom of the view.
+    pub fn detail_message(&self) -> Option<&BroadcastMessage> {
+        let messages = self.messages.get(&self.current_channel.as_ref()?.id)?;
+        self.selected_attachment
+            .as_ref()
+            .and_then(|file_id| {
+                messages
+                    .iter()
+                    .find(|msg| msg.file_id.as_ref() == Some(file_id))
+            })
+            .or_else(|| self.bottom_message())
+    }
+
+    /// Text of the message at the bottom of the view for the clipboard, spoilers included
+    /// whether they were revealed or not.
+    pub fn message_at_bottom_text(&self) -> Option<String> {
+        Some(crate::tui::chat::message_parsing::without_spoiler_markers(
+            &self.bottom_message()?.content,
+        ))
+    }
+
+    /// Pinned messages of the current channel, oldest first.
+    pub fn current_pins(&self) -> &[PinnedMessage] {
+        self.current_channel
+            .as_ref()
+            .and_then(|channel| self.config.pinned_messages.get(&channel.id))
+            .map_or(&[], Vec::as_slice)
+    }
+
+    /// Pins the message at the bottom of the view, or unpins it when it already is. Some(true)
+    /// when it got pinned, None when there's no message there.
+    pub fn toggle_bottom_message_pin(&mut self) -> Option<bool> {
+        let message = self.bottom_message()?;
+        let pin = PinnedMessage {
+            id: message.id.clone().or_else(|| message.client_id.clone())?,
+            user: message.user.clone(),
+            preview: crate::tui::chat::message_parsing::pin_preview(&message.content),
+        };
+        let channel_id = self.current_channel.as_ref()?.id.clone();
+        let pins = self
+            .config
+            .pinned_messages
+            .entry(channel_id.clone())
+            .or_default();
+        let pinned = match pins.iter().position(|p| p.id == pin.id) {
+            Some(index) => {
+                pins.remove(index);
+                false
+            }
+            None => {
+                pins.push(pin);
+                true
+            }
+        };
+        if pins.is_empty() {
+            self.config.pinned_messages.remove(&channel_id);
+        }
+        self.selected_pin = None;
+        Some(pinned)
+    }
+
+    /// Selects the next (or previous) pin of the channel, wrapping around, and jumps to it
+    /// like /goto does. None when the channel has no pins.
+    pub fn jump_to_pin(&mut self, forward: bool) -> Option<GotoOutcome> {
+        let count = self.current_pins().len();
+        if count == 0 {
+            return None;
+        }
+        // the selection may be left over from another channel
+        let index = match self.selected_pin.filter(|&i| i < count) {
+            Some(i) if forward => (i + 1) % count,
+            Some(i) => (i + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        self.selected_pin = Some(index);
+        let id = self.current_pins()[index].id.clone();
+        Some(self.goto_message(&id))
+    }
+
+    /// Opens the inspector on the message at the bottom of the view.
+    pub fn inspect_bottom_message(&mut self) {
+        self.inspected_message = self.bottom_message().cloned();
+        self.log_scroll_offset = 0;
+        self.popup_state.show = true;
+        self.popup_state.popup_type = PopupType::MessageInspector;
+    }
+
+    /// The accent a channel was given, None when it goes with the theme's.
+    pub fn channel_accent(&self, channel_id: &str) -> Option<Rgb> {
+        self.config.channel_accents.get(channel_id).copied()
+    }
+
+    /// Sets or (with None) clears a channel's accent. Its messages are drawn again, the
+    /// headers carry the color.
+    pub fn set_channel_accent(&mut self, channel_id: &str, accent: Option<Rgb>) {
+        match accent {
+            Some(accent) => {
+                self.config
+                    .channel_accents
+                    .insert(channel_id.to_string(), accent);
+            }
+            None => {
+                self.config.channel_accents.remove(channel_id);
+            }
+        }
+        self.rendered_messages.remove(channel_id);
+    }
+
+    pub fn open_channel_menu(&mut self, channel: Channel) {
+        let current = self.channel_accent(&channel.id);
+        self.channel_menu = ChannelMenuState::open(channel, current);
+        self.popup_state.show = true;
+        self.popup_state.popup_type = PopupType::ChannelMenu;
+    }
+
+    /// Copy mode starts at the newest messages whatever the pane was scrolled to.
+    pub fn enter_copy_mode(&mut self) {
+        self.copy_mode = true;
+        self.copy_mode_scroll = 0;
+    }
+
+    /// Whether a message can go out at `now`, false when it's too soon after the last one.
+    /// Only sends that go through count, so a held Enter still gets one through per interval.
+    pub fn claim_send_slot(&mut self, now: tokio::time::Instant) -> bool {
+        let interval = Duration::from_millis(self.config.min_send_interval_ms);
+        if self
+            .last_sent_at
+            .is_some_and(|last| now.duration_since(last) < interval)
+        {
+            return false;
+        }
+        self.last_sent_at = Some(now);
+        self.send_throttled = false;
+        true
+    }
+
+    /// Back to the newest messages after sending one, unless the user turned that off. There's
+    /// one offset for the shown channel, switching channels starts at the bottom anyway.
+    pub fn scroll_to_bottom_after_send(&mut self) {
+        if self.config.scroll_on_send {
+            self.message_scroll_offset = 0;
+            // a /goto still waiting to scroll would pull the view away again
+            self.scroll_to_message = None;
+        }
+    }
+
+    /// Keeps the scroll offset within the messages we have for the current view height.
+    pub fn clamp_message_scroll(&mut self) {
+        let max_offset = self
+            .total_chat_buffer_length
+            .saturating_sub(self.last_chat_view_height);
+        self.message_scroll_offset = self.message_scroll_offset.min(max_offset);
+    }
+
+    /// The cached message lines were wrapped for the old width, the next draw wraps them again
+    /// and re-clamps the scroll against the new height.
+    pub fn handle_resize(&mut self) {
+        self.rendered_messages.clear();
+        self.clamp_message_scroll();
+    }
+
     pub fn scroll_messages_up(&mut self, scroll_amount: usize) {
         let max_offset = self
             .total_chat_buffer_length
@@ -458,3 +1466,27 @@ impl AppState {
             .insert(message_id, true);
     }
 }
+
+// everything after `command` up to the end of the json string it sits in (or of the text)
+fn redact_command_arguments(text: &str, command: &str) -> String {
+    let needle = format!("{} ", command);
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(found) = rest.find(&needle) {
+        let arguments = found + needle.len();
+        redacted.push_str(&rest[..arguments]);
+        redacted.push_str("<redacted>");
+        let mut escaped = false;
+        let end = rest[arguments..]
+            .char_indices()
+            .find(|&(_, c)| {
+                let closes = c == '"' && !escaped;
+                escaped = c == '\\' && !escaped;
+                closes
+            })
+            .map_or(rest.len(), |(i, _)| arguments + i);
+        rest = &rest[end..];
+    }
+    redacted.push_str(rest);
+    redacted
+}
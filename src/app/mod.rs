@@ -9,6 +9,20 @@ pub enum TuiPage {
     Help,
 }
 
+impl TuiPage {
+    /// Where a launch (or the home screen) leads: the tutorial once, then login until there
+    /// is a token, then straight to the chat.
+    pub fn start_page(config: &crate::config::Config) -> Self {
+        if !config.tutorial_seen {
+            TuiPage::Help
+        } else if config.token.is_none() {
+            TuiPage::Auth
+        } else {
+            TuiPage::Chat
+        }
+    }
+}
+
 pub mod app_state;
 pub use app_state::AppState;
 
@@ -18,15 +32,38 @@ pub enum PopupType {
     Deconnection,
     Mentions,
     Emojis,
+    EmojiPicker,
+    Profiles,
     FileManager,
     DownloadProgress,
-    
+    UnknownCommand,
+    DeleteChannel,
+    ChannelMenu,
+    CommandPalette,
+    WsLog,
+    MessageInspector,
     Settings,
     Downloads,
     None,
     Notification,
 }
 
+impl PopupType {
+    /// Popups whose keys go to a text field (or straight into the input), the only ones a
+    /// paste is typed into. Elsewhere its letters would be shortcuts, a `y` answering a prompt.
+    pub fn takes_text(self) -> bool {
+        matches!(
+            self,
+            PopupType::CreateChannel
+                | PopupType::Mentions
+                | PopupType::Emojis
+                | PopupType::EmojiPicker
+                | PopupType::Profiles
+                | PopupType::CommandPalette
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct PopupState {
     pub show: bool,
@@ -0,0 +1,16 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about = "A cozy terminal chat client 󰭹")]
+pub struct Cli {
+    /// Use this config file instead of the default one (REETUI_CONFIG works too)
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Talk to another server, the WebSocket URL is derived from it (https -> wss)
+    #[arg(long, value_name = "URL")]
+    pub server: Option<String>,
+    /// Show troubleshooting notices on startup, like where the config is read from
+    #[arg(long)]
+    pub debug: bool,
+}
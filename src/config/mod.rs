@@ -1,36 +1,545 @@
+use crate::themes::Rgb;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+pub mod profiles;
+pub mod token_store;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TokenStorage {
+    /// in reetui.json next to everything else
+    #[default]
+    PlainFile,
+    /// in the OS keyring, the json only says so
+    Keyring,
+}
+
+/// Corner the notification toasts stack up in.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NotificationAnchor {
+    #[default]
+    TopRight,
+    TopLeft,
+    BottomRight,
+    BottomLeft,
+}
+
+/// Shape of the text cursor while typing a message.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CursorShape {
+    /// whatever the terminal is set up with
+    #[default]
+    Default,
+    Block,
+    Bar,
+    Underline,
+}
+
+/// How copying reaches the clipboard.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClipboardMode {
+    /// the system clipboard, OSC 52 over SSH or when that can't be reached
+    #[default]
+    Auto,
+    /// always through the terminal with OSC 52
+    Osc52,
+}
+
+/// Whether RGB colors are sent as they are or brought down to the 256 color palette.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrueColorMode {
+    /// whatever `COLORTERM` says, truecolor when it says nothing
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+/// A message pinned in a channel. The preview is kept so the pinned strip has something to
+/// show before (or without) the message being loaded.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PinnedMessage {
+    /// server id when the message has one, its client_id otherwise
+    pub id: String,
+    pub user: String,
+    pub preview: String,
+}
+
+/// Toast colors that win over the theme's, per notification type. Unset ones follow the theme.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct NotificationColors {
+    #[serde(default)]
+    pub success: Option<Rgb>,
+    #[serde(default)]
+    pub warning: Option<Rgb>,
+    #[serde(default)]
+    pub error: Option<Rgb>,
+    #[serde(default)]
+    pub info: Option<Rgb>,
+    #[serde(default)]
+    pub loading: Option<Rgb>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
 pub struct Config {
+    /// schema version, files older than `CONFIG_VERSION` get migrated on load
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default)]
     pub tutorial_seen: bool,
+    #[serde(default)]
     pub token: Option<String>,
+    #[serde(default)]
     pub username: Option<String>,
+    #[serde(default)]
     pub user_icon: Option<String>,
+    /// API server of the active account, None means the hosted one
+    #[serde(default)]
+    pub server: Option<String>,
+    /// which file under `profiles/` the login above belongs to
+    #[serde(default = "default_active_profile")]
+    pub active_profile: String,
+    #[serde(default)]
     pub current_theme_name: crate::themes::ThemeName,
+    /// blank lines between two message groups from different authors (0 or 1)
+    #[serde(default = "default_message_group_spacing")]
+    pub message_group_spacing: u16,
+    /// the `%H:%M` time next to each author, when off the messages pane shows the full time of
+    /// the message at the bottom of the view instead
+    #[serde(default = "default_show_timestamps")]
+    pub show_timestamps: bool,
+    /// the details panel beside the messages, toggled with I in the messages pane
+    #[serde(default)]
+    pub show_message_details: bool,
+    /// a faint background on every other message group, off by default since it can look busy
+    #[serde(default)]
+    pub zebra_stripes: bool,
+    /// messages taking more lines than this show a preview until expanded, 0 means never
+    #[serde(default)]
+    pub collapse_after_lines: usize,
+    /// fetch the title of linked pages for a card under the message, off by default since
+    /// every link then gets a request from this machine
+    #[serde(default)]
+    pub link_previews: bool,
+    /// max message length in graphemes, 0 means no limit
+    #[serde(default = "default_max_message_length")]
+    pub max_message_length: usize,
+    /// `/name` -> text, expanded in the input when followed by space or enter
+    #[serde(default = "default_macros")]
+    pub macros: HashMap<String, String>,
+    /// show the final state of every animation right away (title sheen, home logo frames,
+    /// help gauge and typewriter, GIF previews), nice on slow terminals / ssh
+    #[serde(default)]
+    pub reduce_motion: bool,
+    /// draw with the terminal's default colors only, also forced by the NO_COLOR env var
+    #[serde(default)]
+    pub no_color: bool,
+    /// plain characters for icons when no Nerd Font was found, off for fonts fontconfig
+    /// doesn't know about
+    #[serde(default = "default_icon_fallback")]
+    pub icon_fallback: bool,
+    /// 24-bit colors, for terminals that do them without saying so (or say so wrongly)
+    #[serde(default)]
+    pub truecolor: TrueColorMode,
+    /// directories bookmarked in the file manager
+    #[serde(default)]
+    pub file_bookmarks: Vec<PathBuf>,
+    /// channel ids in the order the user arranged them, unknown channels go after
+    #[serde(default)]
+    pub channel_order: Vec<String>,
+    /// channel ids always shown at the top of the list
+    #[serde(default)]
+    pub pinned_channels: Vec<String>,
+    /// pinned messages per channel id, oldest pin first
+    #[serde(default)]
+    pub pinned_messages: HashMap<String, Vec<PinnedMessage>>,
+    /// accent color per channel id, for the messages pane border and headers, the theme's
+    /// accent when a channel has none
+    #[serde(default)]
+    pub channel_accents: HashMap<String, Rgb>,
+    /// wrap messages at this column instead of the full pane width, None means full width
+    #[serde(default)]
+    pub max_message_width: Option<u16>,
+    #[serde(default)]
+    pub cursor_shape: CursorShape,
+    /// only for a shape picked here, the terminal's default keeps its own blinking
+    #[serde(default = "default_cursor_blink")]
+    pub cursor_blink: bool,
+    /// jump to the newest messages after sending one, off keeps the view where it was
+    #[serde(default = "default_scroll_on_send")]
+    pub scroll_on_send: bool,
+    /// sends closer together than this are held back, 0 lets everything through
+    #[serde(default = "default_min_send_interval_ms")]
+    pub min_send_interval_ms: u64,
+    /// ask before sending a `/something` nobody knows as a plain message, off sends it right away
+    #[serde(default = "default_confirm_unknown_commands")]
+    pub confirm_unknown_commands: bool,
+    /// Tab in the channel list opens the next channel instead of moving to the next pane
+    #[serde(default)]
+    pub tab_switches_channels: bool,
+    /// width of the channel list in percent of the terminal, Alt+Left/Right while it's focused
+    #[serde(default = "default_channel_pane_percent")]
+    pub channel_pane_percent: u16,
+    /// keep the last raw websocket frames around for the log popup (REETUI_WS_DEBUG works too)
+    #[serde(default)]
+    pub ws_debug: bool,
+    /// troubleshooting notices on startup, like where the config is read from (--debug works too)
+    #[serde(default)]
+    pub debug: bool,
+    /// how long to wait for the websocket before giving up on the server
+    #[serde(default = "default_ws_connect_timeout_secs")]
+    pub ws_connect_timeout_secs: u64,
+    /// seconds between keepalive pings, two missed pongs in a row mean the connection is dead,
+    /// 0 turns the keepalive off
+    #[serde(default = "default_heartbeat_interval_secs")]
+    pub heartbeat_interval_secs: u64,
+    /// where the auth token is kept between runs
+    #[serde(default)]
+    pub token_storage: TokenStorage,
+    /// small chafa preview under the selected image attachment (Left/Right in the messages pane)
+    #[serde(default = "default_inline_thumbnails")]
+    pub inline_thumbnails: bool,
+    #[serde(default)]
+    pub notification_anchor: NotificationAnchor,
+    /// e.g. `"notification_colors": { "error": [255, 128, 0] }`
+    #[serde(default)]
+    pub notification_colors: NotificationColors,
+    #[serde(default)]
+    pub clipboard: ClipboardMode,
+    /// toasts on screen at once, the rest wait behind a "+N more" line
+    #[serde(default = "default_max_notifications")]
+    pub max_notifications: usize,
+    /// how long each home page logo frame stays up
+    #[serde(default = "default_home_frame_duration_ms")]
+    pub home_frame_duration_ms: u64,
+    /// pause before the home page moves on after a key press, 0 continues right away
+    #[serde(default)]
+    pub home_intro_delay_ms: u64,
+    /// only error toasts get through while this is on
+    #[serde(default)]
+    pub do_not_disturb: bool,
+    /// unix time do not disturb turns itself off at, None keeps it on until turned off
+    #[serde(default)]
+    pub do_not_disturb_until: Option<i64>,
+    /// how long do not disturb lasts when turned on, 0 means until turned off
+    #[serde(default)]
+    pub do_not_disturb_minutes: u64,
+}
+
+fn default_active_profile() -> String {
+    profiles::DEFAULT_PROFILE.to_string()
+}
+
+fn default_message_group_spacing() -> u16 {
+    1
+}
+
+fn default_show_timestamps() -> bool {
+    true
+}
+
+fn default_max_message_length() -> usize {
+    2000
+}
+
+pub const CONFIG_VERSION: u32 = 1;
+
+/// `MIGRATIONS[n]` upgrades a version n file to n + 1, working on the raw JSON so fields that
+/// were renamed or changed type can be carried over instead of failing the whole file.
+const MIGRATIONS: &[fn(&mut serde_json::Map<String, serde_json::Value>)] = &[migrate_v0_to_v1];
+
+// v0 is everything from before the version field existed
+fn migrate_v0_to_v1(config: &mut serde_json::Map<String, serde_json::Value>) {
+    // a theme this build doesn't have anymore used to throw away the token along with it
+    let theme_known = config.get("current_theme_name").is_some_and(|theme| {
+        serde_json::from_value::<crate::themes::ThemeName>(theme.clone()).is_ok()
+    });
+    if !theme_known {
+        config.insert(
+            "current_theme_name".to_string(),
+            serde_json::to_value(crate::themes::ThemeName::default()).unwrap_or_default(),
+        );
+    }
+}
+
+/// Brings a raw config up to `CONFIG_VERSION`, returns the version it started at. Files from
+/// a newer build are left alone.
+pub fn migrate_config(raw: &mut serde_json::Value) -> u32 {
+    let Some(config) = raw.as_object_mut() else {
+        return CONFIG_VERSION;
+    };
+    let from_version = config
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .map_or(0, |v| v as u32);
+    for migration in MIGRATIONS.iter().skip(from_version as usize) {
+        migration(config);
+    }
+    if from_version < CONFIG_VERSION {
+        config.insert("version".to_string(), CONFIG_VERSION.into());
+    }
+    from_version
+}
+
+pub const CHANNEL_PANE_PERCENT_RANGE: std::ops::RangeInclusive<u16> = 10..=40;
+
+fn default_channel_pane_percent() -> u16 {
+    20
+}
+
+fn default_ws_connect_timeout_secs() -> u64 {
+    10
+}
+
+fn default_heartbeat_interval_secs() -> u64 {
+    15
+}
+
+fn default_icon_fallback() -> bool {
+    true
+}
+
+fn default_cursor_blink() -> bool {
+    true
 }
 
-pub fn get_config_path() -> PathBuf {
-    let mut config_dir = dirs::config_dir().unwrap();
-    config_dir.push("reetui");
-    fs::create_dir_all(&config_dir).unwrap();
-    config_dir.push("reetui.json");
-    config_dir
+fn default_scroll_on_send() -> bool {
+    true
+}
+
+// short enough that nobody typing notices, long enough to stop a held Enter
+fn default_min_send_interval_ms() -> u64 {
+    150
+}
+
+fn default_confirm_unknown_commands() -> bool {
+    true
+}
+
+fn default_inline_thumbnails() -> bool {
+    true
+}
+
+fn default_max_notifications() -> usize {
+    5
+}
+
+fn default_home_frame_duration_ms() -> u64 {
+    crate::tui::home::page::FRAME_DURATION_MS
+}
+
+fn default_macros() -> HashMap<String, String> {
+    [
+        ("shrug", "¯\\_(ツ)_/¯"),
+        ("tableflip", "(╯°□°)╯︵ ┻━┻"),
+        ("unflip", "┬─┬ノ( º _ ºノ)"),
+        ("lenny", "( ͡° ͜ʖ ͡°)"),
+    ]
+    .into_iter()
+    .map(|(name, text)| (name.to_string(), text.to_string()))
+    .collect()
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            tutorial_seen: false,
+            token: None,
+            username: None,
+            user_icon: None,
+            server: None,
+            active_profile: default_active_profile(),
+            current_theme_name: crate::themes::ThemeName::default(),
+            message_group_spacing: default_message_group_spacing(),
+            show_timestamps: default_show_timestamps(),
+            show_message_details: false,
+            zebra_stripes: false,
+            collapse_after_lines: 0,
+            link_previews: false,
+            max_message_length: default_max_message_length(),
+            macros: default_macros(),
+            reduce_motion: false,
+            no_color: false,
+            icon_fallback: default_icon_fallback(),
+            truecolor: TrueColorMode::Auto,
+            file_bookmarks: Vec::new(),
+            channel_order: Vec::new(),
+            pinned_channels: Vec::new(),
+            pinned_messages: HashMap::new(),
+            channel_accents: HashMap::new(),
+            max_message_width: None,
+            cursor_shape: CursorShape::default(),
+            cursor_blink: default_cursor_blink(),
+            scroll_on_send: default_scroll_on_send(),
+            min_send_interval_ms: default_min_send_interval_ms(),
+            confirm_unknown_commands: default_confirm_unknown_commands(),
+            tab_switches_channels: false,
+            channel_pane_percent: default_channel_pane_percent(),
+            ws_debug: false,
+            debug: false,
+            ws_connect_timeout_secs: default_ws_connect_timeout_secs(),
+            heartbeat_interval_secs: default_heartbeat_interval_secs(),
+            token_storage: TokenStorage::default(),
+            inline_thumbnails: default_inline_thumbnails(),
+            notification_anchor: NotificationAnchor::default(),
+            notification_colors: NotificationColors::default(),
+            clipboard: ClipboardMode::default(),
+            max_notifications: default_max_notifications(),
+            home_frame_duration_ms: default_home_frame_duration_ms(),
+            home_intro_delay_ms: 0,
+            do_not_disturb: false,
+            do_not_disturb_until: None,
+            do_not_disturb_minutes: 0,
+        }
+    }
+}
+
+impl Config {
+    /// Turns do not disturb on or off at unix time `now`, on starts the
+    /// `do_not_disturb_minutes` countdown.
+    pub fn set_do_not_disturb(&mut self, on: bool, now: i64) {
+        self.do_not_disturb = on;
+        self.do_not_disturb_until = (on && self.do_not_disturb_minutes > 0)
+            .then(|| now + self.do_not_disturb_minutes as i64 * 60);
+    }
+
+    pub fn do_not_disturb_active(&self, now: i64) -> bool {
+        self.do_not_disturb && self.do_not_disturb_until.is_none_or(|until| now < until)
+    }
+
+    pub fn no_color_active(&self) -> bool {
+        self.no_color || crate::themes::no_color_env()
+    }
+}
+
+static CONFIG_PATH_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Use `path` instead of the default config file from now on (set from `--config`).
+pub fn set_config_path(path: PathBuf) {
+    let _ = CONFIG_PATH_OVERRIDE.set(path);
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    NoConfigDir,
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, serde_json::Error),
+    Keyring(keyring::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::NoConfigDir => write!(
+                f,
+                "Couldn't find a config directory (is HOME set?), use --config or REETUI_CONFIG"
+            ),
+            ConfigError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            ConfigError::Parse(path, e) => {
+                write!(f, "{} isn't valid config JSON: {}", path.display(), e)
+            }
+            ConfigError::Keyring(e) => write!(
+                f,
+                "Couldn't put the token in the keyring, kept it in the config file: {}",
+                e
+            ),
+        }
+    }
+}
+
+/// `--config`, then `REETUI_CONFIG`, then `<config dir>/reetui/reetui.json`. The parent
+/// directory is created so saving to a fresh location just works.
+pub fn get_config_path() -> Result<PathBuf, ConfigError> {
+    let config_path = match CONFIG_PATH_OVERRIDE.get().cloned().or_else(|| {
+        std::env::var_os("REETUI_CONFIG")
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+    }) {
+        Some(path) => path,
+        None => {
+            let mut config_dir = dirs::config_dir().ok_or(ConfigError::NoConfigDir)?;
+            config_dir.push("reetui");
+            config_dir.push("reetui.json");
+            config_dir
+        }
+    };
+    if let Some(parent) = config_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| ConfigError::Io(parent.to_path_buf(), e))?;
+    }
+    Ok(config_path)
+}
+
+/// The saved config, or the defaults when there's none yet. The error next to it is set when
+/// an older file was migrated but couldn't be backed up or written back: the migrated config
+/// is still the one to use, the file on disk just stays old until the next save.
+pub fn load_config() -> Result<(Config, Option<ConfigError>), ConfigError> {
+    load_config_from(&get_config_path()?)
+}
+
+pub fn load_config_from(config_path: &Path) -> Result<(Config, Option<ConfigError>), ConfigError> {
+    if !config_path.exists() {
+        return Ok((Config::default(), None));
+    }
+    let config_data = fs::read_to_string(config_path)
+        .map_err(|e| ConfigError::Io(config_path.to_path_buf(), e))?;
+    let parse_error = |e| ConfigError::Parse(config_path.to_path_buf(), e);
+    let mut raw: serde_json::Value = serde_json::from_str(&config_data).map_err(parse_error)?;
+    let from_version = migrate_config(&mut raw);
+    let config: Config = serde_json::from_value(raw).map_err(parse_error)?;
+    let mut migration_error = None;
+    if from_version < CONFIG_VERSION {
+        // keep the old file around in case the migration got something wrong, no backup
+        // means no rewrite either
+        let backup_path = config_path.with_extension(format!("json.v{}.bak", from_version));
+        migration_error = fs::copy(config_path, &backup_path)
+            .map_err(|e| ConfigError::Io(backup_path, e))
+            .and_then(|_| save_config_to(&config, config_path))
+            .err();
+    }
+    Ok((config, migration_error))
+}
+
+pub fn save_config(config: &Config) -> Result<(), ConfigError> {
+    save_config_to(config, &get_config_path()?)
+}
+
+pub fn save_config_to(config: &Config, config_path: &Path) -> Result<(), ConfigError> {
+    let parse_error = |e| ConfigError::Parse(config_path.to_path_buf(), e);
+    let mut raw = serde_json::to_value(config).map_err(parse_error)?;
+    let mut keyring_error = None;
+    if let (TokenStorage::Keyring, Some(token)) = (config.token_storage, &config.token) {
+        let server = config.server.as_deref();
+        match token_store::store_token(server, config.username.as_deref(), token) {
+            Ok(()) => raw["token"] = serde_json::Value::Null,
+            // a plaintext token beats being logged out next time
+            Err(e) => keyring_error = Some(ConfigError::Keyring(e)),
+        }
+    }
+    write_config(&raw, config_path)?;
+    keyring_error.map_or(Ok(()), Err)
 }
 
-pub fn load_config() -> Config {
-    let config_path = get_config_path();
-    if config_path.exists() {
-        let config_data = fs::read_to_string(config_path).unwrap();
-        serde_json::from_str(&config_data).unwrap_or_default()
-    } else {
-        Config::default()
+/// Like `save_config_to` but never touches the keyring, for when a keyring call could hang.
+/// A keyring token stays out of the file, the login already put it in the keyring.
+pub fn save_config_file_to(config: &Config, config_path: &Path) -> Result<(), ConfigError> {
+    let mut raw = serde_json::to_value(config)
+        .map_err(|e| ConfigError::Parse(config_path.to_path_buf(), e))?;
+    if config.token_storage == TokenStorage::Keyring {
+        raw["token"] = serde_json::Value::Null;
     }
+    write_config(&raw, config_path)
 }
 
-pub fn save_config(config: &Config) {
-    let config_path = get_config_path();
-    let config_data = serde_json::to_string_pretty(config).unwrap();
-    fs::write(config_path, config_data).unwrap();
+fn write_config(raw: &serde_json::Value, config_path: &Path) -> Result<(), ConfigError> {
+    let config_data = serde_json::to_string_pretty(raw)
+        .map_err(|e| ConfigError::Parse(config_path.to_path_buf(), e))?;
+    fs::write(config_path, config_data).map_err(|e| ConfigError::Io(config_path.to_path_buf(), e))
 }
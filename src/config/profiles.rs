@@ -0,0 +1,204 @@
+use crate::config::{get_config_path, save_config, token_store, Config, ConfigError, TokenStorage};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// One account, kept in `profiles/<name>.json` next to the config. The config itself holds
+/// the login of the active profile (that's what everything else reads), switching swaps it
+/// with the chosen file.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct Profile {
+    #[serde(default)]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub user_icon: Option<String>,
+    /// API server, None means the hosted one
+    #[serde(default)]
+    pub server: Option<String>,
+}
+
+impl Profile {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            token: config.token.clone(),
+            username: config.username.clone(),
+            user_icon: config.user_icon.clone(),
+            server: config.server.clone(),
+        }
+    }
+
+    pub fn apply_to(self, config: &mut Config) {
+        config.token = self.token;
+        config.username = self.username;
+        config.user_icon = self.user_icon;
+        config.server = self.server;
+    }
+}
+
+/// Names end up as file names, so letters, digits, `-` and `_` only.
+pub fn is_valid_profile_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// `profiles/` next to the config file, created if needed.
+pub fn get_profiles_dir() -> Result<PathBuf, ConfigError> {
+    let config_path = get_config_path()?;
+    let dir = config_path.parent().map_or_else(
+        || PathBuf::from("profiles"),
+        |parent| parent.join("profiles"),
+    );
+    fs::create_dir_all(&dir).map_err(|e| ConfigError::Io(dir.clone(), e))?;
+    Ok(dir)
+}
+
+fn profile_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.json", name))
+}
+
+/// Every saved profile name, sorted.
+pub fn list_profiles_in(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .filter(|name| is_valid_profile_name(name))
+        .collect();
+    names.sort();
+    names
+}
+
+pub fn list_profiles() -> Result<Vec<String>, ConfigError> {
+    Ok(list_profiles_in(&get_profiles_dir()?))
+}
+
+/// The profile saved as `name`, None when there's no such file. A token kept in the keyring
+/// is looked up under the profile's server and username, if that fails the profile just starts
+/// logged out.
+pub fn load_profile_from(
+    dir: &Path,
+    name: &str,
+    storage: TokenStorage,
+) -> Result<Option<Profile>, ConfigError> {
+    let path = profile_path(dir, name);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let data = fs::read_to_string(&path).map_err(|e| ConfigError::Io(path.clone(), e))?;
+    let mut profile: Profile =
+        serde_json::from_str(&data).map_err(|e| ConfigError::Parse(path.clone(), e))?;
+    if storage == TokenStorage::Keyring && profile.token.is_none() {
+        profile.token =
+            token_store::load_token(profile.server.as_deref(), profile.username.as_deref())
+                .ok()
+                .flatten();
+    }
+    Ok(Some(profile))
+}
+
+/// Same rules as the config: with keyring storage the token goes there and the file only
+/// keeps the rest, unless the keyring can't be reached.
+pub fn save_profile_to(
+    dir: &Path,
+    name: &str,
+    profile: &Profile,
+    storage: TokenStorage,
+) -> Result<(), ConfigError> {
+    let path = profile_path(dir, name);
+    let mut on_disk = profile.clone();
+    let mut keyring_error = None;
+    if let (TokenStorage::Keyring, Some(token)) = (storage, &profile.token) {
+        let server = profile.server.as_deref();
+        match token_store::store_token(server, profile.username.as_deref(), token) {
+            Ok(()) => on_disk.token = None,
+            Err(e) => keyring_error = Some(ConfigError::Keyring(e)),
+        }
+    }
+    let data =
+        serde_json::to_string_pretty(&on_disk).map_err(|e| ConfigError::Parse(path.clone(), e))?;
+    fs::write(&path, data).map_err(|e| ConfigError::Io(path, e))?;
+    keyring_error.map_or(Ok(()), Err)
+}
+
+/// Configs from before profiles existed: the login they hold becomes the active profile's
+/// file, so switching away and back finds it again.
+pub fn ensure_active_profile_in(dir: &Path, config: &Config) -> Result<(), ConfigError> {
+    if profile_path(dir, &config.active_profile).exists() {
+        return Ok(());
+    }
+    save_profile_to(
+        dir,
+        &config.active_profile,
+        &Profile::from_config(config),
+        config.token_storage,
+    )
+}
+
+pub fn ensure_active_profile(config: &Config) -> Result<(), ConfigError> {
+    ensure_active_profile_in(&get_profiles_dir()?, config)
+}
+
+// a keyring that can't be reached still leaves a usable (plaintext) file, so it shouldn't stop
+// a switch halfway, it's handed back at the end instead
+fn defer_keyring_error(
+    result: Result<(), ConfigError>,
+    deferred: &mut Option<ConfigError>,
+) -> Result<(), ConfigError> {
+    match result {
+        Err(e @ ConfigError::Keyring(_)) => {
+            *deferred = Some(e);
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+/// Stores the current login under the active profile and takes on `name`'s. A name that
+/// isn't saved yet starts logged out on the same server.
+pub fn switch_profile_in(dir: &Path, config: &mut Config, name: &str) -> Result<(), ConfigError> {
+    let mut keyring_error = None;
+    defer_keyring_error(
+        save_profile_to(
+            dir,
+            &config.active_profile,
+            &Profile::from_config(config),
+            config.token_storage,
+        ),
+        &mut keyring_error,
+    )?;
+    let next = load_profile_from(dir, name, config.token_storage)?.unwrap_or_else(|| Profile {
+        server: config.server.clone(),
+        ..Profile::default()
+    });
+    next.apply_to(config);
+    config.active_profile = name.to_string();
+    defer_keyring_error(
+        save_profile_to(
+            dir,
+            name,
+            &Profile::from_config(config),
+            config.token_storage,
+        ),
+        &mut keyring_error,
+    )?;
+    keyring_error.map_or(Ok(()), Err)
+}
+
+pub fn switch_profile(config: &mut Config, name: &str) -> Result<(), ConfigError> {
+    let mut keyring_error = None;
+    defer_keyring_error(
+        switch_profile_in(&get_profiles_dir()?, config, name),
+        &mut keyring_error,
+    )?;
+    save_config(config)?;
+    keyring_error.map_or(Ok(()), Err)
+}
@@ -0,0 +1,199 @@
+use crate::config::{
+    load_config_from, save_config_file_to, save_config_to, Config, ConfigError, CursorShape,
+    NotificationColors, TokenStorage, CONFIG_VERSION,
+};
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_config_path() -> PathBuf {
+    std::env::temp_dir().join(format!("reetui-config-test-{}.json", uuid::Uuid::new_v4()))
+}
+
+#[test]
+fn test_missing_config_file_gives_defaults() {
+    let path = temp_config_path();
+    let (config, _) = load_config_from(&path).expect("a missing file isn't an error");
+    assert_eq!(
+        config.max_message_length,
+        Config::default().max_message_length
+    );
+    assert!(config.token.is_none());
+}
+
+#[test]
+fn test_corrupt_config_is_an_error_not_a_panic() {
+    let path = temp_config_path();
+    fs::write(&path, "{ this is not json").unwrap();
+    let result = load_config_from(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(result, Err(ConfigError::Parse(..))));
+}
+
+#[test]
+fn test_config_round_trips() {
+    let path = temp_config_path();
+    let config = Config {
+        username: Some("ree".to_string()),
+        channel_pane_percent: 30,
+        ..Config::default()
+    };
+    save_config_to(&config, &path).unwrap();
+    let loaded = load_config_from(&path);
+    fs::remove_file(&path).unwrap();
+    let (loaded, _) = loaded.unwrap();
+    assert_eq!(loaded.username.as_deref(), Some("ree"));
+    assert_eq!(loaded.channel_pane_percent, 30);
+}
+
+#[test]
+fn test_file_only_save_keeps_a_keyring_token_out_of_the_file() {
+    let path = temp_config_path();
+    let config = Config {
+        token: Some("secret".to_string()),
+        token_storage: TokenStorage::Keyring,
+        ..Config::default()
+    };
+    save_config_file_to(&config, &path).unwrap();
+    let written = fs::read_to_string(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(!written.unwrap().contains("secret"));
+
+    let config = Config {
+        token_storage: TokenStorage::PlainFile,
+        ..config
+    };
+    save_config_file_to(&config, &path).unwrap();
+    let written = fs::read_to_string(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(written.unwrap().contains("secret"));
+}
+
+#[test]
+fn test_saving_into_a_missing_directory_fails_cleanly() {
+    let path = temp_config_path().join("nope").join("reetui.json");
+    let result = save_config_to(&Config::default(), &path);
+    assert!(matches!(result, Err(ConfigError::Io(..))));
+}
+
+#[test]
+fn test_v0_config_is_migrated_and_backed_up() {
+    let path = temp_config_path();
+    // no version field and a theme that doesn't exist anymore
+    let v0 = r#"{
+        "tutorial_seen": true,
+        "token": "abc",
+        "username": "ree",
+        "user_icon": null,
+        "current_theme_name": "Sunset"
+    }"#;
+    fs::write(&path, v0).unwrap();
+    let backup_path = path.with_extension("json.v0.bak");
+
+    let loaded = load_config_from(&path);
+    let rewritten = fs::read_to_string(&path).unwrap();
+    let backup = fs::read_to_string(&backup_path);
+    fs::remove_file(&path).unwrap();
+    let _ = fs::remove_file(&backup_path);
+
+    let (config, migration_error) = loaded.expect("v0 config should migrate");
+    assert!(migration_error.is_none());
+    assert_eq!(config.version, CONFIG_VERSION);
+    assert_eq!(config.token.as_deref(), Some("abc"));
+    assert_eq!(config.username.as_deref(), Some("ree"));
+    assert_eq!(
+        config.current_theme_name,
+        crate::themes::ThemeName::default()
+    );
+    assert_eq!(backup.unwrap(), v0);
+    assert!(rewritten.contains(&format!("\"version\": {}", CONFIG_VERSION)));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_migration_that_cant_be_written_still_loads() {
+    use std::os::unix::fs::PermissionsExt;
+    let dir = temp_config_path().with_extension("d");
+    fs::create_dir(&dir).unwrap();
+    let path = dir.join("reetui.json");
+    fs::write(&path, r#"{"token": "abc", "username": "ree"}"#).unwrap();
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o555)).unwrap();
+    // root writes anywhere, the directory is only really read-only for everyone else
+    let read_only = fs::write(dir.join("probe"), "").is_err();
+
+    let loaded = load_config_from(&path);
+    fs::set_permissions(&dir, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+
+    let (config, migration_error) = loaded.expect("the migrated config is still good");
+    assert_eq!(config.token.as_deref(), Some("abc"));
+    assert_eq!(config.username.as_deref(), Some("ree"));
+    assert_eq!(config.version, CONFIG_VERSION);
+    if read_only {
+        assert!(matches!(migration_error, Some(ConfigError::Io(..))));
+    }
+}
+
+#[test]
+fn test_failed_backup_leaves_the_old_file_alone() {
+    let path = temp_config_path();
+    let old = r#"{"token": "abc"}"#;
+    fs::write(&path, old).unwrap();
+    // something in the way of the backup, fails the same for every user
+    let backup_path = path.with_extension("json.v0.bak");
+    fs::create_dir(&backup_path).unwrap();
+
+    let loaded = load_config_from(&path);
+    let on_disk = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    fs::remove_dir(&backup_path).unwrap();
+
+    let (config, migration_error) = loaded.expect("the migrated config is still good");
+    assert_eq!(config.token.as_deref(), Some("abc"));
+    assert!(matches!(migration_error, Some(ConfigError::Io(..))));
+    assert_eq!(on_disk, old);
+}
+
+#[test]
+fn test_partial_config_keeps_what_is_there() {
+    let path = temp_config_path();
+    fs::write(&path, r#"{"token": "abc", "username": "ree"}"#).unwrap();
+    let loaded = load_config_from(&path);
+    fs::remove_file(&path).unwrap();
+    // no version field, so it went through the migration too
+    let _ = fs::remove_file(path.with_extension("json.v0.bak"));
+
+    let (config, _) = loaded.expect("missing fields should be defaulted");
+    assert_eq!(config.token.as_deref(), Some("abc"));
+    assert_eq!(config.username.as_deref(), Some("ree"));
+    assert!(!config.tutorial_seen);
+    assert!(!config.debug);
+    assert!(config.confirm_unknown_commands);
+    assert!(config.scroll_on_send);
+    assert_eq!(config.cursor_shape, CursorShape::Default);
+    assert!(config.channel_accents.is_empty());
+    assert_eq!(config.min_send_interval_ms, 150);
+    assert_eq!(config.notification_colors, NotificationColors::default());
+    assert_eq!(
+        config.message_group_spacing,
+        Config::default().message_group_spacing
+    );
+    assert_eq!(config.macros, Config::default().macros);
+}
+
+#[test]
+fn test_do_not_disturb_expires_after_its_minutes() {
+    let mut config = Config {
+        do_not_disturb_minutes: 30,
+        ..Config::default()
+    };
+    config.set_do_not_disturb(true, 1_000);
+    assert!(config.do_not_disturb_active(1_000 + 29 * 60));
+    assert!(!config.do_not_disturb_active(1_000 + 30 * 60));
+
+    // 0 minutes keeps it on until turned off
+    config.do_not_disturb_minutes = 0;
+    config.set_do_not_disturb(true, 1_000);
+    assert!(config.do_not_disturb_active(i64::MAX));
+    config.set_do_not_disturb(false, 1_000);
+    assert!(!config.do_not_disturb_active(1_000));
+}
@@ -0,0 +1,3 @@
+pub mod config_test;
+pub mod profiles_test;
+pub mod token_store_test;
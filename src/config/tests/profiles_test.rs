@@ -0,0 +1,67 @@
+use crate::config::profiles::{
+    ensure_active_profile_in, is_valid_profile_name, list_profiles_in, load_profile_from,
+    switch_profile_in, Profile, DEFAULT_PROFILE,
+};
+use crate::config::{Config, TokenStorage};
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_profiles_dir() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("reetui-profiles-test-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn logged_in_config() -> Config {
+    Config {
+        token: Some("home-token".to_string()),
+        username: Some("ree".to_string()),
+        user_icon: Some("".to_string()),
+        server: Some("https://chat.example".to_string()),
+        ..Config::default()
+    }
+}
+
+#[test]
+fn test_existing_login_becomes_the_default_profile() {
+    let dir = temp_profiles_dir();
+    let config = logged_in_config();
+    ensure_active_profile_in(&dir, &config).unwrap();
+    let profile = load_profile_from(&dir, DEFAULT_PROFILE, TokenStorage::PlainFile).unwrap();
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(profile, Some(Profile::from_config(&config)));
+}
+
+#[test]
+fn test_switching_away_and_back_restores_the_login() {
+    let dir = temp_profiles_dir();
+    let mut config = logged_in_config();
+    ensure_active_profile_in(&dir, &config).unwrap();
+
+    switch_profile_in(&dir, &mut config, "work").unwrap();
+    // a new profile starts logged out, on the server the user was already using
+    assert_eq!(config.active_profile, "work");
+    assert_eq!(config.token, None);
+    assert_eq!(config.username, None);
+    assert_eq!(config.server.as_deref(), Some("https://chat.example"));
+
+    config.token = Some("work-token".to_string());
+    config.username = Some("ree-at-work".to_string());
+    switch_profile_in(&dir, &mut config, DEFAULT_PROFILE).unwrap();
+    assert_eq!(config.token.as_deref(), Some("home-token"));
+    assert_eq!(config.username.as_deref(), Some("ree"));
+
+    switch_profile_in(&dir, &mut config, "work").unwrap();
+    let names = list_profiles_in(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(config.token.as_deref(), Some("work-token"));
+    assert_eq!(names, vec!["default".to_string(), "work".to_string()]);
+}
+
+#[test]
+fn test_profile_names_stay_file_name_safe() {
+    assert!(is_valid_profile_name("work_2-alt"));
+    assert!(!is_valid_profile_name(""));
+    assert!(!is_valid_profile_name("../config"));
+    assert!(!is_valid_profile_name("with space"));
+}
@@ -0,0 +1,32 @@
+use crate::config::token_store::{account_key, delete_token, load_token, store_token};
+
+// keyring's Linux backend runs a runtime of its own, it must not try that on one of ours
+#[tokio::test]
+async fn test_keyring_calls_work_inside_the_runtime() {
+    let username = format!("reetui-test-{}", uuid::Uuid::new_v4());
+    // no keyring on a headless box is fine, only a panic or a hang fails this
+    if store_token(None, Some(&username), "secret").is_ok() {
+        assert_eq!(
+            load_token(None, Some(&username)).unwrap().as_deref(),
+            Some("secret")
+        );
+    }
+    let _ = delete_token(None, Some(&username));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_keyring_calls_work_on_a_worker_thread() {
+    let username = format!("reetui-test-{}", uuid::Uuid::new_v4());
+    let _ = load_token(None, Some(&username));
+}
+
+#[test]
+fn test_same_username_on_two_servers_is_two_entries() {
+    let hosted = account_key(None, Some("ree"));
+    let home = account_key(Some("https://chat.home"), Some("ree"));
+    let work = account_key(Some("https://chat.work"), Some("ree"));
+    assert_ne!(home, work);
+    assert_ne!(hosted, home);
+    // tokens stored before servers were part of the name are still found
+    assert_eq!(hosted, "ree");
+}
@@ -0,0 +1,68 @@
+use crate::config::{Config, TokenStorage};
+use keyring::Entry;
+
+const KEYRING_SERVICE: &str = "reetui";
+
+/// The keyring entry name of an account. The same username on two servers is two accounts,
+/// so a self-hosted server is part of the name, the hosted one keeps the bare username the
+/// tokens were first stored under.
+pub fn account_key(server: Option<&str>, username: Option<&str>) -> String {
+    let username = username.unwrap_or("default");
+    match server {
+        Some(server) => format!("{}@{}", username, server),
+        None => username.to_string(),
+    }
+}
+
+// one entry per account, so logging in as someone else doesn't clobber the first token
+fn entry(server: Option<&str>, username: Option<&str>) -> keyring::Result<Entry> {
+    Entry::new(KEYRING_SERVICE, &account_key(server, username))
+}
+
+// the Linux keyring blocks on a runtime of its own, which tokio won't start from one of its
+// threads, so every call is made from a plain thread
+fn off_runtime<T: Send>(call: impl FnOnce() -> T + Send) -> T {
+    std::thread::scope(|scope| scope.spawn(call).join().expect("keyring thread panicked"))
+}
+
+pub fn store_token(
+    server: Option<&str>,
+    username: Option<&str>,
+    token: &str,
+) -> keyring::Result<()> {
+    off_runtime(|| entry(server, username)?.set_password(token))
+}
+
+pub fn load_token(server: Option<&str>, username: Option<&str>) -> keyring::Result<Option<String>> {
+    off_runtime(|| match entry(server, username)?.get_password() {
+        Ok(token) => Ok(Some(token)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(e),
+    })
+}
+
+pub fn delete_token(server: Option<&str>, username: Option<&str>) -> keyring::Result<()> {
+    off_runtime(|| match entry(server, username)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(e),
+    })
+}
+
+/// Fills `config.token` from the keyring when that's where it lives. A plaintext token still
+/// in the file is left in memory, the next save moves it into the keyring. If the keyring
+/// can't be reached the config falls back to the plain file and the error is handed back.
+pub fn resolve_token(config: &mut Config) -> keyring::Result<()> {
+    if config.token_storage != TokenStorage::Keyring || config.token.is_some() {
+        return Ok(());
+    }
+    match load_token(config.server.as_deref(), config.username.as_deref()) {
+        Ok(token) => {
+            config.token = token;
+            Ok(())
+        }
+        Err(e) => {
+            config.token_storage = TokenStorage::PlainFile;
+            Err(e)
+        }
+    }
+}
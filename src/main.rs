@@ -1,17 +1,22 @@
 pub mod api;
 pub mod app;
+mod cli;
 pub mod config;
 mod themes;
 pub mod tui;
 
 use crate::app::app_state::AppState;
 use crate::app::TuiPage;
+use crate::cli::Cli;
 use crate::tui::auth::run_auth_page;
+use crate::tui::capabilities::{GlyphFallbackBackend, TerminalCapabilities};
 use crate::tui::home::run_home_page;
 use crate::tui::help::run_help_page;
+use clap::Parser;
 use crossterm::{
+    event::EnableBracketedPaste,
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{enable_raw_mode, EnterAlternateScreen},
 };
 use ratatui::{backend::CrosstermBackend, prelude::Backend, Terminal};
 use std::io::{self};
@@ -20,57 +25,206 @@ use tokio::sync::Mutex;
 
 #[tokio::main]
 async fn main() -> io::Result<()> {
+    // before raw mode, so --help and --version print like any other CLI
+    let cli = Cli::parse();
+    if let Some(path) = cli.config {
+        crate::config::set_config_path(path);
+    }
+    if let Some(server) = &cli.server {
+        crate::api::server::set_server(server);
+    }
+
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
+    // pastes arrive as one event instead of keystrokes, so a pasted line break isn't Enter
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    let backend = GlyphFallbackBackend::new(CrosstermBackend::new(stdout));
     let mut terminal = Terminal::new(backend)?;
 
-    let config = crate::config::load_config();
-
-    let initial_page = if !config.tutorial_seen {
-        TuiPage::Help
-    } else if config.token.is_none() {
-        TuiPage::Auth
-    } else {
-        TuiPage::Chat
+    // a broken config shouldn't lock anyone out, start with the defaults and say why
+    let (mut config, migration_error, config_error) = match crate::config::load_config() {
+        Ok((config, migration_error)) => (config, migration_error, None),
+        Err(e) => (crate::config::Config::default(), None, Some(e)),
     };
+    // keyring lookups block, they get a thread of their own
+    let (config, keyring_error, profile_error) = tokio::task::spawn_blocking(move || {
+        let keyring_error = crate::config::token_store::resolve_token(&mut config).err();
+        let profile_error = crate::config::profiles::ensure_active_profile(&config).err();
+        (config, keyring_error, profile_error)
+    })
+    .await
+    .unwrap();
+    crate::api::server::set_profile_server(config.server.as_deref());
+    crate::themes::set_no_color(config.no_color_active());
 
-    let app_state = Arc::new(Mutex::new(AppState::new(config)));
+    let initial_page = TuiPage::start_page(&config);
+    // --debug is just for this run, it doesn't end up in the saved config
+    let debug = cli.debug || config.debug;
 
-    let config_path_debug = {
-        let mut config_dir = dirs::config_dir().unwrap();
-        let mut debug_path_string = format!("Initial config_dir: {:?}\n", config_dir);
-        config_dir.push("reetui");
-        debug_path_string.push_str(&format!("After 'reetui' push: {:?}\n", config_dir));
-        config_dir.push("reetui.json");
-        debug_path_string.push_str(&format!("Final path: {:?}", config_dir));
-        debug_path_string
-    };
+    let capabilities = TerminalCapabilities::detect().await;
+    capabilities.apply(&config);
+
+    let mut state = AppState::new(config);
+    state.capabilities = capabilities;
+    let app_state = Arc::new(Mutex::new(state));
+    // from here on a panic or a kill still leaves a usable terminal behind
+    crate::tui::shutdown::install_panic_hook(app_state.clone());
+    tokio::spawn(crate::tui::shutdown::handle_signals(app_state.clone()));
 
-    app_state
-        .lock()
-        .await
-        .notification_manager
-        .add(
-            "Config Path Debug".to_string(),
-            config_path_debug,
-            crate::tui::notification::notification::NotificationType::Info,
-            Some(std::time::Duration::from_secs(20)),
-            app_state.clone(),
-        )
-        .await;
+    let (retry_tx, mut retry_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    app_state.lock().await.api.set_retry_notices(retry_tx);
+    let app_state_for_retries = app_state.clone();
+    tokio::spawn(async move {
+        while let Some(notice) = retry_rx.recv().await {
+            app_state_for_retries
+                .lock()
+                .await
+                .notification_manager
+                .add(
+                    "Network Hiccup 󰖪".to_string(),
+                    notice,
+                    crate::tui::notification::notification::NotificationType::Warning,
+                    Some(std::time::Duration::from_secs(3)),
+                    app_state_for_retries.clone(),
+                )
+                .await;
+        }
+    });
+
+    if let Some(e) = config_error {
+        app_state
+            .lock()
+            .await
+            .notification_manager
+            .add(
+                "Config Not Loaded 󰒓".to_string(),
+                format!("{}\nUsing the defaults for now.", e),
+                crate::tui::notification::notification::NotificationType::Warning,
+                Some(std::time::Duration::from_secs(10)),
+                app_state.clone(),
+            )
+            .await;
+    }
+
+    if let Some(e) = migration_error {
+        app_state
+            .lock()
+            .await
+            .notification_manager
+            .add(
+                "Config Not Updated 󰒓".to_string(),
+                format!(
+                    "{}\nYour settings loaded fine, the file gets rewritten on the next save.",
+                    e
+                ),
+                crate::tui::notification::notification::NotificationType::Warning,
+                Some(std::time::Duration::from_secs(10)),
+                app_state.clone(),
+            )
+            .await;
+    }
+
+    if let Some(e) = profile_error {
+        app_state
+            .lock()
+            .await
+            .notification_manager
+            .add(
+                "Profile Not Saved 󰀉".to_string(),
+                format!("{}\nSwitching profiles may lose this login.", e),
+                crate::tui::notification::notification::NotificationType::Warning,
+                Some(std::time::Duration::from_secs(10)),
+                app_state.clone(),
+            )
+            .await;
+    }
+
+    if let Some(e) = keyring_error {
+        app_state
+            .lock()
+            .await
+            .notification_manager
+            .add(
+                "Keyring Unavailable 󰌆".to_string(),
+                format!(
+                    "{}\nThe token goes back to the config file, log in again if u got logged out.",
+                    e
+                ),
+                crate::tui::notification::notification::NotificationType::Warning,
+                Some(std::time::Duration::from_secs(10)),
+                app_state.clone(),
+            )
+            .await;
+    }
+
+    // internal paths, so only for whoever is troubleshooting
+    if debug {
+        let config_path_debug = {
+            let mut config_dir = dirs::config_dir().unwrap_or_default();
+            let mut debug_path_string = format!("Initial config_dir: {:?}\n", config_dir);
+            config_dir.push("reetui");
+            debug_path_string.push_str(&format!("After 'reetui' push: {:?}\n", config_dir));
+            config_dir.push("reetui.json");
+            debug_path_string.push_str(&format!("Final path: {:?}", config_dir));
+            debug_path_string
+        };
+
+        app_state
+            .lock()
+            .await
+            .notification_manager
+            .add(
+                "Config Path Debug".to_string(),
+                config_path_debug,
+                crate::tui::notification::notification::NotificationType::Info,
+                Some(std::time::Duration::from_secs(20)),
+                app_state.clone(),
+            )
+            .await;
+    }
+
+    let low_contrast_themes: Vec<String> = {
+        let state = app_state.lock().await;
+        let mut themes: Vec<String> = state
+            .themes
+            .values()
+            .filter_map(|theme| {
+                let pairs = crate::themes::low_contrast_pairs(&theme.colors);
+                (!pairs.is_empty()).then(|| format!("{:?}: {}", theme.name, pairs.join(", ")))
+            })
+            .collect();
+        themes.sort();
+        themes
+    };
+    if !low_contrast_themes.is_empty() {
+        app_state
+            .lock()
+            .await
+            .notification_manager
+            .add(
+                "Low Contrast Themes 󰈈".to_string(),
+                format!(
+                    "These might be hard to read:\n{}",
+                    low_contrast_themes.join("\n")
+                ),
+                crate::tui::notification::notification::NotificationType::Warning,
+                Some(std::time::Duration::from_secs(10)),
+                app_state.clone(),
+            )
+            .await;
+    }
 
     run_app(&mut terminal, app_state.clone(), initial_page).await?;
 
     // Save config before exiting
-    let app_state_locked = app_state.lock().await;
-    if app_state_locked.config.token.is_some() {
-        crate::config::save_config(&app_state_locked.config);
-    }
+    let save_result = crate::tui::shutdown::save_config_on_exit(&app_state.lock().await.config);
+
+    crate::tui::shutdown::restore_terminal()?;
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    // the TUI is gone by now, the terminal is the only place left to say it
+    if let Err(e) = save_result {
+        eprintln!("Couldn't save the config: {}", e);
+    }
 
     Ok(())
 }
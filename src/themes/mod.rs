@@ -1,6 +1,12 @@
-use ratatui::style::Color;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::BorderType;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(test)]
+mod tests;
 
 #[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 pub struct Rgb(pub u8, pub u8, pub u8);
@@ -12,8 +18,238 @@ pub fn interpolate_rgb(start: &Rgb, end: &Rgb, fraction: f32) -> Rgb {
     Rgb(r, g, b)
 }
 
+// how far the zebra stripe moves from the background toward `dim`, kept faint on purpose
+const STRIPE_STRENGTH: f32 = 0.12;
+
+// every color goes through `rgb_to_color` or `terminal_color`, so this is the one switch for
+// no-color mode
+#[cfg(not(test))]
+static NO_COLOR: AtomicBool = AtomicBool::new(false);
+
+// per thread in tests, so one can render without colors while the others check them
+#[cfg(test)]
+thread_local! {
+    static NO_COLOR: AtomicBool = const { AtomicBool::new(false) };
+}
+
+/// Renders everything in the terminal's own foreground and background from now on.
+pub fn set_no_color(on: bool) {
+    #[cfg(not(test))]
+    NO_COLOR.store(on, Ordering::Relaxed);
+    #[cfg(test)]
+    NO_COLOR.with(|flag| flag.store(on, Ordering::Relaxed));
+}
+
+pub fn no_color() -> bool {
+    #[cfg(not(test))]
+    return NO_COLOR.load(Ordering::Relaxed);
+    #[cfg(test)]
+    return NO_COLOR.with(|flag| flag.load(Ordering::Relaxed));
+}
+
+// off on terminals without 24-bit color, every RGB color then becomes its nearest match in
+// the 256 color palette, see `tui::capabilities`
+#[cfg(not(test))]
+static TRUECOLOR: AtomicBool = AtomicBool::new(true);
+
+#[cfg(test)]
+thread_local! {
+    static TRUECOLOR: AtomicBool = const { AtomicBool::new(true) };
+}
+
+pub fn set_truecolor(on: bool) {
+    #[cfg(not(test))]
+    TRUECOLOR.store(on, Ordering::Relaxed);
+    #[cfg(test)]
+    TRUECOLOR.with(|flag| flag.store(on, Ordering::Relaxed));
+}
+
+pub fn truecolor() -> bool {
+    #[cfg(not(test))]
+    return TRUECOLOR.load(Ordering::Relaxed);
+    #[cfg(test)]
+    return TRUECOLOR.with(|flag| flag.load(Ordering::Relaxed));
+}
+
+// off without a Nerd Font, icons are then drawn as plain characters, see `tui::icons`
+#[cfg(not(test))]
+static NERD_FONT: AtomicBool = AtomicBool::new(true);
+
+#[cfg(test)]
+thread_local! {
+    static NERD_FONT: AtomicBool = const { AtomicBool::new(true) };
+}
+
+pub fn set_nerd_font(on: bool) {
+    #[cfg(not(test))]
+    NERD_FONT.store(on, Ordering::Relaxed);
+    #[cfg(test)]
+    NERD_FONT.with(|flag| flag.store(on, Ordering::Relaxed));
+}
+
+pub fn nerd_font() -> bool {
+    #[cfg(not(test))]
+    return NERD_FONT.load(Ordering::Relaxed);
+    #[cfg(test)]
+    return NERD_FONT.with(|flag| flag.load(Ordering::Relaxed));
+}
+
+/// Nearest of the 256 color palette's 6x6x6 cube (16-231) and gray ramp (232-255), the part
+/// that looks the same on every terminal, unlike the 16 themable colors below it.
+pub fn rgb_to_ansi256(rgb: &Rgb) -> u8 {
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let nearest_level = |value: u8| {
+        (0..LEVELS.len())
+            .min_by_key(|&i| (value as i32 - LEVELS[i] as i32).abs())
+            .unwrap_or(0)
+    };
+    let (r, g, b) = (nearest_level(rgb.0), nearest_level(rgb.1), nearest_level(rgb.2));
+    let cube = Rgb(LEVELS[r], LEVELS[g], LEVELS[b]);
+
+    let average = (rgb.0 as i32 + rgb.1 as i32 + rgb.2 as i32) / 3;
+    let gray_step = ((average - 8 + 5) / 10).clamp(0, 23);
+    let gray_value = (8 + 10 * gray_step) as u8;
+    let gray = Rgb(gray_value, gray_value, gray_value);
+
+    let distance = |other: &Rgb| {
+        [(rgb.0, other.0), (rgb.1, other.1), (rgb.2, other.2)]
+            .iter()
+            .map(|&(a, b)| (a as i32 - b as i32).pow(2))
+            .sum::<i32>()
+    };
+    if distance(&gray) < distance(&cube) {
+        232 + gray_step as u8
+    } else {
+        16 + 36 * r as u8 + 6 * g as u8 + b as u8
+    }
+}
+
+/// `color` as the terminal can show it, RGB is swapped for a palette color without truecolor.
+fn fit_color(color: Color, truecolor: bool) -> Color {
+    match color {
+        Color::Rgb(r, g, b) if !truecolor => Color::Indexed(rgb_to_ansi256(&Rgb(r, g, b))),
+        color => color,
+    }
+}
+
+/// The `NO_COLOR` convention (no-color.org): set to anything but the empty string.
+pub fn no_color_env() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
 pub fn rgb_to_color(rgb: &Rgb) -> Color {
-    Color::Rgb(rgb.0, rgb.1, rgb.2)
+    color_for(rgb, no_color())
+}
+
+/// A fixed color that isn't part of the theme (file type icons, syntax highlighting), or the
+/// terminal's default when colors are off.
+pub fn terminal_color(color: Color) -> Color {
+    if no_color() {
+        Color::Reset
+    } else {
+        fit_color(color, truecolor())
+    }
+}
+
+/// Extra chafa arguments, so image previews don't bring their colors back in (or more of
+/// them than the terminal has).
+pub fn chafa_color_args() -> &'static [&'static str] {
+    if no_color() {
+        &["--colors", "none"]
+    } else if !truecolor() {
+        &["--colors", "256"]
+    } else {
+        &[]
+    }
+}
+
+fn color_for(rgb: &Rgb, no_color: bool) -> Color {
+    if no_color {
+        Color::Reset
+    } else {
+        fit_color(Color::Rgb(rgb.0, rgb.1, rgb.2), truecolor())
+    }
+}
+
+/// `fg` on `bg` for the selected row, button or chip. Without colors the two can't tell it
+/// apart, so it's reversed instead.
+pub fn highlight_style(fg: &Rgb, bg: &Rgb) -> Style {
+    highlight_style_for(fg, bg, no_color())
+}
+
+fn highlight_style_for(fg: &Rgb, bg: &Rgb, no_color: bool) -> Style {
+    if no_color {
+        Style::default().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::default()
+            .fg(color_for(fg, false))
+            .bg(color_for(bg, false))
+    }
+}
+
+/// `modifier` when colors are off, nothing otherwise. For the spots that only stand out by
+/// their color.
+pub fn no_color_emphasis(modifier: Modifier) -> Modifier {
+    if no_color() {
+        modifier
+    } else {
+        Modifier::empty()
+    }
+}
+
+/// Background tint of every other message group when striping is on, a nudge from the
+/// background toward `dim`. None without colors, a stripe can't be drawn with them off.
+pub fn stripe_color(theme: &Theme) -> Option<Color> {
+    stripe_color_for(&theme.colors, no_color())
+}
+
+fn stripe_color_for(colors: &ThemeColors, no_color: bool) -> Option<Color> {
+    if no_color {
+        return None;
+    }
+    let tint = interpolate_rgb(&colors.background, &colors.dim, STRIPE_STRENGTH);
+    Some(color_for(&tint, false))
+}
+
+/// Border of whatever has focus. Without colors it's drawn thick so it still stands out.
+pub fn focus_border_type(focused: bool) -> BorderType {
+    if focused && no_color() {
+        BorderType::Thick
+    } else {
+        BorderType::Rounded
+    }
+}
+
+/// Colors each character of `text` along the gradient, `start` on the first one and `end` on the last.
+pub fn gradient_text(text: &str, start: &Rgb, end: &Rgb) -> Line<'static> {
+    let char_count = text.chars().count();
+    let steps = char_count.saturating_sub(1).max(1) as f32;
+    let spans: Vec<Span<'static>> = text
+        .chars()
+        .enumerate()
+        .map(|(i, ch)| {
+            let color = interpolate_rgb(start, end, i as f32 / steps);
+            Span::styled(ch.to_string(), Style::default().fg(rgb_to_color(&color)))
+        })
+        .collect();
+    Line::from(spans)
+}
+
+/// Like `gradient_text`, but the gradient goes start -> end -> start and is shifted by `phase`
+/// (one full turn per 1.0), so bumping the phase every frame moves a sheen across the text.
+pub fn animated_gradient_text(text: &str, start: &Rgb, end: &Rgb, phase: f32) -> Line<'static> {
+    let char_count = text.chars().count().max(1) as f32;
+    let spans: Vec<Span<'static>> = text
+        .chars()
+        .enumerate()
+        .map(|(i, ch)| {
+            let position = (i as f32 / char_count - phase).rem_euclid(1.0);
+            let fraction = 1.0 - (2.0 * position - 1.0).abs();
+            let color = interpolate_rgb(start, end, fraction);
+            Span::styled(ch.to_string(), Style::default().fg(rgb_to_color(&color)))
+        })
+        .collect();
+    Line::from(spans)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -31,6 +267,11 @@ pub enum ThemeName {
     AutumnLeaves,
     HighContrastLight,
     Amethyst,
+    /// Okabe-Ito colors: blue/orange/yellow where other themes lean on red against green,
+    /// and the notification colors also differ in brightness (warning yellow brightest,
+    /// then success sky blue, error vermillion, info blue), so they tell apart even with no
+    /// hue to go by
+    ColorblindSafe,
 }
 
 impl Default for ThemeName {
@@ -41,6 +282,34 @@ impl Default for ThemeName {
 
 impl ThemeName {
     pub fn icon(&self) -> &str {
+        if nerd_font() {
+            self.nerd_font_icon()
+        } else {
+            self.fallback_label()
+        }
+    }
+
+    /// What stands in for the icon without a Nerd Font.
+    pub fn fallback_label(&self) -> &'static str {
+        match self {
+            ThemeName::Default => "DEF ",
+            ThemeName::Oceanic => "OCN ",
+            ThemeName::Forest => "FOR ",
+            ThemeName::Monochrome => "MON ",
+            ThemeName::CatppuccinMocha => "CAT ",
+            ThemeName::Dracula => "DRA ",
+            ThemeName::SolarizedDark => "SOL ",
+            ThemeName::GruvboxDark => "GRV ",
+            ThemeName::Nord => "NRD ",
+            ThemeName::Cyberpunk => "CYB ",
+            ThemeName::AutumnLeaves => "AUT ",
+            ThemeName::HighContrastLight => "HCL ",
+            ThemeName::Amethyst => "AME ",
+            ThemeName::ColorblindSafe => "CBS ",
+        }
+    }
+
+    fn nerd_font_icon(&self) -> &'static str {
         match self {
             ThemeName::Default => r"󰆄 ",
             ThemeName::Oceanic => "󰞍 ",
@@ -55,6 +324,7 @@ impl ThemeName {
             ThemeName::AutumnLeaves => " ",
             ThemeName::HighContrastLight => " ",
             ThemeName::Amethyst => "󰮊 ",
+            ThemeName::ColorblindSafe => "󰈈 ",
         }
     }
 
@@ -73,6 +343,7 @@ impl ThemeName {
             ThemeName::AutumnLeaves,
             ThemeName::HighContrastLight,
             ThemeName::Amethyst,
+            ThemeName::ColorblindSafe,
         ]
     }
 }
@@ -120,6 +391,61 @@ pub struct Theme {
     pub colors: ThemeColors,
 }
 
+// how far (in brightness) a username color has to be from the background to stay readable
+const MIN_USER_COLOR_CONTRAST: f32 = 70.0;
+
+// FNV-1a, so a username keeps its color between runs (DefaultHasher is not guaranteed to)
+fn stable_hash(value: &str) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in value.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+impl Theme {
+    /// Color used for `username` in this theme. Picks from the theme's `username_colors`,
+    /// skipping the ones that are too close to the background, and generates one when the
+    /// palette has nothing usable.
+    pub fn user_color(&self, username: &str) -> Rgb {
+        let background = &self.colors.background;
+        let bg_brightness = brightness(background);
+        let hash = stable_hash(username);
+
+        let readable: Vec<&Rgb> = self
+            .colors
+            .username_colors
+            .iter()
+            .filter(|c| (brightness(c) - bg_brightness).abs() >= MIN_USER_COLOR_CONTRAST)
+            .collect();
+        if !readable.is_empty() {
+            return *readable[(hash as usize) % readable.len()];
+        }
+
+        let generated = Rgb(
+            (hash & 0xFF) as u8,
+            ((hash >> 8) & 0xFF) as u8,
+            ((hash >> 16) & 0xFF) as u8,
+        );
+        // push the generated color away from the background until it's readable
+        let target = if bg_brightness > 128.0 {
+            Rgb(0, 0, 0)
+        } else {
+            Rgb(255, 255, 255)
+        };
+        let mut color = generated;
+        let mut fraction = 0.0;
+        while (brightness(&color) - bg_brightness).abs() < MIN_USER_COLOR_CONTRAST
+            && fraction < 1.0
+        {
+            fraction += 0.1;
+            color = interpolate_rgb(&generated, &target, fraction);
+        }
+        color
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct ThemesConfig {
     pub themes: Vec<Theme>,
@@ -141,11 +467,39 @@ pub fn color_to_rgb(color: Color) -> Option<Rgb> {
     }
 }
 
+pub fn brightness(color: &Rgb) -> f32 {
+    (color.0 as f32 * 299.0 + color.1 as f32 * 587.0 + color.2 as f32 * 114.0) / 1000.0
+}
+
+// below this brightness difference text gets hard to read on its background
+pub const MIN_READABLE_CONTRAST: f32 = 60.0;
+
+/// Whether `fg` stays readable on top of `bg`.
+pub fn has_readable_contrast(fg: &Rgb, bg: &Rgb) -> bool {
+    (brightness(fg) - brightness(bg)).abs() >= MIN_READABLE_CONTRAST
+}
+
+/// The foreground/background pairs of a theme that are too close to be readable.
+pub fn low_contrast_pairs(colors: &ThemeColors) -> Vec<&'static str> {
+    let pairs = [
+        ("text / background", &colors.text, &colors.background),
+        (
+            "button_text_active / button_bg_active",
+            &colors.button_text_active,
+            &colors.button_bg_active,
+        ),
+    ];
+    pairs
+        .into_iter()
+        .filter(|(_, fg, bg)| !has_readable_contrast(fg, bg))
+        .map(|(name, _, _)| name)
+        .collect()
+}
+
 pub fn get_contrasting_text_color(bg_color: &Rgb) -> Color {
-    let brightness =
-        (bg_color.0 as f32 * 299.0 + bg_color.1 as f32 * 587.0 + bg_color.2 as f32 * 114.0)
-            / 1000.0;
-    if brightness > 128.0 {
+    if no_color() {
+        Color::Reset
+    } else if brightness(bg_color) > 128.0 {
         Color::Black
     } else {
         Color::White
@@ -0,0 +1,34 @@
+use crate::themes::{color_for, fit_color, rgb_to_ansi256, set_truecolor, Rgb};
+use ratatui::style::Color;
+
+#[test]
+fn test_rgb_lands_on_the_nearest_palette_color() {
+    assert_eq!(rgb_to_ansi256(&Rgb(0, 0, 0)), 16);
+    assert_eq!(rgb_to_ansi256(&Rgb(255, 255, 255)), 231);
+    assert_eq!(rgb_to_ansi256(&Rgb(255, 0, 0)), 196);
+    assert_eq!(rgb_to_ansi256(&Rgb(0, 95, 135)), 24);
+    // grays go to the gray ramp when it's closer than the cube
+    assert_eq!(rgb_to_ansi256(&Rgb(128, 128, 128)), 244);
+    assert_eq!(rgb_to_ansi256(&Rgb(30, 30, 46)), 235);
+}
+
+#[test]
+fn test_colors_are_only_quantized_without_truecolor() {
+    assert_eq!(
+        fit_color(Color::Rgb(255, 0, 0), true),
+        Color::Rgb(255, 0, 0)
+    );
+    assert_eq!(fit_color(Color::Rgb(255, 0, 0), false), Color::Indexed(196));
+    assert_eq!(fit_color(Color::Reset, false), Color::Reset);
+    assert_eq!(fit_color(Color::Cyan, false), Color::Cyan);
+}
+
+#[test]
+fn test_theme_colors_follow_the_truecolor_switch() {
+    // the switch is per thread in tests
+    set_truecolor(false);
+    let fitted = color_for(&Rgb(255, 0, 0), false);
+    set_truecolor(true);
+    assert_eq!(fitted, Color::Indexed(196));
+    assert_eq!(color_for(&Rgb(255, 0, 0), true), Color::Reset);
+}
@@ -0,0 +1,70 @@
+use crate::themes::{
+    brightness, has_readable_contrast, low_contrast_pairs, Rgb, ThemeName, ThemesConfig,
+};
+
+#[test]
+fn test_has_readable_contrast() {
+    assert!(
+        has_readable_contrast(&Rgb(255, 255, 255), &Rgb(0, 0, 0)),
+        "White on black should be readable"
+    );
+    assert!(
+        has_readable_contrast(&Rgb(0, 0, 0), &Rgb(255, 255, 255)),
+        "Black on white should be readable"
+    );
+    assert!(
+        !has_readable_contrast(&Rgb(40, 40, 40), &Rgb(30, 30, 30)),
+        "Dark grey on almost black should not be readable"
+    );
+    assert!(
+        !has_readable_contrast(&Rgb(250, 250, 0), &Rgb(255, 255, 255)),
+        "Yellow on white should not be readable"
+    );
+}
+
+#[test]
+fn test_low_contrast_pairs() {
+    let themes = ThemesConfig::get_all_themes().unwrap();
+    for theme in themes.values() {
+        assert_eq!(
+            low_contrast_pairs(&theme.colors),
+            Vec::<&str>::new(),
+            "Built-in theme {:?} should be readable",
+            theme.name
+        );
+    }
+
+    let mut colors = themes.values().next().unwrap().colors.clone();
+    colors.text = colors.background;
+    assert_eq!(low_contrast_pairs(&colors), vec!["text / background"]);
+}
+
+#[test]
+fn test_colorblind_theme_keeps_notifications_apart() {
+    let themes = ThemesConfig::get_all_themes().unwrap();
+    let colors = &themes[&ThemeName::ColorblindSafe].colors;
+    let notification_colors = [
+        colors.success_color,
+        colors.warning_color,
+        colors.error,
+        colors.info_color,
+    ];
+    for color in notification_colors.iter().chain(&colors.username_colors) {
+        assert!(
+            has_readable_contrast(color, &colors.background),
+            "{:?} should be readable on the background",
+            color
+        );
+    }
+    // brightness alone has to tell the notification types apart, that's all some users get
+    for (i, a) in notification_colors.iter().enumerate() {
+        for b in &notification_colors[i + 1..] {
+            assert!(
+                (brightness(a) - brightness(b)).abs() >= 30.0,
+                "{:?} and {:?} are too close",
+                a,
+                b
+            );
+        }
+    }
+}
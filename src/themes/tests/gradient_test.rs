@@ -0,0 +1,19 @@
+use crate::themes::{gradient_text, Rgb};
+use ratatui::style::Color;
+
+#[test]
+fn test_gradient_text() {
+    let line = gradient_text("abc", &Rgb(0, 0, 0), &Rgb(200, 100, 50));
+    let colors: Vec<Option<Color>> = line.spans.iter().map(|s| s.style.fg).collect();
+    assert_eq!(line.spans.len(), 3);
+    assert_eq!(colors[0], Some(Color::Rgb(0, 0, 0)), "First char gets the start color");
+    assert_eq!(colors[1], Some(Color::Rgb(100, 50, 25)), "Middle char is interpolated");
+    assert_eq!(colors[2], Some(Color::Rgb(200, 100, 50)), "Last char gets the end color");
+}
+
+#[test]
+fn test_gradient_text_single_char() {
+    let line = gradient_text("a", &Rgb(10, 20, 30), &Rgb(200, 100, 50));
+    assert_eq!(line.spans.len(), 1);
+    assert_eq!(line.spans[0].style.fg, Some(Color::Rgb(10, 20, 30)));
+}
@@ -0,0 +1,4 @@
+pub mod ansi256_test;
+pub mod contrast_test;
+pub mod gradient_test;
+pub mod no_color_test;
@@ -0,0 +1,44 @@
+use crate::themes::{color_for, highlight_style_for, stripe_color_for, Rgb, ThemesConfig};
+use ratatui::style::{Color, Modifier};
+
+// the switch is per thread under test, the render tests flip it for themselves
+
+#[test]
+fn test_no_color_uses_the_terminal_defaults() {
+    let rgb = Rgb(12, 34, 56);
+    assert_eq!(color_for(&rgb, false), Color::Rgb(12, 34, 56));
+    assert_eq!(color_for(&rgb, true), Color::Reset);
+}
+
+#[test]
+fn test_highlight_is_reversed_without_colors() {
+    let fg = Rgb(0, 0, 0);
+    let bg = Rgb(255, 255, 255);
+
+    let colored = highlight_style_for(&fg, &bg, false);
+    assert_eq!(colored.fg, Some(Color::Rgb(0, 0, 0)));
+    assert_eq!(colored.bg, Some(Color::Rgb(255, 255, 255)));
+    assert!(!colored.add_modifier.contains(Modifier::REVERSED));
+
+    let plain = highlight_style_for(&fg, &bg, true);
+    assert_eq!(plain.fg, None);
+    assert_eq!(plain.bg, None);
+    assert!(plain.add_modifier.contains(Modifier::REVERSED));
+}
+
+#[test]
+fn test_stripes_sit_between_background_and_dim_and_vanish_without_colors() {
+    for theme in ThemesConfig::get_all_themes().unwrap().values() {
+        let colors = &theme.colors;
+        let Some(Color::Rgb(r, _, _)) = stripe_color_for(colors, false) else {
+            panic!("{:?} has no stripe color", theme.name);
+        };
+        let (low, high) = if colors.background.0 <= colors.dim.0 {
+            (colors.background.0, colors.dim.0)
+        } else {
+            (colors.dim.0, colors.background.0)
+        };
+        assert!((low..=high).contains(&r), "{:?}", theme.name);
+        assert_eq!(stripe_color_for(colors, true), None);
+    }
+}
@@ -30,6 +30,14 @@ pub fn handle_auth_event(
     let mut next_page = None;
     let mut should_submit = false; // Initialize to false
 
+    if let Event::Paste(text) = &event {
+        match selected_field {
+            SelectedField::Username => username_input.insert_pasted(text),
+            SelectedField::Password => password_input.insert_pasted(text),
+            _ => {}
+        }
+    }
+
     if let Event::Key(key) = event {
         if key.kind == KeyEventKind::Press {
             match key.code {
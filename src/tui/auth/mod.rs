@@ -2,7 +2,6 @@ pub mod events;
 pub mod page;
 pub mod state;
 
-use crate::api::auth_api;
 use crate::app::app_state::AppState;
 use crate::app::TuiPage;
 use crate::tui::auth::events::handle_auth_event;
@@ -11,10 +10,45 @@ use crate::tui::auth::state::{AuthMode, AuthState, SelectedField};
 use crate::tui::notification::notification::NotificationType;
 use crossterm::event;
 use ratatui::Terminal;
-use sha2::{Digest, Sha256};
 use std::sync::Arc;
 use std::{io, time::Duration};
 
+/// Keeps drawing the page while an auth request is in flight, so the loading toast spins
+/// instead of freezing on its first frame.
+async fn redraw_while_pending<B: ratatui::backend::Backend, T>(
+    terminal: &mut Terminal<B>,
+    app_state: &Arc<tokio::sync::Mutex<AppState>>,
+    auth_state: &AuthState,
+    settings_state: &mut crate::tui::settings::state::SettingsState,
+    request: impl std::future::Future<Output = T>,
+) -> io::Result<T> {
+    tokio::pin!(request);
+    let mut ticker = tokio::time::interval(Duration::from_millis(80));
+    loop {
+        tokio::select! {
+            result = &mut request => return Ok(result),
+            _ = ticker.tick() => {
+                let mut app_state_guard = app_state.lock().await;
+                app_state_guard.update_notifications();
+                let theme = app_state_guard.current_theme.clone();
+                terminal.draw(|f| {
+                    draw_auth_ui::<B>(
+                        f,
+                        &auth_state.username_input,
+                        &auth_state.password_input,
+                        auth_state.selected_icon_index,
+                        &auth_state.current_mode,
+                        &auth_state.selected_field,
+                        &theme,
+                        &mut app_state_guard,
+                        settings_state,
+                    );
+                })?;
+            }
+        }
+    }
+}
+
 pub async fn run_auth_page<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app_state: Arc<tokio::sync::Mutex<AppState>>,
@@ -35,7 +69,7 @@ pub async fn run_auth_page<B: ratatui::backend::Backend>(
             state.disconnect_selection,
         )
     };
-    let client = reqwest::Client::new();
+    let api = app_state.lock().await.api.clone();
     terminal.show_cursor()?;
     app_state
         .lock()
@@ -52,7 +86,8 @@ pub async fn run_auth_page<B: ratatui::backend::Backend>(
 
     loop {
         let mut app_state_guard = app_state.lock().await;
-        app_state_guard.notification_manager.update();
+        app_state_guard.sync_do_not_disturb();
+        app_state_guard.update_notifications();
 
         let theme = app_state_guard.current_theme.clone();
         terminal.draw(|f| {
@@ -103,6 +138,12 @@ pub async fn run_auth_page<B: ratatui::backend::Backend>(
                 }
             } else {
                 if let event::Event::Key(key) = event {
+                    // every field here takes text, so only F1
+                    if key.kind == event::KeyEventKind::Press
+                        && app_state_guard.keybindings.handle_key(&key, false)
+                    {
+                        continue;
+                    }
                     if key.code == event::KeyCode::Char('s')
                         && key.modifiers.contains(event::KeyModifiers::CONTROL)
                     {
@@ -132,260 +173,284 @@ pub async fn run_auth_page<B: ratatui::backend::Backend>(
 
                 if event_result.should_submit {
                     match auth_state.selected_field {
-                        SelectedField::RegisterButton => {
-                            if auth_state.current_mode == AuthMode::Register {
-                                let validation_error = get_validation_error(
-                                    &auth_state.username_input,
-                                    &auth_state.password_input,
-                                    &auth_state.current_mode,
-                                    &mut app_state_guard.notification_manager,
+                        SelectedField::RegisterButton
+                            if auth_state.current_mode == AuthMode::Register =>
+                        {
+                            let validation_error = get_validation_error(
+                                &auth_state.username_input,
+                                &auth_state.password_input,
+                                &auth_state.current_mode,
+                                &mut app_state_guard.notification_manager,
+                                app_state.clone(),
+                            )
+                            .await;
+                            if validation_error.is_some() {
+                                continue;
+                            }
+
+                            let loading_notification = app_state_guard
+                                .notification_manager
+                                .add(
+                                    "Registering... ".to_string(),
+                                    "Please wait... 󰞌".to_string(),
+                                    NotificationType::Loading,
+                                    None,
                                     app_state.clone(),
                                 )
                                 .await;
-                                if validation_error.is_some() {
-                                    continue;
-                                }
 
-                                let hashed_password = format!(
-                                    "{:x}",
-                                    Sha256::digest(auth_state.password_input.text.as_bytes())
+                            terminal.draw(|f| {
+                                draw_auth_ui::<B>(
+                                    f,
+                                    &auth_state.username_input,
+                                    &auth_state.password_input,
+                                    auth_state.selected_icon_index,
+                                    &auth_state.current_mode,
+                                    &auth_state.selected_field,
+                                    &theme,
+                                    &mut app_state_guard,
+                                    &mut settings_state,
                                 );
-                                let loading_notification = app_state_guard
-                                    .notification_manager
-                                    .add(
-                                        "Registering... ".to_string(),
-                                        "Please wait... 󰞌".to_string(),
-                                        NotificationType::Loading,
-                                        None,
-                                        app_state.clone(),
-                                    )
-                                    .await;
-
-                                terminal.draw(|f| {
-                                    draw_auth_ui::<B>(
-                                        f,
-                                        &auth_state.username_input,
-                                        &auth_state.password_input,
-                                        auth_state.selected_icon_index,
-                                        &auth_state.current_mode,
-                                        &auth_state.selected_field,
-                                        &theme,
-                                        &mut app_state_guard,
-                                        &mut settings_state,
-                                    );
-                                })?;
+                            })?;
 
-                                drop(app_state_guard); // Release the lock before async calls that might re-acquire it
+                            drop(app_state_guard); // Release the lock before async calls that might re-acquire it
 
-                                let register_result = auth_api::register(
-                                    &client,
+                            let register_result = redraw_while_pending(
+                                terminal,
+                                &app_state,
+                                &auth_state,
+                                &mut settings_state,
+                                api.register(
                                     &auth_state.username_input.text,
-                                    &hashed_password,
+                                    &auth_state.password_input.text,
                                     ICONS[auth_state.selected_icon_index],
-                                )
-                                .await;
+                                ),
+                            )
+                            .await?;
 
-                                let mut app_state_guard = app_state.lock().await; // Re-acquire the lock once after the API call
+                            let mut app_state_guard = app_state.lock().await; // Re-acquire the lock once after the API call
 
-                                match register_result {
-                                    Ok(token_response) => {
-                                        if let Some(loading) = loading_notification {
-                                            tokio::spawn(async move {
-                                                loading.remove().await;
-                                            });
-                                        }
-                                        app_state_guard
-                                            .notification_manager
-                                            .add(
-                                                "Registration Success".to_string(),
-                                                "You have been successfully registered. 󰱰"
-                                                    .to_string(),
-                                                NotificationType::Success,
-                                                Some(Duration::from_secs(3)),
-                                                app_state.clone(),
-                                            )
-                                            .await;
-                                        app_state_guard.set_user_auth(
-                                            token_response.token.clone(),
-                                            auth_state.username_input.text.clone(),
-                                            token_response.icon.clone(),
-                                        );
-                                        app_state_guard.config.token = Some(token_response.token);
-                                        app_state_guard.config.username = Some(auth_state.username_input.text.clone());
-                                        app_state_guard.config.user_icon = Some(token_response.icon);
-                                        terminal.draw(|f| {
-                                            draw_auth_ui::<B>(
-                                                f,
-                                                &auth_state.username_input,
-                                                &auth_state.password_input,
-                                                auth_state.selected_icon_index,
-                                                &auth_state.current_mode,
-                                                &auth_state.selected_field,
-                                                &theme,
-                                                &mut app_state_guard,
-                                                &mut settings_state,
-                                            );
-                                        })?;
-                                        return Ok(TuiPage::Chat);
+                            match register_result {
+                                Ok(token_response) => {
+                                    if let Some(loading) = loading_notification {
+                                        app_state_guard.notification_manager.resolve(loading);
                                     }
-                                    Err(e) => {
-                                        if let Some(loading) = loading_notification {
-                                            tokio::spawn(async move {
-                                                loading.remove().await;
-                                            });
-                                        }
+                                    app_state_guard
+                                        .notification_manager
+                                        .add(
+                                            "Registration Success".to_string(),
+                                            "You have been successfully registered. 󰱰".to_string(),
+                                            NotificationType::Success,
+                                            Some(Duration::from_secs(3)),
+                                            app_state.clone(),
+                                        )
+                                        .await;
+                                    app_state_guard.set_user_auth(
+                                        token_response.token.clone(),
+                                        auth_state.username_input.text.clone(),
+                                        token_response.icon.clone(),
+                                    );
+                                    app_state_guard.config.token = Some(token_response.token);
+                                    app_state_guard.config.username =
+                                        Some(auth_state.username_input.text.clone());
+                                    app_state_guard.config.user_icon = Some(token_response.icon);
+                                    // right away, so the token lands in the keyring now and not only at exit
+                                    if let Err(e) =
+                                        crate::config::save_config(&app_state_guard.config)
+                                    {
                                         app_state_guard
                                             .notification_manager
                                             .add(
-                                                "Registration Error ".to_string(),
+                                                "Config Not Saved 󰒓".to_string(),
                                                 e.to_string(),
-                                                NotificationType::Error,
-                                                Some(Duration::from_secs(3)),
+                                                NotificationType::Warning,
+                                                Some(Duration::from_secs(5)),
                                                 app_state.clone(),
                                             )
                                             .await;
-                                        terminal.draw(|f| {
-                                            draw_auth_ui::<B>(
-                                                f,
-                                                &auth_state.username_input,
-                                                &auth_state.password_input,
-                                                auth_state.selected_icon_index,
-                                                &auth_state.current_mode,
-                                                &auth_state.selected_field,
-                                                &theme,
-                                                &mut app_state_guard,
-                                                &mut settings_state,
-                                            );
-                                        })?;
                                     }
+                                    terminal.draw(|f| {
+                                        draw_auth_ui::<B>(
+                                            f,
+                                            &auth_state.username_input,
+                                            &auth_state.password_input,
+                                            auth_state.selected_icon_index,
+                                            &auth_state.current_mode,
+                                            &auth_state.selected_field,
+                                            &theme,
+                                            &mut app_state_guard,
+                                            &mut settings_state,
+                                        );
+                                    })?;
+                                    return Ok(TuiPage::Chat);
+                                }
+                                Err(e) => {
+                                    if let Some(loading) = loading_notification {
+                                        app_state_guard.notification_manager.resolve(loading);
+                                    }
+                                    app_state_guard
+                                        .notification_manager
+                                        .add(
+                                            "Registration Error ".to_string(),
+                                            e.to_string(),
+                                            NotificationType::Error,
+                                            Some(Duration::from_secs(3)),
+                                            app_state.clone(),
+                                        )
+                                        .await;
+                                    terminal.draw(|f| {
+                                        draw_auth_ui::<B>(
+                                            f,
+                                            &auth_state.username_input,
+                                            &auth_state.password_input,
+                                            auth_state.selected_icon_index,
+                                            &auth_state.current_mode,
+                                            &auth_state.selected_field,
+                                            &theme,
+                                            &mut app_state_guard,
+                                            &mut settings_state,
+                                        );
+                                    })?;
                                 }
                             }
                         }
-                        SelectedField::LoginButton => {
-                            if auth_state.current_mode == AuthMode::Login {
-                                let validation_error = get_validation_error(
-                                    &auth_state.username_input,
-                                    &auth_state.password_input,
-                                    &auth_state.current_mode,
-                                    &mut app_state_guard.notification_manager,
+                        SelectedField::LoginButton
+                            if auth_state.current_mode == AuthMode::Login =>
+                        {
+                            let validation_error = get_validation_error(
+                                &auth_state.username_input,
+                                &auth_state.password_input,
+                                &auth_state.current_mode,
+                                &mut app_state_guard.notification_manager,
+                                app_state.clone(),
+                            )
+                            .await;
+                            if validation_error.is_some() {
+                                continue;
+                            }
+
+                            let loading_notification = app_state_guard
+                                .notification_manager
+                                .add(
+                                    "Logging in... ".to_string(),
+                                    "Please wait... 󰞌".to_string(),
+                                    NotificationType::Loading,
+                                    None,
                                     app_state.clone(),
                                 )
                                 .await;
-                                if validation_error.is_some() {
-                                    continue;
-                                }
-
-                                let loading_notification = app_state_guard
-                                    .notification_manager
-                                    .add(
-                                        "Logging in... ".to_string(),
-                                        "Please wait... 󰞌".to_string(),
-                                        NotificationType::Loading,
-                                        None,
-                                        app_state.clone(),
-                                    )
-                                    .await;
 
-                                terminal.draw(|f| {
-                                    draw_auth_ui::<B>(
-                                        f,
-                                        &auth_state.username_input,
-                                        &auth_state.password_input,
-                                        auth_state.selected_icon_index,
-                                        &auth_state.current_mode,
-                                        &auth_state.selected_field,
-                                        &theme,
-                                        &mut app_state_guard,
-                                        &mut settings_state,
-                                    );
-                                })?;
+                            terminal.draw(|f| {
+                                draw_auth_ui::<B>(
+                                    f,
+                                    &auth_state.username_input,
+                                    &auth_state.password_input,
+                                    auth_state.selected_icon_index,
+                                    &auth_state.current_mode,
+                                    &auth_state.selected_field,
+                                    &theme,
+                                    &mut app_state_guard,
+                                    &mut settings_state,
+                                );
+                            })?;
 
-                                drop(app_state_guard); // Release the lock before async calls that might re-acquire it
+                            drop(app_state_guard); // Release the lock before async calls that might re-acquire it
 
-                                let hashed_password = format!(
-                                    "{:x}",
-                                    Sha256::digest(auth_state.password_input.text.as_bytes())
-                                );
-                                let login_result = auth_api::login(
-                                    &client,
+                            let login_result = redraw_while_pending(
+                                terminal,
+                                &app_state,
+                                &auth_state,
+                                &mut settings_state,
+                                api.login(
                                     &auth_state.username_input.text,
-                                    &hashed_password,
-                                )
-                                .await;
+                                    &auth_state.password_input.text,
+                                ),
+                            )
+                            .await?;
 
-                                let mut app_state_guard = app_state.lock().await; // Re-acquire the lock once after the API call
+                            let mut app_state_guard = app_state.lock().await; // Re-acquire the lock once after the API call
 
-                                match login_result {
-                                    Ok(token_response) => {
-                                        if let Some(loading) = loading_notification {
-                                            tokio::spawn(async move {
-                                                loading.remove().await;
-                                            });
-                                        }
-                                        app_state_guard
-                                            .notification_manager
-                                            .add(
-                                                "Login Success 󰱨".to_string(),
-                                                "You have been successfully logged in. ⬱ "
-                                                    .to_string(),
-                                                NotificationType::Success,
-                                                Some(Duration::from_secs(3)),
-                                                app_state.clone(),
-                                            )
-                                            .await;
-                                        app_state_guard.set_user_auth(
-                                            token_response.token.clone(),
-                                            auth_state.username_input.text.clone(),
-                                            token_response.icon.clone(),
-                                        );
-                                        app_state_guard.config.token = Some(token_response.token);
-                                        app_state_guard.config.username = Some(auth_state.username_input.text.clone());
-                                        app_state_guard.config.user_icon = Some(token_response.icon);
-                                        terminal.draw(|f| {
-                                            draw_auth_ui::<B>(
-                                                f,
-                                                &auth_state.username_input,
-                                                &auth_state.password_input,
-                                                auth_state.selected_icon_index,
-                                                &auth_state.current_mode,
-                                                &auth_state.selected_field,
-                                                &theme,
-                                                &mut app_state_guard,
-                                                &mut settings_state,
-                                            );
-                                        })?;
-                                        return Ok(TuiPage::Chat);
+                            match login_result {
+                                Ok(token_response) => {
+                                    if let Some(loading) = loading_notification {
+                                        app_state_guard.notification_manager.resolve(loading);
                                     }
-                                    Err(e) => {
-                                        if let Some(loading) = loading_notification {
-                                            tokio::spawn(async move {
-                                                loading.remove().await;
-                                            });
-                                        }
+                                    app_state_guard
+                                        .notification_manager
+                                        .add(
+                                            "Login Success 󰱨".to_string(),
+                                            "You have been successfully logged in. ⬱ ".to_string(),
+                                            NotificationType::Success,
+                                            Some(Duration::from_secs(3)),
+                                            app_state.clone(),
+                                        )
+                                        .await;
+                                    app_state_guard.set_user_auth(
+                                        token_response.token.clone(),
+                                        auth_state.username_input.text.clone(),
+                                        token_response.icon.clone(),
+                                    );
+                                    app_state_guard.config.token = Some(token_response.token);
+                                    app_state_guard.config.username =
+                                        Some(auth_state.username_input.text.clone());
+                                    app_state_guard.config.user_icon = Some(token_response.icon);
+                                    // right away, so the token lands in the keyring now and not only at exit
+                                    if let Err(e) =
+                                        crate::config::save_config(&app_state_guard.config)
+                                    {
                                         app_state_guard
                                             .notification_manager
                                             .add(
-                                                "Login Error ".to_string(),
+                                                "Config Not Saved 󰒓".to_string(),
                                                 e.to_string(),
-                                                NotificationType::Error,
-                                                Some(Duration::from_secs(3)),
+                                                NotificationType::Warning,
+                                                Some(Duration::from_secs(5)),
                                                 app_state.clone(),
                                             )
                                             .await;
-                                        terminal.draw(|f| {
-                                            draw_auth_ui::<B>(
-                                                f,
-                                                &auth_state.username_input,
-                                                &auth_state.password_input,
-                                                auth_state.selected_icon_index,
-                                                &auth_state.current_mode,
-                                                &auth_state.selected_field,
-                                                &theme,
-                                                &mut app_state_guard,
-                                                &mut settings_state,
-                                            );
-                                        })?;
                                     }
+                                    terminal.draw(|f| {
+                                        draw_auth_ui::<B>(
+                                            f,
+                                            &auth_state.username_input,
+                                            &auth_state.password_input,
+                                            auth_state.selected_icon_index,
+                                            &auth_state.current_mode,
+                                            &auth_state.selected_field,
+                                            &theme,
+                                            &mut app_state_guard,
+                                            &mut settings_state,
+                                        );
+                                    })?;
+                                    return Ok(TuiPage::Chat);
+                                }
+                                Err(e) => {
+                                    if let Some(loading) = loading_notification {
+                                        app_state_guard.notification_manager.resolve(loading);
+                                    }
+                                    app_state_guard
+                                        .notification_manager
+                                        .add(
+                                            "Login Error ".to_string(),
+                                            e.to_string(),
+                                            NotificationType::Error,
+                                            Some(Duration::from_secs(3)),
+                                            app_state.clone(),
+                                        )
+                                        .await;
+                                    terminal.draw(|f| {
+                                        draw_auth_ui::<B>(
+                                            f,
+                                            &auth_state.username_input,
+                                            &auth_state.password_input,
+                                            auth_state.selected_icon_index,
+                                            &auth_state.current_mode,
+                                            &auth_state.selected_field,
+                                            &theme,
+                                            &mut app_state_guard,
+                                            &mut settings_state,
+                                        );
+                                    })?;
                                 }
                             }
                         }
@@ -1,5 +1,6 @@
-use crate::themes::{interpolate_rgb, rgb_to_color, Theme};
+use crate::themes::{gradient_text, rgb_to_color, Theme};
 use crate::tui::auth::state::{AuthMode, SelectedField};
+use crate::tui::keymap::draw_keybindings_overlay;
 use crate::tui::notification::notification::NotificationType;
 use crate::tui::notification::ui::draw_notifications;
 use ratatui::{
@@ -10,22 +11,35 @@ use std::sync::Arc;
 
 pub const ICONS: [&str; 11] = ["󰱨", "󰱩", "󱃞", "󰱫", "󰱬", "󰱮", "󰱰", "󰽌", "󰱱", "󰱸", "󰇹"];
 
-pub fn validate_input(username_input: &TextInput, password_input: &TextInput) -> Option<String> {
-    if username_input.text.trim().is_empty() {
+/// Same rules for registering and for renaming from the settings.
+pub fn validate_username(username: &str) -> Option<String> {
+    if username.trim().is_empty() {
         return Some("Username cannot be empty, try to type smt, magic will appear 󱈔".to_string());
     }
-    if username_input.text.contains(' ') {
+    if username.contains(' ') {
         return Some("Username cannot contain spaces, because the owner is too lazy to solve the bugs that will happen with the mention rendering .".to_string());
     }
-    if password_input.text.trim().is_empty() {
+    None
+}
+
+/// The password policy, shared with the change-password form in the settings.
+pub fn validate_password(password: &str) -> Option<String> {
+    if password.trim().is_empty() {
         return Some("Password cannot be empty, make your account secure >:3.".to_string());
     }
-    if password_input.text.contains(' ') {
+    if password.contains(' ') {
         return Some(r"Password cannot contain spaces, the username and password have the same rules so ¯\_(ツ)_/¯.".to_string());
     }
     None
 }
 
+pub fn validate_input(username_input: &TextInput, password_input: &TextInput) -> Option<String> {
+    if let Some(e) = validate_username(&username_input.text) {
+        return Some(e);
+    }
+    validate_password(&password_input.text)
+}
+
 pub async fn get_validation_error(
     username_input: &TextInput,
     password_input: &TextInput,
@@ -86,26 +100,12 @@ pub fn draw_ascii_title(f: &mut Frame, area: Rect, theme: &Theme, current_mode:
     let num_lines = lines.len();
     let max_line_width = lines.iter().map(|line| line.len()).max().unwrap_or(0) as u16;
     let mut text_lines: Vec<Line> = Vec::new();
-    let num_lines_for_gradient = if num_lines <= 1 {
-        1.0
-    } else {
-        (num_lines - 1) as f32
-    };
-    for (line_idx, line_str) in lines.iter().enumerate() {
-        let mut spans = Vec::new();
-        let fraction_y = line_idx as f32 / num_lines_for_gradient;
-        for ch in line_str.chars() {
-            let interpolated_rgb = interpolate_rgb(
-                &theme.colors.title_gradient_start,
-                &theme.colors.title_gradient_end,
-                fraction_y,
-            );
-            spans.push(Span::styled(
-                ch.to_string(),
-                Style::default().fg(rgb_to_color(&interpolated_rgb)),
-            ));
-        }
-        text_lines.push(Line::from(spans));
+    for line_str in lines.iter() {
+        text_lines.push(gradient_text(
+            line_str,
+            &theme.colors.title_gradient_start,
+            &theme.colors.title_gradient_end,
+        ));
     }
     let title_paragraph = Paragraph::new(text_lines).alignment(Alignment::Center);
     let centered_title_width = max_line_width;
@@ -115,7 +115,9 @@ pub fn draw_ascii_title(f: &mut Frame, area: Rect, theme: &Theme, current_mode:
         y: area.y + 1,
         width: centered_title_width.min(area.width),
         height: num_lines as u16,
-    };
+    }
+    // a tiny terminal squeezes the title area below its art
+    .intersection(area);
     f.render_widget(title_paragraph, title_area);
 }
 
@@ -355,8 +357,10 @@ pub fn draw_auth_ui<'a, B: Backend>(
     f.render_widget(btn_para, rows[button_chunk_index]);
 
     let footer_area = main_chunks[2];
-    let help_text_area = Rect::new(footer_area.x, footer_area.y, footer_area.width, 1);
-    let instructions_area = Rect::new(footer_area.x, footer_area.y + 2, footer_area.width, 1);
+    let help_text_area =
+        Rect::new(footer_area.x, footer_area.y, footer_area.width, 1).intersection(footer_area);
+    let instructions_area = Rect::new(footer_area.x, footer_area.y + 2, footer_area.width, 1)
+        .intersection(footer_area);
 
     let help_text = if *current_mode == AuthMode::Register {
         "Already have an account? Press < Tab> to switch to Login."
@@ -388,5 +392,6 @@ pub fn draw_auth_ui<'a, B: Backend>(
             .unwrap();
     }
 
+    draw_keybindings_overlay(f, app_state);
     draw_notifications(f, app_state);
 }
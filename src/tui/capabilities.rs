@@ -0,0 +1,148 @@
+use crate::config::{Config, TrueColorMode};
+use crate::themes::nerd_font;
+use crate::tui::icons::plain_symbol;
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::Cell,
+    layout::{Position, Size},
+};
+use std::io;
+use tokio::process::Command;
+
+/// What the terminal turned out to support at startup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalCapabilities {
+    /// 24-bit colors, without it RGB is brought down to the 256 color palette
+    pub truecolor: bool,
+    /// whether a Nerd Font is installed, `None` without fontconfig to ask (Windows, most
+    /// macs). Icons are only drawn as plain characters on a sure no.
+    pub nerd_font: Option<bool>,
+}
+
+impl Default for TerminalCapabilities {
+    fn default() -> Self {
+        Self {
+            truecolor: true,
+            nerd_font: Some(true),
+        }
+    }
+}
+
+impl TerminalCapabilities {
+    /// Checks `COLORTERM` and asks fontconfig for a Nerd Font. No `fc-list` (or no answer)
+    /// leaves the Nerd Font unknown.
+    pub async fn detect() -> Self {
+        let nerd_font = match Command::new("fc-list").output().await {
+            Ok(output) if output.status.success() => {
+                Some(has_nerd_font(&String::from_utf8_lossy(&output.stdout)))
+            }
+            _ => None,
+        };
+        Self {
+            truecolor: truecolor_from(std::env::var("COLORTERM").ok().as_deref()),
+            nerd_font,
+        }
+    }
+
+    /// Makes the drawing code follow these from now on, unless the config says otherwise.
+    /// Icons only fall back when the config lets them, some fonts are there without
+    /// fontconfig knowing.
+    pub fn apply(&self, config: &Config) {
+        crate::themes::set_truecolor(match config.truecolor {
+            TrueColorMode::Auto => self.truecolor,
+            TrueColorMode::On => true,
+            TrueColorMode::Off => false,
+        });
+        crate::themes::set_nerd_font(self.nerd_font != Some(false) || !config.icon_fallback);
+    }
+}
+
+/// `COLORTERM=truecolor` (or `24bit`) is how terminals say they do 24-bit colors. Plenty
+/// that do never set it, so only another value there counts as a no.
+pub fn truecolor_from(colorterm: Option<&str>) -> bool {
+    match colorterm {
+        None | Some("") => true,
+        Some(value) => {
+            value.eq_ignore_ascii_case("truecolor") || value.eq_ignore_ascii_case("24bit")
+        }
+    }
+}
+
+/// Whether `fc-list` output has a Nerd Font in it.
+pub fn has_nerd_font(fc_list: &str) -> bool {
+    fc_list.to_lowercase().contains("nerd font")
+}
+
+/// Wraps the real backend so every page gets plain icons without a Nerd Font, without any
+/// of them having to know. Glyphs are one cell wide either way, the layout doesn't move.
+pub struct GlyphFallbackBackend<B: Backend> {
+    inner: B,
+}
+
+impl<B: Backend> GlyphFallbackBackend<B> {
+    pub fn new(inner: B) -> Self {
+        Self { inner }
+    }
+}
+
+impl<B: Backend> Backend for GlyphFallbackBackend<B> {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        if nerd_font() {
+            return self.inner.draw(content);
+        }
+        let cells: Vec<(u16, u16, Cell)> = content
+            .map(|(x, y, cell)| {
+                let mut cell = cell.clone();
+                if let std::borrow::Cow::Owned(symbol) = plain_symbol(cell.symbol()) {
+                    cell.set_symbol(&symbol);
+                }
+                (x, y, cell)
+            })
+            .collect();
+        self.inner
+            .draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.inner.append_lines(n)
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.inner.hide_cursor()
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.inner.show_cursor()
+    }
+
+    fn get_cursor_position(&mut self) -> io::Result<Position> {
+        self.inner.get_cursor_position()
+    }
+
+    fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> io::Result<()> {
+        self.inner.set_cursor_position(position)
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.inner.clear()
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.inner.clear_region(clear_type)
+    }
+
+    fn size(&self) -> io::Result<Size> {
+        self.inner.size()
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        self.inner.window_size()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+// Every slash command the client knows about, the ones handled locally and the ones the server understands.
+pub struct SlashCommand {
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub description: &'static str,
+}
+
+pub const COMMANDS: &[SlashCommand] = &[
+    SlashCommand {
+        name: "download",
+        usage: "/download <file_id>",
+        description: "Download a file shared in the channel",
+    },
+    SlashCommand {
+        name: "download_all",
+        usage: "/download_all [directory]",
+        description: "Download every file shared in the channel (into downloads/ by default)",
+    },
+    SlashCommand {
+        name: "goto",
+        usage: "/goto <message_id>",
+        description: "Jump to a message, fetching older history if it's not loaded",
+    },
+    SlashCommand {
+        name: "propose_channel",
+        usage: "/propose_channel <name> <icon>",
+        description: "Ask for a new channel to be created",
+    },
+    SlashCommand {
+        name: "delete_channel",
+        usage: "/delete_channel <channel_id>",
+        description: "Delete a channel (or leave it)",
+    },
+    SlashCommand {
+        name: "get_history",
+        usage: "/get_history <channel_id> <offset>",
+        description: "Fetch older messages of a channel",
+    },
+    SlashCommand {
+        name: "update_profile",
+        usage: "/update_profile <username> <icon>",
+        description: "Change your username and icon",
+    },
+    SlashCommand {
+        name: "change_password",
+        usage: "/change_password <current_hash> <new_hash>",
+        description: "Change your password (the settings screen does the hashing)",
+    },
+    SlashCommand {
+        name: "delete_account",
+        usage: "/delete_account",
+        description: "Delete your account for good",
+    },
+    SlashCommand {
+        name: "get_active_users",
+        usage: "/get_active_users",
+        description: "List who's online",
+    },
+];
+
+/// The command token (without the `/`) when the input looks like a command.
+pub fn command_token(input: &str) -> Option<&str> {
+    let rest = input.strip_prefix('/')?;
+    let token = rest.split_whitespace().next().unwrap_or("");
+    // a lone "/" or "/ something" isn't a command, just a slash
+    if token.is_empty() || rest.starts_with(char::is_whitespace) {
+        None
+    } else {
+        Some(token)
+    }
+}
+
+pub fn find_command(name: &str) -> Option<&'static SlashCommand> {
+    COMMANDS.iter().find(|c| c.name == name)
+}
+
+/// A command the client or server knows, or one of the user's macros.
+pub fn is_known_command(token: &str, macros: &HashMap<String, String>) -> bool {
+    find_command(token).is_some() || macros.contains_key(token)
+}
+
+/// True when the input starts like a command but nobody knows that command.
+pub fn is_unknown_command(input: &str, macros: &HashMap<String, String>) -> bool {
+    command_token(input).is_some_and(|token| !is_known_command(token, macros))
+}
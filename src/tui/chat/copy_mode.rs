@@ -0,0 +1,133 @@
+// Copy mode: the current channel as plain text over the whole screen, no borders or panes,
+// so the terminal's own mouse selection only picks up what was said.
+
+use crate::api::models::BroadcastMessage;
+use crate::app::AppState;
+use crate::themes::rgb_to_color;
+use crate::tui::chat::message_parsing::split_spoilers;
+use chrono::{TimeZone, Utc};
+use crossterm::event::KeyCode;
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Text},
+    widgets::{Clear, Paragraph},
+    Frame,
+};
+use std::collections::HashSet;
+
+const HINT: &str = "copy mode · select with the mouse · ↑↓ PgUp PgDn scroll · Esc back";
+
+/// A message's text as it reads on screen, hidden spoilers stay hidden.
+pub fn plain_content(content: &str, revealed: bool) -> String {
+    split_spoilers(content)
+        .into_iter()
+        .map(|segment| {
+            if segment.spoiler && !revealed {
+                "▒▒▒"
+            } else {
+                segment.text
+            }
+        })
+        .collect()
+}
+
+fn plain_message(message: &BroadcastMessage, revealed: bool) -> String {
+    match &message.file_name {
+        Some(name) if message.file_id.is_some() => match message.file_extension.as_deref() {
+            Some(extension) if !extension.is_empty() => format!("[file] {}.{}", name, extension),
+            _ => format!("[file] {}", name),
+        },
+        _ => plain_content(&message.content, revealed),
+    }
+}
+
+/// One `[time] user: text` entry per message, wrapped to `width`. Lines after a message's
+/// first are left as they are so a copied code block keeps its indentation.
+pub fn transcript_lines<'a>(
+    messages: impl IntoIterator<Item = &'a BroadcastMessage>,
+    revealed_spoilers: &HashSet<String>,
+    width: usize,
+) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for message in messages {
+        let revealed = message
+            .client_id
+            .as_ref()
+            .is_some_and(|id| revealed_spoilers.contains(id));
+        let time = Utc
+            .timestamp_opt(message.timestamp, 0)
+            .single()
+            .unwrap_or_default()
+            .with_timezone(&chrono::Local)
+            .format("%b %-d %H:%M");
+        let text = format!(
+            "[{}] {}: {}",
+            time,
+            message.user,
+            plain_message(message, revealed)
+        );
+        for line in text.lines() {
+            lines.extend(
+                textwrap::wrap(line, width)
+                    .into_iter()
+                    .map(|part| part.into_owned()),
+            );
+        }
+    }
+    lines
+}
+
+/// Scrolls or leaves copy mode, every other key is ignored so a stray one doesn't drop the
+/// selection.
+pub fn handle_copy_mode_key(state: &mut AppState, code: KeyCode) {
+    let page = state.last_chat_view_height.max(1);
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => state.copy_mode = false,
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.copy_mode_scroll = state.copy_mode_scroll.saturating_add(1)
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.copy_mode_scroll = state.copy_mode_scroll.saturating_sub(1)
+        }
+        KeyCode::PageUp => state.copy_mode_scroll = state.copy_mode_scroll.saturating_add(page),
+        KeyCode::PageDown => state.copy_mode_scroll = state.copy_mode_scroll.saturating_sub(page),
+        KeyCode::Home => state.copy_mode_scroll = usize::MAX,
+        KeyCode::End => state.copy_mode_scroll = 0,
+        _ => {}
+    }
+}
+
+pub fn draw_copy_mode(f: &mut Frame, state: &mut AppState) {
+    let area = f.area();
+    let view_height = area.height.saturating_sub(1) as usize;
+    let revealed = &state.revealed_spoilers;
+    let lines = state
+        .current_channel
+        .as_ref()
+        .and_then(|channel| state.messages.get(&channel.id))
+        .map(|messages| transcript_lines(messages, revealed, area.width as usize))
+        .unwrap_or_default();
+
+    // counted up from the newest line like the messages pane, kept in range here
+    let max_scroll = lines.len().saturating_sub(view_height);
+    state.copy_mode_scroll = state.copy_mode_scroll.min(max_scroll);
+    let end = lines.len() - state.copy_mode_scroll;
+    let start = end.saturating_sub(view_height);
+
+    f.render_widget(Clear, area);
+    let transcript: Vec<Line> = lines[start..end]
+        .iter()
+        .map(|line| Line::raw(line.as_str()))
+        .collect();
+    f.render_widget(
+        Paragraph::new(Text::from(transcript)),
+        Rect::new(area.x, area.y, area.width, view_height as u16),
+    );
+    f.render_widget(
+        Paragraph::new(HINT)
+            .style(Style::default().fg(rgb_to_color(&state.current_theme.colors.dim))),
+        Rect::new(area.x, area.bottom().saturating_sub(1), area.width, 1),
+    );
+}
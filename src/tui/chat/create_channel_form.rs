@@ -1,9 +1,15 @@
+use crate::tui::chat::popups::icon_picker::IconPickerState;
+
 pub const ICONS: [&str; 11] = ["󰱨", "󰱩", "󱃞", "󰱫", "󰱬", "󰱮", "󰱰", "󰽌", "󰱱", "󰱸", "󰇹"];
 #[derive(Default, Debug, Clone)]
 pub struct CreateChannelForm {
     pub name: String,
     pub input_focused: CreateChannelInput,
     pub selected_icon_index: usize,
+    /// picked in the full picker and not one of `ICONS`, wins over the carousel until it moves
+    pub picked_icon: Option<&'static str>,
+    /// the full icon picker, open over the form while Some
+    pub icon_picker: Option<IconPickerState>,
 }
 
 #[derive(PartialEq, Default, Clone, Copy, Debug)]
@@ -16,11 +22,12 @@ pub enum CreateChannelInput {
 
 impl CreateChannelForm {
     pub fn new() -> Self {
-
         Self {
             name: String::new(),
             input_focused: CreateChannelInput::Name,
             selected_icon_index: 0,
+            picked_icon: None,
+            icon_picker: None,
         }
     }
 
@@ -30,7 +37,6 @@ impl CreateChannelForm {
             CreateChannelInput::Icon => CreateChannelInput::CreateButton,
             CreateChannelInput::CreateButton => CreateChannelInput::Name,
         };
-
     }
 
     pub fn previous_input(&mut self) {
@@ -42,15 +48,35 @@ impl CreateChannelForm {
     }
 
     pub fn next_icon(&mut self) {
+        self.picked_icon = None;
         self.selected_icon_index = (self.selected_icon_index + 1) % ICONS.len();
     }
 
     pub fn previous_icon(&mut self) {
+        self.picked_icon = None;
         self.selected_icon_index = (self.selected_icon_index + ICONS.len() - 1) % ICONS.len();
     }
 
     pub fn get_selected_icon(&self) -> String {
-        ICONS[self.selected_icon_index].to_string()
+        self.picked_icon
+            .unwrap_or(ICONS[self.selected_icon_index])
+            .to_string()
     }
-}
 
+    pub fn open_icon_picker(&mut self) {
+        self.icon_picker = Some(IconPickerState::default());
+    }
+
+    /// Takes the icon chosen in the full picker and closes it. One of `ICONS` just moves the
+    /// carousel there.
+    pub fn pick_icon(&mut self, glyph: &'static str) {
+        match ICONS.iter().position(|icon| *icon == glyph) {
+            Some(index) => {
+                self.selected_icon_index = index;
+                self.picked_icon = None;
+            }
+            None => self.picked_icon = Some(glyph),
+        }
+        self.icon_picker = None;
+    }
+}
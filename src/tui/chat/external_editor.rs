@@ -0,0 +1,55 @@
+use crate::tui::utils::sanitize_paste;
+use std::io;
+use std::path::PathBuf;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// The editor to run and its arguments, `$VISUAL` first then `$EDITOR` like git does. None
+/// when neither is set to anything.
+pub fn editor_command(visual: Option<&str>, editor: Option<&str>) -> Option<Vec<String>> {
+    [visual, editor].into_iter().flatten().find_map(|value| {
+        // "code --wait" and friends come with their own arguments
+        let parts: Vec<String> = value.split_whitespace().map(str::to_string).collect();
+        (!parts.is_empty()).then_some(parts)
+    })
+}
+
+/// What came back from the editor, as input text. Editors end the file with a newline nobody
+/// meant to send, and control characters are dropped like in a paste.
+pub fn edited_text(contents: &str) -> String {
+    sanitize_paste(contents.trim_end_matches(['\n', '\r']), true)
+}
+
+/// Creates the draft file under a name nobody can guess ahead of time, so it can't be a
+/// symlink someone left there, readable by us only.
+async fn create_draft(draft: &str) -> io::Result<PathBuf> {
+    let path = std::env::temp_dir().join(format!("reetui-message-{}.md", uuid::Uuid::new_v4()));
+    let mut options = tokio::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    options.mode(0o600);
+    let mut file = options.open(&path).await?;
+    file.write_all(draft.as_bytes()).await?;
+    file.flush().await?;
+    Ok(path)
+}
+
+/// Writes `draft` to a temp file, waits for the editor to close and reads it back. The
+/// terminal has to be handed over before this and taken back after, see `shutdown`.
+pub async fn edit_in_editor(command: &[String], draft: &str) -> io::Result<String> {
+    let (program, args) = command
+        .split_first()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no editor set"))?;
+    let path = create_draft(draft).await?;
+    let status = Command::new(program).args(args).arg(&path).status().await;
+    let contents = tokio::fs::read_to_string(&path).await;
+    let _ = tokio::fs::remove_file(&path).await;
+    let status = status?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "{} exited with {}",
+            program, status
+        )));
+    }
+    Ok(edited_text(&contents?))
+}
@@ -142,12 +142,27 @@ pub async fn spawn_gif_animation(
     redraw_tx: mpsc::UnboundedSender<String>,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
+        // true once the first frame is on screen with reduce motion on
+        let mut parked = false;
         loop {
+            let reduce_motion = app_state.lock().await.config.reduce_motion;
+            if reduce_motion && parked {
+                if !animation_state.lock().await.running {
+                    break;
+                }
+                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+                continue;
+            }
+            parked = reduce_motion;
             let (frame_content, delay, message_id, _next_frame_index) = {
                 let mut state = animation_state.lock().await;
                 if !state.running || state.frames.is_empty() {
                     break;
                 }
+                if reduce_motion {
+                    // park on the first frame, and check again later in case it gets turned off
+                    state.current_frame = 0;
+                }
                 let frame_content = state.frames[state.current_frame].clone();
                 let delay = state
                     .delays
@@ -156,7 +171,11 @@ pub async fn spawn_gif_animation(
                     .unwrap_or(100);
 
                 let message_id = state.message_id.clone();
-                let next_frame_index = (state.current_frame + 1) % state.frames.len();
+                let next_frame_index = if reduce_motion {
+                    0
+                } else {
+                    (state.current_frame + 1) % state.frames.len()
+                };
                 state.current_frame = next_frame_index;
                 state.last_frame_time = Some(Instant::now());
 
@@ -1,5 +1,6 @@
 use crate::api::models::BroadcastMessage;
-use crate::app::app_state::{AppState};
+use crate::app::app_state::{AppState, Thumbnail};
+use crate::themes::chafa_color_args;
 use image::ImageReader;
 use image::{GenericImageView, ImageFormat};
 
@@ -30,6 +31,87 @@ async fn update_and_log_message(
     }
 }
 
+/// Rows an inline thumbnail may take, so a picture never pushes the whole chat off screen.
+pub const THUMBNAIL_HEIGHT: u16 = 8;
+const THUMBNAIL_MAX_WIDTH: u16 = 40;
+
+static CHAFA_AVAILABLE: tokio::sync::OnceCell<bool> = tokio::sync::OnceCell::const_new();
+
+/// Extensions chafa is asked to draw, shared with the file manager preview.
+pub fn is_image_extension(extension: &str) -> bool {
+    matches!(
+        extension.to_lowercase().as_str(),
+        "png" | "jpg" | "jpeg" | "gif" | "bmp"
+    )
+}
+
+/// Whether chafa can be started at all, checked once per run.
+pub async fn chafa_available() -> bool {
+    *CHAFA_AVAILABLE
+        .get_or_init(|| async {
+            Command::new("chafa")
+                .arg("--version")
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status()
+                .await
+                .is_ok_and(|status| status.success())
+        })
+        .await
+}
+
+/// Downloads an image attachment into the temp dir and keeps a small chafa rendering of it
+/// in `AppState::thumbnails`.
+pub async fn load_thumbnail(
+    app_state: Arc<Mutex<AppState>>,
+    message: BroadcastMessage,
+    api: crate::api::client::ApiClient,
+    chat_width: u16,
+    redraw_tx: mpsc::UnboundedSender<String>,
+) {
+    let Some(file_id) = message.file_id.clone() else {
+        return;
+    };
+    let result = render_thumbnail(&message, &file_id, &api, chat_width).await;
+    let mut state = app_state.lock().await;
+    state.thumbnails.insert(
+        file_id.clone(),
+        match result {
+            Ok(thumbnail) => Thumbnail::Ready(thumbnail),
+            Err(e) => Thumbnail::Failed(e),
+        },
+    );
+    state.refresh_attachment(&file_id);
+    let _ = redraw_tx.send(file_id);
+}
+
+async fn render_thumbnail(
+    message: &BroadcastMessage,
+    file_id: &str,
+    api: &crate::api::client::ApiClient,
+    chat_width: u16,
+) -> Result<String, String> {
+    if !chafa_available().await {
+        return Err("chafa isn't installed".to_string());
+    }
+    let file_name = match (&message.file_name, &message.file_extension) {
+        (Some(name), Some(extension)) => format!("{}.{}", name, extension),
+        (Some(name), None) => name.clone(),
+        _ => file_id.to_string(),
+    };
+    let expected = crate::api::file_api::ExpectedChecksum::from_message(message);
+    let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move { while progress_rx.recv().await.is_some() {} });
+    let path = api
+        .download_file(file_id, &file_name, progress_tx, None, &expected)
+        .await
+        .map_err(|e| e.to_string())?;
+    let image_data = tokio::fs::read(&path).await.map_err(|e| e.to_string());
+    let _ = tokio::fs::remove_file(&path).await;
+    let width = chat_width.saturating_sub(4).clamp(1, THUMBNAIL_MAX_WIDTH);
+    run_chafa(&image_data?, &format!("{}x{}", width, THUMBNAIL_HEIGHT)).await
+}
+
 /// A robust, non-blocking function to execute the chafa command.
 pub async fn run_chafa(image_data: &[u8], size: &str) -> Result<String, String> {
     let size_arg = format!("--size={}", size);
@@ -38,6 +120,7 @@ pub async fn run_chafa(image_data: &[u8], size: &str) -> Result<String, String>
 
     let mut command = Command::new("chafa");
     command.args(&args);
+    command.args(chafa_color_args());
     command.stdin(std::process::Stdio::piped());
     #[cfg(not(windows))]
     command.stdout(std::process::Stdio::piped());
@@ -127,17 +210,19 @@ pub fn is_gif(data: &[u8]) -> bool {
 pub async fn process_image_message(
     app_state: Arc<Mutex<AppState>>,
     mut message: BroadcastMessage,
-    http_client: &reqwest::Client,
+    api: &crate::api::client::ApiClient,
     chat_width: u16,
     redraw_tx: mpsc::UnboundedSender<String>, // Add redraw_tx here
 ) {
     let file_id = message.file_id.clone().unwrap_or_default();
     let file_name = message.file_name.clone().unwrap_or_default();
+    let expected = crate::api::file_api::ExpectedChecksum::from_message(&message);
     let (progress_tx, mut progress_rx) = mpsc::unbounded_channel();
 
     tokio::spawn(async move { while let Some(_) = progress_rx.recv().await {} });
 
-    match crate::api::file_api::download_file(http_client, &file_id, &file_name, progress_tx, false)
+    match api
+        .download_file(&file_id, &file_name, progress_tx, None, &expected)
         .await
     {
         Ok(file_path) => {
@@ -0,0 +1,100 @@
+use crate::api::client::ApiClient;
+use crate::api::link_preview::{previewable_url, LinkPreview};
+use crate::api::models::BroadcastMessage;
+use crate::app::app_state::{AppState, LinkPreviewState};
+use crate::themes::{rgb_to_color, Theme};
+use crate::tui::utils::truncate_to_width;
+use ratatui::{
+    style::{Modifier, Style},
+    text::{Line, Span},
+};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+/// Starts fetching the preview of the message's link, once per URL and only with previews on.
+pub fn request_link_preview(
+    state: &mut AppState,
+    message: &BroadcastMessage,
+    app_state: &Arc<Mutex<AppState>>,
+    api: &ApiClient,
+    redraw_tx: &mpsc::UnboundedSender<String>,
+) {
+    if !state.config.link_previews || message.file_id.is_some() {
+        return;
+    }
+    let Some(url) = previewable_url(&message.content) else {
+        return;
+    };
+    if state.link_previews.contains_key(&url) {
+        return;
+    }
+    state
+        .link_previews
+        .insert(url.clone(), LinkPreviewState::Loading);
+    let app_state = app_state.clone();
+    let api = api.clone();
+    let redraw_tx = redraw_tx.clone();
+    tokio::spawn(async move {
+        let preview = match api.link_preview(&url).await {
+            Ok(preview) => LinkPreviewState::Ready(preview),
+            Err(_) => LinkPreviewState::Failed,
+        };
+        app_state
+            .lock()
+            .await
+            .link_previews
+            .insert(url.clone(), preview);
+        let _ = redraw_tx.send(url);
+    });
+}
+
+/// The loaded preview for a message, None while it loads, when it failed or with previews off.
+pub fn message_link_preview<'a>(
+    state: &'a AppState,
+    message: &BroadcastMessage,
+) -> Option<&'a LinkPreview> {
+    if !state.config.link_previews || message.file_id.is_some() {
+        return None;
+    }
+    match state
+        .link_previews
+        .get(&previewable_url(&message.content)?)?
+    {
+        LinkPreviewState::Ready(preview) => Some(preview),
+        LinkPreviewState::Loading | LinkPreviewState::Failed => None,
+    }
+}
+
+/// The card under the message: site, title and the start of the description behind a bar.
+pub fn link_preview_lines(preview: &LinkPreview, width: u16, theme: &Theme) -> Vec<Line<'static>> {
+    let text_width = (width as usize).saturating_sub(4);
+    let bar = Span::styled(
+        "  ▎",
+        Style::default().fg(rgb_to_color(&theme.colors.accent)),
+    );
+    let row = |text: &str, style: Style| {
+        Line::from(vec![
+            bar.clone(),
+            Span::styled(truncate_to_width(text, text_width), style),
+        ])
+    };
+    let mut lines = vec![
+        row(
+            &preview.site,
+            Style::default().fg(rgb_to_color(&theme.colors.dim)),
+        ),
+        row(
+            &preview.title,
+            Style::default()
+                .fg(rgb_to_color(&theme.colors.text))
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    if let Some(description) = &preview.description {
+        lines.push(row(
+            description,
+            Style::default().fg(rgb_to_color(&theme.colors.dim)),
+        ));
+    }
+    lines
+}
@@ -0,0 +1,132 @@
+// The details panel next to the messages, laid out like the file manager's metadata pane:
+// one small box per field for the message at the bottom of the view (or the picked attachment),
+// a line per field when there isn't room for the boxes.
+
+use crate::api::models::BroadcastMessage;
+use crate::themes::{rgb_to_color, Theme};
+use crate::tui::chat::popups::message_inspector::text_stats;
+use crate::tui::chat::ui::format_timestamp_detail;
+use crate::tui::utils::format_file_size;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Borders, Padding, Paragraph, Wrap},
+    Frame,
+};
+
+/// Columns the panel takes, it stays hidden when the messages would get less than that.
+pub const DETAILS_WIDTH: u16 = 36;
+
+/// Label and value of every box in the panel, "N/A" for what the message doesn't have.
+pub fn message_detail_fields(
+    msg: &BroadcastMessage,
+    channel_name: &str,
+    now: i64,
+) -> Vec<(&'static str, String)> {
+    let stats = text_stats(&msg.content);
+    let attachment = match &msg.file_name {
+        Some(name) if msg.file_id.is_some() => {
+            let name = match msg.file_extension.as_deref() {
+                Some(extension) if !extension.is_empty() => format!("{}.{}", name, extension),
+                _ => name.clone(),
+            };
+            match msg.file_size() {
+                Some(size) => format!("{} · {}", name, format_file_size(size)),
+                None => name,
+            }
+        }
+        _ => "None".to_string(),
+    };
+    vec![
+        ("Author", msg.user.clone()),
+        ("Sent", format_timestamp_detail(msg.timestamp, now)),
+        ("Channel", format!("#{}", channel_name)),
+        ("Id", msg.id.clone().unwrap_or_else(|| "N/A".to_string())),
+        (
+            "Client Id",
+            msg.client_id.clone().unwrap_or_else(|| "N/A".to_string()),
+        ),
+        ("Type", msg.message_type.clone()),
+        ("Attachment", attachment),
+        (
+            "Length",
+            format!("{} graphemes · {} bytes", stats.graphemes, stats.bytes),
+        ),
+    ]
+}
+
+pub fn draw_message_details(
+    f: &mut Frame,
+    area: Rect,
+    msg: Option<&BroadcastMessage>,
+    channel_name: &str,
+    theme: &Theme,
+    now: i64,
+) {
+    let details_block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .border_style(Style::default().fg(rgb_to_color(&theme.colors.accent)))
+        .title(Span::styled(
+            "Details",
+            Style::default().fg(rgb_to_color(&theme.colors.text)),
+        ))
+        .padding(Padding::new(1, 1, 1, 1));
+    let inner_area = details_block.inner(area);
+    f.render_widget(
+        Block::default().bg(rgb_to_color(&theme.colors.background)),
+        area,
+    );
+    f.render_widget(details_block, area);
+
+    let Some(msg) = msg else {
+        let p = Paragraph::new("No message selected")
+            .style(Style::default().bg(rgb_to_color(&theme.colors.background)));
+        f.render_widget(p, inner_area);
+        return;
+    };
+
+    let fields = message_detail_fields(msg, channel_name, now);
+    // short terminals get a line per field, the boxes would be cut off
+    if (inner_area.height as usize) < fields.len() * 3 {
+        let lines: Vec<Line> = fields
+            .iter()
+            .map(|(label, value)| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{}: ", label),
+                        Style::default().fg(rgb_to_color(&theme.colors.accent)),
+                    ),
+                    Span::styled(
+                        value.as_str(),
+                        Style::default().fg(rgb_to_color(&theme.colors.text)),
+                    ),
+                ])
+            })
+            .collect();
+        let p = Paragraph::new(lines)
+            .style(Style::default().bg(rgb_to_color(&theme.colors.background)));
+        f.render_widget(p, inner_area);
+        return;
+    }
+    let field_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(fields.iter().map(|_| Constraint::Length(3)))
+        .split(inner_area);
+    for ((label, value), chunk) in fields.iter().zip(field_chunks.iter()) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(rgb_to_color(&theme.colors.accent)))
+            .title(Span::styled(
+                *label,
+                Style::default().fg(rgb_to_color(&theme.colors.text)),
+            ));
+        let p = Paragraph::new(value.as_str())
+            .style(Style::default().bg(rgb_to_color(&theme.colors.background)))
+            .wrap(Wrap { trim: true });
+        f.render_widget(p, block.inner(*chunk));
+        f.render_widget(block, *chunk);
+    }
+}
@@ -1,3 +1,170 @@
+use crate::tui::utils::sanitize_paste;
+use std::collections::HashMap;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Length of a message as the user sees it, so an emoji counts as one.
+pub fn message_length(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// If the word right before `cursor` is `/name` and `name` is a macro, swaps it for the
+/// macro text. Returns the new cursor position (unchanged when nothing was expanded).
+pub fn expand_macro_at_cursor(
+    text: &mut String,
+    cursor: usize,
+    macros: &HashMap<String, String>,
+) -> usize {
+    let before_cursor = &text[..cursor];
+    let word_start = before_cursor
+        .rfind(char::is_whitespace)
+        .map_or(0, |i| i + before_cursor[i..].chars().next().unwrap().len_utf8());
+    let Some(name) = before_cursor[word_start..].strip_prefix('/') else {
+        return cursor;
+    };
+    match macros.get(name) {
+        Some(expansion) => {
+            let expansion = expansion.clone();
+            text.replace_range(word_start..cursor, &expansion);
+            word_start + expansion.len()
+        }
+        None => cursor,
+    }
+}
+
+/// Puts a paste into the message at `cursor` as plain text, line breaks included, so a
+/// leading `/` or a newline in it never runs a command or sends. Returns the new cursor.
+pub fn insert_pasted_text(text: &mut String, cursor: usize, pasted: &str) -> usize {
+    let pasted = sanitize_paste(pasted, true);
+    text.insert_str(cursor, &pasted);
+    cursor + pasted.len()
+}
+
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphanumeric)
+}
+
+/// Byte index of the start of the word before `cursor`, skipping the spaces and
+/// punctuation in between (like Ctrl+Left in a shell).
+pub fn previous_word_boundary(text: &str, cursor: usize) -> usize {
+    text[..cursor]
+        .split_word_bound_indices()
+        .rev()
+        .find(|(_, segment)| is_word(segment))
+        .map_or(0, |(i, _)| i)
+}
+
+/// Byte index of the end of the word after `cursor`.
+pub fn next_word_boundary(text: &str, cursor: usize) -> usize {
+    text[cursor..]
+        .split_word_bound_indices()
+        .find(|(_, segment)| is_word(segment))
+        .map_or(text.len(), |(i, segment)| cursor + i + segment.len())
+}
+
+/// Byte index of the grapheme before `cursor`, so a ZWJ emoji or a flag is one step.
+pub fn previous_grapheme_boundary(text: &str, cursor: usize) -> usize {
+    text[..cursor]
+        .grapheme_indices(true)
+        .next_back()
+        .map_or(0, |(i, _)| i)
+}
+
+/// Byte index just past the grapheme after `cursor`.
+pub fn next_grapheme_boundary(text: &str, cursor: usize) -> usize {
+    text[cursor..]
+        .graphemes(true)
+        .next()
+        .map_or(text.len(), |g| cursor + g.len())
+}
+
+/// Where the cursor lands on screen as (column, line) inside the input. `cursor` is a byte
+/// index, the column is in terminal cells, so wide emoji count as two.
+pub fn cursor_screen_offset(text: &str, cursor: usize) -> (u16, u16) {
+    let before = &text[..cursor];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    let column = before[line_start..].width();
+    let line = before.matches('\n').count();
+    (column as u16, line as u16)
+}
+
+/// A piece of a message, `spoiler` when it sat between a pair of `||`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentSegment<'a> {
+    pub text: &'a str,
+    pub spoiler: bool,
+}
+
+/// Splits a message on `||spoiler||` markers. A `||` without a partner is left as it is, and
+/// `||||` hides nothing so it stays literal too.
+pub fn split_spoilers(content: &str) -> Vec<ContentSegment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = content;
+    let mut plain_start = 0;
+    let mut offset = 0;
+    while let Some(open) = rest.find("||") {
+        let after_open = &rest[open + 2..];
+        match after_open.find("||") {
+            Some(close) if close > 0 => {
+                let open_at = offset + open;
+                if plain_start < open_at {
+                    segments.push(ContentSegment {
+                        text: &content[plain_start..open_at],
+                        spoiler: false,
+                    });
+                }
+                segments.push(ContentSegment {
+                    text: &after_open[..close],
+                    spoiler: true,
+                });
+                let consumed = open + 2 + close + 2;
+                offset += consumed;
+                plain_start = offset;
+                rest = &rest[consumed..];
+            }
+            _ => {
+                offset += open + 2;
+                rest = &rest[open + 2..];
+            }
+        }
+    }
+    if plain_start < content.len() {
+        segments.push(ContentSegment {
+            text: &content[plain_start..],
+            spoiler: false,
+        });
+    }
+    segments
+}
+
+pub fn has_spoilers(content: &str) -> bool {
+    split_spoilers(content).iter().any(|segment| segment.spoiler)
+}
+
+/// The message as it reads once every spoiler is revealed, what copying it gives.
+pub fn without_spoiler_markers(content: &str) -> String {
+    split_spoilers(content)
+        .into_iter()
+        .map(|segment| segment.text)
+        .collect()
+}
+
+/// First line of a message for the pinned strip, spoilers stay hidden in it.
+pub fn pin_preview(content: &str) -> String {
+    let shown: String = split_spoilers(content)
+        .into_iter()
+        .map(|segment| {
+            if segment.spoiler {
+                "▒▒▒".to_string()
+            } else {
+                segment.text.to_string()
+            }
+        })
+        .collect();
+    shown.lines().next().unwrap_or("").trim().to_string()
+}
+
 pub fn replace_shortcodes_with_emojis(text: &str) -> String {
     let mut result = String::with_capacity(text.len());
     let mut current_pos = 0;
@@ -27,108 +194,75 @@ pub fn replace_shortcodes_with_emojis(text: &str) -> String {
     result
 }
 
-pub fn should_show_emoji_popup(input_text: &str) -> bool {
-    if let Some(last_colon_idx) = input_text.rfind(':') {
-        let potential_shortcode_segment_from_last_colon = &input_text[last_colon_idx..];
-
-        // Rule: If there's a space immediately after the last colon (e.g., ": ")
-        if potential_shortcode_segment_from_last_colon.len() > 1
-            && potential_shortcode_segment_from_last_colon
-                .chars()
-                .nth(1)
-                .unwrap_or(' ')
-                .is_whitespace()
-        {
-            return false;
-        }
-
-        // Rule: If the last colon is part of a closed shortcode like ":smile:"
-        if potential_shortcode_segment_from_last_colon.ends_with(':') {
-            if let Some(open_colon_idx) = input_text[..last_colon_idx].rfind(':') {
-                let segment = &input_text[open_colon_idx..=last_colon_idx];
-                if segment.starts_with(':') && segment.ends_with(':') && segment.len() > 1 {
-                    let inner_content = &segment[1..segment.len() - 1];
-                    if !inner_content.contains(' ') && !inner_content.contains(':') {
-                        return false; // It's a valid, completed shortcode
-                    }
-                }
-            }
-        }
-
-        // Rule: If the last colon is part of a word (e.g., "foo:bar")
-        if potential_shortcode_segment_from_last_colon.len() > 1
-            && last_colon_idx > 0
-            && input_text
-                .chars()
-                .nth(last_colon_idx - 1)
-                .unwrap_or(' ')
-                .is_alphanumeric()
-        {
-            return false;
-        }
+// The shortcode being typed at the end of the input, without its leading `:`. Only a `:` that
+// starts a token counts, so URLs (`http://`) and times (`12:30`) don't open the picker.
+fn trailing_shortcode(input_text: &str) -> Option<&str> {
+    let token_start = input_text.rfind(char::is_whitespace).map_or(0, |i| {
+        i + input_text[i..].chars().next().map_or(1, char::len_utf8)
+    });
+    let name = input_text[token_start..].strip_prefix(':')?;
+    name.chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '+' | '-'))
+        .then_some(name)
+}
 
-        true
-    } else {
-        false // No colon found
-    }
+pub fn should_show_emoji_popup(input_text: &str) -> bool {
+    trailing_shortcode(input_text).is_some()
 }
 
 pub fn get_emoji_query(input_text: &str) -> String {
-    if let Some(last_colon_idx) = input_text.rfind(':') {
-        let potential_query = &input_text[last_colon_idx + 1..];
-        // Only consider it a query if it doesn't contain a space or another colon
-        if !potential_query.contains(' ') && !potential_query.contains(':') {
-            return potential_query.to_string();
-        }
+    trailing_shortcode(input_text)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Byte range of the `@mention` the cursor is in: from the `@` nearest before the cursor to
+/// the end of that word. None when there isn't one, e.g. after a space, right after another
+/// `@` or inside an email address.
+pub fn mention_token_range(text: &str, cursor: usize) -> Option<Range<usize>> {
+    let before = &text[..cursor];
+    let at = before.rfind('@')?;
+    if before[at + 1..].contains(|c: char| c.is_whitespace() || c == '@') {
+        return None;
     }
-    String::new()
+    if before[..at]
+        .chars()
+        .next_back()
+        .is_some_and(|c| !c.is_whitespace())
+    {
+        return None;
+    }
+    let end = text[cursor..]
+        .find(char::is_whitespace)
+        .map_or(text.len(), |i| cursor + i);
+    Some(at..end)
 }
 
-pub fn should_show_mention_popup(input_text: &str) -> bool {
-    if let Some(last_at_idx) = input_text.rfind('@') {
-        // Rule 1: Double '@' (e.g., "@@")
-        // If the current '@' is immediately preceded by another '@'.
-        if last_at_idx > 0 && input_text.chars().nth(last_at_idx - 1).unwrap_or(' ') == '@' {
-            return false;
-        }
+/// What's typed between the mention's `@` and the cursor, the mention popup filters on it.
+pub fn get_mention_query(input_text: &str, cursor: usize) -> String {
+    mention_token_range(input_text, cursor)
+        .map_or("", |range| &input_text[range.start + 1..cursor])
+        .to_string()
+}
 
-        // Determine the "word" segment that contains the last '@'.
-        // This segment starts from the last non-whitespace character before the last '@'
-        // (or beginning of string) and extends to the end of the string.
-        let mut word_start_for_last_at = last_at_idx;
-        while word_start_for_last_at > 0
-            && !input_text
-                .chars()
-                .nth(word_start_for_last_at - 1)
-                .unwrap_or(' ')
-                .is_whitespace()
-        {
-            word_start_for_last_at -= 1;
-        }
-        let segment_containing_last_at_word = &input_text[word_start_for_last_at..];
-
-        // Rule 2: If there's a space within the potential mention word (e.g., "@user name" or "@user ")
-        // This check needs to apply to the part *after* the initial '@' within the "word".
-        // If the segment starts with '@' and contains a space anywhere after that '@', it's invalid.
-        if segment_containing_last_at_word.starts_with('@')
-            && segment_containing_last_at_word[1..].contains(' ')
-        {
-            return false;
-        }
+pub fn should_show_mention_popup(input_text: &str, cursor: usize) -> bool {
+    mention_token_range(input_text, cursor).is_some()
+}
 
-        // Rule 3: If the "word" containing the last '@' itself contains another '@' symbol
-        // after its initial character (e.g., "@user@name" or "@user@").
-        // This correctly handles "hello @user@".
-        if segment_containing_last_at_word.starts_with('@')
-            && segment_containing_last_at_word[1..].contains('@')
-        {
-            return false;
+/// Swaps the mention the cursor is in for `@username` followed by a space, leaving earlier
+/// mentions alone. Returns the cursor, just past that space.
+pub fn complete_mention(text: &mut String, cursor: usize, username: &str) -> usize {
+    let Some(range) = mention_token_range(text, cursor) else {
+        return cursor;
+    };
+    let mention = format!("@{}", username);
+    text.replace_range(range.clone(), &mention);
+    let end = range.start + mention.len();
+    match text[end..].chars().next() {
+        Some(c) if c.is_whitespace() => end + c.len_utf8(),
+        _ => {
+            text.insert(end, ' ');
+            end + 1
         }
-
-        // If none of the above "don't show" conditions are met, then show the popup.
-        true
-    } else {
-        // No '@' found in the input string.
-        false
     }
 }
@@ -1,7 +1,14 @@
+pub mod commands;
+pub mod copy_mode;
 pub mod create_channel_form;
+pub mod external_editor;
 pub mod gif_renderer;
 pub mod image_handler;
+pub mod link_preview;
+pub mod message_details;
 pub mod message_parsing;
+pub mod palette;
+pub mod pinned_strip;
 pub mod popups;
 pub mod theme_settings_form;
 pub mod ui;
@@ -11,26 +18,46 @@ pub mod ws_command;
 #[cfg(test)]
 pub mod tests;
 use crate::api::websocket;
+use crate::api::{auth_api, file_api};
+use crate::app::app_state::{
+    AccountDeletion, GotoOutcome, PasswordChange, ProfileUpdate, Thumbnail, WsFrameDirection,
+};
 use crate::app::{AppState, PopupType};
+use crate::config::{save_config, CursorShape, CHANNEL_PANE_PERCENT_RANGE};
 
+use crate::tui::chat::commands::{command_token, is_unknown_command};
+use crate::tui::chat::copy_mode::handle_copy_mode_key;
 use crate::tui::chat::create_channel_form::{CreateChannelForm, CreateChannelInput};
+use crate::tui::chat::external_editor::{edit_in_editor, editor_command};
 use crate::tui::chat::message_parsing::{
-    get_emoji_query, replace_shortcodes_with_emojis, should_show_emoji_popup,
-    should_show_mention_popup,
+    complete_mention, expand_macro_at_cursor, get_emoji_query, get_mention_query,
+    insert_pasted_text, message_length, next_grapheme_boundary, next_word_boundary,
+    previous_grapheme_boundary, previous_word_boundary, replace_shortcodes_with_emojis,
+    should_show_emoji_popup, should_show_mention_popup,
 };
+use crate::tui::chat::palette::{filtered_entries, PaletteAction};
+use crate::tui::chat::popups::channel_menu::ChannelMenuOutcome;
+use crate::tui::chat::popups::emoji_picker::EmojiPickerOutcome;
+use crate::tui::chat::popups::emojis::selectable_emojis;
+use crate::tui::chat::popups::icon_picker::IconPickerOutcome;
 use crate::tui::chat::popups::mentions::get_filtered_users;
+use crate::tui::chat::popups::profiles::{ProfileSwitcherOutcome, ProfileSwitcherState};
 use crate::tui::chat::ui::draw_chat_ui;
+use crate::tui::chat::utils::copy_to_clipboard;
 use crate::tui::chat::ws_command::WsCommand;
+use crate::tui::cursor_style::CursorStyleState;
 use crate::tui::notification::notification::NotificationType;
 use crate::tui::settings::{self, state::SettingsState};
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers, MouseEventKind};
+use crate::tui::utils::sanitize_paste;
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEventKind,
+};
 use lazy_static::lazy_static;
 use ratatui::{prelude::Backend, widgets::ListState, Terminal};
 use regex::Regex;
-use std::{io, sync::Arc, time::Duration};
+use std::{collections::VecDeque, io, path::PathBuf, sync::Arc, time::Duration};
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
-use unicode_segmentation::UnicodeSegmentation;
 
 use crate::tui::file_manager_module::file_manager::{FileManager, FileManagerEvent};
 
@@ -39,13 +66,307 @@ lazy_static! {
     static ref EMOJI_REGEX: Regex = Regex::new(r":[a-zA-Z0-9_]+:").unwrap();
 }
 
+// how long a freshly created channel waits for the server's ChannelUpdate before it's dropped
+const PENDING_CHANNEL_TIMEOUT: Duration = Duration::from_secs(15);
+// and how long a profile change waits for its ProfileUpdate
+const PENDING_PROFILE_TIMEOUT: Duration = Duration::from_secs(15);
+
+
+
+async fn send_input_message(
+    state: &mut AppState,
+    command_tx: &mpsc::UnboundedSender<WsCommand>,
+    input_text: &str,
+    app_state: Arc<tokio::sync::Mutex<AppState>>,
+) -> bool {
+    if !state.claim_send_slot(tokio::time::Instant::now()) {
+        // once per burst, a held Enter would bury everything else in toasts
+        if !state.send_throttled {
+            state.send_throttled = true;
+            state
+                .notification_manager
+                .add(
+                    "Slow down 󰾆".to_string(),
+                    "Ur sending faster than the flood guard lets through, the message is still in the input."
+                        .to_string(),
+                    NotificationType::Warning,
+                    Some(Duration::from_secs(2)),
+                    app_state,
+                )
+                .await;
+        }
+        return false;
+    }
+    state.push_input_history(input_text);
+    if let Some(current_channel) = &state.current_channel {
+        let channel_id = current_channel.id.clone();
+        let content = replace_shortcodes_with_emojis(input_text);
+
+        if command_tx
+            .send(WsCommand::Message {
+                channel_id,
+                content,
+            })
+            .is_ok()
+        {
+            state.scroll_to_bottom_after_send();
+        } else {
+            state
+                .notification_manager
+                .add(
+                    "Message Send Error 󱙍".to_string(),
+                    "Failed to send message, this might be because the server is down, please restart ur client."
+                        .to_string(),
+                    NotificationType::Error,
+                    Some(Duration::from_secs(3)),
+                    app_state,
+                )
+                .await;
+        }
+    }
+    true
+}
+
+/// Hands the terminal to `$EDITOR` with the input in it and puts what comes back in the input.
+/// Runs without the state locked so the websocket keeps up while the editor is open.
+async fn compose_in_editor<B: Backend>(
+    terminal: &mut Terminal<B>,
+    input_text: &mut String,
+    app_state: Arc<tokio::sync::Mutex<AppState>>,
+) -> io::Result<()> {
+    let command = editor_command(
+        std::env::var("VISUAL").ok().as_deref(),
+        std::env::var("EDITOR").ok().as_deref(),
+    );
+    let Some(command) = command else {
+        let mut state = app_state.lock().await;
+        state
+            .notification_manager
+            .add(
+                "No Editor Set 󰷈".to_string(),
+                "Set $EDITOR to the one u want first, like `export EDITOR=nvim`.".to_string(),
+                NotificationType::Warning,
+                Some(Duration::from_secs(4)),
+                app_state.clone(),
+            )
+            .await;
+        return Ok(());
+    };
+
+    crate::tui::shutdown::restore_terminal()?;
+    let result = edit_in_editor(&command, input_text).await;
+    crate::tui::shutdown::resume_terminal()?;
+    // the editor drew over everything, ratatui's idea of the screen is stale
+    terminal.clear()?;
+
+    let mut state = app_state.lock().await;
+    match result {
+        Ok(text) => {
+            *input_text = text;
+            state.cursor_position = input_text.len();
+            state.chat_focused_pane = crate::app::app_state::ChatFocusedPane::Input;
+        }
+        Err(e) => {
+            state
+                .notification_manager
+                .add(
+                    "Editor Failed 󰷈".to_string(),
+                    format!("{}\nThe input is left as it was.", e),
+                    NotificationType::Error,
+                    Some(Duration::from_secs(4)),
+                    app_state.clone(),
+                )
+                .await;
+        }
+    }
+    Ok(())
+}
+
+/// Asks for older history when the message isn't loaded yet, or says it's not there.
+async fn follow_goto(
+    state: &mut AppState,
+    command_tx: &mpsc::UnboundedSender<WsCommand>,
+    outcome: GotoOutcome,
+    not_found: String,
+    app_state: Arc<tokio::sync::Mutex<AppState>>,
+) {
+    match outcome {
+        GotoOutcome::Found => {}
+        GotoOutcome::Fetch { channel_id, offset } => {
+            let _ = command_tx.send(WsCommand::Message {
+                channel_id: channel_id.clone(),
+                content: format!("/get_history {} {}", channel_id, offset),
+            });
+            state
+                .notification_manager
+                .add(
+                    "Looking Further Back 󰍉".to_string(),
+                    "That message isn't loaded, fetching older history.".to_string(),
+                    NotificationType::Info,
+                    Some(Duration::from_secs(2)),
+                    app_state,
+                )
+                .await;
+        }
+        GotoOutcome::NotFound => {
+            state
+                .notification_manager
+                .add(
+                    "Message Not Found 󰍉".to_string(),
+                    not_found,
+                    NotificationType::Warning,
+                    Some(Duration::from_secs(3)),
+                    app_state,
+                )
+                .await;
+        }
+    }
+}
+
+fn open_channel(
+    state: &mut AppState,
+    command_tx: &mpsc::UnboundedSender<WsCommand>,
+    channel: crate::api::models::Channel,
+) {
+    let channel_id = channel.id.clone();
+    state.set_current_channel(channel);
+    let initial_fetched = state
+        .channel_history_state
+        .get(&channel_id)
+        .is_some_and(|&(_, _, initial_fetched)| initial_fetched);
+    if state.messages.get(&channel_id).is_none_or(|m| m.is_empty())
+        && !initial_fetched
+        && command_tx
+            .send(WsCommand::Message {
+                channel_id: channel_id.clone(),
+                content: format!("/get_history {}", channel_id),
+            })
+            .is_ok()
+    {
+        state
+            .channel_history_state
+            .entry(channel_id)
+            .or_insert((0, false, false))
+            .2 = true;
+    }
+}
+
+// the Ctrl+E grid, opens on the tab used last time with a fresh search
+fn open_emoji_picker(state: &mut AppState) {
+    state.emoji_picker.query.clear();
+    state.emoji_picker.selected = 0;
+    state.popup_state.show = true;
+    state.popup_state.popup_type = PopupType::EmojiPicker;
+}
+
+// Tab order is Input -> ChannelList -> Messages, Shift+Tab walks it backwards
+fn next_pane(
+    pane: crate::app::app_state::ChatFocusedPane,
+    forward: bool,
+) -> crate::app::app_state::ChatFocusedPane {
+    use crate::app::app_state::ChatFocusedPane::*;
+    match (pane, forward) {
+        (Input, true) | (Messages, false) => ChannelList,
+        (ChannelList, true) | (Input, false) => Messages,
+        (Messages, true) | (ChannelList, false) => Input,
+    }
+}
+
+// selects the channel above/below the current one (wrapping) and opens it, pending ones are
+// selected but not opened since they don't exist on the server yet
+fn step_channel(
+    state: &mut AppState,
+    channel_list_state: &mut ListState,
+    command_tx: &mpsc::UnboundedSender<WsCommand>,
+    forward: bool,
+) {
+    let count = state.channels.len();
+    if count == 0 {
+        return;
+    }
+    let i = match channel_list_state.selected() {
+        Some(i) if forward => (i + 1) % count,
+        Some(i) => (i + count - 1) % count,
+        None => 0,
+    };
+    channel_list_state.select(Some(i));
+    if let Some(channel) = state.channels.get(i).filter(|c| !c.pending).cloned() {
+        open_channel(state, command_tx, channel);
+    }
+}
 
+// typing goes into the filter, Up/Down walk the matches, Enter opens one, Esc gives the full list back
+fn handle_channel_filter_key(
+    state: &mut AppState,
+    channel_list_state: &mut ListState,
+    command_tx: &mpsc::UnboundedSender<WsCommand>,
+    code: KeyCode,
+) {
+    let Some(filter) = state.channel_filter.as_mut() else {
+        return;
+    };
+    match code {
+        KeyCode::Char(c) => filter.push(c),
+        KeyCode::Backspace => {
+            filter.pop();
+        }
+        KeyCode::Esc => {
+            state.channel_filter = None;
+            let current = state
+                .current_channel
+                .as_ref()
+                .and_then(|current| state.channels.iter().position(|c| c.id == current.id));
+            channel_list_state.select(current.or(Some(0)));
+            return;
+        }
+        KeyCode::Enter => {
+            let selected = channel_list_state
+                .selected()
+                .filter(|i| state.visible_channel_indices().contains(i))
+                .and_then(|i| state.channels.get(i))
+                .filter(|c| !c.pending)
+                .cloned();
+            if let Some(channel) = selected {
+                state.channel_filter = None;
+                open_channel(state, command_tx, channel);
+            }
+            return;
+        }
+        KeyCode::Up | KeyCode::Down | KeyCode::Tab => {
+            let visible = state.visible_channel_indices();
+            if visible.is_empty() {
+                return;
+            }
+            let pos = channel_list_state
+                .selected()
+                .and_then(|s| visible.iter().position(|&i| i == s));
+            let next = match (pos, code) {
+                (Some(p), KeyCode::Up) => p.checked_sub(1).unwrap_or(visible.len() - 1),
+                (Some(p), _) => (p + 1) % visible.len(),
+                (None, _) => 0,
+            };
+            channel_list_state.select(Some(visible[next]));
+            return;
+        }
+        _ => return,
+    }
+    // the filter changed, keep the selection on something that's still shown
+    let visible = state.visible_channel_indices();
+    if !channel_list_state
+        .selected()
+        .is_some_and(|s| visible.contains(&s))
+    {
+        channel_list_state.select(visible.first().copied());
+    }
+}
 
 pub async fn run_chat_page<B: Backend>(
     terminal: &mut Terminal<B>,
     app_state: Arc<tokio::sync::Mutex<AppState>>,
 ) -> io::Result<Option<crate::app::TuiPage>> {
     let mut input_text = String::new();
+    // a paste into a popup, handed out one key per round like typing
+    let mut pasted_keys: VecDeque<Event> = VecDeque::new();
     let mut channel_list_state = ListState::default();
     channel_list_state.select(Some(0));
 
@@ -70,56 +391,60 @@ pub async fn run_chat_page<B: Backend>(
     };
 
     let cancellation_token = CancellationToken::new();
-    let (mut ws_writer, ws_reader) = {
-        let state = app_state.lock().await;
+    let transport = {
+        let mut state = app_state.lock().await;
         let token = state
             .auth_token
             .clone()
             .expect(" Auth token not found for WebSocket connection");
-        websocket::connect(&token)
-            .await
-            .expect(" Failed to connect to WebSocket")
+        let timeout = Duration::from_secs(state.config.ws_connect_timeout_secs);
+        match websocket::connect(&token, timeout).await {
+            Ok(connection) => {
+                // the first frame is the token itself, logged redacted like everything else
+                state.log_ws_frame(WsFrameDirection::Outbound, &token, true);
+                connection
+            }
+            Err(e) => {
+                let title = if e.is_retryable() {
+                    "Connection Timed Out 󰖪"
+                } else {
+                    "Connection Failed 󰖪"
+                };
+                state
+                    .notification_manager
+                    .add(
+                        title.to_string(),
+                        format!("{}\nBack to the home screen, try again from there.", e),
+                        crate::tui::notification::notification::NotificationType::Error,
+                        Some(Duration::from_secs(5)),
+                        app_state.clone(),
+                    )
+                    .await;
+                return Ok(Some(crate::app::TuiPage::Home));
+            }
+        }
     };
 
-    let (command_tx, mut command_rx) = mpsc::unbounded_channel::<WsCommand>();
+    let (command_tx, command_rx) = mpsc::unbounded_channel::<WsCommand>();
     let (filecommand_tx, mut file_command_rx) = mpsc::unbounded_channel::<WsCommand>();
     let (progress_tx, mut progress_rx) = mpsc::unbounded_channel::<(String, u8)>();
-    let http_client = reqwest::Client::new();
+    let api = app_state.lock().await.api.clone();
 
     let command_tx_clone = command_tx.clone();
-    let ws_task = tokio::spawn(websocket::handle_websocket_communication(
-        ws_reader,
+    let ws_task = tokio::spawn(websocket::handle_server_communication(
+        transport,
         app_state.clone(),
         command_tx_clone,
-        http_client.clone(),
+        command_rx,
+        api.clone(),
         redraw_tx.clone(),
         cancellation_token.clone(),
     ));
 
-    tokio::spawn(async move {
-        while let Some(command) = command_rx.recv().await {
-            match command {
-                WsCommand::Message {
-                    channel_id,
-                    content,
-                } => {
-                    if websocket::send_message(&mut ws_writer, &channel_id, &content)
-                        .await
-                        .is_err()
-                    {
-                        break;
-                    }
-                }
-                _ => {}
-            }
-        }
-    });
-
     let app_state_for_file_commands = app_state.clone();
-    let http_client_for_file_commands = http_client.clone();
+    let api_for_file_commands = api.clone();
     let progress_tx2 = progress_tx.clone();
     tokio::spawn(async move {
-        use crate::api::file_api;
         while let Some(command) = file_command_rx.recv().await {
             match command {
                 WsCommand::UploadFile {
@@ -127,65 +452,70 @@ pub async fn run_chat_page<B: Backend>(
                     file_path,
                 } => {
                     let app_state_for_upload_clone = app_state_for_file_commands.clone();
-                    let token = {
-                        let state = app_state_for_upload_clone.lock().await;
-                        state.auth_token.clone()
-                    };
-                    if let Some(token) = token {
-                        match file_api::upload_file(
-                            &http_client_for_file_commands,
-                            &token,
-                            &channel_id,
-                            file_path,
-                            progress_tx2.clone(),
-                        )
+                    match api_for_file_commands
+                        .upload_file(&channel_id, file_path, progress_tx2.clone())
                         .await
-                        {
-                            Ok(_file_id) => {
-                                let mut state = app_state_for_upload_clone.lock().await;
-                                state
-                                    .notification_manager
-                                    .add(
-                                        "File Upload Success 󰪹".to_string(),
-                                        "File uploaded successfully! 󰔓".to_string(),
-                                        NotificationType::Success,
-                                        Some(Duration::from_secs(3)),
-                                        app_state_for_upload_clone.clone(),
-                                    )
-                                    .await;
-                            }
-                            Err(e) => {
-                                let mut state = app_state_for_upload_clone.lock().await;
-                                state
-                                    .notification_manager
-                                    .add(
-                                        "File Upload Error 󰩋".to_string(),
-                                        format!("Failed to upload file: {}\nTell to the owner (Youssef 󰊤 :'YoussefDevPro')\nIn the repo 󰌷 https://github.com/YoussefDevPro/ReeTUI", e),
-                                        NotificationType::Error,
-                                        Some(Duration::from_secs(5)),
-                                        app_state_for_upload_clone.clone(),
-                                    )
-                                    .await;
-                            }
+                    {
+                        Ok(_file_id) => {
+                            let mut state = app_state_for_upload_clone.lock().await;
+                            state
+                                .notification_manager
+                                .add(
+                                    "File Upload Success 󰪹".to_string(),
+                                    "File uploaded successfully! 󰔓".to_string(),
+                                    NotificationType::Success,
+                                    Some(Duration::from_secs(3)),
+                                    app_state_for_upload_clone.clone(),
+                                )
+                                .await;
+                        }
+                        Err(e) => {
+                            let mut state = app_state_for_upload_clone.lock().await;
+                            state
+                                .notification_manager
+                                .add(
+                                    "File Upload Error 󰩋".to_string(),
+                                    format!("Failed to upload file: {}\nTell to the owner (Youssef 󰊤 :'YoussefDevPro')\nIn the repo 󰌷 https://github.com/YoussefDevPro/ReeTUI", e),
+                                    NotificationType::Error,
+                                    Some(Duration::from_secs(5)),
+                                    app_state_for_upload_clone.clone(),
+                                )
+                                .await;
                         }
                     }
                 }
                 WsCommand::DownloadFile { file_id, file_name } => {
                     let app_state_for_download = app_state_for_file_commands.clone();
                     let progress_tx3 = progress_tx2.clone();
-                    let http_client_clone = http_client_for_file_commands.clone();
+                    let api = api_for_file_commands.clone();
                     tokio::spawn(async move {
-                        match file_api::download_file(
-                            &http_client_clone,
-                            &file_id,
-                            &file_name,
-                            progress_tx3.clone(),
-                            true,
-                        )
-                        .await
-                        {
+                        let expected = app_state_for_download
+                            .lock()
+                            .await
+                            .downloadable_files
+                            .get(&file_id)
+                            .map(|file| file.expected.clone())
+                            .unwrap_or_default();
+                        let destination = file_api::default_download_dir();
+                        let result = match &destination {
+                            Ok(dir) => {
+                                api.download_file(
+                                    &file_id,
+                                    &file_name,
+                                    progress_tx3.clone(),
+                                    Some(dir),
+                                    &expected,
+                                )
+                                .await
+                            }
+                            Err(e) => Err(file_api::FileApiError::Other(e.to_string())),
+                        };
+                        match result {
                             Ok(_) => {
                                 let mut state = app_state_for_download.lock().await;
+                                if let Some(file) = state.downloadable_files.get_mut(&file_id) {
+                                    file.verified = !expected.is_empty();
+                                }
                                 state
                                     .notification_manager
                                     .add(
@@ -216,6 +546,82 @@ pub async fn run_chat_page<B: Backend>(
                         }
                     });
                 }
+                WsCommand::DownloadAll { files, target_dir } => {
+                    let app_state_for_download = app_state_for_file_commands.clone();
+                    let progress_tx3 = progress_tx2.clone();
+                    let api = api_for_file_commands.clone();
+                    tokio::spawn(async move {
+                        let total = files.len();
+                        let (mut downloaded, mut skipped, mut failed) = (0, 0, 0);
+                        for (index, (file_id, file_name)) in files.into_iter().enumerate() {
+                            let expected = app_state_for_download
+                                .lock()
+                                .await
+                                .downloadable_files
+                                .get(&file_id)
+                                .map(|file| file.expected.clone())
+                                .unwrap_or_default();
+                            if file_api::matches_local_file(&target_dir.join(&file_name), &expected)
+                                .await
+                            {
+                                skipped += 1;
+                                continue;
+                            }
+                            // one progress bar for the whole batch instead of one per file
+                            let (file_progress_tx, mut file_progress_rx) =
+                                mpsc::unbounded_channel::<(String, u8)>();
+                            let overall_tx = progress_tx3.clone();
+                            let forward = tokio::spawn(async move {
+                                while let Some((_, percent)) = file_progress_rx.recv().await {
+                                    let overall = (index * 100 + percent as usize) / total;
+                                    let _ = overall_tx
+                                        .send(("download_all".to_string(), overall as u8));
+                                }
+                            });
+                            let result = api
+                                .download_file(
+                                    &file_id,
+                                    &file_name,
+                                    file_progress_tx,
+                                    Some(&target_dir),
+                                    &expected,
+                                )
+                                .await;
+                            let _ = forward.await;
+                            match result {
+                                Ok(_) => {
+                                    downloaded += 1;
+                                    let mut state = app_state_for_download.lock().await;
+                                    if let Some(file) = state.downloadable_files.get_mut(&file_id) {
+                                        file.verified = !expected.is_empty();
+                                    }
+                                }
+                                Err(_) => failed += 1,
+                            }
+                        }
+                        let mut state = app_state_for_download.lock().await;
+                        state
+                            .notification_manager
+                            .add(
+                                "Download All 󰇚".to_string(),
+                                format!(
+                                    "{} downloaded, {} already there, {} failed\nSaved in {}",
+                                    downloaded,
+                                    skipped,
+                                    failed,
+                                    target_dir.display()
+                                ),
+                                if failed == 0 {
+                                    NotificationType::Success
+                                } else {
+                                    NotificationType::Warning
+                                },
+                                Some(Duration::from_secs(5)),
+                                app_state_for_download.clone(),
+                            )
+                            .await;
+                    });
+                }
 
                 _ => {}
             }
@@ -229,8 +635,8 @@ pub async fn run_chat_page<B: Backend>(
             state
                 .notification_manager
                 .add(
-                    "Download Progress ".to_string(),
-                    format!("Downloading: {}%", progress),
+                    "Transfer Progress ".to_string(),
+                    format!("{}%", progress),
                     NotificationType::Info,
                     Some(Duration::from_secs(3)),
                     app_state_clone_for_progress.clone(),
@@ -249,53 +655,15 @@ pub async fn run_chat_page<B: Backend>(
 
         loop {
             tokio::time::sleep(Duration::from_secs(2)).await;
-            let mut state = app_state_for_notifs.lock().await;
-            if let Some(username) = &state.username {
-                let username_mention = format!("@{}", username.clone());
-                let mut channels_to_update: Vec<(String, usize)> = Vec::new();
-
-                for (channel_id, messages) in &state.messages {
-                    let current_message_count = messages.len();
-                    let last_count = *state.last_message_counts.get(channel_id).unwrap_or(&0);
-
-                    if !state.initial_load_complete {
-                        channels_to_update.push((channel_id.clone(), current_message_count));
-                        continue;
-                    }
-
-                    if current_message_count > last_count {
-                        for i in last_count..current_message_count {
-                            if let Some(msg) = messages.get(i) {
-                                if &msg.user != username && msg.content.contains(&username_mention)
-                                {
-                                    let channel_name = state
-                                        .channels
-                                        .iter()
-                                        .find(|c| c.id == *channel_id)
-                                        .map(|c| c.name.as_str())
-                                        .unwrap_or("unknown")
-                                        .to_string();
-                                    let title = format!(
-                                        ":O ⇶ New mention from {} in #{}",
-                                        msg.user, channel_name
-                                    );
-                                    let body = msg.content.clone();
-                                    tokio::spawn(async move {
-                                        let _ =
-                                            Notification::new().summary(&title).body(&body).show();
-                                    });
-                                }
-                            }
-                        }
-                    }
-                    channels_to_update.push((channel_id.clone(), current_message_count));
-                }
-
-                for (channel_id, count) in channels_to_update {
-                    state.update_last_message_count(channel_id, count);
-                }
+            let bells = app_state_for_notifs
+                .lock()
+                .await
+                .take_mention_bells(chrono::Utc::now().timestamp());
+            for (title, body) in bells {
+                tokio::spawn(async move {
+                    let _ = Notification::new().summary(&title).body(&body).show();
+                });
             }
-            state.set_initial_load_complete(true);
         }
     });
 
@@ -310,9 +678,28 @@ pub async fn run_chat_page<B: Backend>(
         }
     });
 
+    // Ctrl+X was pressed and the next key may finish the chord
+    let mut ctrl_x_pending = false;
+    let mut open_editor = false;
+    let mut cursor_style = CursorStyleState::default();
+
     loop {
+        if std::mem::take(&mut open_editor) {
+            compose_in_editor(terminal, &mut input_text, app_state.clone()).await?;
+            cursor_style.forget();
+        }
+
         let mut state_guard = app_state.lock().await;
-        state_guard.notification_manager.update();
+        state_guard.sync_do_not_disturb();
+        state_guard.update_notifications();
+
+        // set by the websocket task, which can't leave the chat on its own (account deleted)
+        if let Some(page) = state_guard.next_page.take() {
+            drop(state_guard);
+            cancellation_token.cancel();
+            ws_task.await.unwrap().unwrap();
+            return Ok(Some(page));
+        }
 
         if let Some(current_channel) = &state_guard.current_channel {
             if let Some(index) = state_guard
@@ -344,19 +731,40 @@ pub async fn run_chat_page<B: Backend>(
             );
         })?;
 
-        let event = tokio::select! {
-            Some(_) = redraw_rx.recv() => {
-                None
-            },
-            event_result = tokio::task::spawn_blocking(|| event::poll(Duration::from_millis(16))) => {
-                match event_result {
-                    Ok(Ok(true)) => Some(tokio::task::spawn_blocking(event::read).await.unwrap().unwrap()),
-                    _ => None,
+        let typing = state_guard.chat_focused_pane
+            == crate::app::app_state::ChatFocusedPane::Input
+            && state_guard.popup_state.popup_type == PopupType::None
+            && !state_guard.show_settings
+            && !state_guard.copy_mode;
+        if typing {
+            cursor_style.apply(state_guard.config.cursor_shape, state_guard.config.cursor_blink)?;
+        } else {
+            cursor_style.apply(CursorShape::Default, true)?;
+        }
+
+        let event = match pasted_keys.pop_front() {
+            Some(event) => Some(event),
+            None => tokio::select! {
+                Some(_) = redraw_rx.recv() => {
+                    None
+                },
+                event_result = tokio::task::spawn_blocking(|| event::poll(Duration::from_millis(16))) => {
+                    match event_result {
+                        Ok(Ok(true)) => Some(tokio::task::spawn_blocking(event::read).await.unwrap().unwrap()),
+                        _ => None,
+                    }
                 }
-            }
+            },
         };
 
         if let Some(event) = event {
+            if let Event::Resize(..) = event {
+                state_guard.handle_resize();
+                file_manager.handle_resize();
+                // start the next frame from a blank screen, not what the old size left behind
+                terminal.clear()?;
+                continue;
+            }
             if state_guard.show_settings {
                 if let Some(target_page) = settings::handle_settings_key_event(
                     settings::SettingsEvent::Key(event.clone()),
@@ -373,8 +781,118 @@ pub async fn run_chat_page<B: Backend>(
                         return Ok(Some(target_page));
                     }
                 }
+                if let ProfileUpdate::Requested { username, icon } =
+                    state_guard.profile_update.clone()
+                {
+                    let channel_id = state_guard
+                        .current_channel
+                        .as_ref()
+                        .map(|c| c.id.clone())
+                        .unwrap_or_default();
+                    let _ = command_tx.send(WsCommand::Message {
+                        channel_id,
+                        content: format!("/update_profile {} {}", username, icon),
+                    });
+                    let app_state_clone = app_state.clone();
+                    let (sent_username, sent_icon) = (username.clone(), icon.clone());
+                    tokio::spawn(async move {
+                        tokio::time::sleep(PENDING_PROFILE_TIMEOUT).await;
+                        app_state_clone
+                            .lock()
+                            .await
+                            .expire_profile_update(&sent_username, &sent_icon);
+                    });
+                    state_guard.profile_update = ProfileUpdate::Sent { username, icon };
+                }
+                if let PasswordChange::Requested { current, new } =
+                    state_guard.password_change.clone()
+                {
+                    let channel_id = state_guard
+                        .current_channel
+                        .as_ref()
+                        .map(|c| c.id.clone())
+                        .unwrap_or_default();
+                    let username = state_guard.username.clone().unwrap_or_default();
+                    let api = api.clone();
+                    let command_tx = command_tx.clone();
+                    let app_state_clone = app_state.clone();
+                    tokio::spawn(async move {
+                        let scheme = match api.password_scheme(&username).await {
+                            Ok(scheme) => scheme,
+                            Err(e) => {
+                                app_state_clone.lock().await.password_change =
+                                    PasswordChange::Rejected(e.to_string());
+                                return;
+                            }
+                        };
+                        let _ = command_tx.send(WsCommand::Message {
+                            channel_id,
+                            content: format!(
+                                "/change_password {} {}",
+                                auth_api::hash_password(&current, &scheme),
+                                auth_api::hash_password(&new, &scheme)
+                            ),
+                        });
+                    });
+                    state_guard.password_change = PasswordChange::Sent;
+                }
+                if state_guard.account_deletion == AccountDeletion::Requested {
+                    let channel_id = state_guard
+                        .current_channel
+                        .as_ref()
+                        .map(|c| c.id.clone())
+                        .unwrap_or_default();
+                    let _ = command_tx.send(WsCommand::Message {
+                        channel_id,
+                        content: "/delete_account".to_string(),
+                    });
+                    state_guard.account_deletion = AccountDeletion::Sent;
+                }
             } else {
+                // mouse capture is never turned on, so with nothing but text on screen the
+                // terminal's own selection works, only keys are ours here
+                if state_guard.copy_mode {
+                    if let Event::Key(key) = event {
+                        if key.kind == KeyEventKind::Press {
+                            handle_copy_mode_key(&mut state_guard, key.code);
+                        }
+                    }
+                    continue;
+                }
+                if let Event::Paste(text) = &event {
+                    let popup_type = state_guard.popup_state.popup_type;
+                    // the channel form's icon row opens its picker on a space
+                    let popup_takes_text = popup_type.takes_text()
+                        && !(popup_type == PopupType::CreateChannel
+                            && create_channel_form.icon_picker.is_none()
+                            && create_channel_form.input_focused == CreateChannelInput::Icon);
+                    if state_guard.keybindings.open || popup_takes_text {
+                        pasted_keys.extend(sanitize_paste(text, false).chars().map(|c| {
+                            Event::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+                        }));
+                    } else if popup_type == PopupType::None
+                        && state_guard.chat_focused_pane
+                            == crate::app::app_state::ChatFocusedPane::Input
+                    {
+                        state_guard.cursor_position =
+                            insert_pasted_text(&mut input_text, state_guard.cursor_position, text);
+                    }
+                    // the other popups, the channel list and the messages pane would take it for
+                    // shortcuts
+                    continue;
+                }
                 if let Event::Key(key) = event {
+                    // `?` is text while typing or inside a popup
+                    let question_mark_opens = state_guard.chat_focused_pane
+                        != crate::app::app_state::ChatFocusedPane::Input
+                        && state_guard.popup_state.popup_type == PopupType::None;
+                    if key.kind == KeyEventKind::Press
+                        && state_guard
+                            .keybindings
+                            .handle_key(&key, question_mark_opens)
+                    {
+                        continue;
+                    }
                     if key.code == KeyCode::Char('d')
                         && key.modifiers.contains(KeyModifiers::CONTROL)
                     {
@@ -388,6 +906,39 @@ pub async fn run_chat_page<B: Backend>(
                         state_guard.show_settings = true;
                         continue;
                     }
+                    if key.code == KeyCode::Char('p')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                        && state_guard.popup_state.popup_type == PopupType::None
+                    {
+                        state_guard.palette_query.clear();
+                        state_guard.selected_palette_index = 0;
+                        state_guard.popup_state.show = true;
+                        state_guard.popup_state.popup_type = PopupType::CommandPalette;
+                        continue;
+                    }
+                    // Ctrl+X Ctrl+E, the readline way of writing the message in $EDITOR
+                    let chord_started =
+                        key.kind == KeyEventKind::Press && std::mem::take(&mut ctrl_x_pending);
+                    if key.kind == KeyEventKind::Press
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                        && state_guard.popup_state.popup_type == PopupType::None
+                    {
+                        if key.code == KeyCode::Char('x') {
+                            ctrl_x_pending = true;
+                            continue;
+                        }
+                        if chord_started && key.code == KeyCode::Char('e') {
+                            open_editor = true;
+                            continue;
+                        }
+                    }
+                    if key.code == KeyCode::Char('e')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)
+                        && state_guard.popup_state.popup_type == PopupType::None
+                    {
+                        open_emoji_picker(&mut state_guard);
+                        continue;
+                    }
                 }
 
                 if let Event::Mouse(mouse_event) = event {
@@ -408,6 +959,19 @@ pub async fn run_chat_page<B: Backend>(
                         let current_popup_type = state_guard.popup_state.popup_type;
 
                         match current_popup_type {
+                            PopupType::CreateChannel if create_channel_form.icon_picker.is_some() => {
+                                if let Some(picker) = create_channel_form.icon_picker.as_mut() {
+                                    match picker.handle_key(&key) {
+                                        IconPickerOutcome::Stay => {}
+                                        IconPickerOutcome::Close => {
+                                            create_channel_form.icon_picker = None;
+                                        }
+                                        IconPickerOutcome::Pick(icon) => {
+                                            create_channel_form.pick_icon(icon.glyph);
+                                        }
+                                    }
+                                }
+                            }
                             PopupType::CreateChannel => match key.code {
                                 KeyCode::Esc => {
                                     state_guard.popup_state.show = false;
@@ -427,6 +991,12 @@ pub async fn run_chat_page<B: Backend>(
                                         create_channel_form.name.pop();
                                     }
                                 }
+                                KeyCode::Char(' ')
+                                    if create_channel_form.input_focused
+                                        == CreateChannelInput::Icon =>
+                                {
+                                    create_channel_form.open_icon_picker();
+                                }
                                 KeyCode::Char(c) => {
                                     if let CreateChannelInput::Name =
                                         create_channel_form.input_focused
@@ -478,6 +1048,29 @@ pub async fn run_chat_page<B: Backend>(
                                                     )
                                                     .await;
                                             } else {
+                                                let pending_id = state_guard.add_pending_channel(
+                                                    channel_name.clone(),
+                                                    channel_icon.clone(),
+                                                );
+                                                let app_state_clone = app_state.clone();
+                                                tokio::spawn(async move {
+                                                    tokio::time::sleep(PENDING_CHANNEL_TIMEOUT)
+                                                        .await;
+                                                    let mut state = app_state_clone.lock().await;
+                                                    if state.remove_pending_channel(&pending_id) {
+                                                        state
+                                                            .notification_manager
+                                                            .add(
+                                                                "Channel Creation Error ".to_string(),
+                                                                "The server never confirmed the channel, try again?"
+                                                                    .to_string(),
+                                                                NotificationType::Error,
+                                                                Some(Duration::from_secs(3)),
+                                                                app_state_clone.clone(),
+                                                            )
+                                                            .await;
+                                                    }
+                                                });
                                                 state_guard
                                                     .notification_manager
                                                     .add(
@@ -523,60 +1116,435 @@ pub async fn run_chat_page<B: Backend>(
                                 }
                                 _ => {}
                             },
-                            PopupType::Mentions => {
-                                let filtered_users: Vec<String> = get_filtered_users(&state_guard)
-                                    .into_iter()
-                                    .cloned()
-                                    .collect();
-                                let num_filtered_users = filtered_users.len();
-                                match key.code {
-                                    KeyCode::Up => {
-                                        if num_filtered_users > 0 {
-                                            state_guard.selected_mention_index = (state_guard
-                                                .selected_mention_index
-                                                + num_filtered_users
-                                                - 1)
-                                                % num_filtered_users;
-                                        }
+                            PopupType::UnknownCommand => match key.code {
+                                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                                    if send_input_message(
+                                        &mut state_guard,
+                                        &command_tx,
+                                        &input_text,
+                                        app_state.clone(),
+                                    )
+                                    .await
+                                    {
+                                        input_text.clear();
+                                        state_guard.cursor_position = 0;
                                     }
-                                    KeyCode::Down => {
-                                        if num_filtered_users > 0 {
-                                            state_guard.selected_mention_index =
-                                                (state_guard.selected_mention_index + 1)
-                                                    % num_filtered_users;
-                                        }
+                                    state_guard.popup_state.show = false;
+                                    state_guard.popup_state.popup_type = PopupType::None;
+                                }
+                                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                                    state_guard.popup_state.show = false;
+                                    state_guard.popup_state.popup_type = PopupType::None;
+                                }
+                                _ => {}
+                            },
+                            PopupType::EmojiPicker => {
+                                match state_guard.emoji_picker.handle_key(&key) {
+                                    EmojiPickerOutcome::Stay => {}
+                                    EmojiPickerOutcome::Close => {
+                                        state_guard.popup_state.show = false;
+                                        state_guard.popup_state.popup_type = PopupType::None;
                                     }
-                                    KeyCode::Enter => {
-                                        if let Some(user) =
-                                            filtered_users.get(state_guard.selected_mention_index)
-                                        {
-                                            let query_start =
-                                                input_text.rfind('@').unwrap_or(input_text.len());
-                                            input_text.replace_range(
-                                                query_start..,
-                                                &format!("@{} ", user),
-                                            );
-                                            state_guard.cursor_position = input_text.len();
-                                        }
+                                    EmojiPickerOutcome::Pick(emoji) => {
+                                        let cursor = state_guard.cursor_position;
+                                        input_text.insert_str(cursor, emoji.as_str());
+                                        state_guard.cursor_position = cursor + emoji.as_str().len();
                                         state_guard.popup_state.show = false;
                                         state_guard.popup_state.popup_type = PopupType::None;
                                     }
-                                    KeyCode::Esc => {
+                                }
+                            }
+                            PopupType::ChannelMenu => {
+                                match state_guard.channel_menu.handle_key(&key) {
+                                    ChannelMenuOutcome::Stay => {}
+                                    ChannelMenuOutcome::Close => {
                                         state_guard.popup_state.show = false;
                                         state_guard.popup_state.popup_type = PopupType::None;
                                     }
-                                    KeyCode::Backspace => {
+                                    ChannelMenuOutcome::SetAccent(accent) => {
+                                        if let Some(channel) = state_guard.channel_menu.channel.clone() {
+                                            state_guard.set_channel_accent(&channel.id, accent);
+                                            if let Err(e) = save_config(&state_guard.config) {
+                                                state_guard
+                                                    .notification_manager
+                                                    .add(
+                                                        "Config Not Saved 󰒓".to_string(),
+                                                        e.to_string(),
+                                                        NotificationType::Error,
+                                                        Some(Duration::from_secs(3)),
+                                                        app_state.clone(),
+                                                    )
+                                                    .await;
+                                            }
+                                        }
+                                        state_guard.popup_state.show = false;
+                                        state_guard.popup_state.popup_type = PopupType::None;
+                                    }
+                                }
+                            }
+                            PopupType::Profiles => {
+                                match state_guard.profile_switcher.handle_key(&key) {
+                                    ProfileSwitcherOutcome::Stay => {}
+                                    ProfileSwitcherOutcome::Close => {
+                                        state_guard.popup_state.show = false;
+                                        state_guard.popup_state.popup_type = PopupType::None;
+                                    }
+                                    ProfileSwitcherOutcome::Switch(name)
+                                        if name == state_guard.config.active_profile =>
+                                    {
+                                        state_guard.popup_state.show = false;
+                                        state_guard.popup_state.popup_type = PopupType::None;
+                                    }
+                                    ProfileSwitcherOutcome::Switch(name) => {
+                                        match state_guard.switch_profile(&name).await {
+                                            // the loop top tears the socket down and leaves
+                                            Ok(page) => state_guard.next_page = Some(page),
+                                            Err(e) => {
+                                                let switched = matches!(
+                                                    e,
+                                                    crate::config::ConfigError::Keyring(_)
+                                                );
+                                                if switched {
+                                                    state_guard.next_page =
+                                                        Some(crate::app::TuiPage::start_page(
+                                                            &state_guard.config,
+                                                        ));
+                                                }
+                                                let (title, notification_type) = if switched {
+                                                    (
+                                                        "Profile Switched 󰀉",
+                                                        NotificationType::Warning,
+                                                    )
+                                                } else {
+                                                    (
+                                                        "Profile Not Switched 󰀉",
+                                                        NotificationType::Error,
+                                                    )
+                                                };
+                                                state_guard
+                                                    .notification_manager
+                                                    .add(
+                                                        title.to_string(),
+                                                        e.to_string(),
+                                                        notification_type,
+                                                        None,
+                                                        app_state.clone(),
+                                                    )
+                                                    .await;
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            PopupType::CommandPalette => {
+                                let entries = filtered_entries(&state_guard.palette_query);
+                                match key.code {
+                                    KeyCode::Esc => {
+                                        state_guard.popup_state.show = false;
+                                        state_guard.popup_state.popup_type = PopupType::None;
+                                    }
+                                    KeyCode::Up => {
+                                        state_guard.selected_palette_index =
+                                            state_guard.selected_palette_index.saturating_sub(1);
+                                    }
+                                    KeyCode::Down | KeyCode::Tab
+                                        if state_guard.selected_palette_index + 1 < entries.len() =>
+                                    {
+                                        state_guard.selected_palette_index += 1;
+                                    }
+                                    KeyCode::Backspace => {
+                                        state_guard.palette_query.pop();
+                                        state_guard.selected_palette_index = 0;
+                                    }
+                                    KeyCode::Char(c) => {
+                                        state_guard.palette_query.push(c);
+                                        state_guard.selected_palette_index = 0;
+                                    }
+                                    KeyCode::Enter => {
+                                        state_guard.popup_state.show = false;
+                                        state_guard.popup_state.popup_type = PopupType::None;
+                                        let selected = entries
+                                            .get(state_guard.selected_palette_index)
+                                            .map(|entry| entry.action);
+                                        match selected {
+                                            Some(PaletteAction::CreateChannel) => {
+                                                state_guard.popup_state.show = true;
+                                                state_guard.popup_state.popup_type =
+                                                    PopupType::CreateChannel;
+                                                create_channel_form = CreateChannelForm::new();
+                                            }
+                                            Some(PaletteAction::UploadFile) => {
+                                                state_guard.popup_state.show = true;
+                                                state_guard.popup_state.popup_type =
+                                                    PopupType::FileManager;
+                                                file_manager = FileManager::new(
+                                                    redraw_tx.clone(),
+                                                    app_state.clone(),
+                                                );
+                                            }
+                                            Some(PaletteAction::Downloads) => {
+                                                state_guard.popup_state.show = true;
+                                                state_guard.popup_state.popup_type =
+                                                    PopupType::Downloads;
+                                            }
+                                            Some(PaletteAction::FilterChannels) => {
+                                                state_guard.channel_filter = Some(String::new());
+                                                state_guard.chat_focused_pane =
+                                                    crate::app::app_state::ChatFocusedPane::ChannelList;
+                                            }
+                                            Some(PaletteAction::GotoMessage) => {
+                                                // leaves the id to type, Enter runs it like any command
+                                                input_text = "/goto ".to_string();
+                                                state_guard.cursor_position = input_text.len();
+                                                state_guard.chat_focused_pane =
+                                                    crate::app::app_state::ChatFocusedPane::Input;
+                                            }
+                                            Some(PaletteAction::NextTheme) => {
+                                                let themes = crate::themes::ThemeName::all_themes();
+                                                let current = state_guard.config.current_theme_name;
+                                                let next = themes
+                                                    .iter()
+                                                    .position(|&t| t == current)
+                                                    .map_or(0, |i| (i + 1) % themes.len());
+                                                if let Some(theme) =
+                                                    state_guard.themes.get(&themes[next]).cloned()
+                                                {
+                                                    state_guard.current_theme = theme;
+                                                    state_guard.config.current_theme_name =
+                                                        themes[next];
+                                                }
+                                            }
+                                            Some(PaletteAction::ToggleReduceMotion) => {
+                                                state_guard.config.reduce_motion =
+                                                    !state_guard.config.reduce_motion;
+                                            }
+                                            Some(PaletteAction::ToggleDoNotDisturb) => {
+                                                let on = !state_guard.config.do_not_disturb;
+                                                state_guard.config.set_do_not_disturb(
+                                                    on,
+                                                    chrono::Utc::now().timestamp(),
+                                                );
+                                                state_guard.sync_do_not_disturb();
+                                            }
+                                            Some(PaletteAction::ToggleTimestamps) => {
+                                                state_guard.config.show_timestamps =
+                                                    !state_guard.config.show_timestamps;
+                                            }
+                                            Some(PaletteAction::Settings) => {
+                                                state_guard.show_settings = true;
+                                            }
+                                            Some(PaletteAction::CopyUsername) => {
+                                                let copied = match state_guard.username.clone() {
+                                                    Some(username) => copy_to_clipboard(
+                                                        &username,
+                                                        state_guard.config.clipboard,
+                                                    )
+                                                    .map(|()| username),
+                                                    None => Err("Log in first, no username yet".to_string()),
+                                                };
+                                                let (title, content, notification_type) = match copied {
+                                                    Ok(username) => (
+                                                        "Copied 󰆏",
+                                                        format!("'{}' is on ur clipboard, go share it", username),
+                                                        NotificationType::Success,
+                                                    ),
+                                                    Err(e) => ("Copy Failed 󰆏", e, NotificationType::Error),
+                                                };
+                                                state_guard
+                                                    .notification_manager
+                                                    .add(
+                                                        title.to_string(),
+                                                        content,
+                                                        notification_type,
+                                                        Some(Duration::from_secs(3)),
+                                                        app_state.clone(),
+                                                    )
+                                                    .await;
+                                            }
+                                            Some(PaletteAction::EmojiPicker) => {
+                                                open_emoji_picker(&mut state_guard);
+                                            }
+                                            Some(PaletteAction::InspectMessage) => {
+                                                state_guard.inspect_bottom_message();
+                                            }
+                                            Some(PaletteAction::WsLog) => {
+                                                state_guard.log_scroll_offset = 0;
+                                                state_guard.popup_state.show = true;
+                                                state_guard.popup_state.popup_type =
+                                                    PopupType::WsLog;
+                                            }
+                                            Some(PaletteAction::SwitchProfile) => {
+                                                match crate::config::profiles::list_profiles() {
+                                                    Ok(names) => {
+                                                        state_guard.profile_switcher =
+                                                            ProfileSwitcherState::new(names);
+                                                        state_guard.popup_state.show = true;
+                                                        state_guard.popup_state.popup_type =
+                                                            PopupType::Profiles;
+                                                    }
+                                                    Err(e) => {
+                                                        state_guard
+                                                            .notification_manager
+                                                            .add(
+                                                                "Profiles Unavailable 󰀉"
+                                                                    .to_string(),
+                                                                e.to_string(),
+                                                                NotificationType::Error,
+                                                                None,
+                                                                app_state.clone(),
+                                                            )
+                                                            .await;
+                                                    }
+                                                }
+                                            }
+                                            Some(PaletteAction::Disconnect) => {
+                                                // still asks first, the palette is too easy to fat-finger
+                                                state_guard.popup_state.show = true;
+                                                state_guard.popup_state.popup_type =
+                                                    PopupType::Deconnection;
+                                            }
+                                            None => {}
+                                        }
+                                    }
+                                    _ => {}
+                                }
+                            }
+                            PopupType::MessageInspector => match key.code {
+                                KeyCode::Esc => {
+                                    state_guard.popup_state.show = false;
+                                    state_guard.popup_state.popup_type = PopupType::None;
+                                    state_guard.inspected_message = None;
+                                }
+                                // counted down from the top, drawing clamps it
+                                KeyCode::Down => state_guard.log_scroll_offset += 1,
+                                KeyCode::Up => {
+                                    state_guard.log_scroll_offset =
+                                        state_guard.log_scroll_offset.saturating_sub(1);
+                                }
+                                KeyCode::PageDown => state_guard.log_scroll_offset += 10,
+                                KeyCode::PageUp => {
+                                    state_guard.log_scroll_offset =
+                                        state_guard.log_scroll_offset.saturating_sub(10);
+                                }
+                                _ => {}
+                            },
+                            PopupType::WsLog => match key.code {
+                                KeyCode::Esc => {
+                                    state_guard.popup_state.show = false;
+                                    state_guard.popup_state.popup_type = PopupType::None;
+                                }
+                                // the offset counts frames up from the newest, drawing clamps it
+                                KeyCode::Up => state_guard.log_scroll_offset += 1,
+                                KeyCode::Down => {
+                                    state_guard.log_scroll_offset =
+                                        state_guard.log_scroll_offset.saturating_sub(1);
+                                }
+                                KeyCode::PageUp => state_guard.log_scroll_offset += 10,
+                                KeyCode::PageDown => {
+                                    state_guard.log_scroll_offset =
+                                        state_guard.log_scroll_offset.saturating_sub(10);
+                                }
+                                KeyCode::Char('c') => {
+                                    state_guard.ws_log.clear();
+                                    state_guard.log_scroll_offset = 0;
+                                }
+                                _ => {}
+                            },
+                            PopupType::DeleteChannel => match key.code {
+                                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
+                                    if let Some(channel) = state_guard.channel_to_delete.take() {
+                                        let _ = command_tx.send(WsCommand::Message {
+                                            channel_id: channel.id.clone(),
+                                            content: format!("/delete_channel {}", channel.id),
+                                        });
+                                        let index = state_guard
+                                            .channels
+                                            .iter()
+                                            .position(|c| c.id == channel.id)
+                                            .unwrap_or(0);
+                                        state_guard.remove_channel(&channel.id);
+                                        // land on the channel that took its spot, or the one above
+                                        let neighbour =
+                                            index.min(state_guard.channels.len().saturating_sub(1));
+                                        channel_list_state.select(Some(neighbour));
+                                        if let Some(next) = state_guard
+                                            .channels
+                                            .get(neighbour)
+                                            .filter(|c| !c.pending)
+                                            .cloned()
+                                        {
+                                            let channel_id = next.id.clone();
+                                            state_guard.set_current_channel(next);
+                                            let _ = command_tx.send(WsCommand::Message {
+                                                channel_id: channel_id.clone(),
+                                                content: format!("/get_history {} 0", channel_id),
+                                            });
+                                        }
+                                    }
+                                    state_guard.popup_state.show = false;
+                                    state_guard.popup_state.popup_type = PopupType::None;
+                                }
+                                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                                    state_guard.channel_to_delete = None;
+                                    state_guard.popup_state.show = false;
+                                    state_guard.popup_state.popup_type = PopupType::None;
+                                }
+                                _ => {}
+                            },
+                            PopupType::Mentions => {
+                                let filtered_users = get_filtered_users(&state_guard);
+                                let num_filtered_users = filtered_users.len();
+                                match key.code {
+                                    KeyCode::Up => {
+                                        if num_filtered_users > 0 {
+                                            state_guard.selected_mention_index = (state_guard
+                                                .selected_mention_index
+                                                + num_filtered_users
+                                                - 1)
+                                                % num_filtered_users;
+                                        }
+                                    }
+                                    KeyCode::Down => {
+                                        if num_filtered_users > 0 {
+                                            state_guard.selected_mention_index =
+                                                (state_guard.selected_mention_index + 1)
+                                                    % num_filtered_users;
+                                        }
+                                    }
+                                    KeyCode::Enter => {
+                                        if let Some(user) =
+                                            filtered_users.get(state_guard.selected_mention_index)
+                                        {
+                                            state_guard.cursor_position = complete_mention(
+                                                &mut input_text,
+                                                state_guard.cursor_position,
+                                                &user.username,
+                                            );
+                                        }
+                                        state_guard.popup_state.show = false;
+                                        state_guard.popup_state.popup_type = PopupType::None;
+                                    }
+                                    KeyCode::Esc => {
+                                        state_guard.popup_state.show = false;
+                                        state_guard.popup_state.popup_type = PopupType::None;
+                                    }
+                                    KeyCode::Backspace => {
                                         if state_guard.cursor_position > 0 {
                                             let old_pos = state_guard.cursor_position;
-                                            let new_pos = input_text[..old_pos]
-                                                .grapheme_indices(true)
-                                                .last()
-                                                .map(|(i, _)| i)
-                                                .unwrap_or(0);
+                                            let new_pos =
+                                                previous_grapheme_boundary(&input_text, old_pos);
                                             input_text.replace_range(new_pos..old_pos, "");
                                             state_guard.cursor_position = new_pos;
                                         }
-                                        if !should_show_mention_popup(&input_text) {
+                                        state_guard.mention_query = get_mention_query(
+                                            &input_text,
+                                            state_guard.cursor_position,
+                                        );
+                                        state_guard.selected_mention_index = 0;
+                                        if !should_show_mention_popup(
+                                            &input_text,
+                                            state_guard.cursor_position,
+                                        ) {
                                             state_guard.popup_state.show = false;
                                             state_guard.popup_state.popup_type = PopupType::None;
                                         }
@@ -584,7 +1552,15 @@ pub async fn run_chat_page<B: Backend>(
                                     KeyCode::Char(c) => {
                                         input_text.insert(state_guard.cursor_position, c);
                                         state_guard.cursor_position += c.len_utf8();
-                                        if !should_show_mention_popup(&input_text) {
+                                        state_guard.mention_query = get_mention_query(
+                                            &input_text,
+                                            state_guard.cursor_position,
+                                        );
+                                        state_guard.selected_mention_index = 0;
+                                        if !should_show_mention_popup(
+                                            &input_text,
+                                            state_guard.cursor_position,
+                                        ) {
                                             state_guard.popup_state.show = false;
                                             state_guard.popup_state.popup_type = PopupType::None;
                                         }
@@ -593,14 +1569,11 @@ pub async fn run_chat_page<B: Backend>(
                                 }
                             }
                             PopupType::Emojis => {
-                                let filtered_emojis: Vec<String> = emojis::iter()
-                                    .filter(|emoji| {
-                                        emoji
-                                            .shortcodes()
-                                            .any(|sc| sc.contains(&state_guard.emoji_query))
-                                    })
-                                    .map(|emoji| emoji.to_string())
-                                    .collect();
+                                let filtered_emojis: Vec<String> =
+                                    selectable_emojis(&state_guard.emoji_query)
+                                        .iter()
+                                        .map(|emoji| emoji.to_string())
+                                        .collect();
                                 let num_filtered_emojis = filtered_emojis.len();
 
                                 match key.code {
@@ -643,11 +1616,8 @@ pub async fn run_chat_page<B: Backend>(
                                     KeyCode::Backspace => {
                                         if state_guard.cursor_position > 0 {
                                             let old_pos = state_guard.cursor_position;
-                                            let new_pos = input_text[..old_pos]
-                                                .grapheme_indices(true)
-                                                .last()
-                                                .map(|(i, _)| i)
-                                                .unwrap_or(0);
+                                            let new_pos =
+                                                previous_grapheme_boundary(&input_text, old_pos);
                                             input_text.replace_range(new_pos..old_pos, "");
                                             state_guard.cursor_position = new_pos;
                                         }
@@ -729,6 +1699,21 @@ pub async fn run_chat_page<B: Backend>(
                                         state_guard.popup_state.show = false;
                                         state_guard.popup_state.popup_type = PopupType::None;
                                     }
+                                    FileManagerEvent::BookmarksChanged(bookmarks) => {
+                                        state_guard.config.file_bookmarks = bookmarks;
+                                    }
+                                    FileManagerEvent::InvalidPath(reason) => {
+                                        state_guard
+                                            .notification_manager
+                                            .add(
+                                                "Can't Go There 󰉖".to_string(),
+                                                reason,
+                                                NotificationType::Error,
+                                                Some(Duration::from_secs(3)),
+                                                app_state.clone(),
+                                            )
+                                            .await;
+                                    }
                                     FileManagerEvent::None => {}
                                 }
                             }
@@ -756,6 +1741,18 @@ pub async fn run_chat_page<B: Backend>(
                             _ => {}
                         }
 
+                        if current_popup_type == PopupType::None
+                            && state_guard.channel_filter.is_some()
+                        {
+                            handle_channel_filter_key(
+                                &mut state_guard,
+                                &mut channel_list_state,
+                                &command_tx,
+                                key.code,
+                            );
+                            continue;
+                        }
+
                         if current_popup_type == PopupType::None {
                             if key.code == KeyCode::Char('d')
                                 && key.modifiers.contains(KeyModifiers::CONTROL)
@@ -764,9 +1761,241 @@ pub async fn run_chat_page<B: Backend>(
                                 state_guard.popup_state.popup_type = PopupType::Deconnection;
                             } else {
                                 match key.code {
+                                    KeyCode::Enter | KeyCode::Char('d')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages
+                                            && state_guard.selected_attachment.is_some() =>
+                                    {
+                                        let selected = state_guard.selected_attachment.clone();
+                                        let download = state_guard
+                                            .channel_attachments()
+                                            .into_iter()
+                                            .find(|(file_id, _)| Some(file_id) == selected.as_ref());
+                                        if let Some((file_id, file_name)) = download {
+                                            let _ = filecommand_tx
+                                                .send(WsCommand::DownloadFile { file_id, file_name });
+                                        }
+                                    }
+                                    KeyCode::Enter
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages
+                                            && state_guard.collapsible_in_view.is_some() =>
+                                    {
+                                        state_guard.toggle_collapsible_in_view();
+                                    }
+                                    KeyCode::Char('s')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages =>
+                                    {
+                                        state_guard.toggle_spoiler_in_view();
+                                    }
+                                    KeyCode::Char('p')
+                                        if key.modifiers.contains(KeyModifiers::ALT)
+                                            && state_guard.chat_focused_pane
+                                                == crate::app::app_state::ChatFocusedPane::Messages =>
+                                    {
+                                        if let Some(pinned) = state_guard.toggle_bottom_message_pin() {
+                                            let title = if pinned { "Pinned 󰐃" } else { "Unpinned 󰤰" };
+                                            state_guard
+                                                .notification_manager
+                                                .add(
+                                                    title.to_string(),
+                                                    "The message at the bottom of the view.".to_string(),
+                                                    NotificationType::Success,
+                                                    Some(Duration::from_secs(2)),
+                                                    app_state.clone(),
+                                                )
+                                                .await;
+                                        }
+                                    }
+                                    KeyCode::Char('p')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages =>
+                                    {
+                                        state_guard.pins_expanded = !state_guard.pins_expanded;
+                                    }
+                                    KeyCode::Char(c @ ('[' | ']'))
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages =>
+                                    {
+                                        if let Some(outcome) = state_guard.jump_to_pin(c == ']') {
+                                            follow_goto(
+                                                &mut state_guard,
+                                                &command_tx,
+                                                outcome,
+                                                "That pinned message isn't in this channel anymore."
+                                                    .to_string(),
+                                                app_state.clone(),
+                                            )
+                                            .await;
+                                        }
+                                    }
+                                    KeyCode::Char('i')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages
+                                            && state_guard.message_at_bottom.is_some() =>
+                                    {
+                                        state_guard.inspect_bottom_message();
+                                    }
+                                    KeyCode::Char('I')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages =>
+                                    {
+                                        state_guard.config.show_message_details =
+                                            !state_guard.config.show_message_details;
+                                        // the panel takes columns, every message wraps anew
+                                        state_guard.rendered_messages.clear();
+                                        if let Err(e) = save_config(&state_guard.config) {
+                                            state_guard
+                                                .notification_manager
+                                                .add(
+                                                    "Config Not Saved 󰒓".to_string(),
+                                                    e.to_string(),
+                                                    NotificationType::Error,
+                                                    Some(Duration::from_secs(3)),
+                                                    app_state.clone(),
+                                                )
+                                                .await;
+                                        }
+                                    }
+                                    KeyCode::Char('v')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages
+                                            && state_guard.current_channel.is_some() =>
+                                    {
+                                        state_guard.enter_copy_mode();
+                                    }
+                                    KeyCode::Char('y')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages =>
+                                    {
+                                        if let Some(text) = state_guard.message_at_bottom_text() {
+                                            let (title, content, notification_type) =
+                                                match copy_to_clipboard(&text, state_guard.config.clipboard) {
+                                                    Ok(()) => (
+                                                        "Copied 󰆏",
+                                                        "The message is on ur clipboard.".to_string(),
+                                                        NotificationType::Success,
+                                                    ),
+                                                    Err(e) => ("Copy Failed 󰆏", e, NotificationType::Error),
+                                                };
+                                            state_guard
+                                                .notification_manager
+                                                .add(
+                                                    title.to_string(),
+                                                    content,
+                                                    notification_type,
+                                                    Some(Duration::from_secs(2)),
+                                                    app_state.clone(),
+                                                )
+                                                .await;
+                                        }
+                                    }
+                                    KeyCode::Char('t')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages =>
+                                    {
+                                        state_guard.config.show_timestamps =
+                                            !state_guard.config.show_timestamps;
+                                    }
                                     KeyCode::Enter => {
-                                        if !input_text.is_empty() {
-                                            if input_text.starts_with("/download ") {
+                                        state_guard.cursor_position = expand_macro_at_cursor(
+                                            &mut input_text,
+                                            state_guard.cursor_position,
+                                            &state_guard.config.macros,
+                                        );
+                                        let max_length = state_guard.config.max_message_length;
+                                        if max_length > 0 && message_length(&input_text) > max_length {
+                                            state_guard
+                                                .notification_manager
+                                                .add(
+                                                    "Message Too Long 󰦨".to_string(),
+                                                    format!(
+                                                        "That's {} characters, the limit is {}. Trim it down a bit and try again.",
+                                                        message_length(&input_text),
+                                                        max_length
+                                                    ),
+                                                    NotificationType::Warning,
+                                                    Some(Duration::from_secs(3)),
+                                                    app_state.clone(),
+                                                )
+                                                .await;
+                                        } else if state_guard.config.confirm_unknown_commands
+                                            && is_unknown_command(&input_text, &state_guard.config.macros)
+                                        {
+                                            state_guard.popup_state.show = true;
+                                            state_guard.popup_state.popup_type =
+                                                PopupType::UnknownCommand;
+                                        } else if !input_text.is_empty() {
+                                            // a held back send leaves the text to try again
+                                            let mut keep_input = false;
+                                            if command_token(&input_text) == Some("download_all") {
+                                                let target_dir = match input_text
+                                                    .split_once(' ')
+                                                    .map(|(_, dir)| dir.trim())
+                                                    .filter(|dir| !dir.is_empty())
+                                                {
+                                                    Some(dir) => Ok(PathBuf::from(dir)),
+                                                    None => file_api::default_download_dir(),
+                                                };
+                                                let files = state_guard.channel_attachments();
+                                                match target_dir {
+                                                    _ if files.is_empty() => {
+                                                        state_guard
+                                                            .notification_manager
+                                                            .add(
+                                                                "Nothing To Download 󰇚".to_string(),
+                                                                "Nobody shared a file in this channel yet."
+                                                                    .to_string(),
+                                                                NotificationType::Info,
+                                                                Some(Duration::from_secs(3)),
+                                                                app_state.clone(),
+                                                            )
+                                                            .await;
+                                                    }
+                                                    Ok(target_dir) => {
+                                                        let _ = filecommand_tx.send(
+                                                            WsCommand::DownloadAll { files, target_dir },
+                                                        );
+                                                    }
+                                                    Err(e) => {
+                                                        state_guard
+                                                            .notification_manager
+                                                            .add(
+                                                                "Download Error ".to_string(),
+                                                                e.to_string(),
+                                                                NotificationType::Error,
+                                                                Some(Duration::from_secs(3)),
+                                                                app_state.clone(),
+                                                            )
+                                                            .await;
+                                                    }
+                                                }
+                                            } else if command_token(&input_text) == Some("goto") {
+                                                let message_id = input_text
+                                                    .split_whitespace()
+                                                    .nth(1)
+                                                    .unwrap_or("")
+                                                    .to_string();
+                                                let outcome = if message_id.is_empty() {
+                                                    GotoOutcome::NotFound
+                                                } else {
+                                                    state_guard.goto_message(&message_id)
+                                                };
+                                                let not_found = if message_id.is_empty() {
+                                                    "Usage: /goto <message_id>".to_string()
+                                                } else {
+                                                    format!("No message '{}' in this channel.", message_id)
+                                                };
+                                                follow_goto(
+                                                    &mut state_guard,
+                                                    &command_tx,
+                                                    outcome,
+                                                    not_found,
+                                                    app_state.clone(),
+                                                )
+                                                .await;
+                                            } else if input_text.starts_with("/download ") {
                                                 let parts: Vec<&str> =
                                                     input_text.splitn(2, ' ').collect();
                                                 if parts.len() == 2 {
@@ -826,38 +2055,18 @@ pub async fn run_chat_page<B: Backend>(
                                                      ).await;
                                                 }
                                             } else {
-                                                if let Some(current_channel) =
-                                                    &state_guard.current_channel
-                                                {
-                                                    let channel_id = current_channel.id.clone();
-                                                    let content =
-                                                        replace_shortcodes_with_emojis(&input_text);
-
-                                                    
-
-                                                    if command_tx
-                                                        .send(WsCommand::Message {
-                                                            channel_id,
-                                                            content,
-                                                        })
-                                                        .is_err()
-                                                    {
-                                                        state_guard
-                                                            .notification_manager
-                                                            .add(
-                                                                "Message Send Error 󱙍".to_string(),
-                                                                "Failed to send message, this might be because the server is down, please restart ur client."
-                                                                    .to_string(),
-                                                                NotificationType::Error,
-                                                                Some(Duration::from_secs(3)),
-                                                                app_state.clone(),
-                                                            )
-                                                            .await;
-                                                    }
-                                                }
+                                                keep_input = !send_input_message(
+                                                    &mut state_guard,
+                                                    &command_tx,
+                                                    &input_text,
+                                                    app_state.clone(),
+                                                )
+                                                .await;
+                                            }
+                                            if !keep_input {
+                                                input_text.clear();
+                                                state_guard.cursor_position = 0;
                                             }
-                                            input_text.clear();
-                                            state_guard.cursor_position = 0;
                                         }
                                     }
                                     KeyCode::Char('@') => {
@@ -865,6 +2074,8 @@ pub async fn run_chat_page<B: Backend>(
                                         state_guard.cursor_position += 1;
                                         state_guard.popup_state.show = true;
                                         state_guard.popup_state.popup_type = PopupType::Mentions;
+                                        state_guard.mention_query.clear();
+                                        state_guard.selected_mention_index = 0;
                                         if command_tx
                                             .send(WsCommand::Message {
                                                 channel_id: "home".to_string(),
@@ -903,6 +2114,13 @@ pub async fn run_chat_page<B: Backend>(
                                             PopupType::CreateChannel;
                                         create_channel_form = CreateChannelForm::new();
                                     }
+                                    KeyCode::Char('f')
+                                        if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                                    {
+                                        state_guard.channel_filter = Some(String::new());
+                                        state_guard.chat_focused_pane =
+                                            crate::app::app_state::ChatFocusedPane::ChannelList;
+                                    }
                                     KeyCode::Char('u')
                                         if key.modifiers.contains(KeyModifiers::CONTROL) =>
                                     {
@@ -912,68 +2130,160 @@ pub async fn run_chat_page<B: Backend>(
                                             FileManager::new(redraw_tx.clone(), app_state.clone());
                                     }
 
-                                    KeyCode::Tab => match state_guard.chat_focused_pane {
-                                        crate::app::app_state::ChatFocusedPane::ChannelList => {
-                                            let i = match channel_list_state.selected() {
-                                                Some(i) => {
-                                                    if i >= state_guard.channels.len() - 1 {
-                                                        0
-                                                    } else {
-                                                        i + 1
-                                                    }
-                                                }
-                                                None => 0,
-                                            };
-                                            channel_list_state.select(Some(i));
-                                            if let Some(selected_channel) =
-                                                state_guard.channels.get(i).cloned()
+                                    KeyCode::Char('m')
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::ChannelList =>
+                                    {
+                                        if let Some(channel) = channel_list_state
+                                            .selected()
+                                            .and_then(|i| state_guard.channels.get(i))
+                                            .filter(|c| !c.pending)
+                                            .cloned()
+                                        {
+                                            state_guard.open_channel_menu(channel);
+                                        }
+                                    }
+                                    KeyCode::Delete
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::ChannelList =>
+                                    {
+                                        let selected = channel_list_state
+                                            .selected()
+                                            .and_then(|i| state_guard.channels.get(i))
+                                            .cloned();
+                                        match selected {
+                                            Some(channel) if channel.id == "home" => {
+                                                state_guard
+                                                    .notification_manager
+                                                    .add(
+                                                        "Nope 󰜺".to_string(),
+                                                        "The home channel stays, everyone needs a home."
+                                                            .to_string(),
+                                                        NotificationType::Warning,
+                                                        Some(Duration::from_secs(3)),
+                                                        app_state.clone(),
+                                                    )
+                                                    .await;
+                                            }
+                                            Some(channel) if !channel.pending => {
+                                                state_guard.channel_to_delete = Some(channel);
+                                                state_guard.popup_state.show = true;
+                                                state_guard.popup_state.popup_type =
+                                                    PopupType::DeleteChannel;
+                                            }
+                                            _ => {}
+                                        }
+                                    }
+                                    KeyCode::Tab
+                                        if state_guard.config.tab_switches_channels
+                                            && state_guard.chat_focused_pane
+                                                == crate::app::app_state::ChatFocusedPane::ChannelList =>
+                                    {
+                                        step_channel(
+                                            &mut state_guard,
+                                            &mut channel_list_state,
+                                            &command_tx,
+                                            true,
+                                        );
+                                    }
+                                    KeyCode::Tab | KeyCode::BackTab => {
+                                        state_guard.chat_focused_pane =
+                                            next_pane(state_guard.chat_focused_pane, key.code == KeyCode::Tab);
+                                    }
+                                    KeyCode::Up | KeyCode::Down
+                                        if key.modifiers.contains(KeyModifiers::ALT)
+                                            && state_guard.chat_focused_pane
+                                                == crate::app::app_state::ChatFocusedPane::ChannelList =>
+                                    {
+                                        if let Some(index) = channel_list_state.selected() {
+                                            if let Some(new_index) = state_guard
+                                                .move_channel(index, key.code == KeyCode::Up)
                                             {
-                                                let channel_id = selected_channel.id.clone();
-                                                state_guard.set_current_channel(selected_channel);
+                                                channel_list_state.select(Some(new_index));
+                                            }
+                                        }
+                                    }
+                                    KeyCode::Left | KeyCode::Right
+                                        if key.modifiers.contains(KeyModifiers::ALT)
+                                            && state_guard.chat_focused_pane
+                                                == crate::app::app_state::ChatFocusedPane::ChannelList =>
+                                    {
+                                        let current = state_guard.config.channel_pane_percent;
+                                        let percent = if key.code == KeyCode::Left {
+                                            current.saturating_sub(2)
+                                        } else {
+                                            current + 2
+                                        }
+                                        .clamp(
+                                            *CHANNEL_PANE_PERCENT_RANGE.start(),
+                                            *CHANNEL_PANE_PERCENT_RANGE.end(),
+                                        );
+                                        if percent != state_guard.config.channel_pane_percent {
+                                            state_guard.config.channel_pane_percent = percent;
+                                            // wrap width changed, so did every message
+                                            state_guard.rendered_messages.clear();
+                                            if let Err(e) = save_config(&state_guard.config) {
                                                 state_guard
-                                                    .messages
-                                                    .entry(channel_id.clone())
-                                                    .or_default();
-                                                if state_guard
-                                                    .messages
-                                                    .get(&channel_id)
-                                                    .map_or(true, |m| m.is_empty())
-                                                    && state_guard
-                                                        .channel_history_state
-                                                        .get(&channel_id)
-                                                        .map_or(true, |&(_, _, initial_fetched)| {
-                                                            !initial_fetched
-                                                        })
-                                                {
-                                                    if command_tx
-                                                        .send(WsCommand::Message {
-                                                            channel_id: channel_id.clone(),
-                                                            content: format!(
-                                                                "/get_history {}",
-                                                                channel_id
-                                                            ),
-                                                        })
-                                                        .is_err()
-                                                    {
-                                                        // Intentionally empty block for error handling
-                                                    } else {
-                                                        state_guard
-                                                            .channel_history_state
-                                                            .entry(channel_id.clone())
-                                                            .or_insert((0, false, false))
-                                                            .2 = true;
-                                                    }
-                                                }
+                                                    .notification_manager
+                                                    .add(
+                                                        "Config Not Saved 󰒓".to_string(),
+                                                        e.to_string(),
+                                                        NotificationType::Error,
+                                                        Some(Duration::from_secs(3)),
+                                                        app_state.clone(),
+                                                    )
+                                                    .await;
                                             }
                                         }
-                                        _ => {
-                                            state_guard.chat_focused_pane = match state_guard.chat_focused_pane {
-                                                    crate::app::app_state::ChatFocusedPane::Input => crate::app::app_state::ChatFocusedPane::ChannelList,
-                                                    crate::app::app_state::ChatFocusedPane::ChannelList => crate::app::app_state::ChatFocusedPane::Messages,
-                                                    crate::app::app_state::ChatFocusedPane::Messages => crate::app::app_state::ChatFocusedPane::Input,
-                                                };
+                                    }
+                                    KeyCode::Char('p')
+                                        if key.modifiers.contains(KeyModifiers::ALT)
+                                            && state_guard.chat_focused_pane
+                                                == crate::app::app_state::ChatFocusedPane::ChannelList =>
+                                    {
+                                        let selected_id = channel_list_state
+                                            .selected()
+                                            .and_then(|i| state_guard.channels.get(i))
+                                            .filter(|c| !c.pending)
+                                            .map(|c| c.id.clone());
+                                        if let Some(id) = selected_id {
+                                            channel_list_state
+                                                .select(state_guard.toggle_channel_pin(&id));
+                                        }
+                                    }
+                                    KeyCode::Up
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Input
+                                            && (input_text.is_empty()
+                                                || state_guard.is_browsing_input_history()) =>
+                                    {
+                                        if let Some(previous) = state_guard.previous_input(&input_text)
+                                        {
+                                            input_text = previous;
+                                            state_guard.cursor_position = input_text.len();
+                                        }
+                                    }
+                                    KeyCode::Down
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Input
+                                            && state_guard.is_browsing_input_history() =>
+                                    {
+                                        if let Some(next) = state_guard.next_input() {
+                                            input_text = next;
+                                            state_guard.cursor_position = input_text.len();
                                         }
-                                    },
+                                    }
+                                    KeyCode::Up | KeyCode::Down
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::ChannelList =>
+                                    {
+                                        step_channel(
+                                            &mut state_guard,
+                                            &mut channel_list_state,
+                                            &command_tx,
+                                            key.code == KeyCode::Down,
+                                        );
+                                    }
                                     KeyCode::Up => {
                                         state_guard.scroll_messages_up(1);
                                     }
@@ -986,19 +2296,87 @@ pub async fn run_chat_page<B: Backend>(
                                     KeyCode::PageDown => {
                                         state_guard.scroll_messages_page_down();
                                     }
+                                    KeyCode::Left | KeyCode::Right
+                                        if state_guard.chat_focused_pane
+                                            == crate::app::app_state::ChatFocusedPane::Messages =>
+                                    {
+                                        let selected = state_guard
+                                            .step_attachment_selection(key.code == KeyCode::Right);
+                                        if let Some(message) = selected {
+                                            let wants_thumbnail = state_guard.config.inline_thumbnails
+                                                && !message.is_image.unwrap_or(false)
+                                                && message
+                                                    .file_extension
+                                                    .as_deref()
+                                                    .is_some_and(image_handler::is_image_extension);
+                                            let file_id = message.file_id.clone().unwrap_or_default();
+                                            if wants_thumbnail
+                                                && !state_guard.thumbnails.contains_key(&file_id)
+                                            {
+                                                state_guard
+                                                    .thumbnails
+                                                    .insert(file_id, Thumbnail::Loading);
+                                                tokio::spawn(image_handler::load_thumbnail(
+                                                    app_state.clone(),
+                                                    message,
+                                                    state_guard.api.clone(),
+                                                    state_guard.chat_width,
+                                                    redraw_tx.clone(),
+                                                ));
+                                            }
+                                        }
+                                    }
+                                    KeyCode::Left | KeyCode::Right
+                                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                                            && state_guard.chat_focused_pane
+                                                == crate::app::app_state::ChatFocusedPane::Input =>
+                                    {
+                                        state_guard.cursor_position = if key.code == KeyCode::Left {
+                                            previous_word_boundary(
+                                                &input_text,
+                                                state_guard.cursor_position,
+                                            )
+                                        } else {
+                                            next_word_boundary(&input_text, state_guard.cursor_position)
+                                        };
+                                    }
+                                    KeyCode::Backspace | KeyCode::Char('w')
+                                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                                            && state_guard.chat_focused_pane
+                                                == crate::app::app_state::ChatFocusedPane::Input =>
+                                    {
+                                        let end = state_guard.cursor_position;
+                                        let start = previous_word_boundary(&input_text, end);
+                                        input_text.replace_range(start..end, "");
+                                        state_guard.cursor_position = start;
+                                    }
+                                    // Ctrl+U stays the upload key, so the line start goes with Alt
+                                    KeyCode::Backspace
+                                        if key.modifiers.contains(KeyModifiers::ALT)
+                                            && state_guard.chat_focused_pane
+                                                == crate::app::app_state::ChatFocusedPane::Input =>
+                                    {
+                                        let end = state_guard.cursor_position;
+                                        input_text.replace_range(..end, "");
+                                        state_guard.cursor_position = 0;
+                                    }
                                     KeyCode::Backspace => {
                                         if state_guard.cursor_position > 0 {
                                             let old_pos = state_guard.cursor_position;
-                                            let new_pos = input_text[..old_pos]
-                                                .grapheme_indices(true)
-                                                .last()
-                                                .map(|(i, _)| i)
-                                                .unwrap_or(0);
+                                            let new_pos =
+                                                previous_grapheme_boundary(&input_text, old_pos);
                                             input_text.replace_range(new_pos..old_pos, "");
                                             state_guard.cursor_position = new_pos;
                                         }
                                     }
                                     KeyCode::Char(c) => {
+                                        if c == ' ' {
+                                            state_guard.cursor_position = expand_macro_at_cursor(
+                                                &mut input_text,
+                                                state_guard.cursor_position,
+                                                &state_guard.config.macros,
+                                            );
+                                        }
                                         input_text.insert(state_guard.cursor_position, c);
                                         state_guard.cursor_position += c.len_utf8();
                                     }
@@ -1006,23 +2384,20 @@ pub async fn run_chat_page<B: Backend>(
                                         if state_guard.chat_focused_pane
                                             == crate::app::app_state::ChatFocusedPane::Input
                                         {
-                                            let current_grapheme_offset = input_text[..state_guard.cursor_position]
-                                                .graphemes(true)
-                                                .rev()
-                                                .nth(0)
-                                                .map_or(0, |g| g.len());
-                                            state_guard.cursor_position = state_guard.cursor_position.saturating_sub(current_grapheme_offset);
+                                            state_guard.cursor_position = previous_grapheme_boundary(
+                                                &input_text,
+                                                state_guard.cursor_position,
+                                            );
                                         }
                                     }
                                     KeyCode::Right => {
                                         if state_guard.chat_focused_pane
                                             == crate::app::app_state::ChatFocusedPane::Input
                                         {
-                                            let next_grapheme_offset = input_text[state_guard.cursor_position..]
-                                                .graphemes(true)
-                                                .nth(0)
-                                                .map_or(0, |g| g.len());
-                                            state_guard.cursor_position = (state_guard.cursor_position + next_grapheme_offset).min(input_text.len());
+                                            state_guard.cursor_position = next_grapheme_boundary(
+                                                &input_text,
+                                                state_guard.cursor_position,
+                                            );
                                         }
                                     }
                                     KeyCode::Home => {
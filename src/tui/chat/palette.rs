@@ -0,0 +1,142 @@
+// Everything the command palette (Ctrl+P) can do. A new feature only needs an entry here
+// and an arm where the chat page runs the action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaletteAction {
+    CreateChannel,
+    UploadFile,
+    Downloads,
+    FilterChannels,
+    GotoMessage,
+    NextTheme,
+    ToggleReduceMotion,
+    ToggleDoNotDisturb,
+    ToggleTimestamps,
+    Settings,
+    CopyUsername,
+    EmojiPicker,
+    SwitchProfile,
+    WsLog,
+    InspectMessage,
+    Disconnect,
+}
+
+pub struct PaletteEntry {
+    pub action: PaletteAction,
+    pub name: &'static str,
+    pub shortcut: &'static str,
+}
+
+pub const PALETTE_ENTRIES: &[PaletteEntry] = &[
+    PaletteEntry {
+        action: PaletteAction::CreateChannel,
+        name: "󰐕 Create channel",
+        shortcut: "Ctrl+N",
+    },
+    PaletteEntry {
+        action: PaletteAction::UploadFile,
+        name: "󰕒 Upload file",
+        shortcut: "Ctrl+U",
+    },
+    PaletteEntry {
+        action: PaletteAction::Downloads,
+        name: "󰇚 Downloads",
+        shortcut: "Ctrl+D",
+    },
+    PaletteEntry {
+        action: PaletteAction::FilterChannels,
+        name: "󰍉 Filter channels",
+        shortcut: "Ctrl+F",
+    },
+    PaletteEntry {
+        action: PaletteAction::GotoMessage,
+        name: "󰁔 Go to message",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::NextTheme,
+        name: "󰏘 Switch to next theme",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::ToggleReduceMotion,
+        name: "󰔡 Toggle reduce motion",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::ToggleDoNotDisturb,
+        name: "󰂛 Toggle do not disturb",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::ToggleTimestamps,
+        name: "󰥔 Toggle message times",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::Settings,
+        name: "󰒓 Settings",
+        shortcut: "Ctrl+S",
+    },
+    PaletteEntry {
+        action: PaletteAction::EmojiPicker,
+        name: "󰞅 Emoji picker",
+        shortcut: "Ctrl+E",
+    },
+    PaletteEntry {
+        action: PaletteAction::CopyUsername,
+        name: "󰆏 Copy my username",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::SwitchProfile,
+        name: "󰀉 Switch profile",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::WsLog,
+        name: "󰈙 Raw WebSocket log",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::InspectMessage,
+        name: "󰍉 Inspect message",
+        shortcut: "",
+    },
+    PaletteEntry {
+        action: PaletteAction::Disconnect,
+        name: "󰗽 Log out",
+        shortcut: "",
+    },
+];
+
+/// Subsequence match, higher is better. Consecutive hits and hits at word starts score more,
+/// so "ct" ranks "Create channel" above "Switch to next theme".
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut last_match: Option<usize> = None;
+    let mut pos = 0;
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = (pos..text.len()).find(|&i| text[i] == q)?;
+        score += 1;
+        if last_match.is_some_and(|last| last + 1 == found) {
+            score += 3;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 2;
+        }
+        last_match = Some(found);
+        pos = found + 1;
+    }
+    Some(score)
+}
+
+/// Entries matching the query, best match first (ties keep the registry order).
+pub fn filtered_entries(query: &str) -> Vec<&'static PaletteEntry> {
+    let mut scored: Vec<(i32, &'static PaletteEntry)> = PALETTE_ENTRIES
+        .iter()
+        .filter_map(|entry| fuzzy_score(query, entry.name).map(|score| (score, entry)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, entry)| entry).collect()
+}
@@ -0,0 +1,73 @@
+// The strip of pinned messages at the top of the messages pane.
+
+use crate::config::PinnedMessage;
+use crate::themes::{highlight_style, rgb_to_color, Theme};
+use crate::tui::utils::truncate_to_width;
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+/// Pins listed at once while expanded, the list follows the selection past that.
+pub const MAX_PIN_ROWS: usize = 5;
+
+/// Rows the strip takes: none without pins, otherwise a header, the pins when expanded and a
+/// line under it all.
+pub fn pinned_strip_height(pins: usize, expanded: bool) -> u16 {
+    match pins {
+        0 => 0,
+        _ if expanded => 2 + pins.min(MAX_PIN_ROWS) as u16,
+        _ => 2,
+    }
+}
+
+pub fn pinned_strip_lines(
+    pins: &[PinnedMessage],
+    expanded: bool,
+    selected: Option<usize>,
+    width: u16,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    let Some(latest) = pins.last() else {
+        return Vec::new();
+    };
+    let width = width as usize;
+    let accent = Style::default().fg(rgb_to_color(&theme.colors.accent));
+    let dim = Style::default().fg(rgb_to_color(&theme.colors.dim));
+    let text = Style::default().fg(rgb_to_color(&theme.colors.text));
+    let selected = selected.filter(|&i| i < pins.len());
+
+    let title = format!("󰐃 {} pinned", pins.len());
+    let mut lines = Vec::new();
+    if expanded {
+        lines.push(Line::from(vec![
+            Span::styled(title, accent),
+            Span::styled(" · [ ] jump · p hide", dim),
+        ]));
+        // keep the selected pin in the window
+        let first = selected
+            .map_or(0, |i| (i + 1).saturating_sub(MAX_PIN_ROWS))
+            .min(pins.len().saturating_sub(MAX_PIN_ROWS));
+        for (i, pin) in pins.iter().enumerate().skip(first).take(MAX_PIN_ROWS) {
+            let row = truncate_to_width(&format!("  {}: {}", pin.user, pin.preview), width);
+            let style = if Some(i) == selected {
+                highlight_style(
+                    &theme.colors.button_text_active,
+                    &theme.colors.button_bg_active,
+                )
+            } else {
+                text
+            };
+            lines.push(Line::from(Span::styled(row, style)));
+        }
+    } else {
+        let latest = format!(" · {}: {}", latest.user, latest.preview);
+        let room = width.saturating_sub(title.chars().count());
+        lines.push(Line::from(vec![
+            Span::styled(title, accent),
+            Span::styled(truncate_to_width(&latest, room), text),
+        ]));
+    }
+    lines.push(Line::from(Span::styled("─".repeat(width), dim)));
+    lines
+}
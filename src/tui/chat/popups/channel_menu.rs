@@ -0,0 +1,134 @@
+use crate::api::models::Channel;
+use crate::app::app_state::AppState;
+use crate::themes::{highlight_style, rgb_to_color, Rgb};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+/// Accent colors a channel can be given, None is the theme's own.
+pub const ACCENT_CHOICES: &[(&str, Option<Rgb>)] = &[
+    ("Theme accent", None),
+    ("Red", Some(Rgb(235, 87, 87))),
+    ("Orange", Some(Rgb(242, 153, 74))),
+    ("Yellow", Some(Rgb(242, 201, 76))),
+    ("Green", Some(Rgb(111, 207, 151))),
+    ("Teal", Some(Rgb(86, 204, 242))),
+    ("Blue", Some(Rgb(47, 128, 237))),
+    ("Purple", Some(Rgb(187, 107, 217))),
+    ("Pink", Some(Rgb(255, 121, 198))),
+];
+
+const POPUP_HINT: &str = "(Enter) Pick  / (Esc) Close";
+
+#[derive(Debug, Default, Clone)]
+pub struct ChannelMenuState {
+    /// the channel the menu was opened on, not necessarily the one being read
+    pub channel: Option<Channel>,
+    pub selected: usize,
+}
+
+pub enum ChannelMenuOutcome {
+    Stay,
+    Close,
+    SetAccent(Option<Rgb>),
+}
+
+impl ChannelMenuState {
+    /// Opens on the channel's current accent, a hand-edited color that isn't a choice lands
+    /// on the theme's.
+    pub fn open(channel: Channel, current: Option<Rgb>) -> Self {
+        let selected = ACCENT_CHOICES
+            .iter()
+            .position(|(_, accent)| *accent == current)
+            .unwrap_or(0);
+        Self {
+            channel: Some(channel),
+            selected,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> ChannelMenuOutcome {
+        match key.code {
+            KeyCode::Esc => return ChannelMenuOutcome::Close,
+            KeyCode::Enter => {
+                return ChannelMenuOutcome::SetAccent(ACCENT_CHOICES[self.selected].1)
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(ACCENT_CHOICES.len() - 1),
+            _ => {}
+        }
+        ChannelMenuOutcome::Stay
+    }
+}
+
+pub fn get_channel_menu_popup_size() -> (u16, u16) {
+    (36, ACCENT_CHOICES.len() as u16 + 6)
+}
+
+pub fn draw_channel_menu_popup(
+    f: &mut Frame,
+    state: &mut AppState,
+    area: Rect,
+    popup_block: &Block,
+) {
+    let current_theme = &state.current_theme;
+    let menu = &state.channel_menu;
+    let current = menu
+        .channel
+        .as_ref()
+        .and_then(|channel| state.channel_accent(&channel.id));
+    let inner_area = popup_block.inner(area);
+    let dim = Style::default().fg(rgb_to_color(&current_theme.colors.dim));
+    let text = Style::default().fg(rgb_to_color(&current_theme.colors.text));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner_area);
+
+    let name = menu.channel.as_ref().map_or("", |c| c.name.as_str());
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(format!(" #{}", name), text),
+            Span::styled(" · accent color", dim),
+        ])),
+        chunks[0],
+    );
+
+    let lines: Vec<Line> = ACCENT_CHOICES
+        .iter()
+        .enumerate()
+        .map(|(i, (label, accent))| {
+            let swatch = accent.unwrap_or(current_theme.colors.accent);
+            let style = if i == menu.selected {
+                highlight_style(
+                    &current_theme.colors.button_text_active,
+                    &current_theme.colors.button_bg_active,
+                )
+            } else {
+                text
+            };
+            let mark = if *accent == current { " 󰄬" } else { "" };
+            Line::from(vec![
+                Span::styled(" ██ ", Style::default().fg(rgb_to_color(&swatch))),
+                Span::styled(format!("{}{}", label, mark), style),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[1]);
+
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(POPUP_HINT, dim)))
+            .alignment(ratatui::layout::Alignment::Center),
+        chunks[2],
+    );
+}
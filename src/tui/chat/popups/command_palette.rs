@@ -0,0 +1,87 @@
+use crate::app::app_state::AppState;
+use crate::themes::{highlight_style, rgb_to_color};
+use crate::tui::chat::palette::filtered_entries;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+pub fn get_command_palette_popup_size() -> (u16, u16) {
+    (50, 14)
+}
+
+pub fn draw_command_palette_popup(
+    f: &mut Frame,
+    state: &mut AppState,
+    area: Rect,
+    popup_block: &Block,
+) {
+    let current_theme = &state.current_theme;
+    let inner_area = popup_block.inner(area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner_area);
+
+    let query_line = Line::from(vec![
+        Span::styled(
+            " ",
+            Style::default().fg(rgb_to_color(&current_theme.colors.accent)),
+        ),
+        Span::styled(
+            format!("{}▏", state.palette_query),
+            Style::default().fg(rgb_to_color(&current_theme.colors.text)),
+        ),
+    ]);
+    f.render_widget(Paragraph::new(query_line), chunks[0]);
+
+    let entries = filtered_entries(&state.palette_query);
+    if entries.is_empty() {
+        let p = Paragraph::new("Nothing matches, try fewer letters 󰘥")
+            .style(Style::default().fg(rgb_to_color(&current_theme.colors.dim)));
+        f.render_widget(p, chunks[1]);
+        return;
+    }
+
+    let list_area = chunks[1];
+    let visible = list_area.height as usize;
+    let start = state
+        .selected_palette_index
+        .saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = entries
+        .iter()
+        .enumerate()
+        .skip(start)
+        .take(visible)
+        .map(|(i, entry)| {
+            let is_selected = i == state.selected_palette_index;
+            let style = if is_selected {
+                highlight_style(
+                    &current_theme.colors.button_text_active,
+                    &current_theme.colors.button_bg_active,
+                )
+                .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(rgb_to_color(&current_theme.colors.text))
+            };
+            let shortcut_style = if is_selected {
+                style
+            } else {
+                Style::default().fg(rgb_to_color(&current_theme.colors.dim))
+            };
+            // shortcut right-aligned so people learn it for next time
+            let padding = (list_area.width as usize)
+                .saturating_sub(entry.name.width() + entry.shortcut.width() + 2);
+            Line::from(vec![
+                Span::styled(format!(" {}{}", entry.name, " ".repeat(padding)), style),
+                Span::styled(format!("{} ", entry.shortcut), shortcut_style),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), list_area);
+}
@@ -9,8 +9,17 @@ use ratatui::{
 use crate::app::app_state::AppState;
 use crate::themes::rgb_to_color;
 use crate::tui::chat::create_channel_form::{CreateChannelForm, CreateChannelInput, ICONS};
+use crate::tui::chat::popups::grid_picker::draw_grid_picker;
+use crate::tui::chat::popups::icon_picker::get_icon_picker_popup_size;
+use crate::tui::utils::pad_to_width;
 
-pub fn get_create_channel_popup_size() -> (u16, u16) {
+// every carousel slot is this wide, emoji icons take two cells and Nerd Font ones one
+const ICON_CELL_WIDTH: usize = 2;
+
+pub fn get_create_channel_popup_size(create_channel_form: &CreateChannelForm) -> (u16, u16) {
+    if create_channel_form.icon_picker.is_some() {
+        return get_icon_picker_popup_size();
+    }
     let width = 40;
     let height = 3 + 3 + 3 + 2 + 2;
     (width, height)
@@ -23,6 +32,11 @@ pub fn draw_create_channel_popup(
     create_channel_form: &mut CreateChannelForm,
     popup_block: &Block,
 ) {
+    // the icon picker takes the form's place while it's open
+    if let Some(picker) = &create_channel_form.icon_picker {
+        draw_grid_picker(f, state, picker, area, popup_block);
+        return;
+    }
     let current_theme = &state.current_theme;
     let inner_area = popup_block.inner(area);
     let fixed_width = 35;
@@ -94,7 +108,7 @@ pub fn draw_create_channel_popup(
     let icon_block = Block::default()
         .borders(ratatui::widgets::Borders::ALL)
         .border_type(ratatui::widgets::BorderType::Rounded)
-        .title("󰓺 icon")
+        .title("󰓺 icon (Space: all)")
         .style(
             if create_channel_form.input_focused == CreateChannelInput::Icon {
                 Style::default().fg(rgb_to_color(&current_theme.colors.input_border_active))
@@ -103,32 +117,41 @@ pub fn draw_create_channel_popup(
             },
         );
 
-    let len = ICONS.len();
-    let center = create_channel_form.selected_icon_index;
-    let display_range = 3;
-
-    let mut spans = Vec::with_capacity(display_range * 2 + 1);
-
-    for i in
-        (center as isize - display_range as isize)..(center as isize + display_range as isize + 1)
-    {
-        let actual_index = (i % len as isize + len as isize) % len as isize;
-        let icon_char = ICONS[actual_index as usize];
-        if actual_index == center as isize {
-            spans.push(Span::styled(
-                icon_char,
-                Style::default()
-                    .fg(rgb_to_color(&current_theme.colors.accent))
-                    .add_modifier(Modifier::BOLD),
-            ));
-        } else {
-            spans.push(Span::styled(
-                icon_char,
-                Style::default().fg(rgb_to_color(&current_theme.colors.dim)),
-            ));
-        }
-        if i != center as isize + display_range as isize {
-            spans.push(Span::raw("   "));
+    let mut spans = Vec::new();
+    if let Some(picked) = create_channel_form.picked_icon {
+        // not in the carousel, so it's shown on its own until Left/Right goes back to it
+        spans.push(Span::styled(
+            pad_to_width(picked, ICON_CELL_WIDTH),
+            Style::default()
+                .fg(rgb_to_color(&current_theme.colors.accent))
+                .add_modifier(Modifier::BOLD),
+        ));
+    } else {
+        let len = ICONS.len();
+        let center = create_channel_form.selected_icon_index;
+        let display_range = 3;
+
+        for i in (center as isize - display_range as isize)
+            ..(center as isize + display_range as isize + 1)
+        {
+            let actual_index = (i % len as isize + len as isize) % len as isize;
+            let icon_char = pad_to_width(ICONS[actual_index as usize], ICON_CELL_WIDTH);
+            if actual_index == center as isize {
+                spans.push(Span::styled(
+                    icon_char,
+                    Style::default()
+                        .fg(rgb_to_color(&current_theme.colors.accent))
+                        .add_modifier(Modifier::BOLD),
+                ));
+            } else {
+                spans.push(Span::styled(
+                    icon_char,
+                    Style::default().fg(rgb_to_color(&current_theme.colors.dim)),
+                ));
+            }
+            if i != center as isize + display_range as isize {
+                spans.push(Span::raw("  "));
+            }
         }
     }
 
@@ -0,0 +1,35 @@
+use crate::app::app_state::AppState;
+use crate::tui::chat::popups::helpers::{draw_dialog_popup, get_dialog_popup_size};
+
+use ratatui::{layout::Rect, widgets::Block, Frame};
+
+const POPUP_HINT: &str = "(Y)es, bye channel  / (N)o, keep it 󰆓";
+
+fn popup_title(state: &AppState) -> String {
+    let name = state
+        .channel_to_delete
+        .as_ref()
+        .map_or("this channel", |c| c.name.as_str());
+    format!("Delete '{}' for good? 󰆴", name)
+}
+
+pub fn get_delete_channel_popup_size(state: &AppState) -> (u16, u16) {
+    get_dialog_popup_size(&popup_title(state), POPUP_HINT)
+}
+
+pub fn draw_delete_channel_popup(
+    f: &mut Frame,
+    state: &mut AppState,
+    area: Rect,
+    popup_block: &Block,
+) {
+    let title = popup_title(state);
+    draw_dialog_popup(
+        f,
+        &state.current_theme,
+        area,
+        popup_block,
+        &title,
+        POPUP_HINT,
+    );
+}
@@ -1,3 +1,4 @@
+use crate::themes::terminal_color;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Gauge},
@@ -14,7 +15,11 @@ pub fn draw_download_progress_popup(f: &mut Frame, area: Rect, progress: u8) {
     let label = format!("{}%", progress);
     let gauge = Gauge::default()
         .block(Block::default().borders(Borders::NONE))
-        .gauge_style(Style::default().fg(Color::Green).bg(Color::DarkGray))
+        .gauge_style(
+            Style::default()
+                .fg(terminal_color(Color::Green))
+                .bg(terminal_color(Color::DarkGray)),
+        )
         .percent(progress as u16)
         .label(label);
 
@@ -1,6 +1,6 @@
 use crate::app::app_state::AppState;
 use crate::app::PopupType;
-use crate::themes::rgb_to_color;
+use crate::themes::{focus_border_type, rgb_to_color, terminal_color};
 use crate::tui::chat::ws_command::WsCommand;
 use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
 use ratatui::widgets::BorderType;
@@ -25,7 +25,7 @@ pub fn draw_downloads_popup(f: &mut Frame, app_state: &mut AppState) {
         .title("Downloads")
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(Color::Blue))
+        .border_style(Style::default().fg(terminal_color(Color::Blue)))
         .bg(rgb_to_color(&current_theme.colors.background)); // Set background on the block as well
 
     f.render_widget(block.clone(), area);
@@ -76,12 +76,12 @@ pub fn draw_downloads_popup(f: &mut Frame, app_state: &mut AppState) {
             let file_block_border_style = if is_selected {
                 Style::default().fg(rgb_to_color(&current_theme.colors.accent))
             } else {
-                Style::default().fg(Color::Blue)
+                Style::default().fg(terminal_color(Color::Blue))
             };
 
             let file_block = Block::default()
                 .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
+                .border_type(focus_border_type(is_selected))
                 .border_style(file_block_border_style)
                 .style(item_style);
 
@@ -96,9 +96,10 @@ pub fn draw_downloads_popup(f: &mut Frame, app_state: &mut AppState) {
                 ])
                 .split(inner_file_area);
 
+            let verified_mark = if file.verified { " ✓" } else { "" };
             let filename_paragraph = Paragraph::new(format!(
-                " {} {}.{}",
-                file.devicon, file.file_name, file.file_extension
+                " {} {}.{}{}",
+                file.devicon, file.file_name, file.file_extension, verified_mark
             ))
             .alignment(Alignment::Left)
             .style(item_style);
@@ -0,0 +1,61 @@
+use crate::tui::chat::popups::grid_picker::{
+    grid_picker_width, GridItem, GridPickerOutcome, GridPickerState,
+};
+use emojis::{Emoji, Group};
+
+/// Emoji per grid row.
+pub const GRID_COLUMNS: usize = 12;
+
+/// Tabs of the picker, the skin tone `Component` group isn't worth a tab of its own.
+pub const GROUPS: &[(Group, &str)] = &[
+    (Group::SmileysAndEmotion, "Smileys"),
+    (Group::PeopleAndBody, "People"),
+    (Group::AnimalsAndNature, "Animals"),
+    (Group::FoodAndDrink, "Food"),
+    (Group::TravelAndPlaces, "Travel"),
+    (Group::Activities, "Activities"),
+    (Group::Objects, "Objects"),
+    (Group::Symbols, "Symbols"),
+    (Group::Flags, "Flags"),
+];
+
+pub type EmojiPickerState = GridPickerState<&'static Emoji>;
+pub type EmojiPickerOutcome = GridPickerOutcome<&'static Emoji>;
+
+impl GridItem for &'static Emoji {
+    const COLUMNS: usize = GRID_COLUMNS;
+    const NOTHING_FOUND: &'static str = "No emoji by that name 󰱶";
+
+    fn tabs() -> Vec<&'static str> {
+        GROUPS.iter().map(|(_, name)| *name).collect()
+    }
+
+    fn tab(index: usize) -> Vec<Self> {
+        GROUPS[index].0.emojis().collect()
+    }
+
+    fn search(query: &str) -> Vec<Self> {
+        emojis::iter()
+            .filter(|emoji| {
+                emoji.name().to_lowercase().contains(query)
+                    || emoji.shortcodes().any(|sc| sc.contains(query))
+            })
+            .collect()
+    }
+
+    fn glyph(&self) -> &str {
+        self.as_str()
+    }
+
+    fn footer(&self) -> String {
+        format!(
+            " {} :{}:",
+            self.name(),
+            self.shortcode().unwrap_or_default()
+        )
+    }
+}
+
+pub fn get_emoji_picker_popup_size() -> (u16, u16) {
+    (grid_picker_width::<&'static Emoji>(), 20)
+}
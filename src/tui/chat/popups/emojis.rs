@@ -6,19 +6,50 @@ use ratatui::{
 };
 
 use crate::app::app_state::AppState;
-use crate::themes::rgb_to_color;
+use crate::themes::{highlight_style, rgb_to_color};
+use crate::tui::chat::popups::emoji_picker::GROUPS;
 
-fn get_filtered_emojis(state: &AppState) -> Vec<&'static emojis::Emoji> {
+/// A line of the `:` popup, headers are shown but never selected.
+pub enum EmojiRow {
+    Header(&'static str),
+    Emoji(&'static emojis::Emoji),
+}
+
+/// Grouped under category headers while nothing is typed, a flat list of matches otherwise.
+pub fn emoji_rows(query: &str) -> Vec<EmojiRow> {
+    if query.is_empty() {
+        return GROUPS
+            .iter()
+            .flat_map(|(group, name)| {
+                std::iter::once(EmojiRow::Header(name)).chain(group.emojis().map(EmojiRow::Emoji))
+            })
+            .collect();
+    }
+    let query = query.to_lowercase();
     emojis::iter()
         .filter(|emoji| {
-            emoji
-                .name()
-                .to_lowercase()
-                .contains(&state.emoji_query.to_lowercase())
+            emoji.name().to_lowercase().contains(&query)
+                || emoji.shortcodes().any(|sc| sc.contains(&query))
+        })
+        .map(EmojiRow::Emoji)
+        .collect()
+}
+
+/// The emoji `selected_emoji_index` counts through, in display order.
+pub fn selectable_emojis(query: &str) -> Vec<&'static emojis::Emoji> {
+    emoji_rows(query)
+        .into_iter()
+        .filter_map(|row| match row {
+            EmojiRow::Emoji(emoji) => Some(emoji),
+            EmojiRow::Header(_) => None,
         })
         .collect()
 }
 
+fn get_filtered_emojis(state: &AppState) -> Vec<&'static emojis::Emoji> {
+    selectable_emojis(&state.emoji_query)
+}
+
 pub fn get_emojis_popup_size(state: &AppState) -> (u16, u16) {
     let filtered_emojis = get_filtered_emojis(state);
 
@@ -36,22 +67,32 @@ pub fn draw_emojis_popup(f: &mut Frame, state: &mut AppState, area: Rect, popup_
     let current_theme = &state.current_theme;
     let inner_area = popup_block.inner(area);
 
-    let filtered_emojis = get_filtered_emojis(state);
-
-    let emoji_list: Vec<ListItem> = filtered_emojis
-        .iter()
+    let mut selected_row = None;
+    let mut emoji_index = 0;
+    let emoji_list: Vec<ListItem> = emoji_rows(&state.emoji_query)
+        .into_iter()
         .enumerate()
-        .map(|(i, emoji)| {
-            let is_selected = i == state.selected_emoji_index;
-            let style = if is_selected {
+        .map(|(row, entry)| match entry {
+            EmojiRow::Header(name) => ListItem::new(format!("── {} ──", name)).style(
                 Style::default()
-                    .fg(rgb_to_color(&current_theme.colors.button_text_active))
-                    .bg(rgb_to_color(&current_theme.colors.button_bg_active))
+                    .fg(rgb_to_color(&current_theme.colors.dim))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            EmojiRow::Emoji(emoji) => {
+                let is_selected = emoji_index == state.selected_emoji_index;
+                emoji_index += 1;
+                let style = if is_selected {
+                    selected_row = Some(row);
+                    highlight_style(
+                        &current_theme.colors.button_text_active,
+                        &current_theme.colors.button_bg_active,
+                    )
                     .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(rgb_to_color(&current_theme.colors.text))
-            };
-            ListItem::new(format!("{} {}", emoji.as_str(), emoji.name())).style(style)
+                } else {
+                    Style::default().fg(rgb_to_color(&current_theme.colors.text))
+                };
+                ListItem::new(format!("{} {}", emoji.as_str(), emoji.name())).style(style)
+            }
         })
         .collect();
 
@@ -66,6 +107,6 @@ pub fn draw_emojis_popup(f: &mut Frame, state: &mut AppState, area: Rect, popup_
         .highlight_symbol("󰨓");
 
     let mut list_state = ListState::default();
-    list_state.select(Some(state.selected_emoji_index));
+    list_state.select(selected_row);
     f.render_stateful_widget(emojis_list, inner_area, &mut list_state);
 }
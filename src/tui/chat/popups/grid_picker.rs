@@ -0,0 +1,205 @@
+use crate::app::app_state::AppState;
+use crate::themes::{highlight_style, rgb_to_color};
+use crate::tui::utils::pad_to_width;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+/// Each cell is a two column glyph with a space on both sides.
+const CELL_WIDTH: u16 = 4;
+
+/// What a grid picker picks from. The items decide the tabs and what a search matches, the
+/// picker does the rest.
+pub trait GridItem: Copy + 'static {
+    /// Cells per grid row.
+    const COLUMNS: usize;
+    /// Shown instead of the grid when a search matches nothing.
+    const NOTHING_FOUND: &'static str;
+
+    fn tabs() -> Vec<&'static str>;
+    fn tab(index: usize) -> Vec<Self>;
+    /// Matches from every tab, `query` is already lowercase.
+    fn search(query: &str) -> Vec<Self>;
+    fn glyph(&self) -> &str;
+    /// Says what the selected item is, under the grid.
+    fn footer(&self) -> String;
+}
+
+#[derive(Debug, Clone)]
+pub struct GridPickerState<T> {
+    pub query: String,
+    pub group: usize,
+    pub selected: usize,
+    items: std::marker::PhantomData<T>,
+}
+
+impl<T> Default for GridPickerState<T> {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            group: 0,
+            selected: 0,
+            items: std::marker::PhantomData,
+        }
+    }
+}
+
+pub enum GridPickerOutcome<T> {
+    Stay,
+    Close,
+    Pick(T),
+}
+
+impl<T: GridItem> GridPickerState<T> {
+    /// What the grid shows: the current tab, or matches from every tab while searching.
+    pub fn visible(&self) -> Vec<T> {
+        if self.query.is_empty() {
+            T::tab(self.group)
+        } else {
+            T::search(&self.query.to_lowercase())
+        }
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> GridPickerOutcome<T> {
+        let count = self.visible().len();
+        let tabs = T::tabs().len();
+        match key.code {
+            KeyCode::Esc => return GridPickerOutcome::Close,
+            KeyCode::Enter => {
+                return match self.visible().get(self.selected) {
+                    Some(item) => GridPickerOutcome::Pick(*item),
+                    None => GridPickerOutcome::Stay,
+                };
+            }
+            KeyCode::Tab | KeyCode::BackTab => {
+                self.group = if key.code == KeyCode::Tab {
+                    (self.group + 1) % tabs
+                } else {
+                    (self.group + tabs - 1) % tabs
+                };
+                // picking a tab means browsing, not searching anymore
+                self.query.clear();
+                self.selected = 0;
+            }
+            KeyCode::Left => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Right => self.selected = (self.selected + 1).min(count.saturating_sub(1)),
+            KeyCode::Up => self.selected = self.selected.saturating_sub(T::COLUMNS),
+            KeyCode::Down if self.selected + T::COLUMNS < count => {
+                self.selected += T::COLUMNS;
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        GridPickerOutcome::Stay
+    }
+}
+
+/// Popup width that fits a row of the grid.
+pub fn grid_picker_width<T: GridItem>() -> u16 {
+    T::COLUMNS as u16 * CELL_WIDTH + 4
+}
+
+pub fn draw_grid_picker<T: GridItem>(
+    f: &mut Frame,
+    state: &AppState,
+    picker: &GridPickerState<T>,
+    area: Rect,
+    popup_block: &Block,
+) {
+    let current_theme = &state.current_theme;
+    let inner_area = popup_block.inner(area);
+    let dim = Style::default().fg(rgb_to_color(&current_theme.colors.dim));
+    let text = Style::default().fg(rgb_to_color(&current_theme.colors.text));
+    let selected_style = highlight_style(
+        &current_theme.colors.button_text_active,
+        &current_theme.colors.button_bg_active,
+    )
+    .add_modifier(Modifier::BOLD);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner_area);
+
+    let query_line = Line::from(vec![
+        Span::styled(
+            "\u{f002} ",
+            Style::default().fg(rgb_to_color(&current_theme.colors.accent)),
+        ),
+        Span::styled(format!("{}▏", picker.query), text),
+    ]);
+    f.render_widget(Paragraph::new(query_line), chunks[0]);
+
+    // tabs scroll along so the active one is always on screen
+    let searching = !picker.query.is_empty();
+    let tabs: Vec<Span> = T::tabs()
+        .into_iter()
+        .enumerate()
+        .skip(picker.group.saturating_sub(2))
+        .map(|(i, name)| {
+            let style = if i == picker.group && !searching {
+                selected_style
+            } else {
+                dim
+            };
+            Span::styled(format!(" {} ", name), style)
+        })
+        .collect();
+    f.render_widget(Paragraph::new(Line::from(tabs)), chunks[1]);
+
+    let items = picker.visible();
+    if items.is_empty() {
+        f.render_widget(Paragraph::new(T::NOTHING_FOUND).style(dim), chunks[2]);
+        return;
+    }
+
+    let rows = chunks[2].height as usize;
+    let selected_row = picker.selected / T::COLUMNS;
+    let first_row = selected_row.saturating_sub(rows.saturating_sub(1));
+    let lines: Vec<Line> = items
+        .chunks(T::COLUMNS)
+        .enumerate()
+        .skip(first_row)
+        .take(rows)
+        .map(|(row, row_items)| {
+            Line::from(
+                row_items
+                    .iter()
+                    .enumerate()
+                    .map(|(column, item)| {
+                        let style = if row * T::COLUMNS + column == picker.selected {
+                            selected_style
+                        } else {
+                            text
+                        };
+                        Span::styled(format!(" {} ", pad_to_width(item.glyph(), 2)), style)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), chunks[2]);
+
+    let footer = items
+        .get(picker.selected)
+        .map(GridItem::footer)
+        .unwrap_or_default();
+    f.render_widget(Paragraph::new(footer).style(dim), chunks[3]);
+}
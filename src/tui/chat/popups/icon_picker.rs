@@ -0,0 +1,173 @@
+use crate::tui::chat::create_channel_form::ICONS;
+use crate::tui::chat::popups::grid_picker::{
+    grid_picker_width, GridItem, GridPickerOutcome, GridPickerState,
+};
+
+/// Icons per grid row.
+pub const GRID_COLUMNS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelIcon {
+    pub glyph: &'static str,
+    /// what the search matches against
+    pub name: &'static str,
+}
+
+const fn icon(glyph: &'static str, name: &'static str) -> ChannelIcon {
+    ChannelIcon { glyph, name }
+}
+
+// the carousel's icons come first so the picker starts where the form left off
+const CLASSIC: [ChannelIcon; ICONS.len()] = {
+    let mut icons = [icon("", "classic"); ICONS.len()];
+    let mut i = 0;
+    while i < ICONS.len() {
+        icons[i].glyph = ICONS[i];
+        i += 1;
+    }
+    icons
+};
+
+const CHAT: &[ChannelIcon] = &[
+    icon("\u{f086}", "comments"),
+    icon("\u{f075}", "comment"),
+    icon("\u{f0e0}", "envelope"),
+    icon("\u{f0f3}", "bell"),
+    icon("\u{f1d8}", "paper plane"),
+    icon("\u{f0a1}", "bullhorn"),
+    icon("\u{f1ea}", "newspaper"),
+    icon("\u{f0c0}", "users"),
+    icon("\u{f007}", "user"),
+    icon("\u{f015}", "home"),
+    icon("\u{f004}", "heart"),
+    icon("\u{f005}", "star"),
+    icon("\u{f118}", "smile"),
+    icon("\u{f164}", "thumbs up"),
+];
+
+const DEV: &[ChannelIcon] = &[
+    icon("\u{f121}", "code"),
+    icon("\u{f120}", "terminal"),
+    icon("\u{f126}", "code fork"),
+    icon("\u{f09b}", "github"),
+    icon("\u{f1d3}", "git"),
+    icon("\u{f17c}", "linux"),
+    icon("\u{f179}", "apple"),
+    icon("\u{f17a}", "windows"),
+    icon("\u{f188}", "bug"),
+    icon("\u{f0ad}", "wrench"),
+    icon("\u{f013}", "cog"),
+    icon("\u{f1c0}", "database"),
+    icon("\u{f233}", "server"),
+    icon("\u{f0c2}", "cloud"),
+    icon("\u{f12e}", "puzzle piece"),
+    icon("\u{f135}", "rocket"),
+];
+
+const MEDIA: &[ChannelIcon] = &[
+    icon("\u{f001}", "music"),
+    icon("\u{f025}", "headphones"),
+    icon("\u{f130}", "microphone"),
+    icon("\u{f03d}", "video camera"),
+    icon("\u{f030}", "camera"),
+    icon("\u{f008}", "film"),
+    icon("\u{f26c}", "television"),
+    icon("\u{f11b}", "gamepad"),
+    icon("\u{f1fc}", "paint brush"),
+    icon("\u{f02d}", "book"),
+];
+
+const NATURE: &[ChannelIcon] = &[
+    icon("\u{f1bb}", "tree"),
+    icon("\u{f06c}", "leaf"),
+    icon("\u{f185}", "sun"),
+    icon("\u{f186}", "moon"),
+    icon("\u{f0e9}", "umbrella"),
+    icon("\u{f2dc}", "snowflake"),
+    icon("\u{f06d}", "fire"),
+    icon("\u{f0e7}", "bolt"),
+    icon("\u{f1b0}", "paw"),
+    icon("\u{f0ac}", "globe"),
+];
+
+const THINGS: &[ChannelIcon] = &[
+    icon("\u{f0f4}", "coffee"),
+    icon("\u{f1fd}", "birthday cake"),
+    icon("\u{f0f5}", "cutlery"),
+    icon("\u{f091}", "trophy"),
+    icon("\u{f19d}", "graduation cap"),
+    icon("\u{f0b1}", "briefcase"),
+    icon("\u{f07b}", "folder"),
+    icon("\u{f0c6}", "paperclip"),
+    icon("\u{f02b}", "tag"),
+    icon("\u{f073}", "calendar"),
+    icon("\u{f017}", "clock"),
+    icon("\u{f023}", "lock"),
+    icon("\u{f084}", "key"),
+    icon("\u{f132}", "shield"),
+    icon("\u{f0eb}", "lightbulb"),
+    icon("\u{f0c3}", "flask"),
+    icon("\u{f1b2}", "cube"),
+    icon("\u{f07a}", "shopping cart"),
+    icon("\u{f0fa}", "medkit"),
+    icon("\u{f1e2}", "bomb"),
+];
+
+const PLACES: &[ChannelIcon] = &[
+    icon("\u{f072}", "plane"),
+    icon("\u{f0d1}", "truck"),
+    icon("\u{f1b9}", "car"),
+    icon("\u{f206}", "bicycle"),
+    icon("\u{f21a}", "ship"),
+    icon("\u{f19c}", "university"),
+    icon("\u{f1ad}", "building"),
+    icon("\u{f024}", "flag"),
+];
+
+/// Tabs of the picker.
+pub const GROUPS: &[(&str, &[ChannelIcon])] = &[
+    ("Classic", &CLASSIC),
+    ("Chat", CHAT),
+    ("Dev", DEV),
+    ("Media", MEDIA),
+    ("Nature", NATURE),
+    ("Things", THINGS),
+    ("Places", PLACES),
+];
+
+pub type IconPickerState = GridPickerState<ChannelIcon>;
+pub type IconPickerOutcome = GridPickerOutcome<ChannelIcon>;
+
+impl GridItem for ChannelIcon {
+    const COLUMNS: usize = GRID_COLUMNS;
+    const NOTHING_FOUND: &'static str = "No icon by that name 󰱶";
+
+    fn tabs() -> Vec<&'static str> {
+        GROUPS.iter().map(|(name, _)| *name).collect()
+    }
+
+    fn tab(index: usize) -> Vec<Self> {
+        GROUPS[index].1.to_vec()
+    }
+
+    fn search(query: &str) -> Vec<Self> {
+        GROUPS
+            .iter()
+            .flat_map(|(_, icons)| icons.iter())
+            .filter(|icon| icon.name.contains(query))
+            .copied()
+            .collect()
+    }
+
+    fn glyph(&self) -> &str {
+        self.glyph
+    }
+
+    fn footer(&self) -> String {
+        format!(" {}  (Enter) pick / (Esc) back", self.name)
+    }
+}
+
+pub fn get_icon_picker_popup_size() -> (u16, u16) {
+    (grid_picker_width::<ChannelIcon>(), 14)
+}
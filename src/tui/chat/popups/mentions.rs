@@ -6,29 +6,73 @@ use ratatui::{
 };
 
 use crate::app::app_state::AppState;
-use crate::themes::rgb_to_color;
+use crate::themes::{highlight_style, rgb_to_color};
+use crate::tui::chat::palette::fuzzy_score;
+use unicode_width::UnicodeWidthStr;
 
-// funny
-pub fn get_filtered_users<'a>(state: &'a AppState) -> Vec<&'a String> {
-    let username = state.username.as_ref().map(|s| s.to_lowercase()); // Get lowercase username once
-    state
-        .active_users
-        .iter()
-        .filter(|user| {
-            let user_lower = user.to_lowercase();
-            user_lower.contains(&state.mention_query.to_lowercase())
-                && username.as_ref().map_or(true, |u| user_lower != *u)
-        })
-        .collect()
+/// how many of a channel's recent authors are kept for the popup
+pub const RECENT_AUTHORS_LIMIT: usize = 20;
+
+/// Someone the mention popup offers, with their icon when a message of theirs showed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MentionCandidate {
+    pub username: String,
+    pub icon: Option<String>,
+}
+
+/// Who's online plus whoever wrote recently in this channel, best fuzzy match on the query
+/// first. Ties keep the online people first and the rest newest first. Never ourselves.
+pub fn get_filtered_users(state: &AppState) -> Vec<MentionCandidate> {
+    let me = state.username.as_deref().map(str::to_lowercase);
+    let recent = state
+        .current_channel
+        .as_ref()
+        .and_then(|channel| state.recent_authors.get(&channel.id));
+    let is_new = |candidates: &[MentionCandidate], username: &str| {
+        me.as_deref() != Some(username.to_lowercase().as_str())
+            && !candidates.iter().any(|c| c.username == username)
+    };
+
+    let mut candidates: Vec<MentionCandidate> = Vec::new();
+    for user in &state.active_users {
+        if is_new(&candidates, user) {
+            let icon = recent
+                .and_then(|authors| authors.iter().find(|a| &a.username == user))
+                .and_then(|author| author.icon.clone());
+            candidates.push(MentionCandidate {
+                username: user.clone(),
+                icon,
+            });
+        }
+    }
+    for author in recent.into_iter().flatten() {
+        if is_new(&candidates, &author.username) {
+            candidates.push(author.clone());
+        }
+    }
+
+    let mut scored: Vec<(i32, MentionCandidate)> = candidates
+        .into_iter()
+        .filter_map(|c| fuzzy_score(&state.mention_query, &c.username).map(|score| (score, c)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, c)| c).collect()
+}
+
+fn candidate_label(candidate: &MentionCandidate) -> String {
+    match &candidate.icon {
+        Some(icon) => format!("{} {}", icon, candidate.username),
+        None => format!("  {}", candidate.username),
+    }
 }
 
 pub fn get_mentions_popup_size(state: &AppState) -> (u16, u16) {
-    let filtered_users = get_filtered_users(state); // Call the helper once
+    let filtered_users = get_filtered_users(state);
 
     let height = std::cmp::min(filtered_users.len() as u16, 10) + 2; // +2 for borders
     let width = filtered_users
         .iter()
-        .map(|user| user.len())
+        .map(|user| candidate_label(user).width())
         .max()
         .unwrap_or(20) as u16
         + 4;
@@ -39,7 +83,7 @@ pub fn draw_mentions_popup(f: &mut Frame, state: &mut AppState, area: Rect, popu
     let current_theme = &state.current_theme;
     let inner_area = popup_block.inner(area);
 
-    let filtered_users = get_filtered_users(state); // Call the helper once
+    let filtered_users = get_filtered_users(state);
 
     let users: Vec<ListItem> = filtered_users
         .iter()
@@ -47,16 +91,17 @@ pub fn draw_mentions_popup(f: &mut Frame, state: &mut AppState, area: Rect, popu
         .map(|(i, user)| {
             let is_selected = i == state.selected_mention_index;
             let style = if is_selected {
-                Style::default()
-                    .fg(rgb_to_color(&current_theme.colors.button_text_active))
-                    .bg(rgb_to_color(&current_theme.colors.button_bg_active))
-                    .add_modifier(Modifier::BOLD)
+                highlight_style(
+                    &current_theme.colors.button_text_active,
+                    &current_theme.colors.button_bg_active,
+                )
+                .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
                     .fg(rgb_to_color(&current_theme.colors.text))
                     .bg(rgb_to_color(&current_theme.colors.dim)) // Applied background color based on "text color of the demi circle"
             };
-            ListItem::new(user.as_str()).style(style) // Use as_str() to avoid cloning String here
+            ListItem::new(candidate_label(user)).style(style)
         })
         .collect();
 
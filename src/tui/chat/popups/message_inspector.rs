@@ -0,0 +1,166 @@
+use crate::app::app_state::AppState;
+use crate::themes::rgb_to_color;
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Wrap},
+    Frame,
+};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// How a message's text adds up, the numbers that disagree when odd Unicode is involved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextStats {
+    pub graphemes: usize,
+    pub chars: usize,
+    pub bytes: usize,
+    /// terminal columns, what the layout goes by
+    pub width: usize,
+    /// characters that draw as nothing, see `visible_symbol`
+    pub invisible: usize,
+}
+
+pub fn text_stats(text: &str) -> TextStats {
+    TextStats {
+        graphemes: text.graphemes(true).count(),
+        chars: text.chars().count(),
+        bytes: text.len(),
+        width: text.width(),
+        invisible: text
+            .chars()
+            .filter(|&c| visible_symbol(c).is_some())
+            .count(),
+    }
+}
+
+/// What to draw for a character that would otherwise be invisible or mess with the
+/// terminal: control pictures for C0 controls, the code point for the rest. None for
+/// everything that shows up fine by itself.
+pub fn visible_symbol(c: char) -> Option<String> {
+    match c {
+        '\u{0}'..='\u{1f}' => char::from_u32(0x2400 + c as u32).map(String::from),
+        '\u{7f}' => Some("␡".to_string()),
+        // C1 controls, zero width and joiners, bidi overrides, word joiner and friends, BOM
+        '\u{80}'..='\u{9f}'
+        | '\u{ad}'
+        | '\u{34f}'
+        | '\u{180e}'
+        | '\u{200b}'..='\u{200f}'
+        | '\u{202a}'..='\u{202e}'
+        | '\u{2060}'..='\u{2064}'
+        | '\u{2066}'..='\u{2069}'
+        | '\u{feff}' => Some(format!("<U+{:04X}>", c as u32)),
+        _ => None,
+    }
+}
+
+/// `text` split into lines of (piece, is_invisible) with the invisible characters swapped for
+/// their symbols. Newlines still break the line, after their ␊.
+pub fn reveal_invisible(text: &str) -> Vec<Vec<(String, bool)>> {
+    let mut lines = vec![Vec::new()];
+    let mut plain = String::new();
+    for c in text.chars() {
+        let Some(symbol) = visible_symbol(c) else {
+            plain.push(c);
+            continue;
+        };
+        let line = lines.last_mut().unwrap();
+        if !plain.is_empty() {
+            line.push((std::mem::take(&mut plain), false));
+        }
+        line.push((symbol, true));
+        if c == '\n' {
+            lines.push(Vec::new());
+        }
+    }
+    if !plain.is_empty() {
+        lines.last_mut().unwrap().push((plain, false));
+    }
+    lines
+}
+
+pub fn get_message_inspector_popup_size() -> (u16, u16) {
+    (100, 30)
+}
+
+pub fn draw_message_inspector_popup(
+    f: &mut Frame,
+    state: &mut AppState,
+    area: Rect,
+    popup_block: &Block,
+) {
+    let current_theme = &state.current_theme;
+    let inner_area = popup_block.inner(area);
+    let dim = Style::default().fg(rgb_to_color(&current_theme.colors.dim));
+    let text = Style::default().fg(rgb_to_color(&current_theme.colors.text));
+    let accent = Style::default().fg(rgb_to_color(&current_theme.colors.accent));
+    let invisible = Style::default()
+        .fg(rgb_to_color(&current_theme.colors.error))
+        .add_modifier(Modifier::BOLD);
+
+    let Some(message) = &state.inspected_message else {
+        f.render_widget(
+            Paragraph::new("No message at the bottom of the view 󰍉").style(dim),
+            inner_area,
+        );
+        return;
+    };
+
+    let stats = text_stats(&message.content);
+    let mut lines = vec![
+        Line::from(Span::styled(" Up/Down scroll · Esc close", dim)),
+        Line::default(),
+        Line::from(
+            [
+                ("Graphemes", stats.graphemes),
+                ("Chars", stats.chars),
+                ("Bytes", stats.bytes),
+                ("Width", stats.width),
+                ("Invisible", stats.invisible),
+            ]
+            .into_iter()
+            .flat_map(|(label, value)| {
+                [
+                    Span::styled(format!("{} ", label), dim),
+                    Span::styled(format!("{}   ", value), accent),
+                ]
+            })
+            .collect::<Vec<_>>(),
+        ),
+        Line::default(),
+        Line::from(Span::styled("Content", accent)),
+    ];
+    lines.extend(
+        reveal_invisible(&message.content)
+            .into_iter()
+            .map(|pieces| {
+                Line::from(
+                    pieces
+                        .into_iter()
+                        .map(|(piece, hidden)| {
+                            Span::styled(piece, if hidden { invisible } else { text })
+                        })
+                        .collect::<Vec<_>>(),
+                )
+            }),
+    );
+    lines.push(Line::default());
+    lines.push(Line::from(Span::styled("JSON", accent)));
+    let json = serde_json::to_string_pretty(message).unwrap_or_default();
+    lines.extend(
+        json.lines()
+            .map(|line| Line::from(Span::styled(line.to_string(), dim))),
+    );
+
+    // the offset is clamped here, the key handler doesn't know how long the text is
+    let max_scroll = lines.len().saturating_sub(inner_area.height as usize);
+    state.log_scroll_offset = state.log_scroll_offset.min(max_scroll);
+    f.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((state.log_scroll_offset as u16, 0)),
+        inner_area,
+    );
+}
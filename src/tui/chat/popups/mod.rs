@@ -1,11 +1,20 @@
+pub mod channel_menu;
+pub mod command_palette;
 pub mod create_channel;
 
 pub mod deconnection;
+pub mod delete_channel;
 pub mod download_progress;
 pub mod downloads;
+pub mod emoji_picker;
 pub mod emojis;
 
-pub mod help;
+pub mod grid_picker;
 pub mod helpers;
+pub mod icon_picker;
 pub mod mentions;
+pub mod message_inspector;
+pub mod profiles;
+pub mod unknown_command;
+pub mod ws_log;
 
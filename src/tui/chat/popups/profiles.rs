@@ -0,0 +1,146 @@
+use crate::app::app_state::AppState;
+use crate::config::profiles::is_valid_profile_name;
+use crate::themes::{highlight_style, rgb_to_color};
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct ProfileSwitcherState {
+    /// saved profiles, read when the popup opens
+    pub names: Vec<String>,
+    pub query: String,
+    pub selected: usize,
+}
+
+pub enum ProfileSwitcherOutcome {
+    Stay,
+    Close,
+    Switch(String),
+}
+
+impl ProfileSwitcherState {
+    pub fn new(names: Vec<String>) -> Self {
+        Self {
+            names,
+            ..Self::default()
+        }
+    }
+
+    /// Profiles whose name contains the query, plus the query itself at the end when it would
+    /// make a new one.
+    pub fn rows(&self) -> Vec<String> {
+        let query = self.query.to_lowercase();
+        let mut rows: Vec<String> = self
+            .names
+            .iter()
+            .filter(|name| name.to_lowercase().contains(&query))
+            .cloned()
+            .collect();
+        if is_valid_profile_name(&self.query) && !self.names.contains(&self.query) {
+            rows.push(self.query.clone());
+        }
+        rows
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> ProfileSwitcherOutcome {
+        let count = self.rows().len();
+        match key.code {
+            KeyCode::Esc => return ProfileSwitcherOutcome::Close,
+            KeyCode::Enter => {
+                return match self.rows().get(self.selected) {
+                    Some(name) => ProfileSwitcherOutcome::Switch(name.clone()),
+                    None => ProfileSwitcherOutcome::Stay,
+                };
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down => self.selected = (self.selected + 1).min(count.saturating_sub(1)),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+            }
+            // the name becomes a file name, so only what's allowed in one gets typed
+            KeyCode::Char(c) if is_valid_profile_name(&c.to_string()) => {
+                self.query.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        ProfileSwitcherOutcome::Stay
+    }
+}
+
+pub fn get_profiles_popup_size() -> (u16, u16) {
+    (44, 14)
+}
+
+pub fn draw_profiles_popup(f: &mut Frame, state: &mut AppState, area: Rect, popup_block: &Block) {
+    let current_theme = &state.current_theme;
+    let switcher = &state.profile_switcher;
+    let active = &state.config.active_profile;
+    let inner_area = popup_block.inner(area);
+    let dim = Style::default().fg(rgb_to_color(&current_theme.colors.dim));
+    let text = Style::default().fg(rgb_to_color(&current_theme.colors.text));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner_area);
+
+    let query_line = Line::from(vec![
+        Span::styled(
+            " ",
+            Style::default().fg(rgb_to_color(&current_theme.colors.accent)),
+        ),
+        Span::styled(format!("{}▏", switcher.query), text),
+    ]);
+    f.render_widget(Paragraph::new(query_line), chunks[0]);
+
+    let list_area = chunks[1];
+    let visible = list_area.height as usize;
+    let start = switcher.selected.saturating_sub(visible.saturating_sub(1));
+    let lines: Vec<Line> = switcher
+        .rows()
+        .into_iter()
+        .enumerate()
+        .skip(start)
+        .take(visible)
+        .map(|(i, name)| {
+            let style = if i == switcher.selected {
+                highlight_style(
+                    &current_theme.colors.button_text_active,
+                    &current_theme.colors.button_bg_active,
+                )
+                .add_modifier(Modifier::BOLD)
+            } else {
+                text
+            };
+            let tag = if &name == active {
+                " (active)"
+            } else if !switcher.names.contains(&name) {
+                " (new)"
+            } else {
+                ""
+            };
+            Line::from(vec![
+                Span::styled(format!(" 󰀉 {}", name), style),
+                Span::styled(tag, dim),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), list_area);
+
+    f.render_widget(
+        Paragraph::new(" Enter switch · type a new name to add one · Esc close").style(dim),
+        chunks[2],
+    );
+}
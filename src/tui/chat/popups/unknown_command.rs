@@ -0,0 +1,28 @@
+use crate::app::app_state::AppState;
+use crate::tui::chat::popups::helpers::{draw_dialog_popup, get_dialog_popup_size};
+
+use ratatui::{layout::Rect, widgets::Block, Frame};
+
+const POPUP_TITLE: &str = "Unknown command, send as message? 󰘥";
+const POPUP_HINT: &str = "(Y)es, send it anyway  / (N)o, lemme fix it 󰏫";
+
+pub fn get_unknown_command_popup_size() -> (u16, u16) {
+    get_dialog_popup_size(POPUP_TITLE, POPUP_HINT)
+}
+
+pub fn draw_unknown_command_popup(
+    f: &mut Frame,
+    state: &mut AppState,
+    area: Rect,
+    popup_block: &Block,
+) {
+    let current_theme = &state.current_theme;
+    draw_dialog_popup(
+        f,
+        current_theme,
+        area,
+        popup_block,
+        POPUP_TITLE,
+        POPUP_HINT,
+    );
+}
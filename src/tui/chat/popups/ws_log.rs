@@ -0,0 +1,76 @@
+use crate::app::app_state::{AppState, WsFrameDirection};
+use crate::themes::rgb_to_color;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+pub fn get_ws_log_popup_size() -> (u16, u16) {
+    (120, 30)
+}
+
+pub fn draw_ws_log_popup(f: &mut Frame, state: &mut AppState, area: Rect, popup_block: &Block) {
+    let current_theme = &state.current_theme;
+    let inner_area = popup_block.inner(area);
+    let dim = Style::default().fg(rgb_to_color(&current_theme.colors.dim));
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner_area);
+
+    f.render_widget(
+        Paragraph::new(" ← in  → out  ✗ not understood · Up/Down scroll · c clear · Esc close")
+            .style(dim),
+        chunks[0],
+    );
+
+    if !state.ws_debug_enabled() {
+        f.render_widget(
+            Paragraph::new("Nothing recorded, set \"ws_debug\": true in the config or run with REETUI_WS_DEBUG=1 󰃤")
+                .style(dim),
+            chunks[1],
+        );
+        return;
+    }
+
+    let list_area = chunks[1];
+    let visible = list_area.height as usize;
+    let max_scroll = state.ws_log.len().saturating_sub(visible);
+    state.log_scroll_offset = state.log_scroll_offset.min(max_scroll);
+    let end = state.ws_log.len() - state.log_scroll_offset;
+    let start = end.saturating_sub(visible);
+
+    let lines: Vec<Line> = state
+        .ws_log
+        .range(start..end)
+        .map(|frame| {
+            let (arrow, arrow_color) = match frame.direction {
+                _ if !frame.parsed => ("✗", &current_theme.colors.error),
+                WsFrameDirection::Inbound => ("←", &current_theme.colors.accent),
+                WsFrameDirection::Outbound => ("→", &current_theme.colors.text),
+            };
+            // one frame per row, the popup is for skimming, not for reading whole payloads
+            let text: String = frame
+                .text
+                .chars()
+                .map(|c| if c.is_control() { ' ' } else { c })
+                .collect();
+            Line::from(vec![
+                Span::styled(format!("{} ", frame.time), dim),
+                Span::styled(
+                    format!("{} ", arrow),
+                    Style::default().fg(rgb_to_color(arrow_color)),
+                ),
+                Span::styled(
+                    text,
+                    Style::default().fg(rgb_to_color(&current_theme.colors.text)),
+                ),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), list_area);
+}
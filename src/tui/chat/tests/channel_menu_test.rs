@@ -0,0 +1,84 @@
+use crate::api::models::Channel;
+use crate::app::{AppState, PopupType};
+use crate::themes::Rgb;
+use crate::tui::chat::popups::channel_menu::{ChannelMenuOutcome, ACCENT_CHOICES};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn alerts() -> Channel {
+    Channel {
+        id: "alerts".to_string(),
+        name: "alerts".to_string(),
+        icon: "#".to_string(),
+        pending: false,
+    }
+}
+
+fn press(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn test_menu_opens_on_the_current_accent() {
+    let mut state = AppState::default();
+    let red = ACCENT_CHOICES[1].1;
+    state.set_channel_accent("alerts", red);
+    state.open_channel_menu(alerts());
+    assert_eq!(state.popup_state.popup_type, PopupType::ChannelMenu);
+    assert_eq!(state.channel_menu.selected, 1);
+
+    // a color typed into the config by hand isn't in the list
+    state.set_channel_accent("alerts", Some(Rgb(1, 2, 3)));
+    state.open_channel_menu(alerts());
+    assert_eq!(state.channel_menu.selected, 0);
+}
+
+#[test]
+fn test_enter_picks_the_selected_accent() {
+    let mut state = AppState::default();
+    state.open_channel_menu(alerts());
+    let menu = &mut state.channel_menu;
+    menu.handle_key(&press(KeyCode::Up));
+    assert_eq!(menu.selected, 0);
+    menu.handle_key(&press(KeyCode::Down));
+    menu.handle_key(&press(KeyCode::Down));
+    assert!(matches!(
+        menu.handle_key(&press(KeyCode::Enter)),
+        ChannelMenuOutcome::SetAccent(accent) if accent == ACCENT_CHOICES[2].1
+    ));
+    for _ in 0..ACCENT_CHOICES.len() * 2 {
+        menu.handle_key(&press(KeyCode::Down));
+    }
+    assert_eq!(menu.selected, ACCENT_CHOICES.len() - 1);
+    assert!(matches!(
+        menu.handle_key(&press(KeyCode::Esc)),
+        ChannelMenuOutcome::Close
+    ));
+}
+
+#[test]
+fn test_channel_accent_is_kept_per_channel() {
+    let mut state = AppState::default();
+    state.set_channel_accent("alerts", Some(Rgb(235, 87, 87)));
+    assert_eq!(state.channel_accent("alerts"), Some(Rgb(235, 87, 87)));
+    assert_eq!(state.channel_accent("home"), None);
+
+    state.set_channel_accent("alerts", None);
+    assert_eq!(state.channel_accent("alerts"), None);
+    assert!(state.config.channel_accents.is_empty());
+}
+
+#[test]
+fn test_changing_the_accent_redraws_the_channels_messages() {
+    let mut state = AppState::default();
+    state
+        .rendered_messages
+        .entry("alerts".to_string())
+        .or_default();
+    state
+        .rendered_messages
+        .entry("home".to_string())
+        .or_default();
+    state.set_channel_accent("alerts", Some(Rgb(235, 87, 87)));
+    assert!(!state.rendered_messages.contains_key("alerts"));
+    assert!(state.rendered_messages.contains_key("home"));
+}
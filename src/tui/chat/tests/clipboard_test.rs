@@ -0,0 +1,18 @@
+use crate::tui::chat::utils::osc52_sequence;
+
+#[test]
+fn test_osc52_sequence_is_base64_encoded() {
+    assert_eq!(
+        osc52_sequence("hi there", false),
+        "\x1b]52;c;aGkgdGhlcmU=\x07"
+    );
+    assert_eq!(osc52_sequence("", false), "\x1b]52;c;\x07");
+}
+
+#[test]
+fn test_osc52_sequence_is_wrapped_for_tmux() {
+    assert_eq!(
+        osc52_sequence("hi", true),
+        "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+    );
+}
@@ -0,0 +1,43 @@
+use crate::app::AppState;
+use crate::config::Config;
+use crate::tui::chat::ui::visible_message_lines;
+use ratatui::text::Line;
+
+fn lines(count: usize) -> Vec<Line<'static>> {
+    (0..count).map(|i| Line::from(format!("line {}", i))).collect()
+}
+
+#[test]
+fn test_collapsing_is_off_by_default() {
+    assert_eq!(Config::default().collapse_after_lines, 0);
+    let theme = AppState::default().current_theme;
+    assert_eq!(visible_message_lines(&lines(50), 0, false, &theme).len(), 50);
+}
+
+#[test]
+fn test_long_messages_show_a_preview_until_expanded() {
+    let theme = AppState::default().current_theme;
+    let collapsed = visible_message_lines(&lines(30), 10, false, &theme);
+    assert_eq!(collapsed.len(), 11);
+    assert!(collapsed[10].to_string().contains("show more (Enter)"));
+    assert!(collapsed[10].to_string().contains("20 more lines"));
+
+    let expanded = visible_message_lines(&lines(30), 10, true, &theme);
+    assert_eq!(expanded.len(), 31);
+    assert!(expanded[30].to_string().contains("show less"));
+
+    // short ones are left alone
+    assert_eq!(visible_message_lines(&lines(10), 10, false, &theme).len(), 10);
+}
+
+#[test]
+fn test_enter_toggles_the_long_message_in_view() {
+    let mut state = AppState::default();
+    assert!(!state.toggle_collapsible_in_view());
+
+    state.collapsible_in_view = Some("long".to_string());
+    assert!(state.toggle_collapsible_in_view());
+    assert!(state.expanded_messages.contains("long"));
+    assert!(state.toggle_collapsible_in_view());
+    assert!(!state.expanded_messages.contains("long"));
+}
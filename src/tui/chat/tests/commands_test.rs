@@ -0,0 +1,21 @@
+use crate::tui::chat::commands::{command_token, is_unknown_command};
+use std::collections::HashMap;
+
+#[test]
+fn test_command_token_needs_a_name_right_after_the_slash() {
+    assert_eq!(command_token("/goto 12"), Some("goto"));
+    assert_eq!(command_token("/"), None);
+    assert_eq!(command_token("/ nope"), None);
+    assert_eq!(command_token("plain text"), None);
+}
+
+#[test]
+fn test_macros_are_not_unknown_commands() {
+    let macros = HashMap::from([("shrug".to_string(), "¯\\_(ツ)_/¯".to_string())]);
+    assert!(!is_unknown_command("/shrug", &macros));
+    assert!(!is_unknown_command("/shrug about that", &macros));
+    assert!(!is_unknown_command("/download 3", &macros));
+    assert!(is_unknown_command("/shrugg", &macros));
+    assert!(is_unknown_command("/shrug", &HashMap::new()));
+    assert!(!is_unknown_command("no command here", &macros));
+}
@@ -0,0 +1,78 @@
+use crate::app::AppState;
+use crate::tui::chat::copy_mode::{handle_copy_mode_key, plain_content, transcript_lines};
+use crate::tui::tests::harness::message;
+use crossterm::event::KeyCode;
+use std::collections::HashSet;
+
+// what comes after the "[time] " prefix, the time itself depends on the local timezone
+fn without_time(line: &str) -> &str {
+    line.split_once("] ").map_or(line, |(_, rest)| rest)
+}
+
+#[test]
+fn test_spoilers_stay_hidden_until_revealed() {
+    assert_eq!(plain_content("the end ||he wins||", false), "the end ▒▒▒");
+    assert_eq!(
+        plain_content("the end ||he wins||", true),
+        "the end he wins"
+    );
+}
+
+#[test]
+fn test_transcript_has_one_entry_per_message() {
+    let messages = [
+        message(serde_json::json!({ "client_id": "a", "content": "hi" })),
+        message(serde_json::json!({ "client_id": "b", "content": "hello" })),
+    ];
+    let lines = transcript_lines(&messages, &HashSet::new(), 80);
+    let shown: Vec<&str> = lines.iter().map(|line| without_time(line)).collect();
+    assert_eq!(shown, ["ree: hi", "ree: hello"]);
+}
+
+#[test]
+fn test_revealed_spoilers_are_shown_in_the_transcript() {
+    let messages = [message(
+        serde_json::json!({ "client_id": "a", "content": "||secret||" }),
+    )];
+    let revealed = HashSet::from(["a".to_string()]);
+    let lines = transcript_lines(&messages, &revealed, 80);
+    assert_eq!(without_time(&lines[0]), "ree: secret");
+}
+
+#[test]
+fn test_long_messages_wrap_and_line_breaks_stay() {
+    let messages = [message(
+        serde_json::json!({ "client_id": "a", "content": "first line\n    indented code" }),
+    )];
+    let lines = transcript_lines(&messages, &HashSet::new(), 80);
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1], "    indented code");
+
+    let messages = [message(
+        serde_json::json!({ "client_id": "a", "content": "word ".repeat(40) }),
+    )];
+    let lines = transcript_lines(&messages, &HashSet::new(), 30);
+    assert!(lines.len() > 1);
+    assert!(lines.iter().all(|line| line.chars().count() <= 30));
+}
+
+#[test]
+fn test_copy_mode_keys_scroll_and_leave() {
+    let mut state = AppState {
+        last_chat_view_height: 10,
+        ..AppState::default()
+    };
+    state.enter_copy_mode();
+    handle_copy_mode_key(&mut state, KeyCode::Up);
+    handle_copy_mode_key(&mut state, KeyCode::PageUp);
+    assert_eq!(state.copy_mode_scroll, 11);
+    handle_copy_mode_key(&mut state, KeyCode::Down);
+    assert_eq!(state.copy_mode_scroll, 10);
+    handle_copy_mode_key(&mut state, KeyCode::End);
+    assert_eq!(state.copy_mode_scroll, 0);
+    // a stray key doesn't throw away what the user was selecting
+    handle_copy_mode_key(&mut state, KeyCode::Char('x'));
+    assert!(state.copy_mode);
+    handle_copy_mode_key(&mut state, KeyCode::Esc);
+    assert!(!state.copy_mode);
+}
@@ -0,0 +1,67 @@
+use crate::tui::chat::popups::emoji_picker::{EmojiPickerOutcome, EmojiPickerState, GRID_COLUMNS};
+use crate::tui::chat::popups::emojis::{emoji_rows, selectable_emojis, EmojiRow};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn press(picker: &mut EmojiPickerState, code: KeyCode) -> EmojiPickerOutcome {
+    picker.handle_key(&KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[test]
+fn test_search_matches_shortcodes_across_tabs() {
+    let mut picker = EmojiPickerState::default();
+    for c in "taco".chars() {
+        press(&mut picker, KeyCode::Char(c));
+    }
+    assert!(picker.visible().iter().any(|e| e.as_str() == "🌮"));
+    match press(&mut picker, KeyCode::Enter) {
+        EmojiPickerOutcome::Pick(emoji) => assert_eq!(emoji.as_str(), "🌮"),
+        _ => panic!("enter should insert the selected emoji"),
+    }
+}
+
+#[test]
+fn test_grid_navigation_stays_in_bounds() {
+    let mut picker = EmojiPickerState::default();
+    press(&mut picker, KeyCode::Up);
+    press(&mut picker, KeyCode::Left);
+    assert_eq!(picker.selected, 0);
+    press(&mut picker, KeyCode::Down);
+    assert_eq!(picker.selected, GRID_COLUMNS);
+    press(&mut picker, KeyCode::Right);
+    assert_eq!(picker.selected, GRID_COLUMNS + 1);
+}
+
+#[test]
+fn test_switching_tabs_drops_the_search() {
+    let mut picker = EmojiPickerState::default();
+    press(&mut picker, KeyCode::Char('x'));
+    press(&mut picker, KeyCode::Tab);
+    assert!(picker.query.is_empty());
+    assert_eq!(picker.group, 1);
+    press(&mut picker, KeyCode::BackTab);
+    press(&mut picker, KeyCode::BackTab);
+    assert_ne!(picker.group, 1);
+    assert!(matches!(
+        press(&mut picker, KeyCode::Esc),
+        EmojiPickerOutcome::Close
+    ));
+}
+
+#[test]
+fn test_colon_popup_groups_without_a_query() {
+    let rows = emoji_rows("");
+    assert!(matches!(rows.first(), Some(EmojiRow::Header("Smileys"))));
+    let headers = rows
+        .iter()
+        .filter(|row| matches!(row, EmojiRow::Header(_)))
+        .count();
+    // headers are skipped when counting what can be selected
+    assert_eq!(selectable_emojis("").len(), rows.len() - headers);
+}
+
+#[test]
+fn test_colon_popup_is_flat_while_searching() {
+    let rows = emoji_rows("smile");
+    assert!(!rows.is_empty());
+    assert!(rows.iter().all(|row| matches!(row, EmojiRow::Emoji(_))));
+}
@@ -0,0 +1,72 @@
+use crate::tui::chat::external_editor::{edit_in_editor, edited_text, editor_command};
+
+#[test]
+fn test_visual_wins_over_editor() {
+    assert_eq!(
+        editor_command(Some("nvim"), Some("nano")),
+        Some(vec!["nvim".to_string()])
+    );
+    assert_eq!(
+        editor_command(None, Some("nano")),
+        Some(vec!["nano".to_string()])
+    );
+}
+
+#[test]
+fn test_editor_arguments_are_kept() {
+    assert_eq!(
+        editor_command(None, Some("code --wait")),
+        Some(vec!["code".to_string(), "--wait".to_string()])
+    );
+}
+
+#[test]
+fn test_unset_or_blank_editor_is_none() {
+    assert_eq!(editor_command(None, None), None);
+    assert_eq!(editor_command(Some("  "), Some("")), None);
+    // a blank $VISUAL doesn't hide a real $EDITOR
+    assert_eq!(
+        editor_command(Some(""), Some("vim")),
+        Some(vec!["vim".to_string()])
+    );
+}
+
+#[test]
+fn test_edited_text_drops_the_final_newline_only() {
+    assert_eq!(edited_text("hello\nworld\n"), "hello\nworld");
+    assert_eq!(edited_text("hello\r\n\r\n"), "hello");
+    assert_eq!(edited_text("  indented\n"), "  indented");
+    assert_eq!(edited_text("bell\u{7}\n"), "bell");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_what_the_editor_wrote_comes_back() {
+    // the draft path is appended last, so it's $0 for the script
+    let command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        r#"cat "$0" > /dev/null && printf 'draft\nmore\n' > "$0""#.to_string(),
+    ];
+    let text = edit_in_editor(&command, "draft").await.unwrap();
+    assert_eq!(text, "draft\nmore");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_a_failing_editor_is_an_error() {
+    let command = vec!["sh".to_string(), "-c".to_string(), "exit 3".to_string()];
+    assert!(edit_in_editor(&command, "draft").await.is_err());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_the_draft_is_only_readable_by_us() {
+    let command = vec![
+        "sh".to_string(),
+        "-c".to_string(),
+        r#"ls -l "$0" | cut -c1-10 > "$0""#.to_string(),
+    ];
+    let mode = edit_in_editor(&command, "secret").await.unwrap();
+    assert_eq!(mode, "-rw-------");
+}
@@ -0,0 +1,49 @@
+use crate::app::AppState;
+use std::time::Duration;
+use tokio::time::Instant;
+
+fn guarded_state(interval_ms: u64) -> AppState {
+    let mut state = AppState::default();
+    state.config.min_send_interval_ms = interval_ms;
+    state
+}
+
+#[test]
+fn test_sends_too_close_together_are_held_back() {
+    let mut state = guarded_state(500);
+    let start = Instant::now();
+    assert!(state.claim_send_slot(start));
+    assert!(!state.claim_send_slot(start + Duration::from_millis(100)));
+    assert!(!state.claim_send_slot(start + Duration::from_millis(499)));
+    assert!(state.claim_send_slot(start + Duration::from_millis(500)));
+}
+
+#[test]
+fn test_held_back_sends_dont_push_the_next_one_out() {
+    let mut state = guarded_state(500);
+    let start = Instant::now();
+    assert!(state.claim_send_slot(start));
+    // a held Enter, every attempt fails but the interval still counts from the last real send
+    for ms in (30..500).step_by(30) {
+        assert!(!state.claim_send_slot(start + Duration::from_millis(ms)));
+    }
+    assert!(state.claim_send_slot(start + Duration::from_millis(510)));
+}
+
+#[test]
+fn test_zero_interval_lets_everything_through() {
+    let mut state = guarded_state(0);
+    let now = Instant::now();
+    assert!(state.claim_send_slot(now));
+    assert!(state.claim_send_slot(now));
+}
+
+#[test]
+fn test_a_send_that_goes_through_ends_the_burst() {
+    let mut state = guarded_state(500);
+    let start = Instant::now();
+    state.claim_send_slot(start);
+    state.send_throttled = true;
+    assert!(state.claim_send_slot(start + Duration::from_secs(1)));
+    assert!(!state.send_throttled);
+}
@@ -0,0 +1,61 @@
+use crate::app::app_state::{ChatFocusedPane, GotoOutcome};
+use crate::tui::chat::ui::{message_line_offset, scroll_offset_revealing};
+use crate::tui::tests::harness::{home_with, sent_message};
+
+#[test]
+fn test_goto_a_loaded_message_scrolls_and_flashes_it() {
+    let mut state = home_with(["a", "b"].map(sent_message));
+    assert_eq!(state.goto_message("a"), GotoOutcome::Found);
+    assert_eq!(state.scroll_to_message.as_deref(), Some("client-a"));
+    assert_eq!(
+        state.message_flash.as_ref().map(|(id, _)| id.as_str()),
+        Some("client-a")
+    );
+    assert_eq!(state.chat_focused_pane, ChatFocusedPane::Messages);
+}
+
+#[test]
+fn test_goto_a_missing_message_fetches_the_next_older_page() {
+    let mut state = home_with(["a", "b"].map(sent_message));
+    assert_eq!(
+        state.goto_message("old"),
+        GotoOutcome::Fetch {
+            channel_id: "home".to_string(),
+            offset: 2
+        }
+    );
+    // older pages are only taken in while a /goto waits on them
+    assert!(state.wants_history("home"));
+
+    state.prepend_history("home", vec![sent_message("old")]);
+    assert_eq!(state.resume_goto("home"), Some(GotoOutcome::Found));
+    assert!(!state.wants_history("home"));
+    assert_eq!(state.scroll_to_message.as_deref(), Some("client-old"));
+}
+
+#[test]
+fn test_goto_gives_up_once_the_history_is_exhausted() {
+    let mut state = home_with(["a"].map(sent_message));
+    state
+        .channel_history_state
+        .insert("home".to_string(), (0, false, true));
+    assert_eq!(state.goto_message("nope"), GotoOutcome::NotFound);
+    assert_eq!(state.pending_goto, None);
+    assert_eq!(state.resume_goto("home"), None);
+}
+
+#[test]
+fn test_line_offset_of_a_message() {
+    // a day separator on line 0, then messages 0 and 1 with a blank line between them
+    let starts = [(1, 0), (4, 1)];
+    assert_eq!(message_line_offset(&starts, 1), Some(4));
+    assert_eq!(message_line_offset(&starts, 2), None);
+}
+
+#[test]
+fn test_scroll_offset_puts_the_line_at_the_top() {
+    // 100 lines, 20 on screen: line 30 at the top means 50 lines below the view
+    assert_eq!(scroll_offset_revealing(30, 100, 20), 50);
+    // the newest lines are already in view at the bottom
+    assert_eq!(scroll_offset_revealing(95, 100, 20), 0);
+}
@@ -0,0 +1,60 @@
+use crate::tui::chat::create_channel_form::{CreateChannelForm, ICONS};
+use crate::tui::chat::popups::icon_picker::{
+    IconPickerOutcome, IconPickerState, GRID_COLUMNS, GROUPS,
+};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn press(picker: &mut IconPickerState, code: KeyCode) -> IconPickerOutcome {
+    picker.handle_key(&KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+#[test]
+fn test_picker_starts_on_the_carousel_icons() {
+    let picker = IconPickerState::default();
+    let glyphs: Vec<&str> = picker.visible().iter().map(|i| i.glyph).collect();
+    assert_eq!(glyphs, ICONS);
+}
+
+#[test]
+fn test_search_finds_icons_in_every_tab() {
+    let mut picker = IconPickerState::default();
+    for c in "rocket".chars() {
+        press(&mut picker, KeyCode::Char(c));
+    }
+    assert_eq!(picker.visible().len(), 1);
+    match press(&mut picker, KeyCode::Enter) {
+        IconPickerOutcome::Pick(icon) => assert_eq!(icon.glyph, "\u{f135}"),
+        _ => panic!("enter should pick the selected icon"),
+    }
+}
+
+#[test]
+fn test_grid_navigation_and_tabs() {
+    let mut picker = IconPickerState::default();
+    press(&mut picker, KeyCode::Tab);
+    assert_eq!(picker.group, 1);
+    press(&mut picker, KeyCode::Down);
+    assert_eq!(picker.selected, GRID_COLUMNS);
+    press(&mut picker, KeyCode::BackTab);
+    press(&mut picker, KeyCode::BackTab);
+    assert_eq!(picker.group, GROUPS.len() - 1);
+    assert_eq!(picker.selected, 0);
+}
+
+#[test]
+fn test_picked_icon_is_what_the_channel_gets() {
+    let mut form = CreateChannelForm::new();
+    form.open_icon_picker();
+    form.pick_icon("\u{f135}");
+    assert!(form.icon_picker.is_none());
+    assert_eq!(form.get_selected_icon(), "\u{f135}");
+
+    // the carousel takes over again once it moves
+    form.next_icon();
+    assert_eq!(form.get_selected_icon(), ICONS[1]);
+
+    // a carousel icon picked from the full picker just moves the carousel there
+    form.pick_icon(ICONS[4]);
+    assert_eq!(form.picked_icon, None);
+    assert_eq!(form.selected_icon_index, 4);
+}
@@ -0,0 +1,36 @@
+use crate::app::AppState;
+
+#[test]
+fn test_input_history_walks_back_and_forth() {
+    let mut state = AppState::default();
+    state.push_input_history("first");
+    state.push_input_history("second");
+
+    assert_eq!(state.previous_input("draft").as_deref(), Some("second"));
+    assert_eq!(state.previous_input("ignored").as_deref(), Some("first"));
+    assert_eq!(state.previous_input("ignored"), None);
+    assert_eq!(state.next_input().as_deref(), Some("second"));
+    // walking past the newest entry gives back what was being typed
+    assert_eq!(state.next_input().as_deref(), Some("draft"));
+    assert!(!state.is_browsing_input_history());
+    assert_eq!(state.next_input(), None);
+}
+
+#[test]
+fn test_input_history_skips_blank_and_repeated_entries() {
+    let mut state = AppState::default();
+    state.push_input_history("hello");
+    state.push_input_history("hello");
+    state.push_input_history("   ");
+    assert_eq!(state.input_history, vec!["hello".to_string()]);
+}
+
+#[test]
+fn test_input_history_is_bounded() {
+    let mut state = AppState::default();
+    for i in 0..150 {
+        state.push_input_history(&i.to_string());
+    }
+    assert_eq!(state.input_history.len(), 100);
+    assert_eq!(state.input_history[0], "50");
+}
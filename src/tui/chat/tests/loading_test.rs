@@ -0,0 +1,56 @@
+use crate::api::models::Channel;
+use crate::app::AppState;
+use crate::tui::chat::ui::skeleton_lines;
+
+fn channel(id: &str) -> Channel {
+    Channel {
+        id: id.to_string(),
+        name: id.to_string(),
+        icon: "#".to_string(),
+        pending: false,
+    }
+}
+
+#[test]
+fn test_both_panes_load_until_the_channel_list_arrives() {
+    let mut state = AppState::default();
+    assert!(state.channels_loading());
+    assert!(state.messages_loading());
+
+    state.set_channels(Vec::new());
+    // an account without channels isn't loading anymore, it's just empty
+    assert!(!state.channels_loading());
+    assert!(!state.messages_loading());
+}
+
+#[test]
+fn test_messages_load_until_the_channel_history_arrives() {
+    let mut state = AppState::default();
+    state.set_channels(vec![channel("home")]);
+    state.set_current_channel(channel("home"));
+    assert!(!state.channels_loading());
+    assert!(state.messages_loading());
+
+    state.history_loaded.insert("home".to_string());
+    assert!(!state.messages_loading());
+}
+
+#[tokio::test]
+async fn test_logging_out_brings_the_skeleton_back() {
+    let mut state = AppState::default();
+    state.set_channels(vec![channel("home")]);
+    state.history_loaded.insert("home".to_string());
+    state.clear_user_auth().await;
+    assert!(state.channels_loading());
+    assert!(state.history_loaded.is_empty());
+}
+
+#[test]
+fn test_skeleton_bars_fit_the_pane() {
+    let state = AppState::default();
+    let lines = skeleton_lines("Loading messages…", 20, 6, &state.current_theme);
+    assert_eq!(lines[0].to_string(), " Loading messages…");
+    // a blank line before every bar
+    assert_eq!(lines.len(), 1 + 6 * 2);
+    assert!(lines.iter().all(|line| line.width() <= 20));
+}
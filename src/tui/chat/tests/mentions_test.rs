@@ -0,0 +1,75 @@
+use crate::api::models::BroadcastMessage;
+use crate::app::AppState;
+use crate::tui::chat::message_parsing::get_mention_query;
+use crate::tui::chat::popups::mentions::get_filtered_users;
+use crate::tui::tests::harness::{home, message};
+
+fn by(user: &str, timestamp: i64) -> BroadcastMessage {
+    message(serde_json::json!({ "user": user, "timestamp": timestamp }))
+}
+
+fn usernames(state: &AppState) -> Vec<String> {
+    get_filtered_users(state)
+        .into_iter()
+        .map(|c| c.username)
+        .collect()
+}
+
+#[test]
+fn test_fuzzy_query_ranks_the_closest_name_first() {
+    let state = AppState {
+        active_users: vec!["mallory".to_string(), "alice".to_string(), "alfred".to_string()],
+        mention_query: "alf".to_string(),
+        ..AppState::default()
+    };
+    assert_eq!(usernames(&state), vec!["alfred"]);
+
+    let state = AppState {
+        mention_query: "al".to_string(),
+        ..state
+    };
+    // "al" is a prefix of both a-names and only scattered in mallory
+    let ranked = usernames(&state);
+    assert_eq!(ranked.len(), 3);
+    assert_eq!(ranked.last().map(String::as_str), Some("mallory"));
+}
+
+#[test]
+fn test_recent_authors_are_offered_with_their_icons() {
+    let mut state = AppState {
+        active_users: vec!["bob".to_string(), "me".to_string()],
+        username: Some("Me".to_string()),
+        ..AppState::default()
+    };
+    state.set_current_channel(home());
+    state.add_message(by("carol", 10));
+    state.add_message(by("bob", 20));
+    state.add_message(by("dave", 30));
+    let candidates = get_filtered_users(&state);
+    let names: Vec<&str> = candidates.iter().map(|c| c.username.as_str()).collect();
+    // online first, then whoever wrote most recently, never ourselves
+    assert_eq!(names, vec!["bob", "dave", "carol"]);
+    assert_eq!(candidates[1].icon.as_deref(), Some(""));
+}
+
+#[test]
+fn test_history_fills_in_behind_the_live_authors() {
+    let mut state = AppState::default();
+    state.set_current_channel(home());
+    state.add_message(by("carol", 30));
+    state.prepend_history("home", vec![by("erin", 10), by("carol", 20)]);
+    assert_eq!(usernames(&state), vec!["carol", "erin"]);
+
+    // authors of other channels aren't offered here
+    let mut elsewhere = by("frank", 40);
+    elsewhere.channel_id = "random".to_string();
+    state.add_message(elsewhere);
+    assert_eq!(usernames(&state), vec!["carol", "erin"]);
+}
+
+#[test]
+fn test_mention_query_is_what_follows_the_at() {
+    assert_eq!(get_mention_query("hi @bo", 6), "bo");
+    assert_eq!(get_mention_query("hi @", 4), "");
+    assert_eq!(get_mention_query("no mention", 10), "");
+}
@@ -0,0 +1,78 @@
+use crate::app::AppState;
+use crate::tui::chat::message_details::{draw_message_details, message_detail_fields};
+use crate::tui::tests::harness::{contains_text, home_with, message, render};
+
+fn field<'a>(fields: &'a [(&'static str, String)], label: &str) -> &'a str {
+    fields
+        .iter()
+        .find(|(name, _)| *name == label)
+        .map(|(_, value)| value.as_str())
+        .unwrap_or_else(|| panic!("no {} field", label))
+}
+
+#[test]
+fn test_details_of_a_text_message() {
+    let msg = message(serde_json::json!({ "id": "42", "content": "héllo" }));
+    let fields = message_detail_fields(&msg, "home", 300);
+    assert_eq!(field(&fields, "Author"), "ree");
+    assert_eq!(field(&fields, "Channel"), "#home");
+    assert_eq!(field(&fields, "Id"), "42");
+    assert_eq!(field(&fields, "Client Id"), "c1");
+    assert_eq!(field(&fields, "Type"), "text");
+    assert_eq!(field(&fields, "Attachment"), "None");
+    assert_eq!(field(&fields, "Length"), "5 graphemes · 6 bytes");
+    assert!(field(&fields, "Sent").ends_with("5 min ago"));
+}
+
+#[test]
+fn test_unsent_message_has_no_id_yet() {
+    let fields = message_detail_fields(&message(serde_json::json!({})), "home", 0);
+    assert_eq!(field(&fields, "Id"), "N/A");
+}
+
+#[test]
+fn test_attachment_shows_its_name_and_size() {
+    let msg = message(serde_json::json!({
+        "file_id": "f1",
+        "file_name": "notes",
+        "file_extension": "txt",
+        "file_size_bytes": 2048,
+    }));
+    let fields = message_detail_fields(&msg, "home", 0);
+    let attachment = field(&fields, "Attachment");
+    assert!(attachment.starts_with("notes.txt · "));
+    assert!(attachment.contains("2.00 KiB"));
+}
+
+#[test]
+fn test_panel_follows_the_picked_attachment() {
+    let mut state = home_with([
+        message(serde_json::json!({ "client_id": "a", "file_id": "f1" })),
+        message(serde_json::json!({ "client_id": "b" })),
+    ]);
+    state.message_at_bottom = Some("b".to_string());
+    assert_eq!(
+        state.detail_message().and_then(|m| m.client_id.as_deref()),
+        Some("b")
+    );
+
+    state.selected_attachment = Some("f1".to_string());
+    assert_eq!(
+        state.detail_message().and_then(|m| m.client_id.as_deref()),
+        Some("a")
+    );
+}
+
+#[test]
+fn test_short_panel_lists_every_field_on_its_own_line() {
+    let msg = message(serde_json::json!({ "id": "42" }));
+    let mut state = AppState::default();
+    let theme = state.current_theme.clone();
+    // 8 boxes of 3 rows don't fit in the 10 rows inside the border and padding
+    let buffer = render(36, 14, &mut state, |f, _| {
+        draw_message_details(f, f.area(), Some(&msg), "home", &theme, 0)
+    });
+    assert!(contains_text(&buffer, "Author: ree"));
+    assert!(contains_text(&buffer, "Id: 42"));
+    assert!(contains_text(&buffer, "Length: 2 graphemes"));
+}
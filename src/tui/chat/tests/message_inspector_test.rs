@@ -0,0 +1,39 @@
+use crate::tui::chat::popups::message_inspector::{reveal_invisible, text_stats, visible_symbol};
+
+#[test]
+fn test_text_stats_count_what_they_say() {
+    // é as e + combining accent, and a thumbs up with a skin tone
+    let stats = text_stats("he\u{301}y 👍🏽");
+    assert_eq!(stats.graphemes, 5);
+    assert_eq!(stats.chars, 7);
+    assert_eq!(stats.bytes, "he\u{301}y 👍🏽".len());
+    assert_eq!(stats.invisible, 0);
+}
+
+#[test]
+fn test_invisible_characters_get_a_symbol() {
+    assert_eq!(visible_symbol('\u{7}').as_deref(), Some("␇"));
+    assert_eq!(visible_symbol('\u{1b}').as_deref(), Some("␛"));
+    assert_eq!(visible_symbol('\u{200b}').as_deref(), Some("<U+200B>"));
+    assert_eq!(visible_symbol('\u{202e}').as_deref(), Some("<U+202E>"));
+    assert_eq!(visible_symbol('a'), None);
+    assert_eq!(visible_symbol('é'), None);
+    assert_eq!(text_stats("a\u{200b}b\u{1b}").invisible, 2);
+}
+
+#[test]
+fn test_reveal_keeps_the_lines_and_marks_the_hidden_parts() {
+    let lines = reveal_invisible("hi\u{200b}there\nbye");
+    assert_eq!(
+        lines,
+        vec![
+            vec![
+                ("hi".to_string(), false),
+                ("<U+200B>".to_string(), true),
+                ("there".to_string(), false),
+                ("␊".to_string(), true),
+            ],
+            vec![("bye".to_string(), false)],
+        ]
+    );
+}
@@ -0,0 +1,44 @@
+use crate::app::AppState;
+use crate::tui::tests::harness::message;
+
+#[test]
+fn test_no_notice_while_the_chat_is_visible() {
+    let state = AppState::default();
+    assert_eq!(
+        state.hidden_message_notice(&message(serde_json::json!({}))),
+        None
+    );
+}
+
+#[test]
+fn test_notice_while_settings_cover_the_chat() {
+    let state = AppState {
+        show_settings: true,
+        username: Some("me".to_string()),
+        ..AppState::default()
+    };
+    assert_eq!(
+        state
+            .hidden_message_notice(&message(serde_json::json!({})))
+            .as_deref(),
+        Some("ree in home: hi")
+    );
+    // no point telling people about their own messages
+    assert_eq!(
+        state.hidden_message_notice(&message(serde_json::json!({ "user": "me" }))),
+        None
+    );
+}
+
+#[test]
+fn test_long_messages_are_cut_in_the_notice() {
+    let state = AppState {
+        show_settings: true,
+        ..AppState::default()
+    };
+    let notice = state
+        .hidden_message_notice(&message(serde_json::json!({ "content": "a".repeat(200) })))
+        .unwrap();
+    assert!(notice.ends_with('…'));
+    assert!(notice.chars().count() < 100);
+}
@@ -1,6 +1,10 @@
 use crate::tui::chat::message_parsing::{
+    complete_mention, cursor_screen_offset, expand_macro_at_cursor, get_emoji_query,
+    get_mention_query, insert_pasted_text, message_length, next_grapheme_boundary,
+    next_word_boundary, previous_grapheme_boundary, previous_word_boundary,
     replace_shortcodes_with_emojis, should_show_emoji_popup, should_show_mention_popup,
 };
+use std::collections::HashMap;
 #[test]
 fn test_replace_shortcodes_with_emojis() {
     assert_eq!(
@@ -92,48 +96,209 @@ fn test_should_show_emoji_popup() {
         false,
         "Should not show for invalid shortcode format"
     );
+    assert!(
+        !should_show_emoji_popup("see http://"),
+        "Should not show for a colon inside a URL"
+    );
+    assert!(
+        !should_show_emoji_popup("meet at 12:30"),
+        "Should not show for a colon inside a time"
+    );
+    assert!(
+        should_show_emoji_popup("nice :smi"),
+        "Should show for a colon starting a token"
+    );
+    assert!(
+        should_show_emoji_popup(":+1"),
+        "Should show at the start of the input"
+    );
+}
+
+#[test]
+fn test_get_emoji_query() {
+    assert_eq!(get_emoji_query("nice :smi"), "smi");
+    assert_eq!(get_emoji_query("nice :"), "");
+    assert_eq!(get_emoji_query("see http://"), "");
+    assert_eq!(get_emoji_query("meet at 12:30"), "");
+    assert_eq!(get_emoji_query("done :smile: "), "");
 }
 
 #[test]
 fn test_should_show_mention_popup() {
     assert_eq!(
-        should_show_mention_popup("hello @"),
+        should_show_mention_popup("hello @", 7),
         true,
         "Should show for trailing @"
     );
     assert_eq!(
-        should_show_mention_popup("hello @u"),
+        should_show_mention_popup("hello @u", 8),
         true,
         "Should show for unclosed mention"
     );
     assert_eq!(
-        should_show_mention_popup("hello @user"),
+        should_show_mention_popup("hello @user", 11),
         true,
         "Should show for unclosed mention"
     );
     assert_eq!(
-        should_show_mention_popup("hello @user "),
+        should_show_mention_popup("hello @user ", 12),
         false,
         "Should not show for mention followed by space"
     );
     assert_eq!(
-        should_show_mention_popup("no at symbol"),
+        should_show_mention_popup("no at symbol", 12),
         false,
         "Should not show if no @"
     );
     assert_eq!(
-        should_show_mention_popup("@@"),
+        should_show_mention_popup("@@", 2),
         false,
         "Should not show for double @"
     );
     assert_eq!(
-        should_show_mention_popup("hello @user@"),
+        should_show_mention_popup("hello @user@", 12),
         false,
         "Should not show for multiple @"
     );
     assert_eq!(
-        should_show_mention_popup("hello @user: "),
+        should_show_mention_popup("hello @user: ", 13),
         false,
         "Should not show for invalid mention format"
     );
 }
+
+#[test]
+fn test_message_length() {
+    assert_eq!(message_length(""), 0);
+    assert_eq!(message_length("hello"), 5);
+    assert_eq!(message_length("👍"), 1, "Emoji should count as one");
+    assert_eq!(
+        message_length("👨‍👩‍👧"),
+        1,
+        "Joined emoji should count as one"
+    );
+    assert_eq!(message_length("é"), 1, "Combining marks should count as one");
+}
+
+#[test]
+fn test_expand_macro_at_cursor() {
+    let macros: HashMap<String, String> =
+        HashMap::from([("shrug".to_string(), "¯\\_(ツ)_/¯".to_string())]);
+
+    let mut text = "/shrug".to_string();
+    let cursor = expand_macro_at_cursor(&mut text, 6, &macros);
+    assert_eq!(text, "¯\\_(ツ)_/¯");
+    assert_eq!(cursor, text.len());
+
+    let mut text = "oh well /shrug".to_string();
+    let cursor = expand_macro_at_cursor(&mut text, 14, &macros);
+    assert_eq!(text, "oh well ¯\\_(ツ)_/¯");
+    assert_eq!(cursor, text.len());
+
+    let mut text = "/shrugs".to_string();
+    assert_eq!(expand_macro_at_cursor(&mut text, 7, &macros), 7);
+    assert_eq!(text, "/shrugs", "Unknown macros should pass through");
+
+    let mut text = "a/shrug".to_string();
+    assert_eq!(expand_macro_at_cursor(&mut text, 7, &macros), 7);
+    assert_eq!(text, "a/shrug", "Macro should only expand at the start of a word");
+}
+
+#[test]
+fn test_word_boundaries() {
+    let text = "hello, wörld  foo";
+    assert_eq!(previous_word_boundary(text, text.len()), 15);
+    // skips the spaces and the comma back to the start of the word
+    assert_eq!(previous_word_boundary(text, 15), 7);
+    assert_eq!(previous_word_boundary(text, 7), 0);
+    assert_eq!(previous_word_boundary(text, 0), 0);
+    assert_eq!(next_word_boundary(text, 0), 5);
+    assert_eq!(next_word_boundary(text, 5), 13);
+    assert_eq!(next_word_boundary(text, 13), text.len());
+    // from the middle of a word it goes to that word's edges
+    assert_eq!(previous_word_boundary(text, 3), 0);
+    assert_eq!(next_word_boundary(text, 3), 5);
+}
+
+#[test]
+fn test_grapheme_boundaries_step_over_emoji() {
+    let family = "👨\u{200d}👩\u{200d}👧";
+    let flag = "🇫🇷";
+    let mut text = String::from("hi");
+    // type the emoji in the middle, the way a paste would
+    let mut cursor = 1;
+    text.insert_str(cursor, family);
+    cursor += family.len();
+    assert_eq!(text, format!("h{}i", family));
+
+    // one Left goes over the whole family, not one codepoint of it
+    assert_eq!(previous_grapheme_boundary(&text, cursor), 1);
+    assert_eq!(next_grapheme_boundary(&text, 1), cursor);
+    assert_eq!(next_grapheme_boundary(&text, cursor), text.len());
+
+    text.push_str(flag);
+    let end = text.len();
+    assert_eq!(previous_grapheme_boundary(&text, end), end - flag.len());
+    assert_eq!(next_grapheme_boundary(&text, end), end);
+    assert_eq!(previous_grapheme_boundary(&text, 0), 0);
+}
+
+#[test]
+fn test_cursor_screen_offset() {
+    let text = "a😄b";
+    assert_eq!(cursor_screen_offset(text, 0), (0, 0));
+    assert_eq!(cursor_screen_offset(text, 1), (1, 0));
+    // the emoji is 4 bytes but two cells wide
+    assert_eq!(cursor_screen_offset(text, 5), (3, 0));
+    assert_eq!(cursor_screen_offset(text, text.len()), (4, 0));
+    assert_eq!(cursor_screen_offset("ab\ncd", 4), (1, 1));
+}
+
+#[test]
+fn test_pasted_text_goes_in_literally_at_the_cursor() {
+    let mut text = "hi !".to_string();
+    let cursor = insert_pasted_text(&mut text, 3, "/delete_account\r\nnow");
+    assert_eq!(text, "hi /delete_account\nnow!");
+    assert_eq!(cursor, text.len() - 1);
+}
+
+#[test]
+fn test_completing_the_second_mention_leaves_the_first_alone() {
+    let mut text = "hi @bob how @al".to_string();
+    let end = text.len();
+    let cursor = complete_mention(&mut text, end, "alice");
+    assert_eq!(text, "hi @bob how @alice ");
+    assert_eq!(cursor, text.len());
+}
+
+#[test]
+fn test_completing_a_mention_mid_string() {
+    // cursor right after "@b", the rest of the message stays where it was
+    let mut text = "hi @b how @al".to_string();
+    let cursor = complete_mention(&mut text, 5, "bob");
+    assert_eq!(text, "hi @bob how @al");
+    assert_eq!(&text[cursor..], "how @al");
+
+    // the whole word under the cursor is replaced, not just what's before it
+    let mut text = "@alxx hi".to_string();
+    let cursor = complete_mention(&mut text, 3, "alice");
+    assert_eq!(text, "@alice hi");
+    assert_eq!(cursor, 7);
+}
+
+#[test]
+fn test_completing_multi_byte_names_moves_the_cursor_by_bytes() {
+    let mut text = "yo @jo".to_string();
+    let end = text.len();
+    let cursor = complete_mention(&mut text, end, "józsef");
+    assert_eq!(text, "yo @józsef ");
+    assert_eq!(cursor, text.len());
+}
+
+#[test]
+fn test_mention_query_stops_at_the_cursor() {
+    assert_eq!(get_mention_query("hi @bob how @al", 7), "bob");
+    assert_eq!(get_mention_query("hi @bob how @al", 15), "al");
+    assert_eq!(get_mention_query("mail me@host.com", 16), "");
+    assert!(!should_show_mention_popup("hi @bob how", 11));
+}
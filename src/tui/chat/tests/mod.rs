@@ -1 +1,28 @@
 pub mod message_parsing_test;
+pub mod palette_test;
+pub mod pinned_test;
+pub mod input_history_test;
+pub mod ws_log_test;
+pub mod message_inspector_test;
+pub mod message_notice_test;
+pub mod emoji_picker_test;
+pub mod profile_switcher_test;
+pub mod render_test;
+pub mod clipboard_test;
+pub mod resize_test;
+pub mod scroll_test;
+pub mod flood_guard_test;
+pub mod external_editor_test;
+pub mod copy_mode_test;
+pub mod channel_menu_test;
+pub mod message_details_test;
+pub mod loading_test;
+pub mod timestamps_test;
+pub mod goto_test;
+pub mod icon_picker_test;
+pub mod collapse_test;
+pub mod spoiler_test;
+pub mod mentions_test;
+pub mod commands_test;
+pub mod profile_update_test;
+pub mod paste_test;
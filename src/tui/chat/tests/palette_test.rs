@@ -0,0 +1,25 @@
+use crate::tui::chat::palette::{filtered_entries, fuzzy_score, PaletteAction, PALETTE_ENTRIES};
+
+#[test]
+fn test_fuzzy_score_matches_subsequences() {
+    assert!(fuzzy_score("crch", "Create channel").is_some());
+    assert!(fuzzy_score("CREATE", "create channel").is_some());
+    assert!(fuzzy_score("xyz", "Create channel").is_none());
+    // letters have to show up in order
+    assert!(fuzzy_score("lennahc", "Create channel").is_none());
+}
+
+#[test]
+fn test_fuzzy_score_prefers_consecutive_and_word_starts() {
+    let consecutive = fuzzy_score("up", "Upload file").unwrap();
+    let scattered = fuzzy_score("up", "Toggle reduce motion post").unwrap();
+    assert!(consecutive > scattered);
+}
+
+#[test]
+fn test_filtered_entries() {
+    assert_eq!(filtered_entries("").len(), PALETTE_ENTRIES.len());
+    let entries = filtered_entries("upload");
+    assert_eq!(entries[0].action, PaletteAction::UploadFile);
+    assert!(filtered_entries("qqqq").is_empty());
+}
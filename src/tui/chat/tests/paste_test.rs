@@ -0,0 +1,30 @@
+use crate::app::PopupType;
+
+#[test]
+fn test_confirmation_popups_never_take_a_paste() {
+    // a `y` in the pasted text would answer them
+    for popup_type in [
+        PopupType::Deconnection,
+        PopupType::UnknownCommand,
+        PopupType::DeleteChannel,
+        PopupType::ChannelMenu,
+        PopupType::FileManager,
+        PopupType::None,
+    ] {
+        assert!(!popup_type.takes_text(), "{:?}", popup_type);
+    }
+}
+
+#[test]
+fn test_popups_with_a_text_field_take_a_paste() {
+    for popup_type in [
+        PopupType::CreateChannel,
+        PopupType::Mentions,
+        PopupType::Emojis,
+        PopupType::EmojiPicker,
+        PopupType::Profiles,
+        PopupType::CommandPalette,
+    ] {
+        assert!(popup_type.takes_text(), "{:?}", popup_type);
+    }
+}
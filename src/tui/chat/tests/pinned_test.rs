@@ -0,0 +1,79 @@
+use crate::api::models::Channel;
+use crate::app::app_state::GotoOutcome;
+use crate::app::AppState;
+use crate::tui::chat::message_parsing::pin_preview;
+use crate::tui::chat::pinned_strip::{pinned_strip_height, MAX_PIN_ROWS};
+use crate::tui::tests::harness::{home, home_with, sent_message};
+
+// what the draw would leave behind with `id` at the bottom edge
+fn at_bottom(state: &mut AppState, id: &str) {
+    state.message_at_bottom = Some(format!("client-{}", id));
+}
+
+#[test]
+fn test_pinning_the_bottom_message_twice_unpins_it() {
+    let mut state = home_with(["a", "b"].map(sent_message));
+    assert_eq!(state.toggle_bottom_message_pin(), None);
+
+    at_bottom(&mut state, "b");
+    assert_eq!(state.toggle_bottom_message_pin(), Some(true));
+    let pins = state.current_pins();
+    assert_eq!(pins.len(), 1);
+    assert_eq!(pins[0].id, "b");
+    assert_eq!(pins[0].preview, "message b");
+
+    assert_eq!(state.toggle_bottom_message_pin(), Some(false));
+    assert!(state.current_pins().is_empty());
+    // no empty lists left behind in the config
+    assert!(state.config.pinned_messages.is_empty());
+}
+
+#[test]
+fn test_jumping_between_pins_wraps_and_scrolls_to_them() {
+    let mut state = home_with(["a", "b", "c"].map(sent_message));
+    assert_eq!(state.jump_to_pin(true), None);
+    for id in ["a", "c"] {
+        at_bottom(&mut state, id);
+        state.toggle_bottom_message_pin();
+    }
+
+    assert_eq!(state.jump_to_pin(true), Some(GotoOutcome::Found));
+    assert_eq!(state.scroll_to_message.as_deref(), Some("client-a"));
+    assert_eq!(state.jump_to_pin(true), Some(GotoOutcome::Found));
+    assert_eq!(state.scroll_to_message.as_deref(), Some("client-c"));
+    assert_eq!(state.jump_to_pin(true), Some(GotoOutcome::Found));
+    assert_eq!(state.selected_pin, Some(0));
+    assert_eq!(state.jump_to_pin(false), Some(GotoOutcome::Found));
+    assert_eq!(state.selected_pin, Some(1));
+}
+
+#[test]
+fn test_pins_belong_to_their_channel() {
+    let mut state = home_with(["a"].map(sent_message));
+    at_bottom(&mut state, "a");
+    state.toggle_bottom_message_pin();
+    state.set_current_channel(Channel {
+        id: "games".to_string(),
+        name: "games".to_string(),
+        ..home()
+    });
+    assert!(state.current_pins().is_empty());
+    assert_eq!(state.config.pinned_messages["home"].len(), 1);
+}
+
+#[test]
+fn test_pin_preview_is_the_first_line_without_spoilers() {
+    assert_eq!(
+        pin_preview("the answer is ||42||\nand more"),
+        "the answer is ▒▒▒"
+    );
+    assert_eq!(pin_preview(""), "");
+}
+
+#[test]
+fn test_strip_height() {
+    assert_eq!(pinned_strip_height(0, true), 0);
+    assert_eq!(pinned_strip_height(3, false), 2);
+    assert_eq!(pinned_strip_height(3, true), 5);
+    assert_eq!(pinned_strip_height(50, true), 2 + MAX_PIN_ROWS as u16);
+}
@@ -0,0 +1,46 @@
+use crate::tui::chat::popups::profiles::{ProfileSwitcherOutcome, ProfileSwitcherState};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn press(switcher: &mut ProfileSwitcherState, code: KeyCode) -> ProfileSwitcherOutcome {
+    switcher.handle_key(&KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+fn switcher() -> ProfileSwitcherState {
+    ProfileSwitcherState::new(vec!["default".to_string(), "work".to_string()])
+}
+
+#[test]
+fn test_typing_filters_and_offers_a_new_profile() {
+    let mut switcher = switcher();
+    for c in "wo".chars() {
+        press(&mut switcher, KeyCode::Char(c));
+    }
+    assert_eq!(switcher.rows(), vec!["work".to_string(), "wo".to_string()]);
+    // an exact match isn't offered twice
+    press(&mut switcher, KeyCode::Char('r'));
+    press(&mut switcher, KeyCode::Char('k'));
+    assert_eq!(switcher.rows(), vec!["work".to_string()]);
+}
+
+#[test]
+fn test_characters_that_cant_be_in_a_file_name_are_ignored() {
+    let mut switcher = switcher();
+    for c in "a/b c".chars() {
+        press(&mut switcher, KeyCode::Char(c));
+    }
+    assert_eq!(switcher.query, "abc");
+}
+
+#[test]
+fn test_enter_switches_to_the_selected_row() {
+    let mut switcher = switcher();
+    press(&mut switcher, KeyCode::Down);
+    match press(&mut switcher, KeyCode::Enter) {
+        ProfileSwitcherOutcome::Switch(name) => assert_eq!(name, "work"),
+        _ => panic!("Enter on a row should switch"),
+    }
+    assert!(matches!(
+        press(&mut switcher, KeyCode::Esc),
+        ProfileSwitcherOutcome::Close
+    ));
+}
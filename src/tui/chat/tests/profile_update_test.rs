@@ -0,0 +1,31 @@
+use crate::app::app_state::{AppState, ProfileUpdate};
+
+fn sent(username: &str, icon: &str) -> AppState {
+    AppState {
+        profile_update: ProfileUpdate::Sent {
+            username: username.to_string(),
+            icon: icon.to_string(),
+        },
+        ..AppState::default()
+    }
+}
+
+#[test]
+fn test_unanswered_profile_update_is_given_up() {
+    let mut state = sent("ree", "R");
+    assert!(state.expire_profile_update("ree", "R"));
+    assert!(matches!(state.profile_update, ProfileUpdate::Rejected(_)));
+}
+
+#[test]
+fn test_answered_profile_update_is_left_alone() {
+    let mut state = sent("ree", "R");
+    state.apply_profile("ree".to_string(), "R".to_string());
+    assert!(!state.expire_profile_update("ree", "R"));
+    assert_eq!(state.profile_update, ProfileUpdate::Idle);
+
+    // a newer change sent since isn't the one that timed out
+    let mut state = sent("ree2", "R");
+    assert!(!state.expire_profile_update("ree", "R"));
+    assert!(matches!(state.profile_update, ProfileUpdate::Sent { .. }));
+}
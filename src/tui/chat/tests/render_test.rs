@@ -0,0 +1,279 @@
+use crate::api::models::Channel;
+use crate::app::app_state::{AppState, ChatFocusedPane};
+use crate::themes::rgb_to_color;
+use crate::tui::chat::create_channel_form::CreateChannelForm;
+use crate::tui::chat::ui::draw_chat_ui;
+use crate::tui::chat::{EMOJI_REGEX, MENTION_REGEX};
+use crate::tui::file_manager_module::file_manager::FileManager;
+use crate::tui::settings::state::SettingsState;
+use crate::tui::tests::harness::{buffer_lines, contains_text, find_text, message, render};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::widgets::ListState;
+use std::sync::Arc;
+
+fn channel(id: &str, name: &str) -> Channel {
+    Channel {
+        id: id.to_string(),
+        name: name.to_string(),
+        icon: "#".to_string(),
+        pending: false,
+    }
+}
+
+fn chat_state() -> AppState {
+    let mut state = AppState {
+        channels: vec![channel("home", "home"), channel("games", "games")],
+        channels_loaded: true,
+        ..AppState::default()
+    };
+    state.current_channel = state.channels.first().cloned();
+    state
+}
+
+fn render_chat(width: u16, height: u16, state: &mut AppState, input_text: &str) -> Buffer {
+    let (redraw_tx, _redraw_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut file_manager = FileManager::new(
+        redraw_tx,
+        Arc::new(tokio::sync::Mutex::new(AppState::default())),
+    );
+    let mut create_channel_form = CreateChannelForm::new();
+    let mut channel_list_state = ListState::default();
+    render(width, height, state, |f, state| {
+        let mut settings_state = SettingsState::new(
+            state.themes.keys().cloned().collect(),
+            state.current_theme.name,
+            "",
+            "",
+            state.settings_main_selection,
+            state.settings_focused_pane,
+            state.quit_confirmation_state,
+            state.quit_selection,
+            state.disconnect_confirmation_state,
+            state.disconnect_selection,
+        );
+        draw_chat_ui::<TestBackend>(
+            f,
+            state,
+            input_text,
+            &mut channel_list_state,
+            &mut create_channel_form,
+            &mut file_manager,
+            &MENTION_REGEX,
+            &EMOJI_REGEX,
+            &mut settings_state,
+        );
+    })
+}
+
+#[test]
+fn test_channel_names_are_listed() {
+    let buffer = render_chat(120, 40, &mut chat_state(), "");
+    assert!(contains_text(&buffer, "home"));
+    assert!(contains_text(&buffer, "games"));
+}
+
+#[test]
+fn test_input_box_shows_what_is_typed() {
+    let buffer = render_chat(120, 40, &mut chat_state(), "hello there");
+    assert!(contains_text(&buffer, "Input"));
+    assert!(contains_text(&buffer, "hello there"));
+}
+
+#[test]
+fn test_focused_pane_border_uses_the_focus_color() {
+    let mut state = chat_state();
+    state.chat_focused_pane = ChatFocusedPane::Input;
+    let buffer = render_chat(120, 40, &mut state, "");
+    let colors = &state.current_theme.colors;
+
+    let (x, y) = find_text(&buffer, "Input [focused]").expect("the input pane has focus");
+    assert_eq!(buffer[(x, y)].fg, rgb_to_color(&colors.border_focus));
+    let (x, y) = find_text(&buffer, "Channels").unwrap();
+    assert_eq!(buffer[(x, y)].fg, rgb_to_color(&colors.border));
+    assert!(!contains_text(&buffer, "Channels [focused]"));
+}
+
+#[test]
+fn test_tiny_terminals_dont_panic() {
+    for (width, height) in [(1, 1), (10, 4), (20, 8), (40, 12)] {
+        let buffer = render_chat(width, height, &mut chat_state(), "hi");
+        assert_eq!(buffer_lines(&buffer).len(), height as usize);
+    }
+}
+
+#[test]
+fn test_channel_names_line_up_whatever_the_icon_width() {
+    let mut state = chat_state();
+    state.channels = vec![
+        Channel {
+            icon: "🚀".to_string(),
+            ..channel("launch", "launch")
+        },
+        Channel {
+            icon: "\u{f121}".to_string(),
+            ..channel("code", "code")
+        },
+        channel("lobby", "lobby"),
+    ];
+    state.current_channel = None;
+    let buffer = render_chat(120, 40, &mut state, "");
+    let columns: Vec<u16> = ["launch", "code", "lobby"]
+        .iter()
+        .map(|name| find_text(&buffer, name).expect("every channel is listed").0)
+        .collect();
+    assert!(
+        columns.iter().all(|&column| column == columns[0]),
+        "names start at {:?}",
+        columns
+    );
+}
+
+#[test]
+fn test_pinned_messages_show_above_the_transcript() {
+    let mut state = chat_state();
+    state.config.pinned_messages.insert(
+        "home".to_string(),
+        vec![crate::config::PinnedMessage {
+            id: "1".to_string(),
+            user: "ree".to_string(),
+            preview: "read the rules".to_string(),
+        }],
+    );
+    let buffer = render_chat(120, 40, &mut state, "");
+    assert!(contains_text(&buffer, "1 pinned · ree: read the rules"));
+
+    state.pins_expanded = true;
+    let buffer = render_chat(120, 40, &mut state, "");
+    assert!(contains_text(&buffer, "  ree: read the rules"));
+}
+
+#[test]
+fn test_copy_mode_draws_only_the_transcript() {
+    let mut state = chat_state();
+    let msg = message(serde_json::json!({ "content": "select me" }));
+    state
+        .messages
+        .entry("home".to_string())
+        .or_default()
+        .push_back(msg);
+    state.enter_copy_mode();
+    let buffer = render_chat(80, 10, &mut state, "");
+    let lines = buffer_lines(&buffer);
+    assert!(lines[0].contains("ree: select me"));
+    assert!(lines[0].starts_with('['));
+    // no pane borders or channel list for the terminal's selection to pick up
+    assert!(!contains_text(&buffer, "games"));
+    assert!(!lines.iter().any(|line| line.contains('│')));
+}
+
+#[test]
+fn test_channel_accent_colors_the_messages_border() {
+    let mut state = chat_state();
+    let red = crate::themes::Rgb(235, 87, 87);
+    state.set_channel_accent("home", Some(red));
+    let buffer = render_chat(120, 40, &mut state, "");
+    let (x, y) = find_text(&buffer, "Messages").expect("the pane has a title");
+    assert_eq!(buffer[(x, y)].fg, rgb_to_color(&red));
+
+    state.set_channel_accent("home", None);
+    let buffer = render_chat(120, 40, &mut state, "");
+    let (x, y) = find_text(&buffer, "Messages").expect("the pane has a title");
+    assert_ne!(buffer[(x, y)].fg, rgb_to_color(&red));
+}
+
+#[test]
+fn test_details_panel_shows_beside_the_messages() {
+    let mut state = chat_state();
+    state.config.show_message_details = true;
+    let buffer = render_chat(140, 40, &mut state, "");
+    assert!(contains_text(&buffer, "Details"));
+    assert!(contains_text(&buffer, "No message selected"));
+
+    // too narrow to share, the messages keep the room
+    let buffer = render_chat(80, 40, &mut state, "");
+    assert!(!contains_text(&buffer, "Details"));
+}
+
+#[test]
+fn test_day_header_takes_its_own_row_when_scrolled() {
+    let mut state = chat_state();
+    let day = 86_400;
+    for (i, (timestamp, content)) in [
+        (day, "yesterday".to_string()),
+        (
+            3 * day,
+            (1..=30)
+                .map(|n| format!("line {}", n))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let msg = message(serde_json::json!({
+            "client_id": format!("c{}", i),
+            "content": content,
+            "timestamp": timestamp,
+        }));
+        state
+            .messages
+            .entry("home".to_string())
+            .or_default()
+            .push_back(msg);
+    }
+    let header = chrono::TimeZone::timestamp_opt(&chrono::Utc, 3 * day, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local)
+        .format("── %A, %B %-d ──")
+        .to_string();
+    // which "line N" of the message sits in the row right under the day header
+    let under_header = |state: &mut AppState| {
+        let buffer = render_chat(120, 30, state, "");
+        let (_, y) = find_text(&buffer, &header).expect("the day stays on screen");
+        let row = &buffer_lines(&buffer)[y as usize + 1];
+        let (_, rest) = row
+            .split_once("line ")
+            .expect("a message line under the day");
+        rest.split_whitespace()
+            .next()
+            .unwrap()
+            .parse::<usize>()
+            .unwrap()
+    };
+
+    state.message_scroll_offset = 5;
+    let first = under_header(&mut state);
+    state.message_scroll_offset = 6;
+    // one more line up shows the line before, none is hidden under the header
+    assert_eq!(under_header(&mut state) + 1, first);
+}
+
+#[test]
+fn test_no_color_leaves_every_cell_to_the_terminal() {
+    let mut state = chat_state();
+    state.config.show_message_details = true;
+    let msg = message(serde_json::json!({ "content": "@ree look :smile:" }));
+    state
+        .messages
+        .entry("home".to_string())
+        .or_default()
+        .push_back(msg);
+    crate::themes::set_no_color(true);
+    let buffer = render_chat(140, 40, &mut state, "/help");
+    crate::themes::set_no_color(false);
+
+    let colored: Vec<(u16, u16)> = buffer
+        .content
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| {
+            matches!(cell.fg, ratatui::style::Color::Rgb(..))
+                || matches!(cell.bg, ratatui::style::Color::Rgb(..))
+        })
+        .map(|(i, _)| buffer.pos_of(i))
+        .collect();
+    assert!(colored.is_empty(), "colored cells at {:?}", colored);
+    assert!(contains_text(&buffer, "ree"));
+}
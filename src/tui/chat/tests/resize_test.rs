@@ -0,0 +1,30 @@
+use crate::app::AppState;
+
+#[test]
+fn test_resize_pulls_the_scroll_back_into_range() {
+    let mut state = AppState {
+        total_chat_buffer_length: 100,
+        last_chat_view_height: 20,
+        message_scroll_offset: 80,
+        ..AppState::default()
+    };
+    // the view got taller, 80 lines up would now be past the first message
+    state.last_chat_view_height = 40;
+    state.handle_resize();
+    assert_eq!(state.message_scroll_offset, 60);
+
+    state.total_chat_buffer_length = 10;
+    state.handle_resize();
+    assert_eq!(state.message_scroll_offset, 0);
+}
+
+#[test]
+fn test_resize_drops_lines_wrapped_for_the_old_width() {
+    let mut state = AppState::default();
+    state
+        .rendered_messages
+        .entry("home".to_string())
+        .or_default();
+    state.handle_resize();
+    assert!(state.rendered_messages.is_empty());
+}
@@ -0,0 +1,106 @@
+use crate::app::AppState;
+use crate::tui::chat::ui::visible_line_range;
+use proptest::prelude::*;
+
+fn scrolled_state(total_lines: usize, view_height: usize) -> AppState {
+    AppState {
+        total_chat_buffer_length: total_lines,
+        last_chat_view_height: view_height,
+        ..AppState::default()
+    }
+}
+
+// what the draw does: clamp the offset, then cut the visible lines out
+fn visible_after_clamp(state: &mut AppState) -> std::ops::Range<usize> {
+    state.clamp_message_scroll();
+    visible_line_range(
+        state.total_chat_buffer_length,
+        state.last_chat_view_height,
+        state.message_scroll_offset,
+    )
+}
+
+proptest! {
+    #[test]
+    fn test_visible_lines_are_in_bounds_and_never_empty(
+        total_lines in 0usize..500,
+        view_height in 1usize..80,
+        scrolls in prop::collection::vec((any::<bool>(), 0usize..200), 0..20),
+    ) {
+        let mut state = scrolled_state(total_lines, view_height);
+        for (up, amount) in scrolls {
+            if up {
+                state.scroll_messages_up(amount);
+            } else {
+                state.scroll_messages_down(amount);
+            }
+            let visible = visible_after_clamp(&mut state);
+            prop_assert!(visible.start <= visible.end);
+            prop_assert!(visible.end <= total_lines);
+            prop_assert_eq!(visible.len(), total_lines.min(view_height));
+        }
+    }
+
+    #[test]
+    fn test_scrolling_up_then_down_lands_back_on_the_bottom(
+        total_lines in 0usize..500,
+        view_height in 1usize..80,
+        amounts in prop::collection::vec(0usize..200, 1..10),
+    ) {
+        let mut state = scrolled_state(total_lines, view_height);
+        for &amount in &amounts {
+            state.scroll_messages_up(amount);
+        }
+        for &amount in amounts.iter().rev() {
+            state.scroll_messages_down(amount);
+        }
+        prop_assert_eq!(state.message_scroll_offset, 0);
+        prop_assert_eq!(visible_after_clamp(&mut state).end, total_lines);
+    }
+
+    #[test]
+    fn test_paging_stays_in_range_and_comes_back(
+        total_lines in 0usize..500,
+        view_height in 1usize..80,
+        pages in 1usize..10,
+    ) {
+        let mut state = scrolled_state(total_lines, view_height);
+        for _ in 0..pages {
+            state.scroll_messages_page_up();
+            prop_assert!(
+                state.message_scroll_offset <= total_lines.saturating_sub(view_height)
+            );
+        }
+        for _ in 0..pages {
+            state.scroll_messages_page_down();
+        }
+        prop_assert_eq!(state.message_scroll_offset, 0);
+    }
+
+    #[test]
+    fn test_a_full_scroll_up_shows_the_first_line(
+        total_lines in 1usize..500,
+        view_height in 1usize..80,
+    ) {
+        let mut state = scrolled_state(total_lines, view_height);
+        state.scroll_messages_up(usize::MAX / 2);
+        prop_assert_eq!(visible_after_clamp(&mut state).start, 0);
+    }
+}
+
+#[test]
+fn test_sending_jumps_back_to_the_newest_messages() {
+    let mut state = scrolled_state(200, 20);
+    state.scroll_messages_up(50);
+    state.scroll_to_bottom_after_send();
+    assert_eq!(state.message_scroll_offset, 0);
+}
+
+#[test]
+fn test_sending_keeps_the_scroll_when_turned_off() {
+    let mut state = scrolled_state(200, 20);
+    state.config.scroll_on_send = false;
+    state.scroll_messages_up(50);
+    state.scroll_to_bottom_after_send();
+    assert_eq!(state.message_scroll_offset, 50);
+}
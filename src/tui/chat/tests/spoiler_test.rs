@@ -0,0 +1,75 @@
+use crate::app::AppState;
+use crate::tui::chat::message_parsing::{
+    has_spoilers, split_spoilers, without_spoiler_markers, ContentSegment,
+};
+use crate::tui::tests::harness::{home_with, message};
+
+fn state_with(content: &str) -> AppState {
+    home_with([message(
+        serde_json::json!({ "client_id": "m1", "content": content }),
+    )])
+}
+
+#[test]
+fn test_spoilers_are_split_out() {
+    assert_eq!(
+        split_spoilers("the end: ||they all die|| lol"),
+        vec![
+            ContentSegment {
+                text: "the end: ",
+                spoiler: false
+            },
+            ContentSegment {
+                text: "they all die",
+                spoiler: true
+            },
+            ContentSegment {
+                text: " lol",
+                spoiler: false
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_unpaired_and_empty_markers_stay_literal() {
+    assert!(!has_spoilers("a || b"));
+    assert!(!has_spoilers("||||"));
+    assert_eq!(without_spoiler_markers("a || b"), "a || b");
+    assert_eq!(
+        split_spoilers("||||x||"),
+        vec![
+            ContentSegment {
+                text: "||",
+                spoiler: false
+            },
+            ContentSegment {
+                text: "x",
+                spoiler: true
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_reveal_toggles_the_spoiler_in_view() {
+    let mut state = state_with("||secret||");
+    assert!(!state.toggle_spoiler_in_view());
+
+    state.spoiler_in_view = Some("m1".to_string());
+    assert!(state.toggle_spoiler_in_view());
+    assert!(state.revealed_spoilers.contains("m1"));
+    assert!(state.toggle_spoiler_in_view());
+    assert!(!state.revealed_spoilers.contains("m1"));
+}
+
+#[test]
+fn test_copy_gives_the_text_even_while_hidden() {
+    let mut state = state_with("the end: ||they all die||");
+    state.message_at_bottom = Some("m1".to_string());
+    assert!(state.revealed_spoilers.is_empty());
+    assert_eq!(
+        state.message_at_bottom_text().as_deref(),
+        Some("the end: they all die")
+    );
+}
@@ -0,0 +1,18 @@
+use crate::config::Config;
+use crate::tui::chat::ui::format_timestamp_detail;
+
+#[test]
+fn test_inline_times_are_on_by_default() {
+    assert!(Config::default().show_timestamps);
+    let config: Config = serde_json::from_str("{}").unwrap();
+    assert!(config.show_timestamps);
+}
+
+#[test]
+fn test_detail_has_the_date_and_how_long_ago() {
+    let timestamp = 1_700_000_000;
+    let detail = format_timestamp_detail(timestamp, timestamp + 5 * 60);
+    assert!(detail.ends_with(" · 5 min ago"), "{}", detail);
+    // the year is in there, unlike the day separators
+    assert!(detail.contains("2023"), "{}", detail);
+}
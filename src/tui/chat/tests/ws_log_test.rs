@@ -0,0 +1,54 @@
+use crate::app::app_state::WsFrameDirection;
+use crate::app::AppState;
+
+#[test]
+fn test_ws_log_redacts_the_token() {
+    let mut state = AppState::default();
+    state.config.ws_debug = true;
+    state.auth_token = Some("s3cret".to_string());
+    state.log_ws_frame(WsFrameDirection::Outbound, "s3cret", true);
+    state.log_ws_frame(
+        WsFrameDirection::Inbound,
+        r#"{"token":"s3cret","x":1}"#,
+        false,
+    );
+
+    assert_eq!(state.ws_log[0].text, "<redacted>");
+    assert_eq!(state.ws_log[1].text, r#"{"token":"<redacted>","x":1}"#);
+    assert!(!state.ws_log[1].parsed);
+}
+
+#[test]
+fn test_ws_log_keeps_only_the_newest_frames() {
+    let mut state = AppState::default();
+    state.config.ws_debug = true;
+    for i in 0..250 {
+        state.log_ws_frame(WsFrameDirection::Inbound, &i.to_string(), true);
+    }
+
+    assert_eq!(state.ws_log.len(), 200);
+    assert_eq!(state.ws_log.front().unwrap().text, "50");
+    assert_eq!(state.ws_log.back().unwrap().text, "249");
+}
+
+#[test]
+fn test_ws_log_redacts_password_commands() {
+    let mut state = AppState::default();
+    state.config.ws_debug = true;
+    state.log_ws_frame(
+        WsFrameDirection::Outbound,
+        r#"{"channel_id":"home","content":"/change_password old\"hash new-hash"}"#,
+        true,
+    );
+    state.log_ws_frame(
+        WsFrameDirection::Outbound,
+        r#"{"channel_id":"home","content":"/update_profile ree R"}"#,
+        true,
+    );
+
+    assert_eq!(
+        state.ws_log[0].text,
+        r#"{"channel_id":"home","content":"/change_password <redacted>"}"#
+    );
+    assert!(state.ws_log[1].text.contains("/update_profile ree R"));
+}
@@ -1,19 +1,28 @@
 use crate::api::models::BroadcastMessage;
+use crate::app::app_state::{Thumbnail, GOTO_FLASH};
 use crate::app::{AppState, PopupType};
+use crate::config::CHANNEL_PANE_PERCENT_RANGE;
 use crate::themes::{
-    color_to_rgb,
-    get_contrasting_text_color,
-    interpolate_rgb,
-    rgb_to_color,
-    Theme,
+    color_to_rgb, focus_border_type, get_contrasting_text_color, interpolate_rgb, no_color,
+    no_color_emphasis, rgb_to_color, stripe_color, Rgb, Theme,
 };
+use crate::tui::chat::commands::{command_token, is_known_command};
+use crate::tui::chat::copy_mode::draw_copy_mode;
 use crate::tui::chat::create_channel_form::CreateChannelForm;
 use crate::tui::chat::gif_renderer::GifAnimationState;
+use crate::tui::chat::link_preview::{link_preview_lines, message_link_preview};
+use crate::tui::chat::message_details::{draw_message_details, DETAILS_WIDTH};
+use crate::tui::chat::message_parsing::{
+    cursor_screen_offset, has_spoilers, message_length, split_spoilers,
+};
 use crate::tui::chat::popups::create_channel::{
     draw_create_channel_popup,
     get_create_channel_popup_size,
 };
 
+use crate::tui::chat::popups::channel_menu::{
+    draw_channel_menu_popup, get_channel_menu_popup_size,
+};
 use crate::tui::chat::popups::deconnection::{
     draw_deconnection_popup,
     get_deconnection_popup_size,
@@ -22,20 +31,45 @@ use crate::tui::chat::popups::download_progress::{
     draw_download_progress_popup,
     get_download_progress_popup_size,
 };
+use crate::tui::chat::popups::emoji_picker::get_emoji_picker_popup_size;
 use crate::tui::chat::popups::emojis::{draw_emojis_popup, get_emojis_popup_size};
+use crate::tui::chat::popups::grid_picker::draw_grid_picker;
 use crate::tui::chat::popups::helpers::get_file_manager_popup_size;
 use crate::tui::chat::popups::mentions::{draw_mentions_popup, get_mentions_popup_size};
+use crate::tui::chat::popups::profiles::{draw_profiles_popup, get_profiles_popup_size};
+use crate::tui::chat::popups::command_palette::{
+    draw_command_palette_popup,
+    get_command_palette_popup_size,
+};
+use crate::tui::chat::popups::delete_channel::{
+    draw_delete_channel_popup,
+    get_delete_channel_popup_size,
+};
+use crate::tui::chat::popups::unknown_command::{
+    draw_unknown_command_popup,
+    get_unknown_command_popup_size,
+};
+use crate::tui::chat::pinned_strip::{pinned_strip_height, pinned_strip_lines};
+use crate::tui::chat::popups::message_inspector::{
+    draw_message_inspector_popup, get_message_inspector_popup_size,
+};
+use crate::tui::chat::popups::ws_log::{draw_ws_log_popup, get_ws_log_popup_size};
 
 use crate::tui::file_manager_module::file_manager::FileManager;
+use crate::tui::icons::icon_or;
+use crate::tui::utils::{format_file_size, format_relative_time, pad_to_width};
+use devicons::icon_for_file;
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
 use crate::tui::chat::utils::{centered_rect, get_color_for_user};
 use ansi_to_tui::IntoText as _;
-use chrono::{TimeZone, Utc};
+use chrono::{NaiveDate, TimeZone, Utc};
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+use crate::tui::keymap::draw_keybindings_overlay;
 use crate::tui::notification::ui::draw_notifications;
 use crate::tui::settings;
 use ratatui::Frame;
@@ -56,6 +90,20 @@ pub struct RenderedMessage {
     pub is_last_in_group: bool,
 }
 
+/// How one message is drawn, besides the theme and the width it wraps at.
+#[derive(Debug, Clone, Copy)]
+pub struct MessageRenderOptions<'a> {
+    pub is_first_in_group: bool,
+    pub is_last_in_group: bool,
+    /// the selected attachment, drawn with its thumbnail when there is one
+    pub is_selected: bool,
+    pub thumbnail: Option<&'a Thumbnail>,
+    pub show_timestamp: bool,
+    pub spoilers_revealed: bool,
+    /// the channel's own border color, None for the theme's
+    pub channel_accent: Option<Rgb>,
+}
+
 #[allow(unused_assignments)]
 fn wrap_spans<'a>(spans: Vec<Span<'a>>, max_width: u16) -> Vec<Line<'a>> {
     let mut lines = Vec::new();
@@ -139,18 +187,34 @@ pub fn draw_chat_ui<B: Backend>(
     emoji_regex: &Regex,
     settings_state: &mut settings::state::SettingsState,
 ) {
-    if state
-        .last_rendered_theme
-        .map_or(true, |name| name != state.current_theme.name)
-    {
+    let rendered_with = (
+        state.current_theme.name,
+        no_color(),
+        state.config.show_timestamps,
+    );
+    if state.last_rendered_theme != Some(rendered_with) {
         state.rendered_messages.clear();
-        state.last_rendered_theme = Some(state.current_theme.name);
+        state.last_rendered_theme = Some(rendered_with);
+    }
+    if state.copy_mode {
+        draw_copy_mode(f, state);
+        return;
     }
     let size = f.area();
     let current_theme = state.current_theme.clone();
+    let channel_pane_percent = state.config.channel_pane_percent.clamp(
+        *CHANNEL_PANE_PERCENT_RANGE.start(),
+        *CHANNEL_PANE_PERCENT_RANGE.end(),
+    );
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(20), Constraint::Percentage(80)].as_ref())
+        .constraints(
+            [
+                Constraint::Percentage(channel_pane_percent),
+                Constraint::Percentage(100 - channel_pane_percent),
+            ]
+            .as_ref(),
+        )
         .split(size);
 
     let left_chunks = Layout::default()
@@ -158,51 +222,86 @@ pub fn draw_chat_ui<B: Backend>(
         .constraints([Constraint::Min(0), Constraint::Length(3)])
         .split(chunks[0]);
 
-    let channels_block = Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .title("Channels")
-        .style(
-            Style::default()
-                .fg(
-                    if state.chat_focused_pane
-                        == crate::app::app_state::ChatFocusedPane::ChannelList
-                    {
-                        rgb_to_color(&current_theme.colors.accent)
-                    } else {
-                        rgb_to_color(&current_theme.colors.border)
-                    },
-                )
-                .bg(rgb_to_color(&current_theme.colors.background)),
-        );
+    let channels_block = pane_block(
+        "Channels".to_string(),
+        state.chat_focused_pane == crate::app::app_state::ChatFocusedPane::ChannelList,
+        &current_theme,
+    );
     f.render_widget(channels_block.clone(), left_chunks[0]); // Render the outer block
 
-    let inner_channels_area = channels_block.inner(left_chunks[0]);
+    let mut inner_channels_area = channels_block.inner(left_chunks[0]);
     let item_height = 3; // User requested 3 lines of height per channel button
 
-    let _num_channels = state.channels.len();
+    if let Some(filter) = &state.channel_filter {
+        let filter_line = Line::from(vec![
+            Span::styled(
+                "󰍉 ",
+                Style::default().fg(rgb_to_color(&current_theme.colors.accent)),
+            ),
+            Span::styled(
+                format!("{}▏", filter),
+                Style::default().fg(rgb_to_color(&current_theme.colors.text)),
+            ),
+        ]);
+        f.render_widget(
+            Paragraph::new(filter_line),
+            Rect::new(
+                inner_channels_area.x,
+                inner_channels_area.y,
+                inner_channels_area.width,
+                1.min(inner_channels_area.height),
+            ),
+        );
+        inner_channels_area.y += 1;
+        inner_channels_area.height = inner_channels_area.height.saturating_sub(1);
+    }
+
+    if state.channels_loading() {
+        f.render_widget(
+            Paragraph::new(skeleton_lines(
+                "Loading channels…",
+                inner_channels_area.width,
+                4,
+                &current_theme,
+            )),
+            inner_channels_area,
+        );
+    }
+
+    let visible_channels = state.visible_channel_indices();
     let visible_items_count = (inner_channels_area.height / item_height) as usize;
 
-    // Calculate scroll offset for the channel list
-    let channel_scroll_offset = channel_list_state.selected().unwrap_or(0);
+    // Calculate scroll offset for the channel list, counted in visible channels
+    let channel_scroll_offset = channel_list_state
+        .selected()
+        .and_then(|s| visible_channels.iter().position(|&i| i == s))
+        .unwrap_or(0);
     let start_channel_index = if channel_scroll_offset >= visible_items_count {
         channel_scroll_offset - visible_items_count + 1
     } else {
         0
     };
 
-    for (i, channel) in state
-        .channels
+    // one icon column for every channel, the widest icon sets it so the names line up
+    let icon_column_width = visible_channels
+        .iter()
+        .map(|&i| icon_or(&state.channels[i].icon, "#").width())
+        .max()
+        .unwrap_or(1)
+        .max(1);
+
+    for (row, &i) in visible_channels
         .iter()
         .enumerate()
         .skip(start_channel_index)
         .take(visible_items_count)
     {
+        let channel = &state.channels[i];
         let is_selected = channel_list_state.selected().map_or(false, |s| s == i);
 
         let item_rect = Rect::new(
             inner_channels_area.x,
-            inner_channels_area.y + (i - start_channel_index) as u16 * item_height,
+            inner_channels_area.y + (row - start_channel_index) as u16 * item_height,
             inner_channels_area.width,
             item_height,
         );
@@ -214,7 +313,9 @@ pub fn draw_chat_ui<B: Backend>(
         };
         let border_style = Style::default().fg(border_color);
 
-        let icon_inner_width = channel.icon.width() as u16 + 2;
+        let is_pinned = state.is_channel_pinned(&channel.id);
+        let channel_icon = pad_to_width(icon_or(&channel.icon, "#"), icon_column_width);
+        let icon_inner_width = icon_column_width as u16 + 2;
         let name_inner_width = inner_channels_area
             .width
             .saturating_sub(icon_inner_width + 3);
@@ -228,11 +329,11 @@ pub fn draw_chat_ui<B: Backend>(
         ]);
 
         let channel_name = channel.name.clone();
-        let max_name_width = if name_inner_width > 1 {
-            name_inner_width - 1
-        } else {
-            0
-        } as usize;
+        let max_name_width = name_inner_width.saturating_sub(1) as usize;
+
+        // the pin sits at the right edge of the name box
+        let pin_width = if is_pinned { 2 } else { 0 };
+        let max_name_width = max_name_width.saturating_sub(pin_width);
 
         let mut truncated_name = String::new();
         let mut current_width = 0;
@@ -248,19 +349,31 @@ pub fn draw_chat_ui<B: Backend>(
         let padding_width = max_name_width.saturating_sub(truncated_name.width());
         let padded_name = format!(" {}{}", truncated_name, " ".repeat(padding_width));
 
-        let text_style = if is_selected {
+        let text_style = if channel.pending {
+            // still waiting on the server
+            Style::default()
+                .fg(rgb_to_color(&current_theme.colors.dim))
+                .add_modifier(Modifier::ITALIC)
+        } else if is_selected {
             Style::default().fg(rgb_to_color(&current_theme.colors.accent))
         } else {
             Style::default().fg(rgb_to_color(&current_theme.colors.text))
         };
 
-        let middle_line = Line::from(vec![
+        let mut middle_spans = vec![
             Span::styled("│", border_style),
-            Span::styled(format!(" {} ", channel.icon), text_style),
+            Span::styled(format!(" {} ", channel_icon), text_style),
             Span::styled("│", border_style),
             Span::styled(padded_name, text_style),
-            Span::styled("│", border_style),
-        ]);
+        ];
+        if is_pinned {
+            middle_spans.push(Span::styled(
+                "󰐃 ",
+                Style::default().fg(rgb_to_color(&current_theme.colors.accent)),
+            ));
+        }
+        middle_spans.push(Span::styled("│", border_style));
+        let middle_line = Line::from(middle_spans);
 
         let bottom_border = Line::from(vec![
             Span::styled("╰", border_style),
@@ -309,34 +422,128 @@ pub fn draw_chat_ui<B: Backend>(
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(1), Constraint::Length(3)].as_ref())
         .split(chunks[1]);
-    let messages_block = Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .title(format!(
-            "{}  Messages",
+    // the details panel only takes its columns when the messages keep at least as many
+    let (messages_pane, details_area) = if state.config.show_message_details
+        && chat_chunks[0].width >= DETAILS_WIDTH * 2
+    {
+        let split = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(DETAILS_WIDTH)])
+            .split(chat_chunks[0]);
+        (split[0], Some(split[1]))
+    } else {
+        (chat_chunks[0], None)
+    };
+    let messages_focused =
+        state.chat_focused_pane == crate::app::app_state::ChatFocusedPane::Messages;
+    let mut messages_block = pane_block(
+        format!(
+            "{}  Messages · 󰀉 {}{}",
             state
                 .current_channel
                 .as_ref()
-                .map_or("XXXXXX".to_string(), |c| c.name.clone())
-        ))
-        .style(
-            Style::default()
-                .fg(
-                    if state.chat_focused_pane == crate::app::app_state::ChatFocusedPane::Messages {
-                        rgb_to_color(&current_theme.colors.accent)
-                    } else {
-                        rgb_to_color(&current_theme.colors.text)
-                    },
-                )
-                .bg(rgb_to_color(&current_theme.colors.background)),
-        );
-    let inner_messages_area = messages_block.inner(chat_chunks[0]);
+                .map_or("XXXXXX".to_string(), |c| c.name.clone()),
+            state.config.active_profile,
+            if state.notification_manager.do_not_disturb() {
+                " · 󰂛 DND"
+            } else {
+                ""
+            }
+        ),
+        messages_focused,
+        &current_theme,
+    );
+    // a channel with its own accent wears it on the border, focused or not
+    if let Some(accent) = state
+        .current_channel
+        .as_ref()
+        .and_then(|channel| state.channel_accent(&channel.id))
+    {
+        let accent_style = Style::default().fg(rgb_to_color(&accent));
+        messages_block = messages_block
+            .border_style(accent_style)
+            .title_style(if messages_focused {
+                accent_style.add_modifier(Modifier::BOLD)
+            } else {
+                accent_style
+            });
+    }
+    let inner_messages_area = messages_block.inner(messages_pane);
+    // the reading column, centered in the pane when the user capped the width
+    let inner_messages_area = match state.config.max_message_width {
+        Some(max_width) if max_width < inner_messages_area.width => Rect {
+            x: inner_messages_area.x + (inner_messages_area.width - max_width) / 2,
+            width: max_width,
+            ..inner_messages_area
+        },
+        _ => inner_messages_area,
+    };
     state.chat_width = inner_messages_area.width;
-    f.render_widget(messages_block, chat_chunks[0]);
+    f.render_widget(messages_block, messages_pane);
+    // pinned messages take the top rows, never more than half the pane
+    let strip_height = pinned_strip_height(state.current_pins().len(), state.pins_expanded)
+        .min(inner_messages_area.height / 2);
+    let inner_messages_area = if strip_height > 0 {
+        let strip_area = Rect {
+            height: strip_height,
+            ..inner_messages_area
+        };
+        f.render_widget(
+            Paragraph::new(pinned_strip_lines(
+                state.current_pins(),
+                state.pins_expanded,
+                state.selected_pin,
+                inner_messages_area.width,
+                &current_theme,
+            )),
+            strip_area,
+        );
+        Rect {
+            y: inner_messages_area.y + strip_height,
+            height: inner_messages_area.height - strip_height,
+            ..inner_messages_area
+        }
+    } else {
+        inner_messages_area
+    };
     let current_channel_clone = state.current_channel.clone();
-    if let Some(current_channel) = &current_channel_clone {
+    state.collapsible_in_view = None;
+    state.spoiler_in_view = None;
+    state.message_at_bottom = None;
+    if state.messages_loading() {
+        f.render_widget(
+            Paragraph::new(skeleton_lines(
+                "Loading messages…",
+                inner_messages_area.width,
+                6,
+                &current_theme,
+            )),
+            inner_messages_area,
+        );
+    } else if let Some(current_channel) = &current_channel_clone {
         let channel_id = &current_channel.id;
         let mut all_rendered_lines: Vec<Line<'static>> = Vec::new();
+        // local day of the message each line belongs to, and whether the line is a day separator
+        let mut line_days: Vec<(NaiveDate, bool)> = Vec::new();
+        // first line of each message, to find the one at the bottom of the view
+        let mut message_starts: Vec<(usize, usize)> = Vec::new();
+        if state
+            .message_flash
+            .as_ref()
+            .is_some_and(|(_, at)| at.elapsed() >= GOTO_FLASH)
+        {
+            state.message_flash = None;
+        }
+        let flash_id = state.message_flash.as_ref().map(|(id, _)| id.clone());
+        let mut flash_lines = 0..0;
+        let stripe = stripe_color(&current_theme).filter(|_| state.config.zebra_stripes);
+        // counts message groups, the odd ones get the stripe
+        let mut group_index = 0usize;
+        let collapse_after = state.config.collapse_after_lines;
+        // line range and client_id of every message long enough to collapse
+        let mut collapsible: Vec<(std::ops::Range<usize>, String)> = Vec::new();
+        // same for the messages with a spoiler in them
+        let mut spoilers: Vec<(std::ops::Range<usize>, String)> = Vec::new();
 
         if let Some(messages) = state.messages.get(channel_id) {
             for i in 0..messages.len() {
@@ -384,6 +591,13 @@ pub fn draw_chat_ui<B: Backend>(
                     .get(&message_id);
 
                 if needs_re_render_this_message || rendered_message_entry.is_none() {
+                    let is_selected =
+                        msg.file_id.is_some() && msg.file_id == state.selected_attachment;
+                    let thumbnail = msg
+                        .file_id
+                        .as_ref()
+                        .filter(|_| is_selected)
+                        .and_then(|file_id| state.thumbnails.get(file_id));
                     let rendered_message = format_message_lines(
                         msg,
                         &state.current_theme,
@@ -391,8 +605,15 @@ pub fn draw_chat_ui<B: Backend>(
                         mention_regex,
                         emoji_regex,
                         &state.active_animations,
-                        is_first_in_group,
-                        is_last_in_group,
+                        MessageRenderOptions {
+                            is_first_in_group,
+                            is_last_in_group,
+                            is_selected,
+                            thumbnail,
+                            show_timestamp: state.config.show_timestamps,
+                            spoilers_revealed: state.revealed_spoilers.contains(&message_id),
+                            channel_accent: state.channel_accent(channel_id),
+                        },
                     );
 
                     state
@@ -413,66 +634,222 @@ pub fn draw_chat_ui<B: Backend>(
                     .get(channel_id)
                     .and_then(|channel_map| channel_map.get(&message_id))
                 {
-                    all_rendered_lines.extend(rendered_message.lines.clone());
+                    let day = local_date(msg.timestamp);
+                    if i == 0 || local_date(messages[i - 1].timestamp) != day {
+                        all_rendered_lines.push(format_day_separator(day, &current_theme));
+                        line_days.push((day, true));
+                    } else if messages[i - 1].user != msg.user {
+                        for _ in 0..state.config.message_group_spacing {
+                            all_rendered_lines.push(Line::from(""));
+                            line_days.push((day, false));
+                        }
+                    }
+                    let mut lines = visible_message_lines(
+                        &rendered_message.lines,
+                        collapse_after,
+                        state.expanded_messages.contains(&message_id),
+                        &current_theme,
+                    );
+                    if let Some(preview) = message_link_preview(state, msg) {
+                        lines.extend(link_preview_lines(
+                            preview,
+                            inner_messages_area.width,
+                            &current_theme,
+                        ));
+                    }
+                    let start = all_rendered_lines.len();
+                    let range = start..start + lines.len();
+                    message_starts.push((start, i));
+                    if flash_id.as_ref() == Some(&message_id) {
+                        flash_lines = range.clone();
+                    }
+                    if collapse_after > 0 && rendered_message.lines.len() > collapse_after {
+                        collapsible.push((range.clone(), message_id.clone()));
+                    }
+                    if has_spoilers(&msg.content) {
+                        spoilers.push((range, message_id.clone()));
+                    }
+                    if rendered_message.is_first_in_group && i > 0 {
+                        group_index += 1;
+                    }
+                    line_days.extend(std::iter::repeat_n((day, false), lines.len()));
+                    match stripe.filter(|_| group_index % 2 == 1) {
+                        Some(color) => all_rendered_lines.extend(
+                            lines
+                                .into_iter()
+                                .map(|line| line.patch_style(Style::default().bg(color))),
+                        ),
+                        None => all_rendered_lines.extend(lines),
+                    }
                 }
             }
         }
         state.total_chat_buffer_length = all_rendered_lines.len();
+        let bottom_line;
+        let goto_line = state.scroll_to_message.take().and_then(|id| {
+            let index = state
+                .messages
+                .get(channel_id)?
+                .iter()
+                .position(|m| m.client_id.as_deref() == Some(id.as_str()))?;
+            message_line_offset(&message_starts, index)
+        });
 
         let messages_paragraph = Paragraph::new({
             let message_count = all_rendered_lines.len();
             let view_height = inner_messages_area.height as usize;
             state.last_chat_view_height = view_height;
-
-            let max_offset = message_count.saturating_sub(view_height).max(0);
-            let scroll_offset = state.message_scroll_offset.min(max_offset);
-
-            let start_index = message_count
-                .saturating_sub(view_height)
-                .saturating_sub(scroll_offset)
-                .min(message_count);
-            let end_index = message_count
-                .saturating_sub(scroll_offset)
-                .min(message_count);
-
-            if message_count > view_height {
-                all_rendered_lines[start_index..end_index].to_vec()
-            } else {
-                all_rendered_lines.to_vec()
+            if let Some(line) = goto_line {
+                // one line of room above for the day header row
+                state.message_scroll_offset =
+                    scroll_offset_revealing(line.saturating_sub(1), message_count, view_height);
+            }
+            state.clamp_message_scroll();
+            let scroll_offset = state.message_scroll_offset;
+
+            let std::ops::Range {
+                start: start_index,
+                end: end_index,
+            } = visible_line_range(message_count, view_height, scroll_offset);
+            bottom_line = end_index.checked_sub(1);
+            // after scrolling up the day of the topmost message keeps a row of its own at the
+            // top, the view then has room for one line less of the list
+            let sticky_day = line_days
+                .get(start_index)
+                .filter(|&&(_, is_separator)| message_count > view_height && !is_separator)
+                .map(|&(day, _)| day);
+            let first_line = (start_index + usize::from(sticky_day.is_some())).min(end_index);
+            let lowest_in_view = |ranges: &[(std::ops::Range<usize>, String)]| {
+                ranges
+                    .iter()
+                    .rev()
+                    .find(|(range, _)| range.start < end_index && range.end > first_line)
+                    .map(|(_, message_id)| message_id.clone())
+            };
+            state.collapsible_in_view = lowest_in_view(&collapsible);
+            state.spoiler_in_view = lowest_in_view(&spoilers);
+
+            let mut visible: Vec<Line<'static>> = sticky_day
+                .map(|day| format_day_separator(day, &current_theme))
+                .into_iter()
+                .collect();
+            let header_rows = visible.len();
+            visible.extend_from_slice(&all_rendered_lines[first_line..end_index]);
+            let flash_style = Style::default()
+                .bg(rgb_to_color(&current_theme.colors.mention_bg))
+                .add_modifier(no_color_emphasis(Modifier::REVERSED));
+            for line in flash_lines.clone() {
+                if let Some(line) = line
+                    .checked_sub(first_line)
+                    .and_then(|i| visible.get_mut(i + header_rows))
+                {
+                    *line = std::mem::take(line).patch_style(flash_style);
+                }
             }
+            visible
         })
         .wrap(ratatui::widgets::Wrap { trim: false });
         f.render_widget(messages_paragraph, inner_messages_area);
+        let bottom_index = bottom_line.and_then(|line| {
+            message_starts
+                .iter()
+                .rev()
+                .find(|&&(start, _)| start <= line)
+                .map(|&(_, i)| i)
+        });
+        state.message_at_bottom = bottom_index
+            .and_then(|i| state.messages.get(channel_id)?.get(i)?.client_id.clone());
+
+        // without inline times the message under the view's bottom edge (or the picked
+        // attachment) gets its full time on the pane's bottom border
+        if !state.config.show_timestamps
+            && state.chat_focused_pane == crate::app::app_state::ChatFocusedPane::Messages
+        {
+            let messages = state.messages.get(channel_id);
+            let selected = messages.and_then(|messages| {
+                messages
+                    .iter()
+                    .find(|msg| msg.file_id.is_some() && msg.file_id == state.selected_attachment)
+            });
+            let at_bottom =
+                bottom_index.and_then(|i| messages.and_then(|messages| messages.get(i)));
+            if let Some(msg) = selected.or(at_bottom) {
+                let detail = format_timestamp_detail(msg.timestamp, Utc::now().timestamp());
+                let pane = messages_pane;
+                f.render_widget(
+                    Paragraph::new(Line::from(Span::styled(
+                        format!(" 󰥔 {} ", detail),
+                        Style::default().fg(rgb_to_color(&current_theme.colors.dim)),
+                    )))
+                    .alignment(Alignment::Right),
+                    Rect::new(
+                        pane.x + 2,
+                        pane.bottom().saturating_sub(1),
+                        pane.width.saturating_sub(4),
+                        1.min(pane.height),
+                    ),
+                );
+            }
+        }
     }
-    let input_block = Block::default()
-        .borders(Borders::ALL)
-        .border_type(BorderType::Rounded)
-        .title("Input")
-        .style(
-            Style::default()
-                .fg(
-                    if state.chat_focused_pane == crate::app::app_state::ChatFocusedPane::Input {
-                        rgb_to_color(&current_theme.colors.accent)
-                    } else {
-                        rgb_to_color(&current_theme.colors.input_border_inactive)
-                    },
-                )
-                .bg(rgb_to_color(&current_theme.colors.background)),
+    if let Some(details_area) = details_area {
+        let channel_name = state
+            .current_channel
+            .as_ref()
+            .map_or("", |channel| channel.name.as_str());
+        draw_message_details(
+            f,
+            details_area,
+            state.detail_message(),
+            channel_name,
+            &current_theme,
+            Utc::now().timestamp(),
         );
+    }
+    let mut input_block = pane_block(
+        "Input".to_string(),
+        state.chat_focused_pane == crate::app::app_state::ChatFocusedPane::Input,
+        &current_theme,
+    );
+    let max_length = state.config.max_message_length;
+    if max_length > 0 {
+        let length = message_length(input_text);
+        // only show up once it starts getting close to the limit
+        if length * 10 >= max_length * 8 {
+            let counter_color = if length > max_length {
+                current_theme.colors.error
+            } else {
+                current_theme.colors.warning_color
+            };
+            input_block = input_block.title(
+                Line::from(Span::styled(
+                    format!(" {}/{} ", length, max_length),
+                    Style::default().fg(rgb_to_color(&counter_color)),
+                ))
+                .alignment(Alignment::Right),
+            );
+        }
+    }
     let input_lines = input_text.split('\n').count();
     let input_height = (input_lines as u16 + 2).min(chat_chunks[1].height);
     let input_area = Layout::default()
         .direction(Direction::Vertical)
         .constraints([Constraint::Min(0), Constraint::Length(input_height)])
         .split(chat_chunks[1])[1];
-    let input_paragraph = Paragraph::new(Text::from(input_text))
+    let input_paragraph = Paragraph::new(styled_input_text(
+        input_text,
+        &current_theme,
+        &state.config.macros,
+    ))
         .block(input_block)
         .style(Style::default().fg(rgb_to_color(&current_theme.colors.input_text_active)));
     f.render_widget(input_paragraph, input_area);
 
+    let (cursor_column, cursor_line) =
+        cursor_screen_offset(input_text, state.cursor_position.min(input_text.len()));
     f.set_cursor_position((
-        input_area.x + state.cursor_position as u16 + 1,
-        input_area.y + 1,
+        input_area.x + cursor_column + 1,
+        input_area.y + cursor_line + 1,
     ));
 
     if state.popup_state.show {
@@ -482,8 +859,16 @@ pub fn draw_chat_ui<B: Backend>(
             PopupType::None => "",
             PopupType::Mentions => "",
             PopupType::Emojis => "",
+            PopupType::EmojiPicker => "Emoji",
+            PopupType::Profiles => "Switch Profile",
             PopupType::FileManager => "File Manager",
             PopupType::DownloadProgress => "Downloading",
+            PopupType::UnknownCommand => "Unknown Command",
+            PopupType::DeleteChannel => "Delete Channel",
+            PopupType::ChannelMenu => "Channel Menu",
+            PopupType::CommandPalette => "Command Palette",
+            PopupType::WsLog => "WebSocket Log",
+            PopupType::MessageInspector => "Message Inspector",
 
             PopupType::Downloads => "Downloads",
             PopupType::Notification => "Notification",
@@ -502,11 +887,19 @@ pub fn draw_chat_ui<B: Backend>(
 
         let (popup_width, popup_height) = match state.popup_state.popup_type {
             PopupType::Deconnection => get_deconnection_popup_size(),
-            PopupType::CreateChannel => get_create_channel_popup_size(),
+            PopupType::CreateChannel => get_create_channel_popup_size(create_channel_form),
             PopupType::Mentions => get_mentions_popup_size(state),
             PopupType::Emojis => get_emojis_popup_size(state),
+            PopupType::EmojiPicker => get_emoji_picker_popup_size(),
+            PopupType::Profiles => get_profiles_popup_size(),
             PopupType::FileManager => get_file_manager_popup_size(),
             PopupType::DownloadProgress => get_download_progress_popup_size(),
+            PopupType::UnknownCommand => get_unknown_command_popup_size(),
+            PopupType::DeleteChannel => get_delete_channel_popup_size(state),
+            PopupType::ChannelMenu => get_channel_menu_popup_size(),
+            PopupType::CommandPalette => get_command_palette_popup_size(),
+            PopupType::WsLog => get_ws_log_popup_size(),
+            PopupType::MessageInspector => get_message_inspector_popup_size(),
 
             _ => (0, 0),
         };
@@ -539,6 +932,24 @@ pub fn draw_chat_ui<B: Backend>(
             PopupType::Deconnection => {
                 draw_deconnection_popup(f, state, popup_area, &popup_block_widget);
             }
+            PopupType::UnknownCommand => {
+                draw_unknown_command_popup(f, state, popup_area, &popup_block_widget);
+            }
+            PopupType::DeleteChannel => {
+                draw_delete_channel_popup(f, state, popup_area, &popup_block_widget);
+            }
+            PopupType::ChannelMenu => {
+                draw_channel_menu_popup(f, state, popup_area, &popup_block_widget);
+            }
+            PopupType::CommandPalette => {
+                draw_command_palette_popup(f, state, popup_area, &popup_block_widget);
+            }
+            PopupType::WsLog => {
+                draw_ws_log_popup(f, state, popup_area, &popup_block_widget);
+            }
+            PopupType::MessageInspector => {
+                draw_message_inspector_popup(f, state, popup_area, &popup_block_widget);
+            }
 
             PopupType::Mentions => {
                 draw_mentions_popup(f, state, popup_area, &popup_block_widget);
@@ -546,6 +957,12 @@ pub fn draw_chat_ui<B: Backend>(
             PopupType::Emojis => {
                 draw_emojis_popup(f, state, popup_area, &popup_block_widget);
             }
+            PopupType::EmojiPicker => {
+                draw_grid_picker(f, state, &state.emoji_picker, popup_area, &popup_block_widget);
+            }
+            PopupType::Profiles => {
+                draw_profiles_popup(f, state, popup_area, &popup_block_widget);
+            }
             PopupType::FileManager => {
                 file_manager.ui(f, popup_area, state);
             }
@@ -564,88 +981,179 @@ pub fn draw_chat_ui<B: Backend>(
         settings::render_settings_popup::<B>(f, state, settings_state, f.area()).unwrap();
     }
 
+    draw_keybindings_overlay(f, state);
     draw_notifications(f, state);
 }
 
-pub fn format_message_lines(
-    msg: &BroadcastMessage,
+// how wide each placeholder bar is, in percent of the pane
+const SKELETON_BARS: [usize; 6] = [30, 85, 60, 40, 90, 55];
+
+/// A dim "Loading…" line over `bars` placeholder bars, shown before the first data comes in.
+pub fn skeleton_lines(label: &str, width: u16, bars: usize, theme: &Theme) -> Vec<Line<'static>> {
+    let dim = Style::default().fg(rgb_to_color(&theme.colors.dim));
+    let usable = width.saturating_sub(2) as usize;
+    let mut lines = vec![Line::from(Span::styled(
+        format!(" {}", label),
+        dim.add_modifier(Modifier::ITALIC),
+    ))];
+    for share in SKELETON_BARS.iter().cycle().take(bars) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(" {}", "░".repeat((usable * share / 100).max(1))),
+            dim,
+        )));
+    }
+    lines
+}
+
+/// Border for one of the three chat panes, `border_focus` plus a "[focused]" tag on the one
+/// taking keys so it's obvious where typing goes.
+fn pane_block(title: String, focused: bool, theme: &Theme) -> Block<'static> {
+    let (border_color, title) = if focused {
+        (&theme.colors.border_focus, format!("{} [focused]", title))
+    } else {
+        (&theme.colors.border, title)
+    };
+    let border_style = Style::default().fg(rgb_to_color(border_color));
+    Block::default()
+        .borders(Borders::ALL)
+        .border_type(focus_border_type(focused))
+        .title(title)
+        .border_style(border_style)
+        .title_style(if focused {
+            border_style.add_modifier(Modifier::BOLD)
+        } else {
+            border_style
+        })
+        .style(
+            Style::default()
+                .fg(rgb_to_color(&theme.colors.text))
+                .bg(rgb_to_color(&theme.colors.background)),
+        )
+}
+
+// colors the command token so it's obvious whether a `/something` will be understood
+fn styled_input_text<'a>(
+    input_text: &'a str,
     theme: &Theme,
-    width: u16,
-    mention_regex: &Regex,
-    emoji_regex: &Regex,
-    active_animations: &HashMap<String, Arc<Mutex<GifAnimationState>>>,
-    is_first_in_group: bool,
-    is_last_in_group: bool,
-) -> RenderedMessage {
-    let message_id = msg
-        .file_id
-        .clone()
-        .unwrap_or_else(|| msg.timestamp.to_string());
-    let mut content_lines = Vec::new();
-    let timestamp_str = Utc
-        .timestamp_opt(msg.timestamp, 0)
+    macros: &HashMap<String, String>,
+) -> Text<'a> {
+    let Some(token) = command_token(input_text) else {
+        return Text::from(input_text);
+    };
+    let command_color = if is_known_command(token, macros) {
+        theme.colors.success_color
+    } else {
+        theme.colors.error
+    };
+    let command_end = token.len() + 1;
+    let mut lines: Vec<Line> = input_text[command_end..]
+        .split('\n')
+        .map(Line::from)
+        .collect();
+    lines[0].spans.insert(
+        0,
+        Span::styled(
+            &input_text[..command_end],
+            Style::default().fg(rgb_to_color(&command_color)),
+        ),
+    );
+    Text::from(lines)
+}
+
+fn local_date(timestamp: i64) -> NaiveDate {
+    Utc.timestamp_opt(timestamp, 0)
         .unwrap()
         .with_timezone(&chrono::Local)
-        .format("%H:%M")
-        .to_string();
-    let user_color = get_color_for_user(&msg.user, &theme.colors.username_colors);
-    let border_rgb = theme.colors.dim;
-    let user_rgb = color_to_rgb(user_color).unwrap_or(border_rgb);
+        .date_naive()
+}
 
-    let is_special_message = msg.file_id.is_some() || msg.is_image.unwrap_or(false);
+/// "Tue, Oct 14 2026 14:02:11 · 5 min ago", what a message's time shows when inline times are off.
+pub fn format_timestamp_detail(timestamp: i64, now: i64) -> String {
+    let local = Utc
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .unwrap_or_default()
+        .with_timezone(&chrono::Local);
+    format!(
+        "{} · {}",
+        local.format("%a, %b %-d %Y %H:%M:%S"),
+        format_relative_time(now - timestamp)
+    )
+}
 
-    if msg.is_image.unwrap_or(false) {
-        if let Some(_gif_frames) = &msg.gif_frames {
-            if let Some(file_id) = &msg.file_id {
-                if let Some(animation_state_arc) = active_animations.get(file_id) {
-                    if let Ok(animation_state) = animation_state_arc.try_lock() {
-                        let frame_count = animation_state.frames.len();
-                        let frame_index = animation_state.current_frame;
-                        if frame_count > 0 && frame_index < frame_count {
-                            let current_frame_content = &animation_state.frames[frame_index];
-                            let chafa_text: ratatui::text::Text = current_frame_content
-                                .clone()
-                                .as_str()
-                                .into_text()
-                                .expect("Failed to convert ANSI to Text");
-                            content_lines.extend(chafa_text.lines);
-                        }
-                    }
-                }
-            }
-        } else if let Some(image_preview) = &msg.image_preview {
-            let chafa_text: ratatui::text::Text = image_preview
-                .clone()
-                .into_text()
-                .expect("Failed to convert ANSI to Text");
-            content_lines.extend(chafa_text.lines);
-        }
-    } else if msg.file_id.is_some() {
-        content_lines.push(Line::from(vec![Span::styled(
-            format!(
-                "{} {}.{} 󰋊 {} MB",
-                msg.file_icon.as_deref().unwrap_or("󱧸"),
-                msg.file_name.as_deref().unwrap_or("Unknown"),
-                msg.file_extension.as_deref().unwrap_or(""),
-                msg.file_size_mb.unwrap_or(0.0)
-            ),
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.accent))
-                .add_modifier(Modifier::BOLD),
-        )]));
-        content_lines.push(Line::from(vec![Span::styled(
+/// What a message shows in the transcript. Past `collapse_after` lines (0 never collapses)
+/// that's its start and a "show more" hint, or everything and a "show less" one once expanded.
+pub fn visible_message_lines(
+    lines: &[Line<'static>],
+    collapse_after: usize,
+    expanded: bool,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
+    if collapse_after == 0 || lines.len() <= collapse_after {
+        return lines.to_vec();
+    }
+    let hint_style = Style::default()
+        .fg(rgb_to_color(&theme.colors.dim))
+        .add_modifier(Modifier::ITALIC);
+    let (shown, hint) = if expanded {
+        (lines, "  … show less (Enter)".to_string())
+    } else {
+        (
+            &lines[..collapse_after],
             format!(
-                "Download with: /download {}",
-                msg.file_id.as_deref().unwrap_or("")
+                "  … show more (Enter), {} more lines",
+                lines.len() - collapse_after
             ),
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.dim))
-                .add_modifier(Modifier::ITALIC),
-        )]));
-    }
+        )
+    };
+    let mut visible = shown.to_vec();
+    visible.push(Line::from(Span::styled(hint, hint_style)));
+    visible
+}
 
-    let mut message_content_spans: Vec<Span> = Vec::new();
-    let mut current_text_slice = msg.content.as_str();
+/// First line of the `index`th message within the formatted transcript, from the
+/// (first line, message index) pairs collected while laying it out.
+pub fn message_line_offset(message_starts: &[(usize, usize)], index: usize) -> Option<usize> {
+    message_starts
+        .iter()
+        .find(|&&(_, i)| i == index)
+        .map(|&(start, _)| start)
+}
+
+/// The `message_scroll_offset` (lines up from the bottom) that puts `line` at the top of the
+/// view, or as close as the transcript allows.
+pub fn scroll_offset_revealing(line: usize, total_lines: usize, view_height: usize) -> usize {
+    total_lines.saturating_sub(line + view_height)
+}
+
+/// The transcript lines on screen, `scroll_offset` lines up from the bottom of `total_lines`.
+pub fn visible_line_range(
+    total_lines: usize,
+    view_height: usize,
+    scroll_offset: usize,
+) -> std::ops::Range<usize> {
+    let end = total_lines.saturating_sub(scroll_offset);
+    end.saturating_sub(view_height)..end
+}
+
+fn format_day_separator(day: NaiveDate, theme: &Theme) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("── {} ──", day.format("%A, %B %-d")),
+        Style::default().fg(rgb_to_color(&theme.colors.dim)),
+    ))
+    .alignment(Alignment::Center)
+}
+
+/// Text, mention chips and emoji of one stretch of a message.
+fn content_spans(
+    text: &str,
+    theme: &Theme,
+    mention_regex: &Regex,
+    emoji_regex: &Regex,
+) -> Vec<Span<'static>> {
+    let mut message_content_spans: Vec<Span<'static>> = Vec::new();
+    let mut current_text_slice = text;
     while !current_text_slice.is_empty() {
         if let Some(mention_match) = mention_regex.find(current_text_slice) {
             let (before_mention, after_mention) =
@@ -678,7 +1186,8 @@ pub fn format_message_lines(
                 mention_match.as_str().to_string(),
                 Style::default()
                     .fg(get_contrasting_text_color(&theme.colors.mention_bg))
-                    .bg(rgb_to_color(&theme.colors.mention_bg)),
+                    .bg(rgb_to_color(&theme.colors.mention_bg))
+                    .add_modifier(no_color_emphasis(Modifier::BOLD | Modifier::UNDERLINED)),
             ));
             message_content_spans.push(Span::styled(
                 "",
@@ -709,6 +1218,166 @@ pub fn format_message_lines(
             current_text_slice = "";
         }
     }
+    message_content_spans
+}
+
+/// A spoiler that's still hidden: a bar in one color, so nothing shows through. Without colors
+/// that can't be done, the text is swapped for blocks instead.
+fn hide_spoiler(spans: Vec<Span<'static>>, theme: &Theme) -> Vec<Span<'static>> {
+    let bar = rgb_to_color(&theme.colors.dim);
+    let style = Style::default().fg(bar).bg(bar);
+    spans
+        .into_iter()
+        .map(|span| {
+            if no_color() {
+                Span::styled("█".repeat(span.content.width()), style)
+            } else {
+                Span::styled(span.content, style)
+            }
+        })
+        .collect()
+}
+
+pub fn format_message_lines(
+    msg: &BroadcastMessage,
+    theme: &Theme,
+    width: u16,
+    mention_regex: &Regex,
+    emoji_regex: &Regex,
+    active_animations: &HashMap<String, Arc<Mutex<GifAnimationState>>>,
+    options: MessageRenderOptions,
+) -> RenderedMessage {
+    let MessageRenderOptions {
+        is_first_in_group,
+        is_last_in_group,
+        is_selected,
+        thumbnail,
+        show_timestamp,
+        spoilers_revealed,
+        channel_accent,
+    } = options;
+    let message_id = msg
+        .file_id
+        .clone()
+        .unwrap_or_else(|| msg.timestamp.to_string());
+    let mut content_lines = Vec::new();
+    let timestamp_str = Utc
+        .timestamp_opt(msg.timestamp, 0)
+        .unwrap()
+        .with_timezone(&chrono::Local)
+        .format("%H:%M")
+        .to_string();
+    let user_color = get_color_for_user(&msg.user, theme);
+    // the header box fades from the author's color into this
+    let border_rgb = channel_accent.unwrap_or(theme.colors.dim);
+    let user_rgb = color_to_rgb(user_color).unwrap_or(border_rgb);
+
+    let is_special_message = msg.file_id.is_some() || msg.is_image.unwrap_or(false);
+
+    if msg.is_image.unwrap_or(false) {
+        if let Some(_gif_frames) = &msg.gif_frames {
+            if let Some(file_id) = &msg.file_id {
+                if let Some(animation_state_arc) = active_animations.get(file_id) {
+                    if let Ok(animation_state) = animation_state_arc.try_lock() {
+                        let frame_count = animation_state.frames.len();
+                        let frame_index = animation_state.current_frame;
+                        if frame_count > 0 && frame_index < frame_count {
+                            let current_frame_content = &animation_state.frames[frame_index];
+                            let chafa_text: ratatui::text::Text = current_frame_content
+                                .clone()
+                                .as_str()
+                                .into_text()
+                                .expect("Failed to convert ANSI to Text");
+                            content_lines.extend(chafa_text.lines);
+                        }
+                    }
+                }
+            }
+        } else if let Some(image_preview) = &msg.image_preview {
+            let chafa_text: ratatui::text::Text = image_preview
+                .clone()
+                .into_text()
+                .expect("Failed to convert ANSI to Text");
+            content_lines.extend(chafa_text.lines);
+        }
+    } else if msg.file_id.is_some() {
+        let file_name = msg.file_name.as_deref().unwrap_or("Unknown");
+        let extension = msg.file_extension.as_deref().unwrap_or("");
+        // the server's icon when it sent one, otherwise one picked from the extension
+        let icon = msg.file_icon.clone().unwrap_or_else(|| {
+            icon_for_file(
+                Path::new(&format!("{}.{}", file_name, extension)),
+                &Some(devicons::Theme::Dark),
+            )
+            .icon
+            .to_string()
+        });
+        // a little pill so attachments stand out from the chatter around them
+        let chip_bg = if is_selected {
+            theme.colors.accent
+        } else {
+            theme.colors.dim
+        };
+        let chip_edge = Style::default().fg(rgb_to_color(&chip_bg));
+        let size = msg
+            .file_size()
+            .map(|size| format!(" {}", format_file_size(size)))
+            .unwrap_or_default();
+        content_lines.push(Line::from(vec![
+            Span::styled("", chip_edge),
+            Span::styled(
+                format!("{} {}.{}{}", icon, file_name, extension, size),
+                Style::default()
+                    .fg(get_contrasting_text_color(&chip_bg))
+                    .bg(rgb_to_color(&chip_bg))
+                    .add_modifier(Modifier::BOLD | no_color_emphasis(Modifier::REVERSED)),
+            ),
+            Span::styled("", chip_edge),
+        ]));
+        let dim_italic = Style::default()
+            .fg(rgb_to_color(&theme.colors.dim))
+            .add_modifier(Modifier::ITALIC);
+        match thumbnail {
+            Some(Thumbnail::Ready(ansi)) => {
+                if let Ok(text) = ansi.as_str().into_text() {
+                    content_lines.extend(
+                        text.lines
+                            .into_iter()
+                            .take(crate::tui::chat::image_handler::THUMBNAIL_HEIGHT as usize),
+                    );
+                }
+            }
+            Some(Thumbnail::Loading) => {
+                content_lines.push(Line::from(Span::styled("Loading preview...", dim_italic)));
+            }
+            Some(Thumbnail::Failed(reason)) => {
+                content_lines.push(Line::from(Span::styled(
+                    format!("No preview: {}", reason.lines().next().unwrap_or_default()),
+                    dim_italic,
+                )));
+            }
+            None => {}
+        }
+        let hint = if is_selected {
+            "Enter or d to download".to_string()
+        } else {
+            format!(
+                "Download with: /download {}",
+                msg.file_id.as_deref().unwrap_or("")
+            )
+        };
+        content_lines.push(Line::from(vec![Span::styled(hint, dim_italic)]));
+    }
+
+    let mut message_content_spans: Vec<Span> = Vec::new();
+    for segment in split_spoilers(&msg.content) {
+        let spans = content_spans(segment.text, theme, mention_regex, emoji_regex);
+        if segment.spoiler && !spoilers_revealed {
+            message_content_spans.extend(hide_spoiler(spans, theme));
+        } else {
+            message_content_spans.extend(spans);
+        }
+    }
 
     if !is_special_message {
         let available_text_width = width.saturating_sub(4);
@@ -719,7 +1388,12 @@ pub fn format_message_lines(
     let available_width = width as usize;
 
     if is_first_in_group {
-        let user_info_str = format!("{} {}", msg.icon, msg.user.as_str());
+        // icons are padded to two cells so names start in the same column either way
+        let user_info_str = format!(
+            "{} {}",
+            pad_to_width(icon_or(&msg.icon, "@"), 2),
+            msg.user.as_str()
+        );
         let user_info_width = user_info_str.width();
         let user_box_width = user_info_width + 4;
 
@@ -775,17 +1449,19 @@ pub fn format_message_lines(
             current_col += c.width().unwrap_or(1);
         }
 
-        let user_line_width = user_line_spans.iter().map(|s| s.width()).sum::<usize>();
-        let timestamp_width = timestamp_str.len();
+        if show_timestamp {
+            let user_line_width = user_line_spans.iter().map(|s| s.width()).sum::<usize>();
+            let timestamp_width = timestamp_str.len();
 
-        if available_width > user_line_width + timestamp_width {
-            let padding = available_width - user_line_width - timestamp_width;
-            user_line_spans.push(Span::raw(" ".repeat(padding)));
+            if available_width > user_line_width + timestamp_width {
+                let padding = available_width - user_line_width - timestamp_width;
+                user_line_spans.push(Span::raw(" ".repeat(padding)));
+            }
+            user_line_spans.push(Span::styled(
+                timestamp_str.clone(),
+                Style::default().fg(rgb_to_color(&theme.colors.dim)),
+            ));
         }
-        user_line_spans.push(Span::styled(
-            timestamp_str.clone(),
-            Style::default().fg(rgb_to_color(&theme.colors.dim)),
-        ));
         new_lines.push(Line::from(user_line_spans));
 
         let separator_left_str = format!("├{}┴", "─".repeat(user_box_width - 2));
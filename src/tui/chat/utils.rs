@@ -1,25 +1,13 @@
-use crate::themes::Rgb;
+use crate::config::ClipboardMode;
+use crate::themes::{rgb_to_color, Theme};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::Color;
-use std::hash::{Hash, Hasher};
-// E
-pub fn get_color_for_user(username: &str, colors: &Vec<Rgb>) -> Color {
-    if colors.is_empty() {
-        let mut hasher = std::collections::hash_map::DefaultHasher::new();
-        username.hash(&mut hasher);
-        let hash = hasher.finish();
-        let r = (hash & 0xFF) as u8;
-        let g = ((hash >> 8) & 0xFF) as u8;
-        let b = ((hash >> 16) & 0xFF) as u8;
-        return Color::Rgb(r, g, b);
-    }
+use std::io::Write;
 
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    username.hash(&mut hasher);
-    let hash = hasher.finish();
-    let index = (hash as usize) % colors.len();
-    let rgb = colors[index];
-    Color::Rgb(rgb.0, rgb.1, rgb.2)
+pub fn get_color_for_user(username: &str, theme: &Theme) -> Color {
+    rgb_to_color(&theme.user_color(username))
 }
 
 pub fn centered_rect(width: u16, height: u16, r: Rect) -> Rect {
@@ -96,3 +84,42 @@ pub fn centered_rect_with_size_and_padding(
         ])
         .split(popup_layout)[1]
 }
+
+/// Puts `text` on the clipboard, the error is already worded for a notification. Over SSH the
+/// system clipboard is the remote machine's, so `Auto` asks the terminal instead (OSC 52), and
+/// it does the same when the system clipboard can't be reached.
+pub fn copy_to_clipboard(text: &str, mode: ClipboardMode) -> Result<(), String> {
+    if mode == ClipboardMode::Osc52 || in_ssh_session() {
+        return copy_with_osc52(text);
+    }
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .or_else(|native| {
+            copy_with_osc52(text)
+                .map_err(|e| format!("Couldn't reach the clipboard: {} ({})", native, e))
+        })
+}
+
+fn in_ssh_session() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+/// OSC 52 "set clipboard" for `text`. tmux only hands it on to the outer terminal inside its
+/// passthrough sequence (and with `allow-passthrough on`).
+pub fn osc52_sequence(text: &str, in_tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+    if in_tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+// there's no answer to wait for, whether it landed is up to the terminal
+fn copy_with_osc52(text: &str) -> Result<(), String> {
+    let mut stdout = std::io::stdout();
+    stdout
+        .write_all(osc52_sequence(text, std::env::var_os("TMUX").is_some()).as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|e| format!("Couldn't send the text to the terminal: {}", e))
+}
@@ -14,5 +14,10 @@ pub enum WsCommand {
         file_id: String,
         file_name: String,
     },
+    /// every attachment of a channel, as (file_id, file_name), one after the other
+    DownloadAll {
+        files: Vec<(String, String)>,
+        target_dir: PathBuf,
+    },
     Pong,
 }
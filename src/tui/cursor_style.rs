@@ -0,0 +1,49 @@
+use crate::config::CursorShape;
+use crossterm::{cursor::SetCursorStyle, execute};
+
+/// The escape for the input's cursor. `Default` hands the choice back to the terminal,
+/// blinking included.
+pub fn cursor_style(shape: CursorShape, blink: bool) -> SetCursorStyle {
+    match (shape, blink) {
+        (CursorShape::Default, _) => SetCursorStyle::DefaultUserShape,
+        (CursorShape::Block, true) => SetCursorStyle::BlinkingBlock,
+        (CursorShape::Block, false) => SetCursorStyle::SteadyBlock,
+        (CursorShape::Bar, true) => SetCursorStyle::BlinkingBar,
+        (CursorShape::Bar, false) => SetCursorStyle::SteadyBar,
+        (CursorShape::Underline, true) => SetCursorStyle::BlinkingUnderScore,
+        (CursorShape::Underline, false) => SetCursorStyle::SteadyUnderScore,
+    }
+}
+
+/// Sends the cursor style only when it changed, the escape would otherwise go out every frame.
+#[derive(Default)]
+pub struct CursorStyleState {
+    applied: Option<(CursorShape, bool)>,
+}
+
+impl CursorStyleState {
+    pub fn apply(&mut self, shape: CursorShape, blink: bool) -> std::io::Result<()> {
+        if self.applied != Some((shape, blink)) {
+            execute!(std::io::stdout(), cursor_style(shape, blink))?;
+            self.applied = Some((shape, blink));
+        }
+        Ok(())
+    }
+
+    /// Something else had the terminal (an editor), the next `apply` sends it again.
+    pub fn forget(&mut self) {
+        self.applied = None;
+    }
+}
+
+// however the page is left, early returns and errors included, the shell gets its own back
+impl Drop for CursorStyleState {
+    fn drop(&mut self) {
+        if self
+            .applied
+            .is_some_and(|(shape, _)| shape != CursorShape::Default)
+        {
+            let _ = execute!(std::io::stdout(), SetCursorStyle::DefaultUserShape);
+        }
+    }
+}
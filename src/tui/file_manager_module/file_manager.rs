@@ -1,8 +1,10 @@
 use crate::app::AppState;
-use crate::themes::rgb_to_color;
+use crate::themes::{chafa_color_args, highlight_style, rgb_to_color, terminal_color};
+use crate::tui::notification::notification::SPINNER_FRAMES;
+use crate::tui::utils::format_file_size;
 use ansi_to_tui::IntoText;
 use chrono::{DateTime, Local};
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use devicons::{icon_for_file, FileIcon, Theme};
 use image::{GenericImageView, ImageReader};
 use ratatui::{
@@ -15,8 +17,9 @@ use std::{
     io::{BufRead, BufReader, Read},
     path::{Path, PathBuf},
     process::Command,
+    sync::atomic::{AtomicBool, Ordering},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use syntect::{
     easy::HighlightLines,
@@ -27,10 +30,13 @@ use tokio::io::AsyncWriteExt;
 use tokio::process::Command as TokioCommand;
 use tokio::sync::mpsc;
 use tokio::time::sleep;
+use unicode_width::UnicodeWidthStr;
 
 pub enum FileManagerEvent {
     FileSelectedForUpload(PathBuf),
     CloseFileManager,
+    InvalidPath(String),
+    BookmarksChanged(Vec<PathBuf>),
     None,
 }
 
@@ -69,6 +75,8 @@ pub struct FileManager {
     preview_rx: mpsc::UnboundedReceiver<(PathBuf, Result<Text<'static>, String>)>,
     preview_cache: HashMap<PathBuf, Result<Text<'static>, String>>,
     generating_preview_for: Option<PathBuf>,
+    preview_started: Option<Instant>,
+    preview_redraw_pending: Arc<AtomicBool>,
     syntax_set: Arc<SyntaxSet>,
     theme_set: Arc<ThemeSet>,
     metadata_tx: mpsc::UnboundedSender<(PathBuf, Result<String, String>)>,
@@ -79,7 +87,19 @@ pub struct FileManager {
     gif_rx: mpsc::UnboundedReceiver<(PathBuf, Result<Vec<(Text<'static>, u32)>, String>)>,
     gif_cache: HashMap<PathBuf, Result<Vec<(Text<'static>, u32)>, String>>,
     current_gif_frame: HashMap<PathBuf, usize>,
+    // when the frame currently on screen was first shown, to know when the next one is due
+    gif_frame_started: Option<Instant>,
+    // only one scheduled redraw in flight, so extra redraws don't speed the GIF up
+    gif_redraw_pending: Arc<AtomicBool>,
+    // the GIF the user paused and the frame they stepped to, separate from the auto-advance
+    paused_gif: Option<(PathBuf, usize)>,
     last_rendered_height: u16,
+    // Some while the user is typing a path to jump to
+    path_input: Option<String>,
+    // copy of config.file_bookmarks, refreshed every draw
+    bookmarks: Vec<PathBuf>,
+    // Some(selected) while the bookmark quick-pick is open
+    bookmark_picker: Option<usize>,
 }
 
 impl FileManager {
@@ -112,6 +132,8 @@ impl FileManager {
             preview_rx,
             preview_cache: HashMap::new(),
             generating_preview_for: None,
+            preview_started: None,
+            preview_redraw_pending: Arc::new(AtomicBool::new(false)),
             syntax_set: Arc::new(SyntaxSet::load_defaults_newlines()),
             theme_set: Arc::new(ThemeSet::load_defaults()),
             metadata_tx,
@@ -122,7 +144,13 @@ impl FileManager {
             gif_rx,
             gif_cache: HashMap::new(),
             current_gif_frame: HashMap::new(),
+            gif_frame_started: None,
+            gif_redraw_pending: Arc::new(AtomicBool::new(false)),
+            paused_gif: None,
             last_rendered_height: 0,
+            path_input: None,
+            bookmarks: Vec::new(),
+            bookmark_picker: None,
         }
     }
 
@@ -152,6 +180,7 @@ impl FileManager {
     }
 
     pub fn ui(&mut self, f: &mut Frame, area: Rect, state: &AppState) {
+        self.bookmarks = state.config.file_bookmarks.clone();
         let theme = &state.current_theme;
 
         let chunks = Layout::default()
@@ -159,9 +188,18 @@ impl FileManager {
             .constraints([Constraint::Percentage(25), Constraint::Percentage(75)].as_ref())
             .split(area);
 
-        let left_area = chunks[0];
+        let mut left_area = chunks[0];
         let right_area = chunks[1];
 
+        if let Some(input) = &self.path_input {
+            let left_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
+                .split(left_area);
+            left_area = left_chunks[0];
+            self.draw_path_input(f, left_chunks[1], input, theme);
+        }
+
         let main_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([Constraint::Percentage(80), Constraint::Percentage(20)].as_ref())
@@ -197,11 +235,9 @@ impl FileManager {
             let y = inner_left_area.y + (i - start_index) as u16;
             let is_selected = i == self.selected_index;
             let style = if is_selected {
-                Style::default()
-                    .fg(rgb_to_color(&theme.colors.background))
-                    .bg(rgb_to_color(&theme.colors.accent))
+                highlight_style(&theme.colors.background, &theme.colors.accent)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(terminal_color(Color::White))
             };
             f.buffer_mut()
                 .set_line(inner_left_area.x, y, line, inner_left_area.width);
@@ -236,11 +272,14 @@ impl FileManager {
             if let Some(Ok(frames_with_delays)) = self.gif_cache.get(&path) {
                 if !frames_with_delays.is_empty() {
                     self.current_gif_frame.insert(path, 0);
+                    self.gif_frame_started = None;
                 }
             }
         }
 
         let mut gif_frame_to_update: Option<(PathBuf, usize)> = None;
+        let gif_frame_started = self.gif_frame_started;
+        let mut start_gif_clock = false;
 
         if let Some(item) = self.get_selected_item() {
             if item.is_dir {
@@ -269,8 +308,15 @@ impl FileManager {
                 if let Some(cached_gif) = self.gif_cache.get(&item.path) {
                     match cached_gif {
                         Ok(frames_with_delays) => {
-                            let current_frame_index =
-                                *self.current_gif_frame.get(&item.path).unwrap_or(&0);
+                            let frame_count = frames_with_delays.len();
+                            let paused_frame = self
+                                .paused_gif
+                                .as_ref()
+                                .filter(|(path, _)| path == &item.path)
+                                .map(|(_, frame)| *frame);
+                            let current_frame_index = paused_frame.unwrap_or_else(|| {
+                                *self.current_gif_frame.get(&item.path).unwrap_or(&0)
+                            });
                             if let Some((frame_text, delay_ms_ref)) =
                                 frames_with_delays.get(current_frame_index)
                             {
@@ -280,33 +326,73 @@ impl FileManager {
                                 );
                                 f.render_widget(p, inner_preview_area);
 
-                                if frames_with_delays.len() > 1 {
-                                    // Prepare update for next frame
-                                    let next_frame_index =
-                                        (current_frame_index + 1) % frames_with_delays.len();
-                                    gif_frame_to_update =
-                                        Some((item.path.clone(), next_frame_index));
+                                if frame_count > 1 {
+                                    Self::draw_gif_overlay(
+                                        f,
+                                        inner_preview_area,
+                                        paused_frame.is_some(),
+                                        current_frame_index,
+                                        frame_count,
+                                        theme,
+                                    );
+                                }
 
-                                    // Schedule redraw for next frame
-                                    let redraw_tx_clone = self.redraw_tx.clone();
-                                    tokio::spawn(async move {
-                                        sleep(Duration::from_millis(delay_ms as u64)).await;
-                                        let _ = redraw_tx_clone.send("redraw".to_string());
+                                if frame_count > 1
+                                    && paused_frame.is_none()
+                                    && !state.config.reduce_motion
+                                {
+                                    // a 0 delay means "as fast as u can", browsers use 100ms for that
+                                    let delay = Duration::from_millis(if delay_ms == 0 {
+                                        100
+                                    } else {
+                                        delay_ms as u64
                                     });
+                                    let elapsed =
+                                        gif_frame_started.map_or(Duration::ZERO, |t| t.elapsed());
+                                    if gif_frame_started.is_none() {
+                                        start_gif_clock = true;
+                                    } else if elapsed >= delay {
+                                        gif_frame_to_update = Some((
+                                            item.path.clone(),
+                                            (current_frame_index + 1) % frame_count,
+                                        ));
+                                    }
+
+                                    // Schedule redraw for next frame
+                                    if !self.gif_redraw_pending.swap(true, Ordering::SeqCst) {
+                                        let wait = delay.saturating_sub(elapsed);
+                                        let pending = Arc::clone(&self.gif_redraw_pending);
+                                        let redraw_tx_clone = self.redraw_tx.clone();
+                                        tokio::spawn(async move {
+                                            sleep(wait).await;
+                                            pending.store(false, Ordering::SeqCst);
+                                            let _ = redraw_tx_clone.send("redraw".to_string());
+                                        });
+                                    }
                                 }
                             } else {
                                 let p = Paragraph::new("GIF frame error").style(
                                     Style::default()
-                                        .fg(Color::Red)
+                                        .fg(terminal_color(Color::Red))
                                         .bg(rgb_to_color(&theme.colors.background)),
                                 );
                                 f.render_widget(p, inner_preview_area);
                             }
                         }
+                        Err(e) if e == GIF_TOO_LARGE => {
+                            let p = Paragraph::new(e.as_str())
+                                .alignment(Alignment::Center)
+                                .style(
+                                    Style::default()
+                                        .fg(rgb_to_color(&theme.colors.dim))
+                                        .bg(rgb_to_color(&theme.colors.background)),
+                                );
+                            f.render_widget(p, inner_preview_area);
+                        }
                         Err(e) => {
                             let p = Paragraph::new(format!("GIF error: {}", e)).style(
                                 Style::default()
-                                    .fg(Color::Red)
+                                    .fg(terminal_color(Color::Red))
                                     .bg(rgb_to_color(&theme.colors.background)),
                             );
                             f.render_widget(p, inner_preview_area);
@@ -322,7 +408,7 @@ impl FileManager {
                         Err(e) => {
                             let p = Paragraph::new(format!("Preview error: {}", e)).style(
                                 Style::default()
-                                    .fg(Color::Red)
+                                    .fg(terminal_color(Color::Red))
                                     .bg(rgb_to_color(&theme.colors.background)),
                             );
                             f.render_widget(p, inner_preview_area);
@@ -332,6 +418,7 @@ impl FileManager {
                     let path = item.path.clone();
                     if self.generating_preview_for.as_ref() != Some(&path) {
                         self.generating_preview_for = Some(path.clone());
+                        self.preview_started = Some(Instant::now());
                         let tx = self.preview_tx.clone();
                         let gif_tx = self.gif_tx.clone();
                         let syntax_set = Arc::clone(&self.syntax_set);
@@ -355,6 +442,7 @@ impl FileManager {
                                 } else {
                                     // Handle other images with chafa
                                     let cmd = TokioCommand::new("chafa")
+                                        .args(chafa_color_args())
                                         .arg("-f")
                                         .arg("symbols")
                                         .arg("--symbols")
@@ -431,8 +519,8 @@ impl FileManager {
                                                     let color = style.foreground;
                                                     Span::styled(
                                                         text.to_string(),
-                                                        Style::default().fg(Color::Rgb(
-                                                            color.r, color.g, color.b,
+                                                        Style::default().fg(terminal_color(
+                                                            Color::Rgb(color.r, color.g, color.b),
                                                         )),
                                                     )
                                                 })
@@ -448,9 +536,12 @@ impl FileManager {
                             let _ = tx.send((path, result));
                         });
                     }
-                    let p = Paragraph::new("Generating preview...")
-                        .style(Style::default().bg(rgb_to_color(&theme.colors.background)));
-                    f.render_widget(p, inner_preview_area);
+                    self.draw_preview_progress(
+                        f,
+                        inner_preview_area,
+                        theme,
+                        state.config.reduce_motion,
+                    );
                 }
             }
         } else {
@@ -462,6 +553,10 @@ impl FileManager {
         // Apply GIF frame update after rendering
         if let Some((path, next_frame_index)) = gif_frame_to_update {
             self.current_gif_frame.insert(path, next_frame_index);
+            start_gif_clock = true;
+        }
+        if start_gif_clock {
+            self.gif_frame_started = Some(Instant::now());
         }
 
         // Metadata block
@@ -496,8 +591,19 @@ impl FileManager {
 
             let mut metadata_info = HashMap::new();
 
-            if item.is_dir {
-                metadata_info.insert("Type".to_string(), "Directory".to_string());
+            if item.is_dir && !self.metadata_cache.contains_key(&item.path) {
+                // the entry count and size come from a bounded walk, kept off the async workers
+                let path = item.path.clone();
+                if self.generating_metadata_for.as_ref() != Some(&path) {
+                    self.generating_metadata_for = Some(path.clone());
+                    let tx = self.metadata_tx.clone();
+
+                    tokio::task::spawn_blocking(move || {
+                        let result = directory_metadata(&path);
+                        let _ = tx.send((path, result));
+                    });
+                }
+                metadata_info.insert("Status".to_string(), "Loading metadata...".to_string());
             } else {
                 if let Some(cached) = self.metadata_cache.get(&item.path) {
                     match cached {
@@ -526,7 +632,7 @@ impl FileManager {
                                     let mut info = String::new();
                                     info.push_str(&format!(
                                         "Size: {}\n",
-                                        FileManager::format_file_size(metadata.len())
+                                        format_file_size(metadata.len())
                                     ));
                                     if let Ok(created) = metadata.created() {
                                         info.push_str(&format!(
@@ -631,9 +737,7 @@ impl FileManager {
                                     if metadata.len() > FileManager::MAX_UPLOAD_SIZE_BYTES {
                                         info.push_str(&format!(
                                             "Too Big to Send: Yes (> {}\n)",
-                                            FileManager::format_file_size(
-                                                FileManager::MAX_UPLOAD_SIZE_BYTES
-                                            )
+                                            format_file_size(FileManager::MAX_UPLOAD_SIZE_BYTES)
                                         ));
                                     } else {
                                         info.push_str("Too Big to Send: No\n");
@@ -726,6 +830,10 @@ impl FileManager {
                 .style(Style::default().bg(rgb_to_color(&theme.colors.background)));
             f.render_widget(p, inner_metadata_area);
         }
+
+        if let Some(selected) = self.bookmark_picker {
+            self.draw_bookmark_picker(f, area, selected, theme);
+        }
     }
 
     fn is_likely_binary(path: &Path) -> bool {
@@ -742,15 +850,9 @@ impl FileManager {
     }
 
     fn is_image(path: &Path) -> bool {
-        let extension = path.extension().and_then(|s| s.to_str());
-        if let Some(ext) = extension {
-            matches!(
-                ext.to_lowercase().as_str(),
-                "png" | "jpg" | "jpeg" | "gif" | "bmp"
-            )
-        } else {
-            false
-        }
+        path.extension()
+            .and_then(|s| s.to_str())
+            .is_some_and(crate::tui::chat::image_handler::is_image_extension)
     }
 
     fn render_tree<'a>(
@@ -764,7 +866,7 @@ impl FileManager {
         let prefix = " ".repeat(depth * 2);
         let icon_span = if item.is_dir {
             let folder_icon = if item.expanded { "" } else { "" };
-            let folder_style = Style::default().fg(Color::White);
+            let folder_style = Style::default().fg(terminal_color(Color::White));
             Span::styled(folder_icon.to_string(), folder_style)
         } else {
             let icon_str = item.icon.icon.to_string();
@@ -778,7 +880,10 @@ impl FileManager {
             let r = ((color_u32 >> 16) & 0xFF) as u8;
             let g = ((color_u32 >> 8) & 0xFF) as u8;
             let b = (color_u32 & 0xFF) as u8;
-            Span::styled(file_icon, Style::default().fg(Color::Rgb(r, g, b)))
+            Span::styled(
+                file_icon,
+                Style::default().fg(terminal_color(Color::Rgb(r, g, b))),
+            )
         };
 
         let file_name = if item.is_parent_nav {
@@ -792,12 +897,19 @@ impl FileManager {
         };
         let file_style = Style::default().fg(rgb_to_color(&theme.colors.text));
 
-        lines.push(Line::from(vec![
+        let mut spans = vec![
             Span::raw(prefix.clone()),
             icon_span.clone(),
             Span::raw(" "),
             Span::styled(file_name.to_string(), file_style),
-        ]));
+        ];
+        if item.is_dir && !item.is_parent_nav && self.bookmarks.contains(&item.path) {
+            spans.push(Span::styled(
+                " ",
+                Style::default().fg(rgb_to_color(&theme.colors.warning_color)),
+            ));
+        }
+        lines.push(Line::from(spans));
         displayed_items.push(item.path.clone());
 
         if item.expanded {
@@ -807,7 +919,352 @@ impl FileManager {
         }
     }
 
+    // spinner + bouncing bar while chafa/syntect chew on the file, so it doesn't look frozen
+    fn draw_preview_progress(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        theme: &crate::themes::Theme,
+        reduce_motion: bool,
+    ) {
+        let background = Style::default().bg(rgb_to_color(&theme.colors.background));
+        if reduce_motion || area.height == 0 {
+            f.render_widget(
+                Paragraph::new("Generating preview...").style(background),
+                area,
+            );
+            return;
+        }
+
+        let tick = self
+            .preview_started
+            .map_or(0, |started| started.elapsed().as_millis() / 80) as usize;
+        let spinner = SPINNER_FRAMES[tick % SPINNER_FRAMES.len()];
+        let bar_width = area.width.min(30) as usize;
+        let block_width = (bar_width / 4).max(1);
+        let travel = bar_width - block_width;
+        // back and forth instead of wrapping, looks less like a glitch
+        let offset = if travel == 0 {
+            0
+        } else {
+            let step = tick % (travel * 2);
+            if step > travel {
+                travel * 2 - step
+            } else {
+                step
+            }
+        };
+        let bar = Line::from(vec![
+            Span::styled(
+                "─".repeat(offset),
+                Style::default().fg(rgb_to_color(&theme.colors.dim)),
+            ),
+            Span::styled(
+                "━".repeat(block_width),
+                Style::default().fg(rgb_to_color(&theme.colors.accent)),
+            ),
+            Span::styled(
+                "─".repeat(travel - offset),
+                Style::default().fg(rgb_to_color(&theme.colors.dim)),
+            ),
+        ]);
+        let text = Text::from(vec![
+            Line::from(vec![
+                Span::styled(
+                    spinner,
+                    Style::default().fg(rgb_to_color(&theme.colors.loading_color)),
+                ),
+                Span::raw(" Generating preview..."),
+            ]),
+            bar,
+        ]);
+        f.render_widget(Paragraph::new(text).style(background), area);
+
+        if !self.preview_redraw_pending.swap(true, Ordering::SeqCst) {
+            let pending = Arc::clone(&self.preview_redraw_pending);
+            let redraw_tx = self.redraw_tx.clone();
+            tokio::spawn(async move {
+                sleep(Duration::from_millis(80)).await;
+                pending.store(false, Ordering::SeqCst);
+                let _ = redraw_tx.send("redraw".to_string());
+            });
+        }
+    }
+
+    fn draw_path_input(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        input: &str,
+        theme: &crate::themes::Theme,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(rgb_to_color(&theme.colors.accent)))
+            .title(Span::styled(
+                " Go to ",
+                Style::default().fg(rgb_to_color(&theme.colors.text)),
+            ));
+        let inner = block.inner(area);
+        let line = if input.is_empty() {
+            Line::from(Span::styled(
+                self.tree.path.display().to_string(),
+                Style::default().fg(rgb_to_color(&theme.colors.dim)),
+            ))
+        } else {
+            Line::from(Span::styled(
+                input.to_string(),
+                Style::default().fg(rgb_to_color(&theme.colors.text)),
+            ))
+        };
+        // keep the end of long paths visible, that's the part being typed
+        let input_width = UnicodeWidthStr::width(input) as u16;
+        let scroll = input_width.saturating_sub(inner.width.saturating_sub(1));
+        f.render_widget(Clear, area);
+        f.render_widget(block, area);
+        f.render_widget(Paragraph::new(line).scroll((0, scroll)), inner);
+        f.set_cursor_position((inner.x + input_width - scroll, inner.y));
+    }
+
+    fn handle_path_input_key(&mut self, key: KeyEvent) -> FileManagerEvent {
+        let Some(input) = self.path_input.as_mut() else {
+            return FileManagerEvent::None;
+        };
+        match key.code {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => self.path_input = None,
+            KeyCode::Enter => {
+                let typed = input.trim().to_string();
+                if typed.is_empty() {
+                    self.path_input = None;
+                    return FileManagerEvent::None;
+                }
+                let path = expand_tilde(&typed);
+                if path.is_dir() {
+                    self.path_input = None;
+                    self.change_root(path);
+                } else if path.exists() {
+                    return FileManagerEvent::InvalidPath(format!("{} is not a directory", typed));
+                } else {
+                    return FileManagerEvent::InvalidPath(format!("{} doesn't exist", typed));
+                }
+            }
+            _ => {}
+        }
+        FileManagerEvent::None
+    }
+
+    fn draw_bookmark_picker(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        selected: usize,
+        theme: &crate::themes::Theme,
+    ) {
+        let width = area.width.min(60);
+        let height = (self.bookmarks.len().max(1) as u16 + 4).min(area.height);
+        let picker_area = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(rgb_to_color(&theme.colors.accent)))
+            .title(Span::styled(
+                " Bookmarks  ",
+                Style::default().fg(rgb_to_color(&theme.colors.text)),
+            ))
+            .title_bottom(Span::styled(
+                " Enter go · d remove · Esc close ",
+                Style::default().fg(rgb_to_color(&theme.colors.dim)),
+            ))
+            .style(Style::default().bg(rgb_to_color(&theme.colors.background)));
+        let inner = block.inner(picker_area);
+        f.render_widget(Clear, picker_area);
+        f.render_widget(block, picker_area);
+
+        if self.bookmarks.is_empty() {
+            let p = Paragraph::new("No bookmarks yet, press b in a folder to add it")
+                .style(Style::default().fg(rgb_to_color(&theme.colors.dim)));
+            f.render_widget(p, inner);
+            return;
+        }
+
+        let lines: Vec<Line> = self
+            .bookmarks
+            .iter()
+            .enumerate()
+            .map(|(i, path)| {
+                let exists = path.is_dir();
+                let mut style = if exists {
+                    Style::default().fg(rgb_to_color(&theme.colors.text))
+                } else {
+                    Style::default().fg(rgb_to_color(&theme.colors.dim))
+                };
+                if i == selected {
+                    style = highlight_style(&theme.colors.background, &theme.colors.accent);
+                }
+                let mut label = format!(" {}", path.display());
+                if !exists {
+                    label.push_str(" (missing)");
+                }
+                Line::from(Span::styled(label, style))
+            })
+            .collect();
+        let scroll = selected.saturating_sub(inner.height.saturating_sub(1) as usize) as u16;
+        f.render_widget(Paragraph::new(lines).scroll((scroll, 0)), inner);
+    }
+
+    fn toggle_bookmark(&mut self) -> FileManagerEvent {
+        let path = self.tree.path.clone();
+        if let Some(pos) = self.bookmarks.iter().position(|b| b == &path) {
+            self.bookmarks.remove(pos);
+        } else {
+            self.bookmarks.push(path);
+        }
+        FileManagerEvent::BookmarksChanged(self.bookmarks.clone())
+    }
+
+    fn handle_bookmark_picker_key(&mut self, key: KeyEvent) -> FileManagerEvent {
+        let Some(selected) = self.bookmark_picker else {
+            return FileManagerEvent::None;
+        };
+        match key.code {
+            KeyCode::Up => self.bookmark_picker = Some(selected.saturating_sub(1)),
+            KeyCode::Down => {
+                let last = self.bookmarks.len().saturating_sub(1);
+                self.bookmark_picker = Some((selected + 1).min(last));
+            }
+            KeyCode::Esc | KeyCode::Char('\'') => self.bookmark_picker = None,
+            KeyCode::Char('d') | KeyCode::Delete if selected < self.bookmarks.len() => {
+                self.bookmarks.remove(selected);
+                let last = self.bookmarks.len().saturating_sub(1);
+                self.bookmark_picker = Some(selected.min(last));
+                return FileManagerEvent::BookmarksChanged(self.bookmarks.clone());
+            }
+            KeyCode::Enter => {
+                if let Some(path) = self.bookmarks.get(selected).cloned() {
+                    if path.is_dir() {
+                        self.bookmark_picker = None;
+                        self.change_root(path);
+                    } else {
+                        return FileManagerEvent::InvalidPath(format!(
+                            "{} is gone, press d to remove the bookmark",
+                            path.display()
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        FileManagerEvent::None
+    }
+
+    fn draw_gif_overlay(
+        f: &mut Frame,
+        area: Rect,
+        paused: bool,
+        frame_index: usize,
+        frame_count: usize,
+        theme: &crate::themes::Theme,
+    ) {
+        let label = format!(
+            " {} frame {}/{} ",
+            if paused { "⏸" } else { "▶" },
+            frame_index + 1,
+            frame_count
+        );
+        let width = (label.chars().count() as u16).min(area.width);
+        let overlay_area = Rect::new(
+            area.x + area.width.saturating_sub(width),
+            area.y + area.height.saturating_sub(1),
+            width,
+            1.min(area.height),
+        );
+        let p = Paragraph::new(label).style(highlight_style(
+            &theme.colors.background,
+            &theme.colors.accent,
+        ));
+        f.render_widget(Clear, overlay_area);
+        f.render_widget(p, overlay_area);
+    }
+
+    // space pauses/resumes, left/right step while paused, r restarts. true when the key was used.
+    fn handle_gif_playback_key(&mut self, code: KeyCode) -> bool {
+        let Some(path) = self.get_selected_item().map(|item| item.path.clone()) else {
+            return false;
+        };
+        let frame_count = match self.gif_cache.get(&path) {
+            Some(Ok(frames)) if frames.len() > 1 => frames.len(),
+            _ => return false,
+        };
+        let paused_frame = self
+            .paused_gif
+            .as_ref()
+            .filter(|(paused_path, _)| paused_path == &path)
+            .map(|(_, frame)| *frame);
+
+        match (code, paused_frame) {
+            (KeyCode::Char(' '), None) => {
+                let frame = *self.current_gif_frame.get(&path).unwrap_or(&0);
+                self.paused_gif = Some((path, frame));
+            }
+            (KeyCode::Char(' '), Some(frame)) => {
+                // carry on from wherever the user stepped to
+                self.current_gif_frame.insert(path, frame);
+                self.paused_gif = None;
+                self.gif_frame_started = None;
+            }
+            (KeyCode::Right, Some(frame)) => {
+                self.paused_gif = Some((path, (frame + 1) % frame_count));
+            }
+            (KeyCode::Left, Some(frame)) => {
+                self.paused_gif = Some((path, (frame + frame_count - 1) % frame_count));
+            }
+            (KeyCode::Char('r'), paused_frame) => {
+                self.current_gif_frame.insert(path.clone(), 0);
+                self.gif_frame_started = None;
+                if paused_frame.is_some() {
+                    self.paused_gif = Some((path, 0));
+                }
+            }
+            _ => return false,
+        }
+        let _ = self.redraw_tx.send("redraw".to_string());
+        true
+    }
+
     pub fn handle_key_event(&mut self, key: KeyEvent) -> FileManagerEvent {
+        if self.path_input.is_some() {
+            return self.handle_path_input_key(key);
+        }
+        if self.bookmark_picker.is_some() {
+            return self.handle_bookmark_picker_key(key);
+        }
+        match key.code {
+            KeyCode::Char('b') => return self.toggle_bookmark(),
+            KeyCode::Char('\'') => {
+                self.bookmark_picker = Some(0);
+                return FileManagerEvent::None;
+            }
+            _ => {}
+        }
+        if key.code == KeyCode::Char(':')
+            || (key.code == KeyCode::Char('l') && key.modifiers.contains(KeyModifiers::CONTROL))
+        {
+            self.path_input = Some(String::new());
+            return FileManagerEvent::None;
+        }
+        if self.handle_gif_playback_key(key.code) {
+            return FileManagerEvent::None;
+        }
         match key.code {
             KeyCode::Up => self.select_previous(),
             KeyCode::Down => self.select_next(),
@@ -815,14 +1272,25 @@ impl FileManager {
             KeyCode::PageDown => self.page_down(),
             KeyCode::Right => self.expand_dir(),
             KeyCode::Left => self.collapse_dir(),
+            KeyCode::Home | KeyCode::Char('g') => self.selected_index = 0,
+            KeyCode::End | KeyCode::Char('G') => {
+                self.selected_index = self.displayed_items.len().saturating_sub(1)
+            }
+            KeyCode::Char('~') => {
+                if let Some(home) = dirs::home_dir() {
+                    self.change_root(home);
+                }
+            }
+            KeyCode::Backspace => {
+                if let Some(parent) = self.tree.path.parent().map(Path::to_path_buf) {
+                    self.change_root(parent);
+                }
+            }
             KeyCode::Enter => {
-                if let Some(item) = self.get_selected_item_mut() {
+                if let Some(item) = self.get_selected_item() {
                     if item.is_dir {
                         let new_root_path = item.path.clone();
-                        self.tree = FileItem::new(new_root_path, true, false);
-                        Self::read_dir(&mut self.tree);
-                        self.tree.expanded = true;
-                        self.selected_index = 0;
+                        self.change_root(new_root_path);
                     } else {
                         return FileManagerEvent::FileSelectedForUpload(item.path.clone());
                     }
@@ -834,6 +1302,27 @@ impl FileManager {
         FileManagerEvent::None
     }
 
+    /// Previews were made for the old size, and the selection has to stay on the list.
+    pub fn handle_resize(&mut self) {
+        self.preview_cache.clear();
+        self.gif_cache.clear();
+        self.current_gif_frame.clear();
+        self.gif_frame_started = None;
+        self.selected_index = self
+            .selected_index
+            .min(self.displayed_items.len().saturating_sub(1));
+        if let Some(selected) = self.bookmark_picker.as_mut() {
+            *selected = (*selected).min(self.bookmarks.len().saturating_sub(1));
+        }
+    }
+
+    fn change_root(&mut self, path: PathBuf) {
+        self.tree = FileItem::new(path, true, false);
+        Self::read_dir(&mut self.tree);
+        self.tree.expanded = true;
+        self.selected_index = 0;
+    }
+
     fn get_selected_item_mut(&mut self) -> Option<&mut FileItem> {
         if self.selected_index >= self.displayed_items.len() {
             return None;
@@ -927,6 +1416,96 @@ impl FileManager {
     }
 }
 
+// "~" and "~/something" point into the home directory, like in a shell
+fn expand_tilde(input: &str) -> PathBuf {
+    if let Some(home) = dirs::home_dir() {
+        if input == "~" {
+            return home;
+        }
+        if let Some(rest) = input.strip_prefix("~/") {
+            return home.join(rest);
+        }
+    }
+    PathBuf::from(input)
+}
+
+// Walking a directory for its size stops at whichever of these runs out first,
+// so selecting ~ or / doesn't grind the disk.
+const DIR_SIZE_MAX_DEPTH: usize = 4;
+const DIR_SIZE_MAX_ENTRIES: usize = 20_000;
+const DIR_SIZE_TIME_BUDGET: Duration = Duration::from_millis(500);
+
+// Same "Key: value" lines as the file metadata, with the child count and a bounded total size.
+fn directory_metadata(path: &Path) -> Result<String, String> {
+    let metadata = fs::metadata(path).map_err(|e| format!("Failed to get metadata: {}", e))?;
+    let child_count = fs::read_dir(path)
+        .map_err(|e| format!("Failed to read directory: {}", e))?
+        .count();
+
+    let started = Instant::now();
+    let mut total_size = 0u64;
+    let mut visited = 0usize;
+    let mut complete = true;
+    let mut pending = vec![(path.to_path_buf(), 0usize)];
+    'walk: while let Some((dir, depth)) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            visited += 1;
+            if visited > DIR_SIZE_MAX_ENTRIES || started.elapsed() > DIR_SIZE_TIME_BUDGET {
+                complete = false;
+                break 'walk;
+            }
+            // symlinks aren't followed, they could loop forever
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                if depth + 1 < DIR_SIZE_MAX_DEPTH {
+                    pending.push((entry.path(), depth + 1));
+                } else {
+                    complete = false;
+                }
+            } else if file_type.is_file() {
+                total_size += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+    }
+
+    let mut info = String::new();
+    info.push_str(&format!(
+        "Size: {}{}\n",
+        if complete { "" } else { "at least " },
+        format_file_size(total_size)
+    ));
+    if let Ok(created) = metadata.created() {
+        info.push_str(&format!(
+            "Created: {}\n",
+            DateTime::<Local>::from(created).format("%Y-%m-%d %H:%M:%S")
+        ));
+    }
+    if let Ok(modified) = metadata.modified() {
+        info.push_str(&format!(
+            "Last Modified: {}\n",
+            DateTime::<Local>::from(modified).format("%Y-%m-%d %H:%M:%S")
+        ));
+    }
+    info.push_str(&format!(
+        "Type: Directory ({} {})\n",
+        child_count,
+        if child_count == 1 { "entry" } else { "entries" }
+    ));
+    info.push_str(&format!("Permissions: {:?}\n", metadata.permissions()));
+    Ok(info)
+}
+
+// Every GIF frame goes through its own chafa run, so big animations have to be cut short
+// or the preview sits there for ages.
+const MAX_GIF_FRAMES: usize = 100;
+const MAX_GIF_PIXELS: u32 = 1920 * 1080;
+const GIF_TOO_LARGE: &str = "GIF too large to preview 󰘖";
+
 async fn decode_gif_frames(
     path: &Path,
     width: u16,
@@ -941,100 +1520,104 @@ async fn decode_gif_frames(
 
     let mut frames_with_delays = Vec::new();
     let (gif_width, gif_height) = (decoder.width() as u32, decoder.height() as u32);
+    if gif_width.saturating_mul(gif_height) > MAX_GIF_PIXELS {
+        return Err(GIF_TOO_LARGE.to_string());
+    }
 
-    if let Some(frame) = decoder
-        .read_next_frame()
-        .map_err(|e| format!("Failed to read GIF frame: {}", e))?
-    {
-        let frame = frame.to_owned();
-        let mut image_buffer = image::RgbaImage::new(gif_width, gif_height);
+    // frames only cover the part that changed, so they get drawn on top of the previous ones
+    let mut image_buffer = image::RgbaImage::new(gif_width, gif_height);
+    while frames_with_delays.len() < MAX_GIF_FRAMES {
+        let frame = match decoder.read_next_frame() {
+            Ok(Some(frame)) => frame.to_owned(),
+            Ok(None) => break,
+            // a broken frame halfway through still leaves us something to play
+            Err(_) if !frames_with_delays.is_empty() => break,
+            Err(e) => return Err(format!("Failed to read GIF frame: {}", e)),
+        };
+        let Some(frame_image) = image::RgbaImage::from_raw(
+            frame.width as u32,
+            frame.height as u32,
+            frame.buffer.to_vec(),
+        ) else {
+            break;
+        };
         // Copy the frame data into the correct position within the full buffer
         image::imageops::overlay(
             &mut image_buffer,
-            &image::RgbaImage::from_raw(
-                frame.width as u32,
-                frame.height as u32,
-                frame.buffer.to_vec(),
-            )
-            .unwrap(),
+            &frame_image,
             frame.left as i64,
             frame.top as i64,
         );
 
-        // Encode image_buffer to PNG in memory
-        let mut png_bytes = Vec::new();
-        image_buffer
-            .write_to(
-                &mut std::io::Cursor::new(&mut png_bytes),
-                image::ImageFormat::Png,
-            )
-            .map_err(|e| format!("Failed to encode GIF frame to PNG: {}", e))?;
-
-        let mut cmd = TokioCommand::new("chafa");
-        cmd.arg("-f")
-            .arg("symbols")
-            .arg(format!("--size={}x{}", width, height))
-            .stdin(std::process::Stdio::piped())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped());
-
-        let mut child = cmd
-            .spawn()
-            .map_err(|e| format!("Failed to spawn chafa: {}", e))?;
-
-        // Write PNG bytes to chafa's stdin
-        if let Some(mut stdin) = child.stdin.take() {
-            stdin
-                .write_all(&png_bytes)
-                .await
-                .map_err(|e| format!("Failed to write to chafa stdin: {}", e))?;
-            // Close stdin to signal EOF to chafa
-            drop(stdin);
-        } else {
-            return Err("Failed to get chafa stdin".to_string());
-        }
-
-        let output = child
-            .wait_with_output()
-            .await
-            .map_err(|e| format!("Failed to wait for chafa: {}", e))?;
-
-        if output.status.success() {
-            let mut chafa_string = String::from_utf8_lossy(&output.stdout).to_string();
-            #[cfg(windows)]
-            {
-                chafa_string = chafa_string.replace("\n", "\r\n");
+        match render_gif_frame(&image_buffer, width, height).await {
+            Ok(text_frame) => {
+                let delay_ms = frame.delay as u32 * 10; // Convert 1/100s to ms
+                frames_with_delays.push((text_frame, delay_ms));
             }
-            let text_frame = chafa_string
-                .into_text()
-                .map_err(|e| format!("Failed to convert ANSI to Text: {:?}", e))?;
-            let delay_ms = frame.delay as u32 * 10; // Convert 1/100s to ms
-            frames_with_delays.push((text_frame, delay_ms));
-        } else {
-            return Err(format!(
-                "Chafa error: {}",
-                String::from_utf8_lossy(&output.stderr)
-            ));
+            Err(_) if !frames_with_delays.is_empty() => break,
+            Err(e) => return Err(e),
         }
     }
     Ok(frames_with_delays)
 }
 
-impl FileManager {
-    fn format_file_size(size: u64) -> String {
-        const KIB: u64 = 1024;
-        const MIB: u64 = KIB * 1024;
-        const GIB: u64 = MIB * 1024;
-        const TIB: u64 = GIB * 1024;
-
-        if size < KIB {
-            format!("󰋊 {} B", size)
-        } else if size < MIB {
-            format!("󰋊 {:.2} KiB", size as f64 / KIB as f64)
-        } else if size < GIB {
-            format!("󰋊 {:.2} MiB", size as f64 / MIB as f64)
-        } else {
-            format!("󰋊{:.2} TiB", size as f64 / TIB as f64)
+async fn render_gif_frame(
+    image_buffer: &image::RgbaImage,
+    width: u16,
+    height: u16,
+) -> Result<Text<'static>, String> {
+    // Encode image_buffer to PNG in memory
+    let mut png_bytes = Vec::new();
+    image_buffer
+        .write_to(
+            &mut std::io::Cursor::new(&mut png_bytes),
+            image::ImageFormat::Png,
+        )
+        .map_err(|e| format!("Failed to encode GIF frame to PNG: {}", e))?;
+
+    let mut cmd = TokioCommand::new("chafa");
+    cmd.args(chafa_color_args())
+        .arg("-f")
+        .arg("symbols")
+        .arg(format!("--size={}x{}", width, height))
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped());
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to spawn chafa: {}", e))?;
+
+    // Write PNG bytes to chafa's stdin
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(&png_bytes)
+            .await
+            .map_err(|e| format!("Failed to write to chafa stdin: {}", e))?;
+        // Close stdin to signal EOF to chafa
+        drop(stdin);
+    } else {
+        return Err("Failed to get chafa stdin".to_string());
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .map_err(|e| format!("Failed to wait for chafa: {}", e))?;
+
+    if output.status.success() {
+        let mut chafa_string = String::from_utf8_lossy(&output.stdout).to_string();
+        #[cfg(windows)]
+        {
+            chafa_string = chafa_string.replace("\n", "\r\n");
         }
+        chafa_string
+            .into_text()
+            .map_err(|e| format!("Failed to convert ANSI to Text: {:?}", e))
+    } else {
+        Err(format!(
+            "Chafa error: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ))
     }
 }
@@ -3,6 +3,9 @@ use crate::tui::help::event::KeyModifiers;
 use crossterm::event::{KeyCode, KeyEvent};
 
 pub fn handle_key_events(key: KeyEvent, app_state: &mut AppState) -> Option<TuiPage> {
+    if app_state.keybindings.handle_key(&key, true) {
+        return None;
+    }
     let current_page = app_state.help_state.current_page;
     let total_pages = app_state.help_state.total_pages;
 
@@ -32,6 +35,22 @@ pub fn handle_key_events(key: KeyEvent, app_state: &mut AppState) -> Option<TuiP
                 None
             }
         }
+        KeyCode::Right | KeyCode::PageDown => {
+            app_state.help_state.forward_page();
+            None
+        }
+        KeyCode::Left | KeyCode::PageUp => {
+            app_state.help_state.previous_page();
+            None
+        }
+        KeyCode::Down => {
+            app_state.help_state.scroll_by(1);
+            None
+        }
+        KeyCode::Up => {
+            app_state.help_state.scroll_by(-1);
+            None
+        }
         _ => None,
     }
 }
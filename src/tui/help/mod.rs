@@ -9,6 +9,8 @@ use tokio::sync::Mutex;
 
 use crate::app::app_state::AppState;
 use crate::app::TuiPage;
+use crate::tui::keymap::draw_keybindings_overlay;
+use crate::tui::notification::ui::draw_notifications;
 
 pub mod events;
 pub mod page;
@@ -20,13 +22,9 @@ pub async fn run_help_page<B: Backend>(
 ) -> io::Result<Option<TuiPage>> {
     let mut app_state_locked = app_state.lock().await;
 
-    // Check for Nerd Font
-    if let Ok(output) = Command::new("fc-list").output().await {
-        if let Ok(output_str) = String::from_utf8(output.stdout) {
-            if output_str.to_lowercase().contains("nerd font") {
-                app_state_locked.help_state.show_font_check_page = false;
-            }
-        }
+    // the Nerd Font probe already ran at startup, when it couldn't tell the page asks
+    if app_state_locked.capabilities.nerd_font == Some(true) {
+        app_state_locked.help_state.show_font_check_page = false;
     }
 
     // Check for chafa
@@ -40,9 +38,13 @@ pub async fn run_help_page<B: Backend>(
 
     loop {
         let mut app_state_locked = app_state.lock().await;
+        // toasts raised before landing here still have to expire
+        app_state_locked.update_notifications();
         terminal.draw(|f| {
             let size = f.area();
             page::render_help_page(f, &mut app_state_locked, size);
+            draw_keybindings_overlay(f, &mut app_state_locked);
+            draw_notifications(f, &mut app_state_locked);
         })?;
 
         if let Some(next_page) = app_state_locked.next_page.take() {
@@ -62,13 +64,7 @@ pub async fn run_help_page<B: Backend>(
         }
 
         let mut app_state_locked = app_state.lock().await;
-        app_state_locked.help_state.info_text_animation_progress += 1;
-        if app_state_locked.help_state.gauge_animation_active {
-            app_state_locked.help_state.gauge_animation_progress += 0.05; // 20 steps * 25ms = 500ms
-            if app_state_locked.help_state.gauge_animation_progress >= 1.0 {
-                app_state_locked.help_state.gauge_animation_progress = 1.0;
-                app_state_locked.help_state.gauge_animation_active = false;
-            }
-        }
+        let reduce_motion = app_state_locked.config.reduce_motion;
+        app_state_locked.help_state.tick(reduce_motion);
     }
 }
\ No newline at end of file
@@ -7,7 +7,7 @@ use ratatui::{
 };
 
 use crate::app::app_state::AppState;
-use crate::themes::{rgb_to_color, Theme};
+use crate::themes::{animated_gradient_text, gradient_text, highlight_style, rgb_to_color, Theme};
 
 fn create_animated_text<'a>(original_text: &Text<'a>, progress: usize) -> Text<'a> {
     let mut taken_chars = 0;
@@ -104,7 +104,7 @@ pub fn render_help_page(frame: &mut Frame, app_state: &mut AppState, area: Rect)
     frame.render_widget(gauge, bottom_chunks[0]);
 
     let page_indicator_text = format!(
-        "Page {}/{}\n(Press Enter to continue)",
+        "Page {}/{}\n(Enter or → next · ← back · ↑↓ scroll)",
         app_state.help_state.current_page + 1,
         app_state.help_state.total_pages
     );
@@ -114,6 +114,43 @@ pub fn render_help_page(frame: &mut Frame, app_state: &mut AppState, area: Rect)
     frame.render_widget(page_indicator, bottom_chunks[1]);
 }
 
+/// The bordered explanation box every page ends with. A box taller than the room it got
+/// scrolls with Up/Down instead of getting cut off.
+fn render_text_box(
+    frame: &mut Frame,
+    app_state: &mut AppState,
+    text: Text,
+    area: Rect,
+    theme: &Theme,
+) {
+    let visible = area.height.saturating_sub(2);
+    app_state
+        .help_state
+        .set_max_scroll((text.height() as u16).saturating_sub(visible));
+    let help_state = &app_state.help_state;
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(rgb_to_color(&theme.colors.border)));
+    if help_state.max_scroll > 0 {
+        let more = match (
+            help_state.scroll_offset > 0,
+            help_state.scroll_offset < help_state.max_scroll,
+        ) {
+            (true, true) => " ↑↓ more ",
+            (true, false) => " ↑ more ",
+            _ => " ↓ more ",
+        };
+        block = block.title_bottom(Line::from(more).alignment(Alignment::Right));
+    }
+    let text_box = Paragraph::new(text)
+        .block(block)
+        .alignment(Alignment::Center)
+        .scroll((help_state.scroll_offset, 0))
+        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
+    frame.render_widget(text_box, area);
+}
+
 fn render_font_check_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
     let text_content = Text::from(vec![
         Line::from(Span::raw("A Nerd Font is recommended for the best experience, like u can't see the most of the icon without of it")),
@@ -149,16 +186,7 @@ fn render_font_check_page(frame: &mut Frame, app_state: &mut AppState, area: Rec
         ])
         .split(chunks[1]);
 
-    let text_box = Paragraph::new(animated_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(rgb_to_color(&theme.colors.border))),
-        )
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
-    frame.render_widget(text_box, text_box_layout[1]);
+    render_text_box(frame, app_state, animated_text, text_box_layout[1], theme);
 }
 
 fn render_chafa_check_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
@@ -198,16 +226,7 @@ fn render_chafa_check_page(frame: &mut Frame, app_state: &mut AppState, area: Re
         ])
         .split(chunks[1]);
 
-    let text_box = Paragraph::new(animated_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(rgb_to_color(&theme.colors.border))),
-        )
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
-    frame.render_widget(text_box, text_box_layout[1]);
+    render_text_box(frame, app_state, animated_text, text_box_layout[1], theme);
 }
 
 fn render_logo_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
@@ -237,32 +256,29 @@ fn render_logo_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, the
         ])
         .split(area);
     // ReeTUI Logo (Placeholder for now)
-    let logo_text = Text::from(vec![
-        Line::from(Span::styled(
-            r"",
-            Style::default().fg(rgb_to_color(&theme.colors.accent)),
-        )),
-        Line::from(Span::styled(
-            r"",
-            Style::default().fg(rgb_to_color(&theme.colors.accent)),
-        )),
-        Line::from(Span::styled(
-            r"Not ReeTOING",
-            Style::default().fg(rgb_to_color(&theme.colors.accent)),
-        )),
-        Line::from(Span::styled(
-            r"",
-            Style::default().fg(rgb_to_color(&theme.colors.accent)),
-        )),
-        Line::from(Span::styled(
-            r"",
-            Style::default().fg(rgb_to_color(&theme.colors.accent)),
-        )),
-        Line::from(Span::styled(
-            r"                             ",
-            Style::default().fg(rgb_to_color(&theme.colors.accent)),
-        )),
-    ]);
+    let logo_lines = [
+        r"",
+        r"",
+        r"Not ReeTOING",
+        r"",
+        r"",
+        r"                             ",
+    ];
+    let logo_text = Text::from(
+        logo_lines
+            .iter()
+            .map(|line| {
+                let start = &theme.colors.title_gradient_start;
+                let end = &theme.colors.title_gradient_end;
+                if !app_state.config.reduce_motion {
+                    let phase = app_state.help_state.info_text_animation_progress as f32 / 80.0;
+                    animated_gradient_text(line, start, end, phase)
+                } else {
+                    gradient_text(line, start, end)
+                }
+            })
+            .collect::<Vec<Line>>(),
+    );
     let logo_paragraph = Paragraph::new(logo_text).alignment(Alignment::Center);
     frame.render_widget(logo_paragraph, chunks[0]);
     // Text box at the bottom
@@ -274,16 +290,7 @@ fn render_logo_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, the
             Constraint::Min(0),
         ])
         .split(chunks[1]);
-    let text_box = Paragraph::new(animated_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(rgb_to_color(&theme.colors.border))),
-        )
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
-    frame.render_widget(text_box, text_box_layout[1]);
+    render_text_box(frame, app_state, animated_text, text_box_layout[1], theme);
 }
 fn render_keyboard_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
     let text_content = Text::from(vec![
@@ -325,13 +332,9 @@ fn render_keyboard_page(frame: &mut Frame, app_state: &mut AppState, area: Rect,
                       is_highlighted: bool,
                       is_ctrl_s_highlight: bool| {
         let style = if is_highlighted {
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.background))
-                .bg(rgb_to_color(&theme.colors.accent))
+            highlight_style(&theme.colors.background, &theme.colors.accent)
         } else if is_ctrl_s_highlight {
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.background))
-                .bg(rgb_to_color(&theme.colors.success_color))
+            highlight_style(&theme.colors.background, &theme.colors.success_color)
         } else {
             Style::default().fg(rgb_to_color(&theme.colors.text))
         };
@@ -480,16 +483,7 @@ fn render_keyboard_page(frame: &mut Frame, app_state: &mut AppState, area: Rect,
             Constraint::Min(0),
         ])
         .split(text_box_area);
-    let text_box = Paragraph::new(animated_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(rgb_to_color(&theme.colors.border))),
-        )
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
-    frame.render_widget(text_box, text_box_layout[1]);
+    render_text_box(frame, app_state, animated_text, text_box_layout[1], theme);
 }
 fn render_create_channel_page(
     frame: &mut Frame,
@@ -535,13 +529,9 @@ fn render_create_channel_page(
                       is_highlighted: bool,
                       is_ctrl_n_highlight: bool| {
         let style = if is_highlighted {
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.background))
-                .bg(rgb_to_color(&theme.colors.accent))
+            highlight_style(&theme.colors.background, &theme.colors.accent)
         } else if is_ctrl_n_highlight {
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.background))
-                .bg(rgb_to_color(&theme.colors.success_color))
+            highlight_style(&theme.colors.background, &theme.colors.success_color)
         } else {
             Style::default().fg(rgb_to_color(&theme.colors.text))
         };
@@ -690,16 +680,7 @@ fn render_create_channel_page(
             Constraint::Min(0),
         ])
         .split(text_box_area);
-    let text_box = Paragraph::new(animated_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(rgb_to_color(&theme.colors.border))),
-        )
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
-    frame.render_widget(text_box, text_box_layout[1]);
+    render_text_box(frame, app_state, animated_text, text_box_layout[1], theme);
 }
 fn render_ctrl_u_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
     let text_content = Text::from(vec![
@@ -739,13 +720,9 @@ fn render_ctrl_u_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, t
                       is_ctrl_highlight: bool,
                       is_u_highlight: bool| {
         let style = if is_ctrl_highlight {
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.background))
-                .bg(rgb_to_color(&theme.colors.accent))
+            highlight_style(&theme.colors.background, &theme.colors.accent)
         } else if is_u_highlight {
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.background))
-                .bg(rgb_to_color(&theme.colors.success_color))
+            highlight_style(&theme.colors.background, &theme.colors.success_color)
         } else {
             Style::default().fg(rgb_to_color(&theme.colors.text))
         };
@@ -894,16 +871,7 @@ fn render_ctrl_u_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, t
             Constraint::Min(0),
         ])
         .split(text_box_area);
-    let text_box = Paragraph::new(animated_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(rgb_to_color(&theme.colors.border))),
-        )
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
-    frame.render_widget(text_box, text_box_layout[1]);
+    render_text_box(frame, app_state, animated_text, text_box_layout[1], theme);
 }
 fn render_ctrl_d_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
     let text_content = Text::from(vec![
@@ -939,13 +907,9 @@ fn render_ctrl_d_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, t
                       is_ctrl_highlight: bool,
                       is_d_highlight: bool| {
         let style = if is_ctrl_highlight {
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.background))
-                .bg(rgb_to_color(&theme.colors.accent))
+            highlight_style(&theme.colors.background, &theme.colors.accent)
         } else if is_d_highlight {
-            Style::default()
-                .fg(rgb_to_color(&theme.colors.background))
-                .bg(rgb_to_color(&theme.colors.success_color))
+            highlight_style(&theme.colors.background, &theme.colors.success_color)
         } else {
             Style::default().fg(rgb_to_color(&theme.colors.text))
         };
@@ -1094,16 +1058,7 @@ fn render_ctrl_d_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, t
             Constraint::Min(0),
         ])
         .split(text_box_area);
-    let text_box = Paragraph::new(animated_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(rgb_to_color(&theme.colors.border))),
-        )
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
-    frame.render_widget(text_box, text_box_layout[1]);
+    render_text_box(frame, app_state, animated_text, text_box_layout[1], theme);
 }
 fn render_esc_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, theme: &Theme) {
     let text_content = Text::from(vec![
@@ -1136,9 +1091,7 @@ fn render_esc_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, them
     let render_key =
         |f: &mut Frame, key_str: &str, x: u16, y: u16, key_width: u16, is_esc_highlight: bool| {
             let style = if is_esc_highlight {
-                Style::default()
-                    .fg(rgb_to_color(&theme.colors.background))
-                    .bg(rgb_to_color(&theme.colors.success_color))
+                highlight_style(&theme.colors.background, &theme.colors.success_color)
             } else {
                 Style::default().fg(rgb_to_color(&theme.colors.text))
             };
@@ -1253,14 +1206,5 @@ fn render_esc_page(frame: &mut Frame, app_state: &mut AppState, area: Rect, them
             Constraint::Min(0),
         ])
         .split(text_box_area);
-    let text_box = Paragraph::new(animated_text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_type(BorderType::Rounded)
-                .border_style(Style::default().fg(rgb_to_color(&theme.colors.border))),
-        )
-        .alignment(Alignment::Center)
-        .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)));
-    frame.render_widget(text_box, text_box_layout[1]);
+    render_text_box(frame, app_state, animated_text, text_box_layout[1], theme);
 }
@@ -9,6 +9,10 @@ pub struct HelpState {
     pub gauge_animation_end_ratio: f64,
     pub gauge_animation_progress: f64,
     pub gauge_animation_active: bool,
+    /// lines the current page's text box is scrolled down by
+    pub scroll_offset: u16,
+    /// how far that box can scroll, set while drawing since it depends on the terminal size
+    pub max_scroll: u16,
 }
 
 impl Default for HelpState {
@@ -23,46 +27,82 @@ impl Default for HelpState {
             gauge_animation_end_ratio: 0.0,
             gauge_animation_progress: 0.0,
             gauge_animation_active: false,
+            scroll_offset: 0,
+            max_scroll: 0,
         }
     }
 }
 
 impl HelpState {
     pub fn next_page(&mut self) -> Option<crate::app::TuiPage> {
-        self.info_text_animation_progress = 0;
-        if self.current_page < self.total_pages - 1 {
-            let start_ratio = if self.total_pages > 0 {
-                (self.current_page + 1) as f64 / self.total_pages as f64
-            } else {
-                0.0
-            };
-
-            self.current_page += 1;
+        if self.current_page + 1 < self.total_pages {
+            self.go_to_page(self.current_page + 1);
+            None
+        } else {
+            self.current_page = 0; // Reset for next time
+            self.scroll_offset = 0;
+            Some(crate::app::TuiPage::Auth)
+        }
+    }
 
-            let end_ratio = if self.total_pages > 0 {
-                (self.current_page + 1) as f64 / self.total_pages as f64
+    /// Jumps to `page` with the gauge easing over from where it was, the text starts typing
+    /// again from the top.
+    fn go_to_page(&mut self, page: usize) {
+        let ratio = |page: usize| {
+            if self.total_pages > 0 {
+                (page + 1) as f64 / self.total_pages as f64
             } else {
                 0.0
-            };
-
-            self.gauge_animation_start_ratio = start_ratio;
-            self.gauge_animation_end_ratio = end_ratio;
-            self.gauge_animation_progress = 0.0;
-            self.gauge_animation_active = true;
+            }
+        };
+        self.gauge_animation_start_ratio = ratio(self.current_page);
+        self.gauge_animation_end_ratio = ratio(page);
+        self.gauge_animation_progress = 0.0;
+        self.gauge_animation_active = true;
+        self.current_page = page;
+        self.info_text_animation_progress = 0;
+        self.scroll_offset = 0;
+    }
 
-            None
+    /// Advances the typewriter text and the gauge easing by one frame, or straight to
+    /// their final state with `reduce_motion`.
+    pub fn tick(&mut self, reduce_motion: bool) {
+        if reduce_motion {
+            self.info_text_animation_progress = usize::MAX;
+            self.gauge_animation_progress = 1.0;
         } else {
-            self.current_page = 0; // Reset for next time
-            Some(crate::app::TuiPage::Auth)
+            self.info_text_animation_progress = self.info_text_animation_progress.saturating_add(1);
+            if self.gauge_animation_active {
+                self.gauge_animation_progress += 0.05; // 20 steps * 25ms = 500ms
+            }
+        }
+        if self.gauge_animation_active && self.gauge_animation_progress >= 1.0 {
+            self.gauge_animation_progress = 1.0;
+            self.gauge_animation_active = false;
         }
     }
 
+    /// One page back, staying put on the first one.
     pub fn previous_page(&mut self) {
-        self.info_text_animation_progress = 0;
         if self.current_page > 0 {
-            self.current_page -= 1;
-        } else {
-            self.current_page = self.total_pages - 1; // Loop back to the last page
+            self.go_to_page(self.current_page - 1);
         }
     }
+
+    /// One page on without ever leaving the tutorial, finishing it takes Enter.
+    pub fn forward_page(&mut self) {
+        if self.current_page + 1 < self.total_pages {
+            self.go_to_page(self.current_page + 1);
+        }
+    }
+
+    pub fn scroll_by(&mut self, delta: i32) {
+        self.scroll_offset =
+            (self.scroll_offset as i32 + delta).clamp(0, self.max_scroll as i32) as u16;
+    }
+
+    pub fn set_max_scroll(&mut self, max_scroll: u16) {
+        self.max_scroll = max_scroll;
+        self.scroll_offset = self.scroll_offset.min(max_scroll);
+    }
 }
\ No newline at end of file
@@ -1,15 +1,21 @@
 use crate::app::TuiPage;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 use std::io;
 
-pub fn handle_home_event(wait_time: std::time::Duration) -> io::Result<Option<TuiPage>> {
+/// `q` quits, Enter (or anything else) moves on to `continue_to`.
+pub fn home_key_target(code: KeyCode, continue_to: TuiPage) -> TuiPage {
+    match code {
+        KeyCode::Char('q') | KeyCode::Char('Q') => TuiPage::Exit,
+        _ => continue_to,
+    }
+}
+
+/// The next key press within `wait_time`, if any.
+pub fn handle_home_event(wait_time: std::time::Duration) -> io::Result<Option<KeyEvent>> {
     if event::poll(wait_time)? {
         if let Event::Key(key) = event::read()? {
             if key.kind == KeyEventKind::Press {
-                match key.code {
-                    KeyCode::Char('Q') => return Ok(Some(TuiPage::Exit)),
-                    _ => return Ok(Some(TuiPage::Help)),
-                }
+                return Ok(Some(key));
             }
         }
     }
@@ -4,9 +4,10 @@ pub mod state;
 
 use crate::app::app_state::AppState;
 use crate::app::TuiPage;
-use crate::tui::home::events::handle_home_event;
-use crate::tui::home::page::{draw_home_ui, ANIMATION_FRAMES, FRAME_DURATION_MS};
+use crate::tui::home::events::{handle_home_event, home_key_target};
+use crate::tui::home::page::{draw_home_ui, ANIMATION_FRAMES, MIN_FRAME_DURATION_MS};
 use crate::tui::home::state::AnimationState;
+use crate::tui::keymap::draw_keybindings_overlay;
 use crate::tui::notification::notification::NotificationType;
 use crate::tui::notification::ui::draw_notifications;
 use ratatui::style::Stylize;
@@ -22,8 +23,6 @@ pub async fn run_home_page<B: Backend>(
     app_state: Arc<tokio::sync::Mutex<AppState>>,
 ) -> io::Result<Option<TuiPage>> {
     let mut animation_state = AnimationState::new();
-    let _frame_count = ANIMATION_FRAMES.len();
-    let frame_duration = Duration::from_millis(FRAME_DURATION_MS);
 
     app_state
         .lock()
@@ -39,20 +38,46 @@ pub async fn run_home_page<B: Backend>(
         .await;
 
     loop {
-        app_state.lock().await.notification_manager.update();
-        let current_frame_index = animation_state.frame_index;
+        app_state.lock().await.update_notifications();
         let mut app_state_locked = app_state.lock().await;
+        let reduce_motion = app_state_locked.config.reduce_motion;
+        // read every round so a change in the settings shows up right away
+        let frame_duration = Duration::from_millis(
+            app_state_locked
+                .config
+                .home_frame_duration_ms
+                .max(MIN_FRAME_DURATION_MS),
+        );
+        // reduce motion gets the first frame, not whichever one it happened to stop on
+        let current_frame_index = if reduce_motion {
+            0
+        } else {
+            animation_state.frame_index
+        };
+        let sheen_phase = (!reduce_motion).then(|| animation_state.sheen_phase());
         terminal.draw(|f| {
             let theme = &app_state_locked.current_theme;
             f.render_widget(
                 Block::default().bg(crate::themes::rgb_to_color(&theme.colors.background)),
                 f.area(),
             );
-            draw_home_ui::<B>(f, current_frame_index, theme);
+            draw_home_ui::<B>(f, current_frame_index, theme, sheen_phase);
+            draw_keybindings_overlay(f, &mut app_state_locked);
             draw_notifications(f, &mut app_state_locked);
         })?;
 
-        if let Some(page) = handle_home_event(Duration::from_millis(100))? {
+        let continue_to = TuiPage::start_page(&app_state_locked.config);
+        let intro_delay = Duration::from_millis(app_state_locked.config.home_intro_delay_ms);
+        drop(app_state_locked);
+
+        // polling for the whole frame keeps the logo pace and still reacts to a key at once
+        let key = handle_home_event(frame_duration)?;
+        let key = match key {
+            Some(key) if app_state.lock().await.keybindings.handle_key(&key, true) => None,
+            key => key,
+        };
+        if let Some(key) = key {
+            let page = home_key_target(key.code, continue_to);
             if page == TuiPage::Exit {
                 app_state
                     .lock()
@@ -66,12 +91,13 @@ pub async fn run_home_page<B: Backend>(
                         app_state.clone(),
                     )
                     .await;
+                sleep(Duration::from_secs(2));
+            } else if !intro_delay.is_zero() {
+                tokio::time::sleep(intro_delay).await;
             }
-            sleep(Duration::from_secs(2));
             return Ok(Some(page));
         }
 
-        animation_state.update(ANIMATION_FRAMES.len(), frame_duration);
-        tokio::time::sleep(frame_duration).await;
+        animation_state.update(ANIMATION_FRAMES.len(), frame_duration, reduce_motion);
     }
 }
\ No newline at end of file
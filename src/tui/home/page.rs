@@ -1,4 +1,4 @@
-use crate::themes::{interpolate_rgb, rgb_to_color, Theme};
+use crate::themes::{animated_gradient_text, gradient_text, rgb_to_color, Theme};
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
@@ -92,8 +92,33 @@ Yb,  88      `8b                Yb,_    88       Yb, `88        88      d8   8b
 ];
 
 pub const FRAME_DURATION_MS: u64 = 500;
+/// floor for `home_frame_duration_ms`, the page sleeps this long between redraws
+pub const MIN_FRAME_DURATION_MS: u64 = 50;
+
+/// One logo frame painted with the theme's title gradient, the sheen slides across it while
+/// `sheen_phase` is set.
+pub fn themed_frame_lines<'a>(
+    frame: &'a str,
+    theme: &Theme,
+    sheen_phase: Option<f32>,
+) -> Vec<Line<'a>> {
+    let start = &theme.colors.title_gradient_start;
+    let end = &theme.colors.title_gradient_end;
+    frame
+        .lines()
+        .map(|line_str| match sheen_phase {
+            Some(phase) => animated_gradient_text(line_str, start, end, phase),
+            None => gradient_text(line_str, start, end),
+        })
+        .collect()
+}
 
-pub fn draw_home_ui<B: Backend>(f: &mut Frame, current_frame_index: usize, theme: &Theme) {
+pub fn draw_home_ui<B: Backend>(
+    f: &mut Frame,
+    current_frame_index: usize,
+    theme: &Theme,
+    sheen_phase: Option<f32>,
+) {
     let size = f.area();
 
     let background = ratatui::widgets::Block::default()
@@ -114,37 +139,13 @@ pub fn draw_home_ui<B: Backend>(f: &mut Frame, current_frame_index: usize, theme
         )
         .split(size);
 
-    let current_frame_str = ANIMATION_FRAMES[current_frame_index];
+    let current_frame_str = ANIMATION_FRAMES[current_frame_index % ANIMATION_FRAMES.len()];
     let lines: Vec<&str> = current_frame_str.lines().collect();
 
     let num_logo_lines = lines.len();
     let max_logo_line_width = lines.iter().map(|line| line.len()).max().unwrap_or(0) as u16;
 
-    let mut text_lines: Vec<Line> = Vec::new();
-
-    let num_lines_for_gradient = if num_logo_lines <= 1 {
-        1.0
-    } else {
-        (num_logo_lines - 1) as f32
-    };
-
-    for (line_idx, line_str) in lines.iter().enumerate() {
-        let mut spans = Vec::new();
-        let fraction_y = line_idx as f32 / num_lines_for_gradient;
-
-        for ch in line_str.chars() {
-            let interpolated_rgb = interpolate_rgb(
-                &theme.colors.title_gradient_start,
-                &theme.colors.title_gradient_end,
-                fraction_y,
-            );
-            spans.push(Span::styled(
-                ch.to_string(),
-                Style::default().fg(rgb_to_color(&interpolated_rgb)),
-            ));
-        }
-        text_lines.push(Line::from(spans));
-    }
+    let text_lines = themed_frame_lines(current_frame_str, theme, sheen_phase);
 
     let logo_paragraph = Paragraph::new(text_lines).alignment(Alignment::Center);
 
@@ -160,7 +161,7 @@ pub fn draw_home_ui<B: Backend>(f: &mut Frame, current_frame_index: usize, theme
         height: logo_area_height.min(chunks[1].height),
     };
     f.render_widget(logo_paragraph, centered_logo_rect);
-    let instructions_text = "Press any  key to go to Chat, 'Q' to quit.";
+    let instructions_text = "Press Enter  to continue, 'q' to quit.";
 
     let instructions = Paragraph::new(Line::from(instructions_text))
         .style(Style::default().fg(rgb_to_color(&theme.colors.instructions_text)))
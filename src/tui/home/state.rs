@@ -3,6 +3,8 @@ use std::time::{Duration, Instant};
 pub struct AnimationState {
     pub frame_index: usize,
     pub last_frame_time: Instant,
+    // bumped on every update, drives the title sheen
+    pub tick: usize,
 }
 
 impl AnimationState {
@@ -10,10 +12,16 @@ impl AnimationState {
         Self {
             frame_index: 0,
             last_frame_time: Instant::now(),
+            tick: 0,
         }
     }
 
-    pub fn update(&mut self, frame_count: usize, frame_duration: Duration) {
+    pub fn update(&mut self, frame_count: usize, frame_duration: Duration, reduce_motion: bool) {
+        if reduce_motion {
+            // stay on the current frame
+            return;
+        }
+        self.tick = self.tick.wrapping_add(1);
         let now = Instant::now();
         let elapsed = now.duration_since(self.last_frame_time);
         if elapsed >= frame_duration {
@@ -21,4 +29,8 @@ impl AnimationState {
             self.last_frame_time = now;
         }
     }
+
+    pub fn sheen_phase(&self) -> f32 {
+        (self.tick % 20) as f32 / 20.0
+    }
 }
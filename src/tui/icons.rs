@@ -0,0 +1,61 @@
+use crate::themes::nerd_font;
+use std::borrow::Cow;
+
+// drawn where a Nerd Font glyph would be when the table has nothing better
+const GLYPH_FALLBACK: char = '*';
+
+/// Plain stand-ins for the glyphs that carry meaning. One cell each, same as the glyph, so
+/// they can be swapped in after the layout is done.
+const GLYPH_FALLBACKS: &[(char, char)] = &[
+    ('\u{f058}', '+'),  // success
+    ('\u{f06a}', '!'),  // warning
+    ('\u{f057}', 'x'),  // error
+    ('\u{f05a}', 'i'),  // info
+    ('\u{e0b6}', '('),  // left end of a chip
+    ('\u{e0b4}', ')'),  // right end of a chip
+    ('\u{f0a13}', '>'), // selected row in the popups
+    ('\u{f14fb}', '#'), // theme color swatch
+    ('\u{f0403}', '^'), // pinned channel
+];
+
+/// Nerd Font icons all live in the private use areas, a character there shows up as a box
+/// (or nothing) in any other font.
+pub fn is_nerd_font_glyph(c: char) -> bool {
+    matches!(c, '\u{E000}'..='\u{F8FF}' | '\u{F0000}'..='\u{FFFFD}' | '\u{100000}'..='\u{10FFFD}')
+}
+
+/// The plain character drawn for a Nerd Font glyph.
+pub fn fallback_glyph(c: char) -> char {
+    GLYPH_FALLBACKS
+        .iter()
+        .find(|(glyph, _)| *glyph == c)
+        .map_or(GLYPH_FALLBACK, |(_, fallback)| *fallback)
+}
+
+/// `symbol` with its Nerd Font glyphs swapped for their fallbacks. Borrowed when there are
+/// none, which is nearly every cell.
+pub fn plain_symbol(symbol: &str) -> Cow<'_, str> {
+    if !symbol.chars().any(is_nerd_font_glyph) {
+        return Cow::Borrowed(symbol);
+    }
+    symbol
+        .chars()
+        .map(|c| {
+            if is_nerd_font_glyph(c) {
+                fallback_glyph(c)
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// `icon`, or `fallback` when it needs a Nerd Font that isn't there. For the spots with room
+/// for more than one cell, like theme and channel icons; emoji icons are kept either way.
+pub fn icon_or<'a>(icon: &'a str, fallback: &'a str) -> &'a str {
+    if nerd_font() || !icon.chars().any(is_nerd_font_glyph) {
+        icon
+    } else {
+        fallback
+    }
+}
@@ -0,0 +1,297 @@
+// The keybindings reference (F1, or ? outside a text field). Everything it lists comes from
+// here and from the palette's shortcuts, so a new key only needs an entry in one of the two.
+use crate::app::app_state::AppState;
+use crate::themes::rgb_to_color;
+use crate::tui::chat::palette::PALETTE_ENTRIES;
+use crate::tui::chat::utils::centered_rect;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyScope {
+    Everywhere,
+    Chat,
+    Input,
+    ChannelList,
+    Messages,
+    Popups,
+    Tutorial,
+}
+
+impl KeyScope {
+    pub fn label(&self) -> &'static str {
+        match self {
+            KeyScope::Everywhere => "Everywhere",
+            KeyScope::Chat => "Chat",
+            KeyScope::Input => "Message input",
+            KeyScope::ChannelList => "Channel list",
+            KeyScope::Messages => "Messages pane",
+            KeyScope::Popups => "Popups",
+            KeyScope::Tutorial => "Tutorial",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyBinding {
+    pub scope: KeyScope,
+    pub keys: &'static str,
+    pub action: &'static str,
+}
+
+const fn bind(scope: KeyScope, keys: &'static str, action: &'static str) -> KeyBinding {
+    KeyBinding {
+        scope,
+        keys,
+        action,
+    }
+}
+
+/// Keys that aren't a palette action. Grouped by scope, in the order they're shown.
+pub const KEYBINDINGS: &[KeyBinding] = &[
+    bind(KeyScope::Everywhere, "F1 / ?", "Keybindings (this list)"),
+    bind(KeyScope::Everywhere, "Ctrl+S", "Settings"),
+    bind(KeyScope::Chat, "Ctrl+P", "Command palette"),
+    bind(
+        KeyScope::Chat,
+        "Tab / Shift+Tab",
+        "Focus next / previous pane",
+    ),
+    bind(
+        KeyScope::Chat,
+        "PageUp / PageDown",
+        "Scroll messages a page",
+    ),
+    bind(KeyScope::Input, "Enter", "Send message"),
+    bind(KeyScope::Input, "Up / Down", "Previous / next sent message"),
+    bind(KeyScope::Input, "@", "Mention someone"),
+    bind(KeyScope::Input, ":", "Emoji by shortcode"),
+    bind(KeyScope::Input, "Ctrl+Left / Ctrl+Right", "Jump a word"),
+    bind(KeyScope::Input, "Ctrl+X Ctrl+E", "Write the message in $EDITOR"),
+    bind(KeyScope::Input, "Home / End", "Start / end of the line"),
+    bind(
+        KeyScope::Input,
+        "Ctrl+W / Ctrl+Backspace",
+        "Delete the word before the cursor",
+    ),
+    bind(KeyScope::Input, "Alt+Backspace", "Delete to line start"),
+    bind(KeyScope::ChannelList, "Up / Down", "Switch channels"),
+    bind(
+        KeyScope::ChannelList,
+        "Alt+Up / Alt+Down",
+        "Move the channel",
+    ),
+    bind(
+        KeyScope::ChannelList,
+        "Alt+Left / Alt+Right",
+        "Resize the channel list",
+    ),
+    bind(KeyScope::ChannelList, "Alt+P", "Pin / unpin the channel"),
+    bind(KeyScope::ChannelList, "m", "Channel menu, accent color"),
+    bind(KeyScope::ChannelList, "Delete", "Delete the channel"),
+    bind(KeyScope::Messages, "Up / Down", "Scroll messages"),
+    bind(KeyScope::Messages, "Left / Right", "Select an attachment"),
+    bind(
+        KeyScope::Messages,
+        "Enter / d",
+        "Download the selected attachment",
+    ),
+    bind(
+        KeyScope::Messages,
+        "Enter",
+        "Show more / less of a long message",
+    ),
+    bind(KeyScope::Messages, "s", "Reveal / hide spoilers"),
+    bind(KeyScope::Messages, "y", "Copy the message at the bottom"),
+    bind(KeyScope::Messages, "i", "Inspect the message at the bottom"),
+    bind(KeyScope::Messages, "I", "Show / hide the details panel"),
+    bind(KeyScope::Messages, "v", "Copy mode, plain text to select with the mouse"),
+    bind(KeyScope::Messages, "Alt+P", "Pin / unpin the message at the bottom"),
+    bind(KeyScope::Messages, "p", "Show all pins / just the count"),
+    bind(KeyScope::Messages, "[ / ]", "Jump to the previous / next pin"),
+    bind(KeyScope::Messages, "t", "Show / hide message times"),
+    bind(KeyScope::Popups, "Esc", "Close / cancel"),
+    bind(KeyScope::Popups, "Enter", "Confirm / select"),
+    bind(KeyScope::Popups, "Y / N", "Answer a yes or no question"),
+    bind(KeyScope::Tutorial, "Enter / Right", "Next page"),
+    bind(KeyScope::Tutorial, "Left", "Previous page"),
+    bind(KeyScope::Tutorial, "Up / Down", "Scroll a long page"),
+];
+
+/// The palette actions that have a shortcut, then `KEYBINDINGS`. A shortcut that works
+/// everywhere is only listed there.
+pub fn all_bindings() -> Vec<KeyBinding> {
+    let everywhere = |shortcut: &str| {
+        KEYBINDINGS
+            .iter()
+            .any(|b| b.scope == KeyScope::Everywhere && b.keys == shortcut)
+    };
+    PALETTE_ENTRIES
+        .iter()
+        .filter(|entry| !entry.shortcut.is_empty() && !everywhere(entry.shortcut))
+        .map(|entry| bind(KeyScope::Chat, entry.shortcut, entry.name))
+        .chain(KEYBINDINGS.iter().copied())
+        .collect()
+}
+
+/// Bindings whose keys, action or scope contain every word of the query.
+pub fn filtered_bindings(query: &str) -> Vec<KeyBinding> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut bindings: Vec<KeyBinding> = all_bindings()
+        .into_iter()
+        .filter(|binding| {
+            let haystack = format!(
+                "{} {} {}",
+                binding.keys,
+                binding.action,
+                binding.scope.label()
+            )
+            .to_lowercase();
+            words.iter().all(|word| haystack.contains(word))
+        })
+        .collect();
+    // palette shortcuts are Chat too, keep each scope in one block
+    bindings.sort_by_key(|binding| binding.scope as u8);
+    bindings
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct KeybindingsOverlay {
+    pub open: bool,
+    pub query: String,
+    pub scroll: usize,
+}
+
+impl KeybindingsOverlay {
+    /// Returns true when the key belonged to the overlay. `?` only opens it where it can't be
+    /// meant as text.
+    pub fn handle_key(&mut self, key: &KeyEvent, question_mark_opens: bool) -> bool {
+        if !self.open {
+            let opens = key.code == KeyCode::F(1)
+                || (question_mark_opens && key.code == KeyCode::Char('?'));
+            if opens {
+                *self = Self {
+                    open: true,
+                    ..Self::default()
+                };
+            }
+            return opens;
+        }
+        match key.code {
+            KeyCode::Esc | KeyCode::F(1) => self.open = false,
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll += 1,
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll += 10,
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.scroll = 0;
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+        true
+    }
+}
+
+pub fn draw_keybindings_overlay(f: &mut Frame, state: &mut AppState) {
+    if !state.keybindings.open {
+        return;
+    }
+    let theme = &state.current_theme;
+    let dim = Style::default().fg(rgb_to_color(&theme.colors.dim));
+    let text = Style::default().fg(rgb_to_color(&theme.colors.text));
+    let accent = Style::default().fg(rgb_to_color(&theme.colors.accent));
+
+    let area = f.area();
+    let area = centered_rect(
+        64.min(area.width),
+        (area.height * 4 / 5).max(10).min(area.height),
+        area,
+    );
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .title("Keybindings")
+        .title_alignment(Alignment::Center)
+        .style(
+            Style::default()
+                .fg(rgb_to_color(&theme.colors.popup_border))
+                .bg(rgb_to_color(&theme.colors.background)),
+        );
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(inner);
+
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(" ", accent),
+            Span::styled(format!("{}▏", state.keybindings.query), text),
+        ])),
+        chunks[0],
+    );
+
+    let bindings = filtered_bindings(&state.keybindings.query);
+    let keys_width = bindings
+        .iter()
+        .map(|binding| binding.keys.width())
+        .max()
+        .unwrap_or(0);
+    let mut lines: Vec<Line> = Vec::new();
+    let mut last_scope = None;
+    for binding in &bindings {
+        if last_scope != Some(binding.scope) {
+            if last_scope.is_some() {
+                lines.push(Line::from(""));
+            }
+            lines.push(Line::from(Span::styled(
+                format!(" {}", binding.scope.label()),
+                accent.add_modifier(Modifier::BOLD),
+            )));
+            last_scope = Some(binding.scope);
+        }
+        let padding = keys_width.saturating_sub(binding.keys.width());
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("   {}{}  ", binding.keys, " ".repeat(padding)),
+                text.add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(binding.action, text),
+        ]));
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(" No key does that 󰘥", dim)));
+    }
+
+    let list_area = chunks[1];
+    let max_scroll = lines.len().saturating_sub(list_area.height as usize);
+    state.keybindings.scroll = state.keybindings.scroll.min(max_scroll);
+    f.render_widget(
+        Paragraph::new(lines).scroll((state.keybindings.scroll as u16, 0)),
+        list_area,
+    );
+
+    f.render_widget(
+        Paragraph::new(" Type to search · Up/Down scroll · Esc close").style(dim),
+        chunks[2],
+    );
+}
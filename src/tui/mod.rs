@@ -3,7 +3,16 @@ pub mod auth;
 pub mod text_input;
 pub mod settings;
 pub mod help;
+pub mod keymap;
 pub mod chat;
 pub mod file_manager_module;
 pub mod notification;
-pub mod animation;
\ No newline at end of file
+pub mod animation;
+pub mod utils;
+pub mod shutdown;
+pub mod capabilities;
+pub mod icons;
+pub mod cursor_style;
+
+#[cfg(test)]
+mod tests;
@@ -1,3 +1,6 @@
 pub mod notification;
 pub mod ui;
 pub use notification::NotificationManager;
+
+#[cfg(test)]
+mod tests;
@@ -4,9 +4,10 @@ use std::time::{Duration, Instant};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
 
+use crate::config::NotificationColors;
 use crate::tui::animation::Animation;
 
-const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+pub const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum NotificationType {
@@ -17,6 +18,19 @@ pub enum NotificationType {
     Loading,
 }
 
+impl NotificationType {
+    /// How long a toast of this type stays up when the caller doesn't pass a timeout.
+    /// Errors linger so they aren't missed, loading toasts stay until they are replaced.
+    pub fn default_timeout(&self) -> Option<Duration> {
+        match self {
+            NotificationType::Success => Some(Duration::from_secs(2)),
+            NotificationType::Info | NotificationType::Warning => Some(Duration::from_secs(4)),
+            NotificationType::Error => Some(Duration::from_secs(8)),
+            NotificationType::Loading => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Notification {
     pub id: usize,
@@ -41,6 +55,8 @@ impl Notification {
         notification_type: NotificationType,
         timeout: Option<Duration>,
     ) -> Self {
+        // an explicit timeout always wins over the per-type default
+        let timeout = timeout.or_else(|| notification_type.default_timeout());
         Self {
             id,
             title,
@@ -66,14 +82,16 @@ impl Notification {
         }
     }
 
-    pub fn color(&self, theme: &crate::themes::Theme) -> Color {
-        match self.notification_type {
-            NotificationType::Success => crate::themes::rgb_to_color(&theme.colors.success_color),
-            NotificationType::Warning => crate::themes::rgb_to_color(&theme.colors.warning_color),
-            NotificationType::Error => crate::themes::rgb_to_color(&theme.colors.error),
-            NotificationType::Info => crate::themes::rgb_to_color(&theme.colors.info_color),
-            NotificationType::Loading => crate::themes::rgb_to_color(&theme.colors.loading_color),
-        }
+    /// The type's color from `overrides`, or the theme's when it has none.
+    pub fn color(&self, theme: &crate::themes::Theme, overrides: &NotificationColors) -> Color {
+        let rgb = match self.notification_type {
+            NotificationType::Success => overrides.success.unwrap_or(theme.colors.success_color),
+            NotificationType::Warning => overrides.warning.unwrap_or(theme.colors.warning_color),
+            NotificationType::Error => overrides.error.unwrap_or(theme.colors.error),
+            NotificationType::Info => overrides.info.unwrap_or(theme.colors.info_color),
+            NotificationType::Loading => overrides.loading.unwrap_or(theme.colors.loading_color),
+        };
+        crate::themes::rgb_to_color(&rgb)
     }
 
     pub fn height(&self, max_width: u16) -> u16 {
@@ -119,6 +137,10 @@ impl LoadingNotification {
 pub struct NotificationManager {
     notifications: Vec<Notification>,
     next_id: usize,
+    #[serde(skip)]
+    do_not_disturb: bool,
+    #[serde(skip)]
+    last_update: Option<Instant>,
 }
 
 impl NotificationManager {
@@ -130,6 +152,15 @@ impl NotificationManager {
         timeout: Option<Duration>,
         app_state: Arc<tokio::sync::Mutex<crate::app::app_state::AppState>>,
     ) -> Option<LoadingNotification> {
+        // errors still matter and a loading toast is the user's own action in progress
+        if self.do_not_disturb
+            && !matches!(
+                notification_type,
+                NotificationType::Error | NotificationType::Loading
+            )
+        {
+            return None;
+        }
         let id = self.next_id;
         self.next_id += 1;
         let mut notification = Notification::new(id, title, content, notification_type, timeout);
@@ -147,8 +178,18 @@ impl NotificationManager {
         }
     }
 
-    pub fn update(&mut self) {
+    /// Spins the loading toasts and drops the expired ones. Only the newest `max_visible` are
+    /// on screen, the clocks of the ones waiting for a spot stand still.
+    pub fn update(&mut self, max_visible: usize) {
         let now = Instant::now();
+        let since_last_update = self
+            .last_update
+            .map_or(Duration::ZERO, |last| now.duration_since(last));
+        self.last_update = Some(now);
+        let waiting = self.notifications.len().saturating_sub(max_visible.max(1));
+        for n in &mut self.notifications[..waiting] {
+            n.created_at = (n.created_at + since_last_update).min(now);
+        }
         let mut i = 0;
         while i < self.notifications.len() {
             let n = &mut self.notifications[i];
@@ -168,6 +209,20 @@ impl NotificationManager {
         }
     }
 
+    /// Drops a loading toast once its work is done, the spinner goes with it.
+    pub fn resolve(&mut self, loading: LoadingNotification) {
+        self.remove(loading.id);
+    }
+
+    /// While on, `add` drops everything but errors and loading toasts.
+    pub fn set_do_not_disturb(&mut self, on: bool) {
+        self.do_not_disturb = on;
+    }
+
+    pub fn do_not_disturb(&self) -> bool {
+        self.do_not_disturb
+    }
+
     pub fn remove(&mut self, id: usize) {
         self.notifications.retain(|n| n.id != id);
     }
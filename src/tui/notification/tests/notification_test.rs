@@ -0,0 +1,262 @@
+use crate::app::AppState;
+use crate::config::NotificationColors;
+use crate::themes::{rgb_to_color, Rgb};
+use crate::tui::notification::notification::{Notification, NotificationType};
+use crate::tui::tests::harness::message;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+// every page that can be on screen ticks update(), this is what the tick has to do there
+#[tokio::test]
+async fn test_update_expires_timed_out_toasts_only() {
+    let app_state = Arc::new(Mutex::new(AppState::default()));
+    let mut state = app_state.lock().await;
+    for (title, timeout) in [
+        ("gone", Some(Duration::ZERO)),
+        ("stays", Some(Duration::from_secs(60))),
+        ("default", None),
+    ] {
+        state
+            .notification_manager
+            .add(
+                title.to_string(),
+                String::new(),
+                NotificationType::Info,
+                timeout,
+                app_state.clone(),
+            )
+            .await;
+    }
+    state.notification_manager.update(5);
+    let titles: Vec<&str> = state
+        .notification_manager
+        .notifications()
+        .iter()
+        .map(|n| n.title.as_str())
+        .collect();
+    assert_eq!(titles, vec!["stays", "default"]);
+}
+
+#[tokio::test]
+async fn test_update_spins_loading_toasts_instead_of_expiring_them() {
+    let app_state = Arc::new(Mutex::new(AppState::default()));
+    let mut state = app_state.lock().await;
+    let _loading = state
+        .notification_manager
+        .add(
+            "loading".to_string(),
+            String::new(),
+            NotificationType::Loading,
+            Some(Duration::ZERO),
+            app_state.clone(),
+        )
+        .await;
+    state.notification_manager.update(5);
+    let notifications = state.notification_manager.notifications();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].current_animation_frame_index, Some(1));
+}
+
+#[tokio::test]
+async fn test_add_without_timeout_uses_the_type_default() {
+    let app_state = Arc::new(Mutex::new(AppState::default()));
+    let mut state = app_state.lock().await;
+    for notification_type in [
+        NotificationType::Success,
+        NotificationType::Error,
+        NotificationType::Loading,
+    ] {
+        let _ = state
+            .notification_manager
+            .add(
+                String::new(),
+                String::new(),
+                notification_type,
+                None,
+                app_state.clone(),
+            )
+            .await;
+    }
+    let timeouts: Vec<Option<Duration>> = state
+        .notification_manager
+        .notifications()
+        .iter()
+        .map(|n| n.timeout)
+        .collect();
+    assert_eq!(
+        timeouts,
+        vec![
+            Some(Duration::from_secs(2)),
+            Some(Duration::from_secs(8)),
+            None
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_add_keeps_an_explicit_timeout() {
+    let app_state = Arc::new(Mutex::new(AppState::default()));
+    let mut state = app_state.lock().await;
+    state
+        .notification_manager
+        .add(
+            String::new(),
+            String::new(),
+            NotificationType::Error,
+            Some(Duration::from_secs(1)),
+            app_state.clone(),
+        )
+        .await;
+    assert_eq!(
+        state.notification_manager.notifications()[0].timeout,
+        Some(Duration::from_secs(1))
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_removes_the_loading_toast() {
+    let app_state = Arc::new(Mutex::new(AppState::default()));
+    let mut state = app_state.lock().await;
+    let loading = state
+        .notification_manager
+        .add(
+            "loading".to_string(),
+            String::new(),
+            NotificationType::Loading,
+            None,
+            app_state.clone(),
+        )
+        .await
+        .expect("loading toasts hand back a handle");
+    state.notification_manager.resolve(loading);
+    assert!(state.notification_manager.notifications().is_empty());
+}
+
+#[tokio::test]
+async fn test_do_not_disturb_only_lets_errors_and_loading_through() {
+    let app_state = Arc::new(Mutex::new(AppState::default()));
+    let mut state = app_state.lock().await;
+    state.notification_manager.set_do_not_disturb(true);
+    for notification_type in [
+        NotificationType::Info,
+        NotificationType::Success,
+        NotificationType::Warning,
+        NotificationType::Error,
+        NotificationType::Loading,
+    ] {
+        let _ = state
+            .notification_manager
+            .add(
+                String::new(),
+                String::new(),
+                notification_type,
+                None,
+                app_state.clone(),
+            )
+            .await;
+    }
+    let types: Vec<NotificationType> = state
+        .notification_manager
+        .notifications()
+        .iter()
+        .map(|n| n.notification_type.clone())
+        .collect();
+    assert_eq!(
+        types,
+        vec![NotificationType::Error, NotificationType::Loading]
+    );
+}
+
+#[test]
+fn test_do_not_disturb_silences_the_mention_bell() {
+    let mention = |content: &str| message(serde_json::json!({ "user": "bob", "content": content }));
+    let mut state = AppState {
+        username: Some("ree".to_string()),
+        ..AppState::default()
+    };
+    // the first pass only takes note of what's already there
+    let home = state.messages.entry("home".to_string()).or_default();
+    home.push_back(mention("old @ree"));
+    assert!(state.take_mention_bells(0).is_empty());
+
+    state.config.set_do_not_disturb(true, 0);
+    let home = state.messages.entry("home".to_string()).or_default();
+    home.push_back(mention("hey @ree"));
+    assert!(state.take_mention_bells(0).is_empty());
+
+    // and it doesn't ring for it once do not disturb is over either
+    state.config.set_do_not_disturb(false, 0);
+    assert!(state.take_mention_bells(0).is_empty());
+    let home = state.messages.entry("home".to_string()).or_default();
+    home.push_back(mention("again @ree"));
+    let bells = state.take_mention_bells(0);
+    assert_eq!(bells.len(), 1);
+    assert_eq!(bells[0].1, "again @ree");
+}
+
+#[test]
+fn test_notification_color_overrides_win_over_the_theme() {
+    let theme = AppState::default().current_theme;
+    let overrides = NotificationColors {
+        error: Some(Rgb(255, 128, 0)),
+        ..Default::default()
+    };
+    let error = Notification::new(
+        0,
+        String::new(),
+        String::new(),
+        NotificationType::Error,
+        None,
+    );
+    let info = Notification::new(
+        0,
+        String::new(),
+        String::new(),
+        NotificationType::Info,
+        None,
+    );
+
+    assert_eq!(
+        error.color(&theme, &overrides),
+        rgb_to_color(&Rgb(255, 128, 0))
+    );
+    // unset types keep following the theme
+    assert_eq!(
+        info.color(&theme, &overrides),
+        rgb_to_color(&theme.colors.info_color)
+    );
+    assert_eq!(
+        error.color(&theme, &NotificationColors::default()),
+        rgb_to_color(&theme.colors.error)
+    );
+}
+
+#[tokio::test]
+async fn test_toasts_waiting_for_a_spot_dont_expire() {
+    let app_state = Arc::new(Mutex::new(AppState::default()));
+    let mut state = app_state.lock().await;
+    for title in ["waiting", "shown"] {
+        state
+            .notification_manager
+            .add(
+                title.to_string(),
+                String::new(),
+                NotificationType::Info,
+                Some(Duration::from_millis(50)),
+                app_state.clone(),
+            )
+            .await;
+    }
+    state.notification_manager.update(1);
+    std::thread::sleep(Duration::from_millis(80));
+    state.notification_manager.update(1);
+    let titles: Vec<&str> = state
+        .notification_manager
+        .notifications()
+        .iter()
+        .map(|n| n.title.as_str())
+        .collect();
+    // the shown one ran out, the one under it only gets its turn now
+    assert_eq!(titles, vec!["waiting"]);
+}
@@ -3,20 +3,22 @@ use ratatui::{
     widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use crate::config::{NotificationAnchor, NotificationColors};
 use crate::{app::app_state::AppState, themes::Theme};
 use crate::tui::animation::{Animation, AnimationType};
 use crate::tui::notification::notification::Notification;
 use std::time::Duration;
 
-const NOTIFICATION_LIMIT: usize = 5;
-
 pub fn draw_notifications(f: &mut Frame, app_state: &mut AppState) {
+    let limit = app_state.config.max_notifications.max(1);
+    let anchor = app_state.config.notification_anchor;
     let notifications = app_state.notification_manager.notifications_mut();
     if notifications.is_empty() {
         return;
     }
 
     let theme = &app_state.current_theme;
+    let colors = &app_state.config.notification_colors;
     let area = f.area();
 
     let max_width = 40;
@@ -33,15 +35,13 @@ pub fn draw_notifications(f: &mut Frame, app_state: &mut AppState) {
         .filter(|n| n.animation.is_some() || n.timeout.map_or(true, |t| n.created_at.elapsed() < t))
         .collect();
 
-    notifications_to_display.sort_by_key(|n| n.created_at);
+    // by id, the manager pushes created_at back while a toast waits below
+    notifications_to_display.sort_by_key(|n| n.id);
 
-    // Apply NOTIFICATION_LIMIT after filtering and sorting
+    // Apply the limit after filtering and sorting, the older ones wait their turn
     let num_active_notifications = notifications_to_display.len();
-    let start_index = if num_active_notifications > NOTIFICATION_LIMIT {
-        num_active_notifications - NOTIFICATION_LIMIT
-    } else {
-        0
-    };
+    let hidden_count = num_active_notifications.saturating_sub(limit);
+    let start_index = hidden_count;
     let mut notifications_to_display_limited = notifications_to_display.drain(start_index..).collect::<Vec<_>>();
 
     let mut total_height = 0;
@@ -52,23 +52,45 @@ pub fn draw_notifications(f: &mut Frame, app_state: &mut AppState) {
         total_height += height;
     }
 
-    if num_active_notifications > NOTIFICATION_LIMIT {
-        total_height += 3;
+    if hidden_count > 0 {
+        total_height += 1;
     }
 
-    let popup_area = Rect::new(
-        area.width.saturating_sub(notification_width + 1),
-        1,
-        notification_width,
-        total_height,
-    );
+    let total_height = total_height.min(area.height.saturating_sub(2));
+    let x = match anchor {
+        NotificationAnchor::TopRight | NotificationAnchor::BottomRight => {
+            area.width.saturating_sub(notification_width + 1)
+        }
+        NotificationAnchor::TopLeft | NotificationAnchor::BottomLeft => 1,
+    };
+    let y = match anchor {
+        NotificationAnchor::TopRight | NotificationAnchor::TopLeft => 1,
+        NotificationAnchor::BottomRight | NotificationAnchor::BottomLeft => {
+            area.height.saturating_sub(total_height + 1)
+        }
+    };
+    let popup_area = Rect::new(x, y, notification_width, total_height);
 
     f.render_widget(Clear, popup_area);
 
     let mut y_offset = popup_area.y;
+    if hidden_count > 0 {
+        let more = Paragraph::new(format!("+{} more", hidden_count))
+            .alignment(Alignment::Right)
+            .style(
+                Style::default()
+                    .fg(crate::themes::rgb_to_color(&theme.colors.dim))
+                    .bg(crate::themes::rgb_to_color(&theme.colors.background)),
+            );
+        f.render_widget(more, Rect::new(popup_area.x, y_offset, popup_area.width, 1));
+        y_offset += 1;
+    }
 
     for (i, notification) in notifications_to_display_limited.iter_mut().enumerate() {
         let height = heights[i];
+        if y_offset + height > popup_area.bottom() {
+            break;
+        }
         let final_y = y_offset;
 
         if notification.animation.is_none() && !notification.animated_once {
@@ -121,7 +143,7 @@ pub fn draw_notifications(f: &mut Frame, app_state: &mut AppState) {
         }
 
         let render_area = Rect::new(current_x, current_y, popup_area.width, height);
-        draw_notification(f, notification, theme, render_area, current_bg_color);
+        draw_notification(f, notification, theme, colors, render_area, current_bg_color);
         y_offset += height;
     }
 
@@ -135,6 +157,7 @@ fn draw_notification(
     f: &mut Frame,
     notification: &crate::tui::notification::notification::Notification,
     theme: &Theme,
+    colors: &NotificationColors,
     area: Rect,
     bg_color: Color,
 ) {
@@ -148,7 +171,7 @@ fn draw_notification(
         .border_type(ratatui::widgets::BorderType::Rounded)
         .style(
             Style::default()
-                .fg(notification.color(theme))
+                .fg(notification.color(theme, colors))
                 .bg(bg_color),
         )
         .border_style(Style::default().bg(bg_color));
@@ -1,13 +1,18 @@
-use crate::app::app_state::AppState;
+use crate::app::app_state::{AccountDeletion, AppState, PasswordChange, ProfileUpdate};
 use crate::app::TuiPage;
 use std::fs;
 use crate::config;
 
+use crate::tui::auth::page::{validate_password, validate_username};
 use crate::tui::settings::state::{
-    DisconnectConfirmationState, FocusedPane, QuitConfirmationState, SettingsScreen, SettingsState,
+    DeleteAccountStep, DisconnectConfirmationState, FocusedPane, PasswordForm, ProfileField,
+    QuitConfirmationState, SettingsScreen, SettingsState,
 };
+use crate::tui::settings::preferences::PREFERENCES;
 use crate::tui::settings::SettingsEvent;
+use crate::tui::utils::sanitize_paste;
 use crossterm::event::{Event, KeyCode, KeyEventKind};
+use zeroize::{Zeroize, Zeroizing};
 
 pub async fn handle_settings_event(
     settings_state: &mut SettingsState,
@@ -15,6 +20,21 @@ pub async fn handle_settings_event(
     event: SettingsEvent,
 ) -> Option<TuiPage> {
     match event {
+        // only the screens with text fields take a paste, it's typed into them minus the line
+        // breaks (no Enter to confirm something by accident)
+        SettingsEvent::Key(Event::Paste(text))
+            if settings_state.focused_pane == FocusedPane::Right
+                && matches!(
+                    settings_state.screen,
+                    SettingsScreen::Profile
+                        | SettingsScreen::Password
+                        | SettingsScreen::DeleteAccount
+                ) =>
+        {
+            for c in sanitize_paste(&text, false).chars() {
+                handle_right_pane_events(settings_state, KeyCode::Char(c), app_state).await;
+            }
+        }
         SettingsEvent::Key(key_event) => {
             if let Event::Key(key) = key_event {
                 if key.kind == KeyEventKind::Press {
@@ -44,13 +64,11 @@ fn handle_left_pane_events(
         KeyCode::Up => settings_state.previous_main_setting(),
         KeyCode::Down => settings_state.next_main_setting(),
         KeyCode::Enter => {
-            if settings_state.main_selection == 3 {
-                // 3 is Quit
+            if settings_state.screen == SettingsScreen::Quit {
                 app_state.quit_confirmation_state = QuitConfirmationState::Active; // Directly update app_state
                 settings_state.focused_pane = FocusedPane::Right;
                 return Some(TuiPage::Settings); // Force redraw of settings page
-            } else if settings_state.main_selection == 2 {
-                // 2 is Disconnect
+            } else if settings_state.screen == SettingsScreen::Disconnect {
                 app_state.disconnect_confirmation_state = DisconnectConfirmationState::Active;
                 settings_state.focused_pane = FocusedPane::Right;
                 return Some(TuiPage::Settings);
@@ -70,15 +88,31 @@ async fn handle_right_pane_events(
     app_state: &mut AppState,
 ) -> Option<TuiPage> {
     match key_code {
-        KeyCode::Esc => return Some(TuiPage::Chat),
+        KeyCode::Esc => {
+            // don't reopen the settings halfway through deleting the account
+            settings_state.delete_account_step = DeleteAccountStep::Inactive;
+            return Some(TuiPage::Chat);
+        }
         _ => {
             match settings_state.screen {
                 SettingsScreen::Themes => {
                     handle_themes_events(settings_state, key_code, app_state);
                 }
+                SettingsScreen::Preferences => {
+                    handle_preferences_events(settings_state, key_code, app_state);
+                }
+                SettingsScreen::Profile => {
+                    handle_profile_events(settings_state, key_code, app_state);
+                }
+                SettingsScreen::Password => {
+                    handle_password_events(settings_state, key_code, app_state);
+                }
                 SettingsScreen::Help => {
                     handle_help_events(settings_state, key_code);
                 }
+                SettingsScreen::DeleteAccount => {
+                    handle_delete_account_events(settings_state, key_code, app_state);
+                }
                 SettingsScreen::Disconnect => {
                     if app_state.disconnect_confirmation_state
                         == DisconnectConfirmationState::Active
@@ -128,6 +162,179 @@ fn handle_themes_events(
     None
 }
 
+fn handle_profile_events(
+    settings_state: &mut SettingsState,
+    key_code: KeyCode,
+    app_state: &mut AppState,
+) -> Option<TuiPage> {
+    match (settings_state.profile_field, key_code) {
+        (_, KeyCode::Up) | (_, KeyCode::Down) => {
+            settings_state.profile_field = match settings_state.profile_field {
+                ProfileField::Username => ProfileField::Icon,
+                ProfileField::Icon => ProfileField::Username,
+            };
+        }
+        (ProfileField::Username, KeyCode::Char(c)) => {
+            settings_state.new_username.push(c);
+            settings_state.profile_error = None;
+        }
+        (ProfileField::Username, KeyCode::Backspace) => {
+            settings_state.new_username.pop();
+            settings_state.profile_error = None;
+        }
+        (ProfileField::Icon, KeyCode::Left) => settings_state.cycle_icon(false),
+        (ProfileField::Icon, KeyCode::Right) => settings_state.cycle_icon(true),
+        (_, KeyCode::Left) => settings_state.focused_pane = FocusedPane::Left,
+        (_, KeyCode::Enter) => {
+            if matches!(app_state.profile_update, ProfileUpdate::Sent { .. }) {
+                return None;
+            }
+            let username = settings_state.new_username.trim().to_string();
+            settings_state.profile_error = validate_username(&username);
+            if settings_state.profile_error.is_some() {
+                return None;
+            }
+            if app_state.username.as_deref() == Some(username.as_str())
+                && app_state.user_icon.as_deref() == Some(settings_state.new_icon.as_str())
+            {
+                settings_state.profile_error =
+                    Some("That's already you, nothing to save".to_string());
+                return None;
+            }
+            // the chat loop owns the websocket, it sends this on its next turn
+            app_state.profile_update = ProfileUpdate::Requested {
+                username,
+                icon: settings_state.new_icon.clone(),
+            };
+        }
+        (_, KeyCode::Esc) => return Some(TuiPage::Chat),
+        _ => {}
+    }
+    None
+}
+
+fn handle_password_events(
+    settings_state: &mut SettingsState,
+    key_code: KeyCode,
+    app_state: &mut AppState,
+) -> Option<TuiPage> {
+    let form = &mut settings_state.password_form;
+    match key_code {
+        KeyCode::Up => form.next_field(false),
+        KeyCode::Down | KeyCode::Tab => form.next_field(true),
+        // Left on an empty spot goes back to the menu like every other pane
+        KeyCode::Left if form.focused_input().cursor_position == 0 => {
+            settings_state.focused_pane = FocusedPane::Left
+        }
+        KeyCode::Left => form.focused_input().move_cursor_left(),
+        KeyCode::Right => form.focused_input().move_cursor_right(),
+        KeyCode::Char(c) => {
+            form.focused_input().insert_char(c);
+            form.error = None;
+        }
+        KeyCode::Backspace => {
+            form.focused_input().delete_char();
+            form.error = None;
+        }
+        KeyCode::Enter => {
+            if app_state.password_change == PasswordChange::Sent {
+                return None;
+            }
+            form.error = if form.current.text.is_empty() {
+                Some("Type your current password first".to_string())
+            } else if let Some(e) = validate_password(&form.new.text) {
+                Some(e)
+            } else if form.new.text != form.confirm.text {
+                Some("The new password and its confirmation don't match".to_string())
+            } else if form.new.text == form.current.text {
+                Some("That's the password you already have".to_string())
+            } else {
+                None
+            };
+            if form.error.is_some() {
+                return None;
+            }
+            // hashing needs the server's salt, the chat loop fetches it before sending
+            app_state.password_change = PasswordChange::Requested {
+                current: Zeroizing::new(std::mem::take(&mut form.current.text)),
+                new: Zeroizing::new(std::mem::take(&mut form.new.text)),
+            };
+            form.confirm.text.zeroize();
+            *form = PasswordForm::default();
+        }
+        _ => {}
+    }
+    None
+}
+
+fn handle_delete_account_events(
+    settings_state: &mut SettingsState,
+    key_code: KeyCode,
+    app_state: &mut AppState,
+) -> Option<TuiPage> {
+    match (settings_state.delete_account_step, key_code) {
+        (DeleteAccountStep::Inactive, KeyCode::Enter)
+            if app_state.account_deletion != AccountDeletion::Sent =>
+        {
+            settings_state.delete_account_input.clear();
+            settings_state.delete_account_step = DeleteAccountStep::TypeUsername;
+        }
+        (DeleteAccountStep::Inactive, KeyCode::Left) => {
+            settings_state.focused_pane = FocusedPane::Left
+        }
+        (DeleteAccountStep::TypeUsername, KeyCode::Char(c)) => {
+            settings_state.delete_account_input.push(c)
+        }
+        (DeleteAccountStep::TypeUsername, KeyCode::Backspace) => {
+            settings_state.delete_account_input.pop();
+        }
+        (DeleteAccountStep::TypeUsername, KeyCode::Enter)
+            if app_state.username.as_deref()
+                == Some(settings_state.delete_account_input.as_str()) =>
+        {
+            // start on "no", the last step should take a deliberate move
+            settings_state.delete_account_selection = 1;
+            settings_state.delete_account_step = DeleteAccountStep::Confirm;
+        }
+        (DeleteAccountStep::Confirm, KeyCode::Left) => settings_state.delete_account_selection = 0,
+        (DeleteAccountStep::Confirm, KeyCode::Right) => settings_state.delete_account_selection = 1,
+        (DeleteAccountStep::Confirm, KeyCode::Enter) => {
+            if settings_state.delete_account_selection == 0 {
+                // the chat loop owns the websocket, it sends this on its next turn
+                app_state.account_deletion = AccountDeletion::Requested;
+            }
+            settings_state.delete_account_step = DeleteAccountStep::Inactive;
+            settings_state.delete_account_input.clear();
+        }
+        _ => {}
+    }
+    None
+}
+
+fn handle_preferences_events(
+    settings_state: &mut SettingsState,
+    key_code: KeyCode,
+    app_state: &mut AppState,
+) -> Option<TuiPage> {
+    match key_code {
+        KeyCode::Up => settings_state.previous_preference(),
+        KeyCode::Down => settings_state.next_preference(),
+        KeyCode::Left => settings_state.focused_pane = FocusedPane::Left,
+        KeyCode::Enter | KeyCode::Char(' ') => {
+            if let Some(preference) = PREFERENCES.get(settings_state.preference_selection) {
+                preference.cycle(&mut app_state.config);
+                // icon fallback and truecolor are switches outside the config, like no-color
+                app_state.capabilities.apply(&app_state.config);
+                // most preferences change how messages look, so drop the cached renders
+                app_state.rendered_messages.clear();
+            }
+        }
+        KeyCode::Esc => return Some(TuiPage::Chat),
+        _ => {}
+    }
+    None
+}
+
 fn handle_help_events(settings_state: &mut SettingsState, key_code: KeyCode) -> Option<TuiPage> {
     // Removed underscore
     match key_code {
@@ -145,9 +352,12 @@ async fn handle_disconnect_events(
 ) -> Option<TuiPage> {
     match key_code {
         KeyCode::Enter => {
+            let _ = config::token_store::delete_token(
+                app_state.config.server.as_deref(),
+                app_state.config.username.as_deref(),
+            );
             app_state.clear_user_auth().await;
-            let config_path = config::get_config_path();
-            if config_path.exists() {
+            if let Ok(config_path) = config::get_config_path() {
                 let _ = fs::remove_file(config_path);
             }
             return Some(TuiPage::Auth);
@@ -165,17 +375,13 @@ fn handle_quit_confirmation_events(
     app_state: &mut AppState,
 ) -> Option<TuiPage> {
     match key_code {
-        KeyCode::Left => {
-            if app_state.quit_selection == 1 {
-                // If "Hell no" is selected
-                app_state.quit_selection = 0; // Select "Ye"
-            }
+        // If "Hell no" is selected
+        KeyCode::Left if app_state.quit_selection == 1 => {
+            app_state.quit_selection = 0; // Select "Ye"
         }
-        KeyCode::Right => {
-            if app_state.quit_selection == 0 {
-                // If "Ye" is selected
-                app_state.quit_selection = 1; // Select "Hell no"
-            }
+        // If "Ye" is selected
+        KeyCode::Right if app_state.quit_selection == 0 => {
+            app_state.quit_selection = 1; // Select "Hell no"
         }
         KeyCode::Enter => {
             if app_state.quit_selection == 0 {
@@ -217,21 +423,20 @@ async fn handle_disconnect_confirmation_events(
     app_state: &mut AppState,
 ) -> Option<TuiPage> {
     match key_code {
-        KeyCode::Left => {
-            if app_state.disconnect_selection == 1 {
-                app_state.disconnect_selection = 0;
-            }
+        KeyCode::Left if app_state.disconnect_selection == 1 => {
+            app_state.disconnect_selection = 0;
         }
-        KeyCode::Right => {
-            if app_state.disconnect_selection == 0 {
-                app_state.disconnect_selection = 1;
-            }
+        KeyCode::Right if app_state.disconnect_selection == 0 => {
+            app_state.disconnect_selection = 1;
         }
         KeyCode::Enter => {
             if app_state.disconnect_selection == 0 {
+                let _ = config::token_store::delete_token(
+                    app_state.config.server.as_deref(),
+                    app_state.config.username.as_deref(),
+                );
                 app_state.clear_user_auth().await;
-                let config_path = config::get_config_path();
-                if config_path.exists() {
+                if let Ok(config_path) = config::get_config_path() {
                     let _ = fs::remove_file(config_path);
                 }
                 return Some(TuiPage::Auth);
@@ -1,7 +1,7 @@
-use crate::themes::Theme;
+use crate::themes::{rgb_to_color, Theme};
 use ratatui::{
     layout::Rect,
-    style::{Style, Color},
+    style::Style,
     widgets::{List, ListItem, ListState},
     Frame,
 };
@@ -18,7 +18,7 @@ pub fn render_styled_list(
     let list_items: Vec<ListItem> = items
         .iter()
         .map(|&item| {
-            ListItem::new(item).style(Style::default().fg(Color::Rgb(theme.colors.text.0, theme.colors.text.1, theme.colors.text.2)))
+            ListItem::new(item).style(Style::default().fg(rgb_to_color(&theme.colors.text)))
         })
         .collect();
 
@@ -34,7 +34,7 @@ pub fn render_styled_list(
 
     let highlight_style = Style::default()
         .add_modifier(ratatui::style::Modifier::REVERSED)
-        .fg(Color::Rgb(theme.colors.selected_icon.0, theme.colors.selected_icon.1, theme.colors.selected_icon.2));
+        .fg(rgb_to_color(&theme.colors.selected_icon));
 
     let symbol_ref = highlight_symbol.as_ref().map(|s| s.as_str());
 
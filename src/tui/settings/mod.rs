@@ -1,6 +1,7 @@
 pub mod events;
 pub mod helpers;
 pub mod page;
+pub mod preferences;
 pub mod state;
 
 use crate::app::app_state::AppState;
@@ -8,8 +9,9 @@ use crate::app::TuiPage;
 use crate::tui::settings::events::handle_settings_event;
 use crate::tui::settings::page::draw_settings_ui;
 use crate::tui::settings::state::SettingsState;
+use crate::tui::keymap::draw_keybindings_overlay;
 use crate::tui::notification::ui::draw_notifications;
-use crossterm::event::{self, Event, KeyCode};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::backend::Backend;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -28,7 +30,7 @@ pub enum SettingsEvent {
 
 #[derive(Debug)]
 pub enum SettingsCommand {
-    UpdateState(SettingsState),
+    UpdateState(Box<SettingsState>),
     ChangePage(TuiPage),
     None,
 }
@@ -64,7 +66,9 @@ pub async fn run_settings_page<B: Backend>(
                 handle_settings_key_event(event, &mut app_state_locked, &mut settings_state).await; // Await here
 
             command_tx
-                .send(SettingsCommand::UpdateState(settings_state.clone()))
+                .send(SettingsCommand::UpdateState(Box::new(
+                    settings_state.clone(),
+                )))
                 .unwrap();
 
             if let Some(page) = page_to_return { // Use the awaited result directly
@@ -92,7 +96,7 @@ pub async fn run_settings_page<B: Backend>(
 
     loop {
         let mut app_state_locked = app_state.lock().await;
-        app_state_locked.notification_manager.update();
+        app_state_locked.update_notifications();
         terminal.draw(|f| {
             render_settings_popup::<B>(f, &mut app_state_locked, &mut current_settings_state, f.area())
                 .unwrap();
@@ -104,7 +108,7 @@ pub async fn run_settings_page<B: Backend>(
                 if let Some(command) = cmd {
                     match command {
                         SettingsCommand::UpdateState(new_state) => {
-                            current_settings_state = new_state;
+                            current_settings_state = *new_state;
                         },
                         SettingsCommand::ChangePage(page) => {
                             return Ok(Some(page));
@@ -122,7 +126,9 @@ pub async fn run_settings_page<B: Backend>(
             }
         };
 
-        if let Some(event) = event_poll_result {
+        if let Some(Event::Resize(..)) = event_poll_result {
+            terminal.clear()?;
+        } else if let Some(event) = event_poll_result {
             settings_tx.send(SettingsEvent::Key(event)).unwrap();
         } else {
             settings_tx.send(SettingsEvent::Tick).unwrap();
@@ -144,6 +150,7 @@ pub fn render_settings_popup<B: Backend>(
     );
     draw_settings_ui::<B>(frame, settings_state, theme, app_state, area);
 
+    draw_keybindings_overlay(frame, app_state);
     draw_notifications(frame, app_state);
     Ok(())
 }
@@ -154,6 +161,13 @@ pub async fn handle_settings_key_event(
     settings_state: &mut SettingsState,
 ) -> Option<TuiPage> {
     match event {
+        // the reference sits on top of the page and takes the keys while it's open, ? is
+        // typed like any letter in the text fields
+        SettingsEvent::Key(Event::Key(key))
+            if key.kind == KeyEventKind::Press
+                && app_state
+                    .keybindings
+                    .handle_key(&key, !settings_state.typing()) => {}
         SettingsEvent::Key(key_event) => {
             handle_settings_event(
                 settings_state,
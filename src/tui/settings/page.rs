@@ -1,17 +1,18 @@
-use crate::app::app_state::AppState;
+use crate::app::app_state::{AccountDeletion, AppState, PasswordChange, ProfileUpdate};
 use crate::themes::Theme;
 
+use crate::tui::settings::preferences::PREFERENCES;
 use crate::tui::settings::state::{
-    DisconnectConfirmationState, FocusedPane, QuitConfirmationState, SettingsScreen, SettingsState,
+    DeleteAccountStep, DisconnectConfirmationState, FocusedPane, ProfileField,
+    QuitConfirmationState, SettingsScreen, SettingsState, SETTINGS_SCREENS,
 };
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style, Stylize},
-    widgets::{Block, BorderType, Borders, Clear, List, ListItem, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
-
-const SETTINGS_OPTIONS: &[&str] = &[" Themes", "󰞋 Help", "  Disconnect", "  Quit"];
+use unicode_width::UnicodeWidthStr;
 
 const HELP_CONTENT: &[&str] = &[
     "Welcome to the Settings Help!",
@@ -28,6 +29,20 @@ const HELP_CONTENT: &[&str] = &[
     "  - Select a theme from the list to change the application's appearance.",
     "  - The selected theme will be applied immediately.",
     "",
+    "Preferences:",
+    "  - Use Up/Down to pick a preference and Enter or Space to change its value.",
+    "",
+    "Profile:",
+    "  - Type a new username, Up/Down to the icon and Left/Right to pick one.",
+    "  - Press Enter to save, the server has to agree (names are first come, first served).",
+    "",
+    "Password:",
+    "  - Fill in the current password and the new one twice, Up/Down or Tab between them.",
+    "  - Press Enter to change it.",
+    "",
+    "Delete account:",
+    "  - Press Enter, type your username, then say Ye. There's no undo.",
+    "",
     "Disconnect:",
     "  - Press Enter to disconnect from the server and return to the login screen.",
     "",
@@ -53,7 +68,11 @@ pub fn draw_settings_ui<B: ratatui::backend::Backend>(
     let main_area = centered_rect(60, 60, area); // Use the passed area as the base for centering
     f.render_widget(main_block, main_area);
 
-    let max_menu_item_width = SETTINGS_OPTIONS.iter().map(|s| s.len()).max().unwrap_or(0);
+    let max_menu_item_width = SETTINGS_SCREENS
+        .iter()
+        .map(|screen| screen.label().len())
+        .max()
+        .unwrap_or(0);
     // +2 for padding, +2 for borders (1 on each side)
     let left_pane_width = max_menu_item_width as u16 + 4;
 
@@ -87,7 +106,7 @@ fn draw_left_pane(f: &mut Frame, settings_state: &mut SettingsState, theme: &The
     let inner_area = menu_block.inner(area);
 
     let item_height = 3; // 1 for content, 2 for borders
-    let constraints: Vec<Constraint> = SETTINGS_OPTIONS
+    let constraints: Vec<Constraint> = SETTINGS_SCREENS
         .iter()
         .map(|_| Constraint::Length(item_height))
         .collect();
@@ -97,7 +116,8 @@ fn draw_left_pane(f: &mut Frame, settings_state: &mut SettingsState, theme: &The
         .constraints(constraints)
         .split(inner_area);
 
-    for (i, &name) in SETTINGS_OPTIONS.iter().enumerate() {
+    for (i, screen) in SETTINGS_SCREENS.iter().enumerate() {
+        let name = screen.label();
         let is_selected = i == settings_state.main_selection;
         let is_disabled = false; // Disconnect button is always enabled
 
@@ -123,8 +143,7 @@ fn draw_left_pane(f: &mut Frame, settings_state: &mut SettingsState, theme: &The
 
         let item_block = Block::default()
             .borders(Borders::ALL)
-            .border_type(BorderType::Rounded)
-            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_type(crate::themes::focus_border_type(is_selected))
             .border_style(border_style);
 
         let paragraph = Paragraph::new(name)
@@ -151,7 +170,7 @@ fn draw_right_pane<B: ratatui::backend::Backend>(
     };
 
     let block = Block::default()
-        .title(SETTINGS_OPTIONS[settings_state.main_selection])
+        .title(settings_state.screen.label())
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
         .border_type(ratatui::widgets::BorderType::Rounded)
@@ -165,7 +184,19 @@ fn draw_right_pane<B: ratatui::backend::Backend>(
         SettingsScreen::Themes => {
             draw_themes_pane::<B>(f, settings_state, theme, inner_area, app_state)
         }
+        SettingsScreen::Preferences => {
+            draw_preferences_pane(f, settings_state, theme, inner_area, app_state)
+        }
+        SettingsScreen::Profile => {
+            draw_profile_pane(f, settings_state, theme, inner_area, app_state)
+        }
+        SettingsScreen::Password => {
+            draw_password_pane::<B>(f, settings_state, theme, inner_area, app_state)
+        }
         SettingsScreen::Help => draw_help_pane(f, theme, inner_area),
+        SettingsScreen::DeleteAccount => {
+            draw_delete_account_pane(f, settings_state, theme, inner_area, app_state)
+        }
         SettingsScreen::Disconnect => {
             draw_disconnect_pane(f, settings_state, theme, inner_area, app_state)
         }
@@ -204,8 +235,10 @@ fn draw_themes_pane<B: ratatui::backend::Backend>(
             };
             let name_fg_color = icon_fg_color; // Same color for name
 
+            let icon =
+                crate::tui::icons::icon_or(&theme_preview.icon, theme_name.fallback_label());
             let icon_span = ratatui::text::Span::styled(
-                theme_preview.icon.clone(),
+                icon.to_string(),
                 Style::default().fg(icon_fg_color),
             );
             let name_span = ratatui::text::Span::styled(
@@ -214,8 +247,7 @@ fn draw_themes_pane<B: ratatui::backend::Backend>(
             );
 
             // Calculate width of icon and name part
-            let icon_name_width =
-                theme_preview.icon.len() as u16 + format!(" {:?}", theme_name).len() as u16;
+            let icon_name_width = (icon.width() + format!(" {:?}", theme_name).width()) as u16;
 
             let mut color_squares_spans: Vec<ratatui::text::Span> = Vec::new();
             let color_squares_rgb = vec![
@@ -257,9 +289,7 @@ fn draw_themes_pane<B: ratatui::backend::Backend>(
 
     let list = List::new(theme_items)
         .highlight_style(
-            Style::default()
-                .bg(crate::themes::rgb_to_color(&theme.colors.accent))
-                .fg(crate::themes::rgb_to_color(&theme.colors.background))
+            crate::themes::highlight_style(&theme.colors.background, &theme.colors.accent)
                 .add_modifier(Modifier::BOLD),
         )
         .highlight_symbol(" ");
@@ -267,6 +297,142 @@ fn draw_themes_pane<B: ratatui::backend::Backend>(
     f.render_stateful_widget(list, area, &mut settings_state.theme_list_state);
 }
 
+fn draw_preferences_pane(
+    f: &mut Frame,
+    settings_state: &mut SettingsState,
+    theme: &Theme,
+    area: Rect,
+    app_state: &AppState,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(3)])
+        .split(area);
+
+    let highlight_symbol_width = 2;
+    let available_width = chunks[0].width.saturating_sub(highlight_symbol_width) as usize;
+    let items: Vec<ListItem> = PREFERENCES
+        .iter()
+        .map(|preference| {
+            let label = preference.label();
+            let value = format!("< {} >", preference.value_label(&app_state.config));
+            let spacer_width = available_width
+                .saturating_sub(label.width() + value.width())
+                .max(1);
+            ListItem::new(ratatui::text::Line::from(vec![
+                ratatui::text::Span::raw(label),
+                ratatui::text::Span::raw(" ".repeat(spacer_width)),
+                ratatui::text::Span::raw(value),
+            ]))
+            .style(
+                Style::default()
+                    .fg(crate::themes::rgb_to_color(&theme.colors.text))
+                    .bg(crate::themes::rgb_to_color(&theme.colors.background)),
+            )
+        })
+        .collect();
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(settings_state.preference_selection));
+    let list = List::new(items)
+        .highlight_style(
+            crate::themes::highlight_style(&theme.colors.background, &theme.colors.accent)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol(" ");
+    f.render_stateful_widget(list, chunks[0], &mut list_state);
+
+    let description = PREFERENCES
+        .get(settings_state.preference_selection)
+        .map_or("", |p| p.description());
+    let p = Paragraph::new(description)
+        .style(
+            Style::default()
+                .fg(crate::themes::rgb_to_color(&theme.colors.dim))
+                .bg(crate::themes::rgb_to_color(&theme.colors.background)),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(p, chunks[1]);
+}
+
+fn draw_profile_pane(
+    f: &mut Frame,
+    settings_state: &mut SettingsState,
+    theme: &Theme,
+    area: Rect,
+    app_state: &AppState,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Username
+            Constraint::Length(3), // Icon
+            Constraint::Min(0),    // Status
+        ])
+        .split(area);
+
+    let is_focused = settings_state.focused_pane == FocusedPane::Right;
+    let field_block = |title: &'static str, field: ProfileField| {
+        let border_color = if is_focused && settings_state.profile_field == field {
+            &theme.colors.accent
+        } else {
+            &theme.colors.border
+        };
+        Block::default()
+            .title(title)
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(crate::themes::rgb_to_color(border_color)))
+            .bg(crate::themes::rgb_to_color(&theme.colors.background))
+    };
+    let text_style = Style::default()
+        .fg(crate::themes::rgb_to_color(&theme.colors.text))
+        .bg(crate::themes::rgb_to_color(&theme.colors.background));
+
+    let cursor = if is_focused && settings_state.profile_field == ProfileField::Username {
+        "▏"
+    } else {
+        ""
+    };
+    let username = Paragraph::new(format!("{}{}", settings_state.new_username, cursor))
+        .style(text_style)
+        .block(field_block("Username", ProfileField::Username));
+    f.render_widget(username, chunks[0]);
+
+    let icon = Paragraph::new(format!("< {} >", settings_state.new_icon))
+        .style(text_style)
+        .alignment(Alignment::Center)
+        .block(field_block("Icon", ProfileField::Icon));
+    f.render_widget(icon, chunks[1]);
+
+    let (status, color) = match (&settings_state.profile_error, &app_state.profile_update) {
+        (Some(error), _) => (error.clone(), &theme.colors.error),
+        (None, ProfileUpdate::Rejected(reason)) => (
+            format!("The server said no: {}", reason),
+            &theme.colors.error,
+        ),
+        (None, ProfileUpdate::Requested { .. } | ProfileUpdate::Sent { .. }) => {
+            ("Asking the server...".to_string(), &theme.colors.dim)
+        }
+        (None, ProfileUpdate::Idle) => (
+            format!(
+                "Currently {} {}, press Enter to save",
+                app_state.user_icon.as_deref().unwrap_or(""),
+                app_state.username.as_deref().unwrap_or("")
+            ),
+            &theme.colors.dim,
+        ),
+    };
+    let p = Paragraph::new(status)
+        .style(
+            Style::default()
+                .fg(crate::themes::rgb_to_color(color))
+                .bg(crate::themes::rgb_to_color(&theme.colors.background)),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(p, chunks[2]);
+}
+
 fn draw_help_pane(f: &mut Frame, theme: &Theme, area: Rect) {
     let help_text: Vec<ratatui::text::Line> = HELP_CONTENT.iter().map(|&s| s.into()).collect();
     let paragraph = Paragraph::new(help_text)
@@ -343,12 +509,11 @@ fn draw_quit_confirmation_pane(
         .split(chunks[1]);
 
     let ye_button_style = if app_state.quit_selection == 0 {
-        Style::default()
-            .fg(crate::themes::rgb_to_color(
-                &theme.colors.button_text_active,
-            ))
-            .bg(crate::themes::rgb_to_color(&theme.colors.button_bg_active))
-            .add_modifier(Modifier::BOLD)
+        crate::themes::highlight_style(
+            &theme.colors.button_text_active,
+            &theme.colors.button_bg_active,
+        )
+        .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
             .fg(crate::themes::rgb_to_color(&theme.colors.text))
@@ -356,12 +521,11 @@ fn draw_quit_confirmation_pane(
     };
 
     let no_button_style = if app_state.quit_selection == 1 {
-        Style::default()
-            .fg(crate::themes::rgb_to_color(
-                &theme.colors.button_text_active,
-            ))
-            .bg(crate::themes::rgb_to_color(&theme.colors.button_bg_active))
-            .add_modifier(Modifier::BOLD)
+        crate::themes::highlight_style(
+            &theme.colors.button_text_active,
+            &theme.colors.button_bg_active,
+        )
+        .add_modifier(Modifier::BOLD)
     } else {
         Style::default()
             .fg(crate::themes::rgb_to_color(&theme.colors.text))
@@ -389,6 +553,174 @@ fn draw_quit_confirmation_pane(
     f.render_widget(no_button, button_chunks[1]);
 }
 
+fn draw_password_pane<B: ratatui::backend::Backend>(
+    f: &mut Frame,
+    settings_state: &mut SettingsState,
+    theme: &Theme,
+    area: Rect,
+    app_state: &AppState,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0), // Status
+        ])
+        .split(area);
+
+    let form = &mut settings_state.password_form;
+    let is_focused = settings_state.focused_pane == FocusedPane::Right;
+    let field = form.field;
+    for (i, input) in form.inputs_mut().into_iter().enumerate() {
+        // only draw the cursor while the pane has the keyboard
+        input.is_focused = is_focused && i == field;
+        input.render::<B>(f, chunks[i], theme);
+    }
+
+    let (status, color) = match (&form.error, &app_state.password_change) {
+        (Some(error), _) => (error.clone(), &theme.colors.error),
+        (None, PasswordChange::Rejected(reason)) => (
+            format!("The server said no: {}", reason),
+            &theme.colors.error,
+        ),
+        (None, PasswordChange::Requested { .. } | PasswordChange::Sent) => {
+            ("Asking the server...".to_string(), &theme.colors.dim)
+        }
+        (None, PasswordChange::Changed) => (
+            "Password changed 󰄬".to_string(),
+            &theme.colors.success_color,
+        ),
+        (None, PasswordChange::Idle) => (
+            "Press Enter to change the password".to_string(),
+            &theme.colors.dim,
+        ),
+    };
+    let p = Paragraph::new(status)
+        .style(
+            Style::default()
+                .fg(crate::themes::rgb_to_color(color))
+                .bg(crate::themes::rgb_to_color(&theme.colors.background)),
+        )
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(p, chunks[3]);
+}
+
+fn draw_delete_account_pane(
+    f: &mut Frame,
+    settings_state: &mut SettingsState,
+    theme: &Theme,
+    area: Rect,
+    app_state: &AppState,
+) {
+    let text_style = Style::default()
+        .fg(crate::themes::rgb_to_color(&theme.colors.text))
+        .bg(crate::themes::rgb_to_color(&theme.colors.background));
+
+    if settings_state.delete_account_step == DeleteAccountStep::Inactive {
+        let (text, color) = match &app_state.account_deletion {
+            AccountDeletion::Rejected(reason) => (
+                format!("The server wouldn't delete the account: {}", reason),
+                &theme.colors.error,
+            ),
+            AccountDeletion::Requested | AccountDeletion::Sent => {
+                ("Deleting the account...".to_string(), &theme.colors.dim)
+            }
+            AccountDeletion::Idle => (
+                "Press Enter to delete your account, messages and all.".to_string(),
+                &theme.colors.text,
+            ),
+        };
+        let p = Paragraph::new(text)
+            .style(text_style.fg(crate::themes::rgb_to_color(color)))
+            .alignment(Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true });
+        f.render_widget(p, area);
+        return;
+    }
+
+    let confirmation_block = Block::default()
+        .title("Delete Account 󰆴")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(crate::themes::rgb_to_color(&theme.colors.error)))
+        .bg(crate::themes::rgb_to_color(&theme.colors.background));
+    let inner_area = confirmation_block.inner(area);
+    f.render_widget(confirmation_block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(0),    // Message
+            Constraint::Length(3), // Input or buttons
+        ])
+        .split(inner_area);
+
+    if settings_state.delete_account_step == DeleteAccountStep::TypeUsername {
+        let message = format!(
+            "Type '{}' to confirm, Esc to back out",
+            app_state.username.as_deref().unwrap_or("")
+        );
+        let p = Paragraph::new(message)
+            .style(text_style)
+            .alignment(Alignment::Center)
+            .wrap(ratatui::widgets::Wrap { trim: true });
+        f.render_widget(p, chunks[0]);
+
+        let matches =
+            app_state.username.as_deref() == Some(settings_state.delete_account_input.as_str());
+        let border_color = if matches {
+            &theme.colors.error
+        } else {
+            &theme.colors.border
+        };
+        let input = Paragraph::new(format!("{}▏", settings_state.delete_account_input))
+            .style(text_style)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(crate::themes::rgb_to_color(border_color))),
+            );
+        f.render_widget(input, chunks[1]);
+        return;
+    }
+
+    let p = Paragraph::new("Last chance, this can't be undone. Delete it for real?")
+        .style(text_style)
+        .alignment(Alignment::Center)
+        .wrap(ratatui::widgets::Wrap { trim: true });
+    f.render_widget(p, chunks[0]);
+
+    let button_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    for (i, label) in ["Ye, delete it", "Hell no"].into_iter().enumerate() {
+        let style = if settings_state.delete_account_selection == i {
+            crate::themes::highlight_style(
+                &theme.colors.button_text_active,
+                &theme.colors.button_bg_active,
+            )
+            .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+                .fg(crate::themes::rgb_to_color(&theme.colors.text))
+                .bg(crate::themes::rgb_to_color(&theme.colors.button))
+        };
+        let button = Paragraph::new(label)
+            .style(style)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded),
+            );
+        f.render_widget(button, button_chunks[i]);
+    }
+}
+
 fn draw_disconnect_pane(
     f: &mut Frame,
     settings_state: &mut SettingsState,
@@ -435,12 +767,11 @@ fn draw_disconnect_pane(
             .split(chunks[1]);
 
         let ye_button_style = if app_state.disconnect_selection == 0 {
-            Style::default()
-                .fg(crate::themes::rgb_to_color(
-                    &theme.colors.button_text_active,
-                ))
-                .bg(crate::themes::rgb_to_color(&theme.colors.button_bg_active))
-                .add_modifier(Modifier::BOLD)
+            crate::themes::highlight_style(
+                &theme.colors.button_text_active,
+                &theme.colors.button_bg_active,
+            )
+            .add_modifier(Modifier::BOLD)
         } else {
             Style::default()
                 .fg(crate::themes::rgb_to_color(&theme.colors.text))
@@ -448,12 +779,11 @@ fn draw_disconnect_pane(
         };
 
         let no_button_style = if app_state.disconnect_selection == 1 {
-            Style::default()
-                .fg(crate::themes::rgb_to_color(
-                    &theme.colors.button_text_active,
-                ))
-                .bg(crate::themes::rgb_to_color(&theme.colors.button_bg_active))
-                .add_modifier(Modifier::BOLD)
+            crate::themes::highlight_style(
+                &theme.colors.button_text_active,
+                &theme.colors.button_bg_active,
+            )
+            .add_modifier(Modifier::BOLD)
         } else {
             Style::default()
                 .fg(crate::themes::rgb_to_color(&theme.colors.text))
@@ -0,0 +1,361 @@
+use crate::config::{
+    ClipboardMode, Config, CursorShape, NotificationAnchor, TokenStorage, TrueColorMode,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Preference {
+    MessageGroupSpacing,
+    ShowTimestamps,
+    ZebraStripes,
+    CollapseAfterLines,
+    LinkPreviews,
+    MaxMessageLength,
+    ReduceMotion,
+    NoColor,
+    IconFallback,
+    TrueColor,
+    MaxMessageWidth,
+    TabSwitchesChannels,
+    CursorShape,
+    CursorBlink,
+    ScrollOnSend,
+    SendInterval,
+    ConfirmUnknownCommands,
+    TokenStorage,
+    NotificationAnchor,
+    MaxNotifications,
+    Clipboard,
+    HomeFrameDuration,
+    DoNotDisturb,
+    DoNotDisturbMinutes,
+}
+
+pub const PREFERENCES: &[Preference] = &[
+    Preference::MessageGroupSpacing,
+    Preference::ShowTimestamps,
+    Preference::ZebraStripes,
+    Preference::CollapseAfterLines,
+    Preference::LinkPreviews,
+    Preference::MaxMessageLength,
+    Preference::ReduceMotion,
+    Preference::NoColor,
+    Preference::IconFallback,
+    Preference::TrueColor,
+    Preference::MaxMessageWidth,
+    Preference::TabSwitchesChannels,
+    Preference::CursorShape,
+    Preference::CursorBlink,
+    Preference::ScrollOnSend,
+    Preference::SendInterval,
+    Preference::ConfirmUnknownCommands,
+    Preference::TokenStorage,
+    Preference::NotificationAnchor,
+    Preference::MaxNotifications,
+    Preference::Clipboard,
+    Preference::HomeFrameDuration,
+    Preference::DoNotDisturb,
+    Preference::DoNotDisturbMinutes,
+];
+
+const MAX_MESSAGE_LENGTHS: &[usize] = &[500, 1000, 2000, 4000, 0];
+const COLLAPSE_AFTER_LINES: &[usize] = &[0, 10, 20, 40];
+const MAX_MESSAGE_WIDTHS: &[Option<u16>] = &[None, Some(80), Some(100), Some(120), Some(160)];
+const MAX_NOTIFICATIONS: &[usize] = &[1, 3, 5, 8];
+const HOME_FRAME_DURATIONS: &[u64] = &[250, 500, 1000];
+const CURSOR_SHAPES: &[CursorShape] = &[
+    CursorShape::Default,
+    CursorShape::Block,
+    CursorShape::Bar,
+    CursorShape::Underline,
+];
+const TRUECOLOR_MODES: &[TrueColorMode] =
+    &[TrueColorMode::Auto, TrueColorMode::On, TrueColorMode::Off];
+const SEND_INTERVALS: &[u64] = &[0, 150, 500, 1000];
+const DO_NOT_DISTURB_MINUTES: &[u64] = &[0, 30, 60, 120, 480];
+const NOTIFICATION_ANCHORS: &[NotificationAnchor] = &[
+    NotificationAnchor::TopRight,
+    NotificationAnchor::TopLeft,
+    NotificationAnchor::BottomRight,
+    NotificationAnchor::BottomLeft,
+];
+
+impl Preference {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Preference::MessageGroupSpacing => "󰕭 Space between authors",
+            Preference::ShowTimestamps => "󰥔 Message times",
+            Preference::ZebraStripes => "󰕰 Striped messages",
+            Preference::CollapseAfterLines => "󰁃 Collapse long messages",
+            Preference::LinkPreviews => "󰌷 Link previews",
+            Preference::MaxMessageLength => "󰦨 Max message length",
+            Preference::ReduceMotion => "󰔡 Reduce motion",
+            Preference::NoColor => "󰸌 No color",
+            Preference::IconFallback => "󰀻 Icon fallback",
+            Preference::TrueColor => "󰏘 True color",
+            Preference::MaxMessageWidth => "󰉶 Reading width",
+            Preference::TabSwitchesChannels => "󰌒 Tab in channel list",
+            Preference::CursorShape => " Cursor shape",
+            Preference::CursorBlink => "󰈈 Cursor blink",
+            Preference::ScrollOnSend => "󰁅 Scroll down on send",
+            Preference::SendInterval => "󰓅 Flood guard",
+            Preference::ConfirmUnknownCommands => "󰘥 Confirm unknown commands",
+            Preference::TokenStorage => "󰌆 Token storage",
+            Preference::NotificationAnchor => "󰂚 Notification corner",
+            Preference::Clipboard => "󰅍 Clipboard",
+            Preference::MaxNotifications => "󰂟 Notifications at once",
+            Preference::HomeFrameDuration => "󰑮 Logo animation speed",
+            Preference::DoNotDisturb => "󰂛 Do not disturb",
+            Preference::DoNotDisturbMinutes => "󰔛 Do not disturb for",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Preference::MessageGroupSpacing => {
+                "Blank lines between messages from different people. 0 if u like it dense."
+            }
+            Preference::ShowTimestamps => {
+                "The time next to each author. Off keeps it cleaner, the full date of the message at the bottom shows up while the messages pane is focused (t toggles it there too)."
+            }
+            Preference::ZebraStripes => {
+                "Tints every other group of messages a little so busy channels are easier to follow. Needs colors."
+            }
+            Preference::CollapseAfterLines => {
+                "Messages longer than this many lines only show their start. Focus the messages pane and hit Enter to read the rest."
+            }
+            Preference::LinkPreviews => {
+                "Shows the title of linked pages under the message. That means ur client visits every link posted, so it's off unless u want it. Links sent as <https://...> never get one."
+            }
+            Preference::MaxMessageLength => {
+                "Stops u from sending (or pasting) a novel by accident. Off means no limit."
+            }
+            Preference::ReduceMotion => {
+                "No moving titles, logo frames, typewriter text or GIFs. Nice on slow terminals or over SSH."
+            }
+            Preference::NoColor => {
+                "Only ur terminal's own colors, with bold, underline and reverse for what stands out. Always on when NO_COLOR is set."
+            }
+            Preference::IconFallback => {
+                "Plain characters instead of icons when no Nerd Font was found. Turn it off if u know u have one."
+            }
+            Preference::TrueColor => {
+                "Full 24-bit colors. Auto trusts COLORTERM and assumes yes when it's not set, Off brings the themes down to 256 colors for terminals that mess them up."
+            }
+            Preference::MaxMessageWidth => {
+                "Messages wrap at this many columns so ultrawide terminals stay readable. Full uses the whole pane."
+            }
+            Preference::TabSwitchesChannels => {
+                "What Tab does while the channel list is focused. Up/Down always switch channels there."
+            }
+            Preference::CursorShape => {
+                "The cursor while u type a message. Terminal keeps whatever ur terminal is set to."
+            }
+            Preference::CursorBlink => {
+                "Whether the cursor blinks, for a shape picked above. The terminal's own shape blinks like it always does."
+            }
+            Preference::ScrollOnSend => {
+                "Sending a message while reading older ones jumps back to the newest so u see it land. Off keeps u where u were."
+            }
+            Preference::SendInterval => {
+                "Messages sent closer together than this are held back, so a stuck Enter doesn't flood the channel."
+            }
+            Preference::ConfirmUnknownCommands => {
+                "Asks before a mistyped /command goes out as a normal message. Off if u post slashes on purpose a lot."
+            }
+            Preference::TokenStorage => {
+                "Keyring keeps ur login token out of reetui.json. Needs Keychain, Credential Manager or a Secret Service (GNOME Keyring, KWallet)."
+            }
+            Preference::Clipboard => {
+                "Auto uses the system clipboard, or asks the terminal (OSC 52) over SSH or when there's none. Terminal always asks it."
+            }
+            Preference::NotificationAnchor => {
+                "Which corner of the screen the toasts pile up in."
+            }
+            Preference::MaxNotifications => {
+                "How many toasts show at the same time, the others wait behind a \"+N more\" line."
+            }
+            Preference::HomeFrameDuration => {
+                "How long each frame of the home page logo stays up. Reduce motion keeps it still."
+            }
+            Preference::DoNotDisturb => {
+                "Only errors pop up. Messages and unread counts keep coming in, just quietly."
+            }
+            Preference::DoNotDisturbMinutes => {
+                "How long do not disturb stays on before turning itself off. Counts from when u turn it on."
+            }
+        }
+    }
+
+    pub fn value_label(&self, config: &Config) -> String {
+        match self {
+            Preference::MessageGroupSpacing => config.message_group_spacing.to_string(),
+            Preference::ShowTimestamps => on_off(config.show_timestamps),
+            Preference::ZebraStripes => on_off(config.zebra_stripes),
+            Preference::CollapseAfterLines => match config.collapse_after_lines {
+                0 => "Off".to_string(),
+                lines => format!("{} lines", lines),
+            },
+            Preference::LinkPreviews => on_off(config.link_previews),
+            Preference::MaxMessageLength => match config.max_message_length {
+                0 => "Off".to_string(),
+                max => max.to_string(),
+            },
+            Preference::ReduceMotion => on_off(config.reduce_motion),
+            Preference::NoColor if crate::themes::no_color_env() => "On (NO_COLOR)".to_string(),
+            Preference::NoColor => on_off(config.no_color),
+            Preference::IconFallback => on_off(config.icon_fallback),
+            Preference::TrueColor => match config.truecolor {
+                TrueColorMode::Auto => "Auto",
+                TrueColorMode::On => "On",
+                TrueColorMode::Off => "Off",
+            }
+            .to_string(),
+            Preference::MaxMessageWidth => match config.max_message_width {
+                Some(width) => width.to_string(),
+                None => "Full".to_string(),
+            },
+            Preference::TabSwitchesChannels => if config.tab_switches_channels {
+                "Next channel"
+            } else {
+                "Next pane"
+            }
+            .to_string(),
+            Preference::CursorShape => match config.cursor_shape {
+                CursorShape::Default => "Terminal",
+                CursorShape::Block => "Block",
+                CursorShape::Bar => "Bar",
+                CursorShape::Underline => "Underline",
+            }
+            .to_string(),
+            Preference::CursorBlink => on_off(config.cursor_blink),
+            Preference::ScrollOnSend => on_off(config.scroll_on_send),
+            Preference::SendInterval => match config.min_send_interval_ms {
+                0 => on_off(false),
+                interval => format!("{}ms", interval),
+            },
+            Preference::ConfirmUnknownCommands => on_off(config.confirm_unknown_commands),
+            Preference::TokenStorage => match config.token_storage {
+                TokenStorage::PlainFile => "Plain file".to_string(),
+                TokenStorage::Keyring => "Keyring".to_string(),
+            },
+            Preference::NotificationAnchor => match config.notification_anchor {
+                NotificationAnchor::TopRight => "Top right",
+                NotificationAnchor::TopLeft => "Top left",
+                NotificationAnchor::BottomRight => "Bottom right",
+                NotificationAnchor::BottomLeft => "Bottom left",
+            }
+            .to_string(),
+            Preference::MaxNotifications => config.max_notifications.to_string(),
+            Preference::Clipboard => match config.clipboard {
+                ClipboardMode::Auto => "Auto",
+                ClipboardMode::Osc52 => "Terminal (OSC 52)",
+            }
+            .to_string(),
+            Preference::HomeFrameDuration => format!("{}ms", config.home_frame_duration_ms),
+            Preference::DoNotDisturb => match config.do_not_disturb_until {
+                Some(until) if config.do_not_disturb => chrono::DateTime::from_timestamp(until, 0)
+                    .map(|until| until.with_timezone(&chrono::Local))
+                    .map_or_else(
+                        || on_off(true),
+                        |until| format!("On until {}", until.format("%H:%M")),
+                    ),
+                _ => on_off(config.do_not_disturb),
+            },
+            Preference::DoNotDisturbMinutes => match config.do_not_disturb_minutes {
+                0 => "Until turned off".to_string(),
+                minutes if minutes % 60 == 0 => format!("{} h", minutes / 60),
+                minutes => format!("{} min", minutes),
+            },
+        }
+    }
+
+    /// Moves the preference to its next value.
+    pub fn cycle(&self, config: &mut Config) {
+        match self {
+            Preference::MessageGroupSpacing => {
+                config.message_group_spacing = (config.message_group_spacing + 1) % 2;
+            }
+            Preference::MaxMessageLength => {
+                config.max_message_length = next_in(MAX_MESSAGE_LENGTHS, config.max_message_length);
+            }
+            Preference::ShowTimestamps => config.show_timestamps = !config.show_timestamps,
+            Preference::ZebraStripes => config.zebra_stripes = !config.zebra_stripes,
+            Preference::LinkPreviews => config.link_previews = !config.link_previews,
+            Preference::CollapseAfterLines => {
+                config.collapse_after_lines =
+                    next_in(COLLAPSE_AFTER_LINES, config.collapse_after_lines);
+            }
+            Preference::ReduceMotion => config.reduce_motion = !config.reduce_motion,
+            // colors are converted without the config at hand, so the switch is flipped here
+            Preference::NoColor => {
+                config.no_color = !config.no_color;
+                crate::themes::set_no_color(config.no_color_active());
+            }
+            Preference::IconFallback => config.icon_fallback = !config.icon_fallback,
+            Preference::TrueColor => {
+                config.truecolor = next_in(TRUECOLOR_MODES, config.truecolor);
+            }
+            Preference::MaxMessageWidth => {
+                config.max_message_width = next_in(MAX_MESSAGE_WIDTHS, config.max_message_width);
+            }
+            Preference::TabSwitchesChannels => {
+                config.tab_switches_channels = !config.tab_switches_channels;
+            }
+            Preference::CursorShape => {
+                config.cursor_shape = next_in(CURSOR_SHAPES, config.cursor_shape);
+            }
+            Preference::CursorBlink => config.cursor_blink = !config.cursor_blink,
+            Preference::ScrollOnSend => config.scroll_on_send = !config.scroll_on_send,
+            Preference::SendInterval => {
+                config.min_send_interval_ms = next_in(SEND_INTERVALS, config.min_send_interval_ms);
+            }
+            Preference::ConfirmUnknownCommands => {
+                config.confirm_unknown_commands = !config.confirm_unknown_commands;
+            }
+            // the token moves on the next save
+            Preference::TokenStorage => {
+                config.token_storage = match config.token_storage {
+                    TokenStorage::PlainFile => TokenStorage::Keyring,
+                    TokenStorage::Keyring => TokenStorage::PlainFile,
+                };
+            }
+            Preference::NotificationAnchor => {
+                config.notification_anchor =
+                    next_in(NOTIFICATION_ANCHORS, config.notification_anchor);
+            }
+            Preference::Clipboard => {
+                config.clipboard = match config.clipboard {
+                    ClipboardMode::Auto => ClipboardMode::Osc52,
+                    ClipboardMode::Osc52 => ClipboardMode::Auto,
+                };
+            }
+            Preference::MaxNotifications => {
+                config.max_notifications = next_in(MAX_NOTIFICATIONS, config.max_notifications);
+            }
+            Preference::HomeFrameDuration => {
+                config.home_frame_duration_ms =
+                    next_in(HOME_FRAME_DURATIONS, config.home_frame_duration_ms);
+            }
+            Preference::DoNotDisturb => {
+                config.set_do_not_disturb(!config.do_not_disturb, chrono::Utc::now().timestamp());
+            }
+            Preference::DoNotDisturbMinutes => {
+                config.do_not_disturb_minutes =
+                    next_in(DO_NOT_DISTURB_MINUTES, config.do_not_disturb_minutes);
+            }
+        }
+    }
+}
+
+fn on_off(value: bool) -> String {
+    if value { "On" } else { "Off" }.to_string()
+}
+
+// next value after `current` in `values`, wrapping around (and starting over if `current` isn't there)
+fn next_in<T: PartialEq + Copy>(values: &[T], current: T) -> T {
+    let next = values
+        .iter()
+        .position(|&v| v == current)
+        .map_or(0, |i| (i + 1) % values.len());
+    values[next]
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use crate::themes::ThemeName;
+use crate::tui::auth::page::ICONS;
+use crate::tui::settings::preferences::PREFERENCES;
+use crate::tui::text_input::TextInput;
 use ratatui::widgets::ListState;
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
@@ -25,11 +28,118 @@ pub enum DisconnectConfirmationState {
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
 pub enum SettingsScreen {
     Themes,
+    Preferences,
+    Profile,
+    Password,
     Help,
+    DeleteAccount,
     Disconnect,
     Quit,
 }
 
+// the settings menu, top to bottom
+pub const SETTINGS_SCREENS: &[SettingsScreen] = &[
+    SettingsScreen::Themes,
+    SettingsScreen::Preferences,
+    SettingsScreen::Profile,
+    SettingsScreen::Password,
+    SettingsScreen::Help,
+    SettingsScreen::DeleteAccount,
+    SettingsScreen::Disconnect,
+    SettingsScreen::Quit,
+];
+
+impl SettingsScreen {
+    /// The screen's entry in the settings menu, also the title of its pane.
+    pub fn label(self) -> &'static str {
+        match self {
+            SettingsScreen::Themes => " Themes",
+            SettingsScreen::Preferences => "󰒓 Preferences",
+            SettingsScreen::Profile => "󰀄 Profile",
+            SettingsScreen::Password => "󰌾 Password",
+            SettingsScreen::Help => "󰞋 Help",
+            SettingsScreen::DeleteAccount => "󰆴 Delete account",
+            SettingsScreen::Disconnect => "  Disconnect",
+            SettingsScreen::Quit => "  Quit",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ProfileField {
+    Username,
+    Icon,
+}
+
+/// The change-password form, masked like the password box on the auth page.
+#[derive(Clone, Debug)]
+pub struct PasswordForm {
+    pub current: TextInput,
+    pub new: TextInput,
+    pub confirm: TextInput,
+    /// index into [current, new, confirm]
+    pub field: usize,
+    pub error: Option<String>,
+}
+
+impl Default for PasswordForm {
+    fn default() -> Self {
+        let masked = |label: &str| {
+            let mut input = TextInput::new(label.to_string());
+            input.is_password = true;
+            input.password_char = Some('󱗼');
+            input
+        };
+        let mut form = Self {
+            current: masked(" Current password"),
+            new: masked(" New password"),
+            confirm: masked(" Confirm new password"),
+            field: 0,
+            error: None,
+        };
+        form.update_focus();
+        form
+    }
+}
+
+impl PasswordForm {
+    pub fn inputs_mut(&mut self) -> [&mut TextInput; 3] {
+        [&mut self.current, &mut self.new, &mut self.confirm]
+    }
+
+    pub fn focused_input(&mut self) -> &mut TextInput {
+        match self.field {
+            0 => &mut self.current,
+            1 => &mut self.new,
+            _ => &mut self.confirm,
+        }
+    }
+
+    pub fn next_field(&mut self, forward: bool) {
+        self.field = if forward {
+            (self.field + 1) % 3
+        } else {
+            (self.field + 2) % 3
+        };
+        self.update_focus();
+    }
+
+    fn update_focus(&mut self) {
+        let field = self.field;
+        for (i, input) in self.inputs_mut().into_iter().enumerate() {
+            input.is_focused = i == field;
+        }
+    }
+}
+
+/// Deleting the account takes typing the username and then a yes, so a stray Enter can't do it.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum DeleteAccountStep {
+    Inactive,
+    TypeUsername,
+    Confirm,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SettingsState {
     pub screen: SettingsScreen,
@@ -46,9 +156,31 @@ pub struct SettingsState {
     pub quit_selection: usize,
     pub disconnect_confirmation_state: DisconnectConfirmationState,
     pub disconnect_selection: usize,
+    pub preference_selection: usize,
+    pub profile_field: ProfileField,
+    /// why the profile can't be saved as typed, shown under the fields
+    pub profile_error: Option<String>,
+    #[serde(skip)]
+    pub password_form: PasswordForm,
+    pub delete_account_step: DeleteAccountStep,
+    pub delete_account_input: String,
+    /// 0 for the yes button, 1 for no, like the quit and disconnect dialogs
+    pub delete_account_selection: usize,
 }
 
 impl SettingsState {
+    /// Whether keys go into a text field right now.
+    pub fn typing(&self) -> bool {
+        self.focused_pane == FocusedPane::Right
+            && match self.screen {
+                SettingsScreen::Profile | SettingsScreen::Password => true,
+                SettingsScreen::DeleteAccount => {
+                    self.delete_account_step == DeleteAccountStep::TypeUsername
+                }
+                _ => false,
+            }
+    }
+
     pub fn new(
         themes: Vec<ThemeName>,
         current_theme_name: ThemeName,
@@ -69,7 +201,7 @@ impl SettingsState {
         theme_list_state.select(Some(theme_selection));
 
         Self {
-            screen: SettingsScreen::Themes,
+            screen: SETTINGS_SCREENS[main_selection % SETTINGS_SCREENS.len()],
             focused_pane,
             main_selection,
             theme_list_state,
@@ -82,6 +214,13 @@ impl SettingsState {
             quit_selection: 0,
             disconnect_confirmation_state: DisconnectConfirmationState::Inactive,
             disconnect_selection: 0,
+            preference_selection: 0,
+            profile_field: ProfileField::Username,
+            profile_error: None,
+            password_form: PasswordForm::default(),
+            delete_account_step: DeleteAccountStep::Inactive,
+            delete_account_input: String::new(),
+            delete_account_selection: 1,
         }
     }
 
@@ -90,13 +229,13 @@ impl SettingsState {
     }
 
     pub fn next_main_setting(&mut self) {
-        self.main_selection = (self.main_selection + 1) % 4; // 4 settings: Themes, Help, User Settings, Quit
+        self.main_selection = (self.main_selection + 1) % SETTINGS_SCREENS.len();
         self.update_screen_from_selection();
     }
 
     pub fn previous_main_setting(&mut self) {
         self.main_selection = if self.main_selection == 0 {
-            3
+            SETTINGS_SCREENS.len() - 1
         } else {
             self.main_selection - 1
         };
@@ -104,13 +243,30 @@ impl SettingsState {
     }
 
     fn update_screen_from_selection(&mut self) {
-        self.screen = match self.main_selection {
-            0 => SettingsScreen::Themes,
-            1 => SettingsScreen::Help,
-            2 => SettingsScreen::Disconnect,
-            3 => SettingsScreen::Quit,
-            _ => unreachable!(),
+        self.screen = SETTINGS_SCREENS[self.main_selection];
+    }
+
+    pub fn next_preference(&mut self) {
+        self.preference_selection = (self.preference_selection + 1) % PREFERENCES.len();
+    }
+
+    pub fn previous_preference(&mut self) {
+        self.preference_selection = if self.preference_selection == 0 {
+            PREFERENCES.len() - 1
+        } else {
+            self.preference_selection - 1
+        };
+    }
+
+    pub fn cycle_icon(&mut self, forward: bool) {
+        let current = ICONS.iter().position(|&i| i == self.new_icon);
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % ICONS.len(),
+            (Some(i), false) => (i + ICONS.len() - 1) % ICONS.len(),
+            // an icon from before the carousel existed, start from the top
+            (None, _) => 0,
         };
+        self.new_icon = ICONS[next].to_string();
     }
 
     pub fn next_theme(&mut self) {
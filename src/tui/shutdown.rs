@@ -0,0 +1,134 @@
+use crate::app::app_state::AppState;
+use crate::config::{Config, ConfigError};
+use crossterm::{
+    cursor::{SetCursorStyle, Show},
+    event::{DisableBracketedPaste, EnableBracketedPaste},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::io::Write;
+use std::panic::PanicHookInfo;
+use std::sync::Arc;
+use std::thread::ThreadId;
+use tokio::sync::Mutex;
+
+pub type PanicHook = Box<dyn Fn(&PanicHookInfo<'_>) + Send + Sync + 'static>;
+
+/// Undoes what `main` set up so the shell is usable again. Safe to call more than once.
+pub fn restore_terminal() -> std::io::Result<()> {
+    restore_terminal_on(&mut std::io::stdout())
+}
+
+/// `restore_terminal` writing its escapes to `out` instead of stdout.
+pub fn restore_terminal_on(out: &mut impl Write) -> std::io::Result<()> {
+    disable_raw_mode()?;
+    execute!(
+        out,
+        DisableBracketedPaste,
+        LeaveAlternateScreen,
+        SetCursorStyle::DefaultUserShape,
+        Show
+    )
+}
+
+/// Takes the terminal back after `restore_terminal`, for when something else (an editor) had
+/// it for a while. The screen needs a full redraw after this.
+pub fn resume_terminal() -> std::io::Result<()> {
+    enable_raw_mode()?;
+    execute!(std::io::stdout(), EnterAlternateScreen, EnableBracketedPaste)
+}
+
+/// Only a logged in session has something worth keeping, saving before that would just
+/// write the defaults out.
+pub fn save_config_on_exit(config: &Config) -> Result<(), ConfigError> {
+    if config.token.is_some() {
+        crate::config::save_config(config)
+    } else {
+        Ok(())
+    }
+}
+
+/// Saves the config file from the panic hook. It has to be quick and can't wait: the
+/// panicking code may still hold the state (the lock would never come back) and a keyring
+/// call could hang, so this skips the save when the state is busy and writes the plain file
+/// only. A keyring token is already in the keyring since the login.
+pub fn save_config_on_panic(app_state: &Mutex<AppState>) -> Result<(), ConfigError> {
+    let Ok(state) = app_state.try_lock() else {
+        return Ok(());
+    };
+    if state.config.token.is_none() {
+        return Ok(());
+    }
+    crate::config::save_config_file_to(&state.config, &crate::config::get_config_path()?)
+}
+
+/// A panic on `ui_thread` runs `save`, restores the terminal through `terminal`, then runs
+/// `previous`, so the message lands on the normal screen instead of the alternate one that's
+/// about to be thrown away. Other threads' panics only go to `previous`, the UI is still up.
+pub fn panic_hook<W: Write>(
+    previous: PanicHook,
+    ui_thread: ThreadId,
+    save: impl Fn() + Send + Sync + 'static,
+    terminal: impl Fn() -> W + Send + Sync + 'static,
+) -> PanicHook {
+    Box::new(move |info| {
+        if std::thread::current().id() == ui_thread {
+            save();
+            let _ = restore_terminal_on(&mut terminal());
+        }
+        previous(info);
+    })
+}
+
+/// Saves what it can and gives the terminal back when the UI thread (the caller) panics,
+/// then ends the app, nothing is left to draw it. A panicking background task just loses its
+/// task, tokio reports it like any other panic.
+pub fn install_panic_hook(app_state: Arc<Mutex<AppState>>) {
+    let ui_thread = std::thread::current().id();
+    let save = move || {
+        // nowhere to report this, the message below is about the panic
+        let _ = save_config_on_panic(&app_state);
+    };
+    let hook = panic_hook(std::panic::take_hook(), ui_thread, save, std::io::stdout);
+    std::panic::set_hook(Box::new(move |info| {
+        hook(info);
+        if std::thread::current().id() == ui_thread {
+            std::process::exit(101);
+        }
+    }));
+}
+
+/// Ctrl+C only reaches us as a key while in raw mode, so these are `kill`s and closed
+/// terminals. Same cleanup as a normal exit, then the usual 128 + signal exit code.
+pub async fn handle_signals(app_state: Arc<Mutex<AppState>>) {
+    let code = wait_for_signal().await;
+    let save_result = save_config_on_exit(&app_state.lock().await.config);
+    let _ = restore_terminal();
+    if let Err(e) = save_result {
+        eprintln!("Couldn't save the config: {}", e);
+    }
+    std::process::exit(code);
+}
+
+#[cfg(unix)]
+async fn wait_for_signal() -> i32 {
+    use tokio::signal::unix::{signal, SignalKind};
+    let (Ok(mut terminate), Ok(mut hangup)) = (
+        signal(SignalKind::terminate()),
+        signal(SignalKind::hangup()),
+    ) else {
+        let _ = tokio::signal::ctrl_c().await;
+        return 130;
+    };
+    tokio::select! {
+        _ = tokio::signal::ctrl_c() => 130,
+        _ = terminate.recv() => 143,
+        _ = hangup.recv() => 129,
+    }
+}
+
+#[cfg(not(unix))]
+async fn wait_for_signal() -> i32 {
+    let _ = tokio::signal::ctrl_c().await;
+    130
+}
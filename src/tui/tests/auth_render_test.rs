@@ -0,0 +1,71 @@
+use crate::app::app_state::AppState;
+use crate::tui::auth::page::draw_auth_ui;
+use crate::tui::auth::state::{AuthMode, AuthState, SelectedField};
+use crate::tui::settings::state::SettingsState;
+use crate::tui::tests::harness::{contains_text, render};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+
+fn render_auth(width: u16, height: u16, auth: &AuthState) -> Buffer {
+    let mut state = AppState::default();
+    render(width, height, &mut state, |f, state| {
+        let theme = state.current_theme.clone();
+        let mut settings_state = SettingsState::new(
+            state.themes.keys().cloned().collect(),
+            state.current_theme.name,
+            "",
+            "",
+            state.settings_main_selection,
+            state.settings_focused_pane,
+            state.quit_confirmation_state,
+            state.quit_selection,
+            state.disconnect_confirmation_state,
+            state.disconnect_selection,
+        );
+        draw_auth_ui::<TestBackend>(
+            f,
+            &auth.username_input,
+            &auth.password_input,
+            auth.selected_icon_index,
+            &auth.current_mode,
+            &auth.selected_field,
+            &theme,
+            state,
+            &mut settings_state,
+        );
+    })
+}
+
+#[test]
+fn test_login_form_shows_its_fields_and_hides_the_password() {
+    let mut auth = AuthState::new();
+    auth.current_mode = AuthMode::Login;
+    auth.selected_field = SelectedField::Username;
+    for c in "ree".chars() {
+        auth.username_input.insert_char(c);
+    }
+    for c in "hunter2".chars() {
+        auth.password_input.insert_char(c);
+    }
+    let buffer = render_auth(100, 40, &auth);
+
+    assert!(contains_text(&buffer, "Username"));
+    assert!(contains_text(&buffer, "Password"));
+    assert!(contains_text(&buffer, "Login"));
+    assert!(contains_text(&buffer, "ree"));
+    assert!(!contains_text(&buffer, "hunter2"));
+}
+
+#[test]
+fn test_register_form_has_the_icon_picker() {
+    let buffer = render_auth(100, 40, &AuthState::new());
+    assert!(contains_text(&buffer, "Icon"));
+    assert!(contains_text(&buffer, "Register"));
+}
+
+#[test]
+fn test_auth_page_survives_tiny_terminals() {
+    for (width, height) in [(1, 1), (12, 5), (30, 10)] {
+        render_auth(width, height, &AuthState::new());
+    }
+}
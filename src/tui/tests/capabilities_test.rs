@@ -0,0 +1,71 @@
+use crate::config::{Config, TrueColorMode};
+use crate::themes::{nerd_font, truecolor};
+use crate::tui::capabilities::{has_nerd_font, truecolor_from, TerminalCapabilities};
+
+#[test]
+fn test_truecolor_comes_from_colorterm() {
+    assert!(truecolor_from(Some("truecolor")));
+    assert!(truecolor_from(Some("24bit")));
+    assert!(truecolor_from(Some("TrueColor")));
+    assert!(!truecolor_from(Some("256color")));
+    // most terminals don't say, and most of them do it
+    assert!(truecolor_from(Some("")));
+    assert!(truecolor_from(None));
+}
+
+#[test]
+fn test_truecolor_setting_overrides_the_detection() {
+    let mut capabilities = TerminalCapabilities::default();
+    let mut config = Config {
+        truecolor: TrueColorMode::Off,
+        ..Config::default()
+    };
+    capabilities.apply(&config);
+    assert!(!truecolor());
+
+    capabilities.truecolor = false;
+    config.truecolor = TrueColorMode::On;
+    capabilities.apply(&config);
+    assert!(truecolor());
+
+    config.truecolor = TrueColorMode::Auto;
+    capabilities.apply(&config);
+    assert!(!truecolor());
+}
+
+#[test]
+fn test_nerd_font_is_found_in_fc_list_output() {
+    let fonts = "/usr/share/fonts/DejaVuSans.ttf: DejaVu Sans:style=Book\n\
+                 /home/ree/.fonts/JetBrainsMonoNerdFont.ttf: JetBrainsMono Nerd Font:style=Regular\n";
+    assert!(has_nerd_font(fonts));
+    assert!(!has_nerd_font(
+        "/usr/share/fonts/DejaVuSans.ttf: DejaVu Sans:style=Book\n"
+    ));
+    assert!(!has_nerd_font(""));
+}
+
+#[test]
+fn test_icon_fallback_can_be_turned_off_for_undetected_fonts() {
+    let capabilities = TerminalCapabilities {
+        truecolor: true,
+        nerd_font: Some(false),
+    };
+    let mut config = Config::default();
+    capabilities.apply(&config);
+    assert!(!nerd_font());
+
+    config.icon_fallback = false;
+    capabilities.apply(&config);
+    assert!(nerd_font());
+}
+
+#[test]
+fn test_icons_stay_when_the_font_probe_cant_tell() {
+    // no fontconfig on Windows and most macs, that says nothing about the font
+    let capabilities = TerminalCapabilities {
+        truecolor: true,
+        nerd_font: None,
+    };
+    capabilities.apply(&Config::default());
+    assert!(nerd_font());
+}
@@ -0,0 +1,25 @@
+use crate::config::CursorShape;
+use crate::tui::cursor_style::cursor_style;
+use crossterm::Command;
+
+fn escape(shape: CursorShape, blink: bool) -> String {
+    let mut ansi = String::new();
+    cursor_style(shape, blink).write_ansi(&mut ansi).unwrap();
+    ansi
+}
+
+#[test]
+fn test_each_shape_has_a_blinking_and_a_steady_escape() {
+    assert_eq!(escape(CursorShape::Block, true), "\x1b[1 q");
+    assert_eq!(escape(CursorShape::Block, false), "\x1b[2 q");
+    assert_eq!(escape(CursorShape::Underline, true), "\x1b[3 q");
+    assert_eq!(escape(CursorShape::Underline, false), "\x1b[4 q");
+    assert_eq!(escape(CursorShape::Bar, true), "\x1b[5 q");
+    assert_eq!(escape(CursorShape::Bar, false), "\x1b[6 q");
+}
+
+#[test]
+fn test_the_terminal_default_ignores_blink() {
+    assert_eq!(escape(CursorShape::Default, true), "\x1b[0 q");
+    assert_eq!(escape(CursorShape::Default, false), "\x1b[0 q");
+}
@@ -0,0 +1,97 @@
+// Renders into a `TestBackend` so tests can look at what ended up on screen, and builds the
+// messages and chat state they render.
+
+use crate::api::models::{BroadcastMessage, Channel};
+use crate::app::app_state::AppState;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::{Frame, Terminal};
+
+/// One frame of `draw` on a `width` x `height` terminal.
+pub fn render(
+    width: u16,
+    height: u16,
+    state: &mut AppState,
+    draw: impl FnOnce(&mut Frame, &mut AppState),
+) -> Buffer {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| draw(f, state)).unwrap();
+    terminal.backend().buffer().clone()
+}
+
+/// Each row of the buffer as text, the cells after a wide character included as blanks.
+pub fn buffer_lines(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    (area.top()..area.bottom())
+        .map(|y| {
+            (area.left()..area.right())
+                .map(|x| buffer[(x, y)].symbol())
+                .collect()
+        })
+        .collect()
+}
+
+/// Where `text` starts on screen, searching row by row. Only for text without wide
+/// characters before it on its row, the column is counted in cells.
+pub fn find_text(buffer: &Buffer, text: &str) -> Option<(u16, u16)> {
+    buffer_lines(buffer)
+        .iter()
+        .enumerate()
+        .find_map(|(y, line)| {
+            let byte = line.find(text)?;
+            let column = line[..byte].chars().count();
+            Some((
+                buffer.area.left() + column as u16,
+                buffer.area.top() + y as u16,
+            ))
+        })
+}
+
+pub fn contains_text(buffer: &Buffer, text: &str) -> bool {
+    find_text(buffer, text).is_some()
+}
+
+/// ree saying "hi" in #home at 0, with `fields` set over that.
+pub fn message(fields: serde_json::Value) -> BroadcastMessage {
+    let mut base = serde_json::json!({
+        "client_id": "c1",
+        "user": "ree",
+        "icon": "",
+        "content": "hi",
+        "timestamp": 0,
+        "channel_id": "home",
+        "channel_name": "home",
+    });
+    base.as_object_mut()
+        .unwrap()
+        .extend(fields.as_object().unwrap().clone());
+    serde_json::from_value(base).unwrap()
+}
+
+/// A message the server has stored as `id`, this client knows it as `client-{id}`.
+pub fn sent_message(id: &str) -> BroadcastMessage {
+    message(serde_json::json!({
+        "id": id,
+        "client_id": format!("client-{}", id),
+        "content": format!("message {}", id),
+    }))
+}
+
+pub fn home() -> Channel {
+    Channel {
+        id: "home".to_string(),
+        name: "home".to_string(),
+        icon: "".to_string(),
+        pending: false,
+    }
+}
+
+/// A state looking at #home, with `messages` loaded there.
+pub fn home_with(messages: impl IntoIterator<Item = BroadcastMessage>) -> AppState {
+    let mut state = AppState::default();
+    state.set_current_channel(home());
+    for message in messages {
+        state.add_message(message);
+    }
+    state
+}
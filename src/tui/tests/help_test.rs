@@ -0,0 +1,36 @@
+use crate::tui::help::state::HelpState;
+
+fn help_state(total_pages: usize) -> HelpState {
+    HelpState {
+        total_pages,
+        ..HelpState::default()
+    }
+}
+
+#[test]
+fn test_arrows_move_both_ways_without_leaving_the_tutorial() {
+    let mut state = help_state(3);
+    state.previous_page();
+    assert_eq!(state.current_page, 0);
+    state.forward_page();
+    state.forward_page();
+    state.forward_page();
+    assert_eq!(state.current_page, 2);
+    state.previous_page();
+    assert_eq!(state.current_page, 1);
+}
+
+#[test]
+fn test_scroll_is_clamped_and_reset_on_page_change() {
+    let mut state = help_state(3);
+    state.set_max_scroll(4);
+    state.scroll_by(10);
+    assert_eq!(state.scroll_offset, 4);
+    state.scroll_by(-1);
+    assert_eq!(state.scroll_offset, 3);
+    // a bigger terminal needs less scrolling
+    state.set_max_scroll(1);
+    assert_eq!(state.scroll_offset, 1);
+    state.forward_page();
+    assert_eq!(state.scroll_offset, 0);
+}
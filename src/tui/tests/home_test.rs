@@ -0,0 +1,52 @@
+use crate::app::{AppState, TuiPage};
+use crate::config::Config;
+use crate::themes::rgb_to_color;
+use crate::tui::home::events::home_key_target;
+use crate::tui::home::page::{themed_frame_lines, ANIMATION_FRAMES};
+
+#[test]
+fn test_every_frame_is_painted_with_the_title_gradient() {
+    let theme = AppState::default().current_theme;
+    let start = rgb_to_color(&theme.colors.title_gradient_start);
+    for frame in ANIMATION_FRAMES {
+        let lines = themed_frame_lines(frame, &theme, None);
+        assert_eq!(lines.len(), frame.lines().count());
+        let first_span = lines
+            .iter()
+            .flat_map(|line| line.spans.iter())
+            .next()
+            .expect("frames are never empty");
+        assert_eq!(first_span.style.fg, Some(start));
+    }
+}
+
+#[test]
+fn test_q_quits_and_everything_else_continues() {
+    use crossterm::event::KeyCode;
+    assert_eq!(
+        home_key_target(KeyCode::Char('q'), TuiPage::Chat),
+        TuiPage::Exit
+    );
+    assert_eq!(
+        home_key_target(KeyCode::Char('Q'), TuiPage::Chat),
+        TuiPage::Exit
+    );
+    assert_eq!(
+        home_key_target(KeyCode::Enter, TuiPage::Chat),
+        TuiPage::Chat
+    );
+    assert_eq!(
+        home_key_target(KeyCode::Char('x'), TuiPage::Auth),
+        TuiPage::Auth
+    );
+}
+
+#[test]
+fn test_returning_users_skip_the_tutorial() {
+    let mut config = Config::default();
+    assert_eq!(TuiPage::start_page(&config), TuiPage::Help);
+    config.tutorial_seen = true;
+    assert_eq!(TuiPage::start_page(&config), TuiPage::Auth);
+    config.token = Some("abc".to_string());
+    assert_eq!(TuiPage::start_page(&config), TuiPage::Chat);
+}
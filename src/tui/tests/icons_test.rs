@@ -0,0 +1,34 @@
+use crate::themes::{set_nerd_font, ThemeName};
+use crate::tui::icons::{icon_or, plain_symbol};
+
+#[test]
+fn test_nerd_font_glyphs_become_plain_characters() {
+    assert_eq!(plain_symbol("\u{f0b79}"), "*");
+    assert_eq!(plain_symbol("\u{f121}"), "*");
+    assert_eq!(plain_symbol("\u{f0b79} Ree"), "* Ree");
+    // emoji and everything else are left alone, and not copied
+    assert_eq!(plain_symbol("😀"), "😀");
+    assert!(matches!(plain_symbol("a"), std::borrow::Cow::Borrowed("a")));
+}
+
+#[test]
+fn test_known_glyphs_get_a_meaningful_stand_in() {
+    assert_eq!(plain_symbol("\u{f057}"), "x");
+    assert_eq!(plain_symbol("\u{f06a}"), "!");
+    assert_eq!(plain_symbol("\u{e0b6}file\u{e0b4}"), "(file)");
+}
+
+#[test]
+fn test_icons_fall_back_only_without_a_nerd_font() {
+    // the switch is per thread in tests
+    assert_eq!(icon_or("\u{f0b79}", "#"), "\u{f0b79}");
+    set_nerd_font(false);
+    let channel = icon_or("\u{f0b79}", "#");
+    let emoji = icon_or("🐸", "#");
+    let theme = ThemeName::Nord.icon().to_string();
+    set_nerd_font(true);
+
+    assert_eq!(channel, "#");
+    assert_eq!(emoji, "🐸");
+    assert_eq!(theme, ThemeName::Nord.fallback_label());
+}
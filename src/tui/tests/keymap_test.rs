@@ -0,0 +1,62 @@
+use crate::tui::chat::palette::PALETTE_ENTRIES;
+use crate::tui::keymap::{all_bindings, filtered_bindings, KeyScope, KeybindingsOverlay};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+fn key(code: KeyCode) -> KeyEvent {
+    KeyEvent::new(code, KeyModifiers::NONE)
+}
+
+#[test]
+fn test_every_palette_shortcut_is_listed_once() {
+    let bindings = all_bindings();
+    for entry in PALETTE_ENTRIES.iter().filter(|e| !e.shortcut.is_empty()) {
+        let count = bindings.iter().filter(|b| b.keys == entry.shortcut).count();
+        assert!(count >= 1, "{} missing", entry.shortcut);
+    }
+    let settings = bindings.iter().filter(|b| b.keys == "Ctrl+S").count();
+    assert_eq!(settings, 1);
+}
+
+#[test]
+fn test_filter_matches_all_words_and_keeps_scopes_together() {
+    let downloads = filtered_bindings("ctrl+d");
+    assert!(downloads
+        .iter()
+        .all(|b| b.keys.to_lowercase().contains("ctrl+d")));
+    assert!(!downloads.is_empty());
+
+    let channel = filtered_bindings("channel move");
+    assert_eq!(channel.len(), 1);
+    assert_eq!(channel[0].scope, KeyScope::ChannelList);
+
+    assert!(filtered_bindings("nothing binds this").is_empty());
+
+    let all = filtered_bindings("");
+    assert!(all.windows(2).all(|w| w[0].scope as u8 <= w[1].scope as u8));
+}
+
+#[test]
+fn test_question_mark_only_opens_where_allowed() {
+    let mut overlay = KeybindingsOverlay::default();
+    assert!(!overlay.handle_key(&key(KeyCode::Char('?')), false));
+    assert!(!overlay.open);
+    assert!(overlay.handle_key(&key(KeyCode::Char('?')), true));
+    assert!(overlay.open);
+    overlay.handle_key(&key(KeyCode::Esc), true);
+    assert!(!overlay.open);
+    assert!(overlay.handle_key(&key(KeyCode::F(1)), false));
+    assert!(overlay.open);
+}
+
+#[test]
+fn test_typing_filters_and_resets_scroll() {
+    let mut overlay = KeybindingsOverlay::default();
+    overlay.handle_key(&key(KeyCode::F(1)), false);
+    overlay.handle_key(&key(KeyCode::PageDown), false);
+    assert_eq!(overlay.scroll, 10);
+    overlay.handle_key(&key(KeyCode::Char('?')), false);
+    assert_eq!(overlay.query, "?");
+    assert_eq!(overlay.scroll, 0);
+    overlay.handle_key(&key(KeyCode::Backspace), false);
+    assert!(overlay.query.is_empty());
+}
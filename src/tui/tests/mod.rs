@@ -0,0 +1,12 @@
+pub mod auth_render_test;
+pub mod capabilities_test;
+pub mod cursor_style_test;
+pub mod harness;
+pub mod help_test;
+pub mod home_test;
+pub mod icons_test;
+pub mod keymap_test;
+pub mod no_color_test;
+pub mod settings_render_test;
+pub mod shutdown_test;
+pub mod utils_test;
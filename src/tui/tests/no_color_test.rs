@@ -0,0 +1,87 @@
+use crate::app::app_state::AppState;
+use crate::themes::set_no_color;
+use crate::tui::auth::page::draw_auth_ui;
+use crate::tui::auth::state::AuthState;
+use crate::tui::help::page::render_help_page;
+use crate::tui::settings::render_settings_popup;
+use crate::tui::settings::state::SettingsState;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use ratatui::Terminal;
+
+fn settings_state(state: &AppState) -> SettingsState {
+    SettingsState::new(
+        state.themes.keys().cloned().collect(),
+        state.current_theme.name,
+        "",
+        "",
+        state.settings_main_selection,
+        state.settings_focused_pane,
+        state.quit_confirmation_state,
+        state.quit_selection,
+        state.disconnect_confirmation_state,
+        state.disconnect_selection,
+    )
+}
+
+fn colored_cells(buffer: &Buffer) -> usize {
+    buffer
+        .content()
+        .iter()
+        .filter(|cell| cell.fg != Color::Reset || cell.bg != Color::Reset)
+        .count()
+}
+
+// the switch is per thread in tests, so this doesn't leak into the others
+fn render_without_color(draw: impl FnOnce(&mut ratatui::Frame, &mut AppState)) -> Buffer {
+    set_no_color(true);
+    let mut state = AppState::default();
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    terminal.draw(|f| draw(f, &mut state)).unwrap();
+    set_no_color(false);
+    terminal.backend().buffer().clone()
+}
+
+#[test]
+fn test_auth_page_has_no_colors() {
+    let buffer = render_without_color(|f, state| {
+        let auth = AuthState::new();
+        let theme = state.current_theme.clone();
+        let mut settings = settings_state(state);
+        draw_auth_ui::<TestBackend>(
+            f,
+            &auth.username_input,
+            &auth.password_input,
+            auth.selected_icon_index,
+            &auth.current_mode,
+            &auth.selected_field,
+            &theme,
+            state,
+            &mut settings,
+        );
+    });
+    assert_eq!(colored_cells(&buffer), 0);
+}
+
+#[test]
+fn test_help_and_settings_have_no_colors() {
+    let buffer = render_without_color(|f, state| render_help_page(f, state, f.area()));
+    assert_eq!(colored_cells(&buffer), 0);
+
+    let buffer = render_without_color(|f, state| {
+        let mut settings = settings_state(state);
+        render_settings_popup::<TestBackend>(f, state, &mut settings, f.area()).unwrap();
+    });
+    assert_eq!(colored_cells(&buffer), 0);
+}
+
+#[test]
+fn test_colors_are_back_once_turned_off() {
+    let mut state = AppState::default();
+    let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+    terminal
+        .draw(|f| render_help_page(f, &mut state, f.area()))
+        .unwrap();
+    assert!(colored_cells(terminal.backend().buffer()) > 0);
+}
@@ -0,0 +1,90 @@
+use crate::app::app_state::AppState;
+use crate::tui::settings::state::{FocusedPane, SettingsScreen, SettingsState, SETTINGS_SCREENS};
+use crate::tui::settings::{handle_settings_key_event, render_settings_popup, SettingsEvent};
+use crate::tui::tests::harness::{buffer_lines, contains_text, render};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+
+fn settings_on(
+    state: &AppState,
+    screen: SettingsScreen,
+    focused_pane: FocusedPane,
+) -> SettingsState {
+    let selection = SETTINGS_SCREENS.iter().position(|&s| s == screen).unwrap();
+    SettingsState::new(
+        state.themes.keys().cloned().collect(),
+        state.current_theme.name,
+        "ree",
+        "",
+        selection,
+        focused_pane,
+        state.quit_confirmation_state,
+        state.quit_selection,
+        state.disconnect_confirmation_state,
+        state.disconnect_selection,
+    )
+}
+
+fn press(code: KeyCode) -> SettingsEvent {
+    SettingsEvent::Key(Event::Key(KeyEvent::new(code, KeyModifiers::NONE)))
+}
+
+#[test]
+fn test_every_settings_screen_renders_with_its_title() {
+    for (selection, screen) in SETTINGS_SCREENS.iter().enumerate() {
+        let mut state = AppState::default();
+        let buffer = render(160, 60, &mut state, |f, state| {
+            let mut settings_state = SettingsState::new(
+                state.themes.keys().cloned().collect(),
+                state.current_theme.name,
+                "ree",
+                "",
+                selection,
+                FocusedPane::Left,
+                state.quit_confirmation_state,
+                state.quit_selection,
+                state.disconnect_confirmation_state,
+                state.disconnect_selection,
+            );
+            render_settings_popup::<TestBackend>(f, state, &mut settings_state, f.area()).unwrap();
+        });
+
+        // the glyph in front of the label is left out, its width depends on the font
+        let title = screen.label().split_once(' ').unwrap().1.trim();
+        let lines = buffer_lines(&buffer);
+        let occurrences = lines.iter().filter(|line| line.contains(title)).count();
+        // once in the menu and once more as the title of the selected pane
+        assert!(occurrences >= 2, "{:?} is missing its pane title", screen);
+    }
+}
+
+#[tokio::test]
+async fn test_question_mark_opens_the_keybindings_in_settings() {
+    let mut state = AppState::default();
+    let mut settings_state = settings_on(&state, SettingsScreen::Themes, FocusedPane::Left);
+    let page =
+        handle_settings_key_event(press(KeyCode::Char('?')), &mut state, &mut settings_state).await;
+    assert_eq!(page, None);
+    assert!(state.keybindings.open);
+    let buffer = render(160, 60, &mut state, |f, state| {
+        render_settings_popup::<TestBackend>(f, state, &mut settings_state, f.area()).unwrap();
+    });
+    assert!(contains_text(&buffer, "Keybindings"));
+
+    // Esc closes the reference, not the settings
+    let page =
+        handle_settings_key_event(press(KeyCode::Esc), &mut state, &mut settings_state).await;
+    assert_eq!(page, None);
+    assert!(!state.keybindings.open);
+}
+
+#[tokio::test]
+async fn test_question_mark_is_typed_into_settings_fields() {
+    let mut state = AppState::default();
+    let mut settings_state = settings_on(&state, SettingsScreen::Profile, FocusedPane::Right);
+    handle_settings_key_event(press(KeyCode::Char('?')), &mut state, &mut settings_state).await;
+    assert!(!state.keybindings.open);
+
+    handle_settings_key_event(press(KeyCode::F(1)), &mut state, &mut settings_state).await;
+    assert!(state.keybindings.open);
+}
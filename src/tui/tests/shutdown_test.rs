@@ -0,0 +1,116 @@
+use crate::app::app_state::AppState;
+use crate::config::Config;
+use crate::tui::shutdown::{panic_hook, save_config_on_exit, save_config_on_panic, PanicHook};
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+// the panic hook is global, tests swapping it can't overlap
+static HOOK_LOCK: Mutex<()> = Mutex::new(());
+
+/// What the hook wrote to "the terminal", shared with the test.
+#[derive(Clone, Default)]
+struct Screen(Arc<Mutex<Vec<u8>>>);
+
+impl Screen {
+    fn restored(&self) -> bool {
+        let written = self.0.lock().unwrap();
+        // LeaveAlternateScreen
+        written.windows(8).any(|w| w == b"\x1b[?1049l")
+    }
+}
+
+impl Write for Screen {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn panic_with(hook: PanicHook, on_thread: bool) -> bool {
+    let _guard = HOOK_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let original = std::panic::take_hook();
+    std::panic::set_hook(hook);
+    let panicked = if on_thread {
+        std::thread::spawn(|| {
+            let nested = || -> u8 { panic!("forced in a thread") };
+            nested()
+        })
+        .join()
+        .is_err()
+    } else {
+        std::panic::catch_unwind(|| panic!("forced")).is_err()
+    };
+    std::panic::set_hook(original);
+    panicked
+}
+
+#[test]
+fn test_a_ui_panic_saves_then_restores_the_terminal_before_the_message() {
+    let screen = Screen::default();
+    let saved_first = Arc::new(AtomicBool::new(false));
+    let restored_first = Arc::new(AtomicBool::new(false));
+    let save = {
+        let (screen, saved_first) = (screen.clone(), saved_first.clone());
+        move || saved_first.store(!screen.restored(), Ordering::SeqCst)
+    };
+    let previous: PanicHook = {
+        let (screen, restored_first) = (screen.clone(), restored_first.clone());
+        Box::new(move |_| restored_first.store(screen.restored(), Ordering::SeqCst))
+    };
+    let terminal = screen.clone();
+    let hook = panic_hook(previous, std::thread::current().id(), save, move || {
+        terminal.clone()
+    });
+
+    assert!(panic_with(hook, false));
+    assert!(screen.restored());
+    assert!(saved_first.load(Ordering::SeqCst));
+    assert!(restored_first.load(Ordering::SeqCst));
+}
+
+#[test]
+fn test_a_background_panic_leaves_the_terminal_alone() {
+    // background tasks panic on worker threads while the UI keeps running
+    let screen = Screen::default();
+    let printed = Arc::new(AtomicBool::new(false));
+    let previous: PanicHook = {
+        let printed = printed.clone();
+        Box::new(move |_| printed.store(true, Ordering::SeqCst))
+    };
+    let saved = Arc::new(AtomicBool::new(false));
+    let save = {
+        let saved = saved.clone();
+        move || saved.store(true, Ordering::SeqCst)
+    };
+    let terminal = screen.clone();
+    let hook = panic_hook(previous, std::thread::current().id(), save, move || {
+        terminal.clone()
+    });
+
+    assert!(panic_with(hook, true));
+    assert!(printed.load(Ordering::SeqCst));
+    assert!(!saved.load(Ordering::SeqCst));
+    assert!(!screen.restored());
+}
+
+#[test]
+fn test_logged_out_sessions_dont_write_the_config() {
+    // no token means nothing is saved, so this can't touch the real config file
+    assert!(save_config_on_exit(&Config::default()).is_ok());
+}
+
+#[test]
+fn test_a_panic_while_the_state_is_locked_skips_the_save() {
+    // the panicking code can be the one holding the state, waiting would never end. The
+    // token would make it save for real, the lock being taken is what keeps it from that.
+    let mut state = AppState::default();
+    state.config.token = Some("secret".to_string());
+    let app_state = tokio::sync::Mutex::new(state);
+    let _held = app_state.try_lock().unwrap();
+    assert!(save_config_on_panic(&app_state).is_ok());
+}
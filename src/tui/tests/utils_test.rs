@@ -0,0 +1,59 @@
+use crate::tui::utils::{format_file_size, format_relative_time, sanitize_paste, truncate_to_width};
+
+#[test]
+fn test_bytes_stay_whole() {
+    assert_eq!(format_file_size(0), "󰋊 0 B");
+    assert_eq!(format_file_size(1023), "󰋊 1023 B");
+}
+
+#[test]
+fn test_exactly_1024_is_a_kib() {
+    assert_eq!(format_file_size(1024), "󰋊 1.00 KiB");
+    assert_eq!(format_file_size(1536), "󰋊 1.50 KiB");
+}
+
+#[test]
+fn test_unit_boundaries() {
+    const KIB: u64 = 1024;
+    assert_eq!(format_file_size(KIB * KIB - 1), "󰋊 1024.00 KiB");
+    assert_eq!(format_file_size(KIB * KIB), "󰋊 1.00 MiB");
+    assert_eq!(format_file_size(KIB.pow(3)), "󰋊 1.00 GiB");
+    assert_eq!(format_file_size(5 * KIB.pow(3) / 2), "󰋊 2.50 GiB");
+    assert_eq!(format_file_size(KIB.pow(4)), "󰋊 1.00 TiB");
+    assert_eq!(format_file_size(3 * KIB.pow(4)), "󰋊 3.00 TiB");
+}
+
+#[test]
+fn test_sanitize_paste_drops_control_characters() {
+    assert_eq!(sanitize_paste("a\x1b[31mb\x07", true), "a[31mb");
+    assert_eq!(
+        sanitize_paste("one\r\ntwo\rthree\n", true),
+        "one\ntwo\nthree\n"
+    );
+    assert_eq!(sanitize_paste("one\r\ntwo\tthree", false), "one two three");
+}
+
+#[test]
+fn test_relative_time_rounds_down_to_the_unit() {
+    assert_eq!(format_relative_time(0), "just now");
+    assert_eq!(format_relative_time(59), "just now");
+    assert_eq!(format_relative_time(60), "1 min ago");
+    assert_eq!(format_relative_time(3599), "59 min ago");
+    assert_eq!(format_relative_time(3600 * 5 + 59), "5 h ago");
+    assert_eq!(format_relative_time(86400), "yesterday");
+    assert_eq!(format_relative_time(86400 * 9), "9 days ago");
+}
+
+#[test]
+fn test_relative_time_in_the_future_is_now() {
+    assert_eq!(format_relative_time(-30), "just now");
+}
+
+#[test]
+fn test_truncate_keeps_short_text_and_cuts_long_text_by_columns() {
+    assert_eq!(truncate_to_width("hello", 5), "hello");
+    assert_eq!(truncate_to_width("hello world", 6), "hello…");
+    // wide glyphs take two columns each
+    assert_eq!(truncate_to_width("日本語です", 5), "日本…");
+    assert_eq!(truncate_to_width("hello", 0), "");
+}
@@ -4,6 +4,7 @@ use ratatui::{
     widgets::{Block, BorderType, Borders, Paragraph},
 };
 
+#[derive(Clone, Debug)]
 pub struct TextInput {
     pub text: String,
     pub cursor_position: usize,
@@ -64,11 +65,7 @@ impl TextInput {
             let cursor_char_pos = text_before_cursor.chars().count();
             
             let text_len_chars = self.text.chars().count();
-            let scroll_offset = if text_len_chars > text_width {
-                text_len_chars - text_width
-            } else {
-                0
-            };
+            let scroll_offset = text_len_chars.saturating_sub(text_width);
 
             let final_cursor_pos = (cursor_char_pos as u16).saturating_sub(scroll_offset as u16);
 
@@ -81,6 +78,13 @@ impl TextInput {
         self.cursor_position += c.len_utf8();
     }
 
+    /// A paste, kept on one line.
+    pub fn insert_pasted(&mut self, text: &str) {
+        let text = crate::tui::utils::sanitize_paste(text, false);
+        self.text.insert_str(self.cursor_position, &text);
+        self.cursor_position += text.len();
+    }
+
     pub fn delete_char(&mut self) {
         if self.cursor_position > 0 {
             let mut prev_char_boundary = self.cursor_position - 1;
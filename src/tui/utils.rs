@@ -0,0 +1,78 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Human readable size with binary units, the way the file manager and attachments show it.
+pub fn format_file_size(size: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = KIB * 1024;
+    const GIB: u64 = MIB * 1024;
+    const TIB: u64 = GIB * 1024;
+
+    if size < KIB {
+        format!("󰋊 {} B", size)
+    } else if size < MIB {
+        format!("󰋊 {:.2} KiB", size as f64 / KIB as f64)
+    } else if size < GIB {
+        format!("󰋊 {:.2} MiB", size as f64 / MIB as f64)
+    } else if size < TIB {
+        format!("󰋊 {:.2} GiB", size as f64 / GIB as f64)
+    } else {
+        format!("󰋊 {:.2} TiB", size as f64 / TIB as f64)
+    }
+}
+
+/// Pasted text without the control characters a terminal could act on. Line breaks become
+/// `\n` with `keep_newlines`, a space otherwise (single-line fields), and tabs a space.
+pub fn sanitize_paste(text: &str, keep_newlines: bool) -> String {
+    text.replace("\r\n", "\n")
+        .chars()
+        .filter_map(|c| match c {
+            '\n' | '\r' if keep_newlines => Some('\n'),
+            '\n' | '\r' | '\t' => Some(' '),
+            c if c.is_control() => None,
+            c => Some(c),
+        })
+        .collect()
+}
+
+/// "just now", "5 min ago", "3 h ago", "2 days ago", for a time `seconds` in the past. A
+/// clock running a bit behind the server's shouldn't give "in 3 s", so the future is now.
+pub fn format_relative_time(seconds: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = MINUTE * 60;
+    const DAY: i64 = HOUR * 24;
+
+    match seconds {
+        s if s < MINUTE => "just now".to_string(),
+        s if s < HOUR => format!("{} min ago", s / MINUTE),
+        s if s < DAY => format!("{} h ago", s / HOUR),
+        s if s < DAY * 2 => "yesterday".to_string(),
+        s => format!("{} days ago", s / DAY),
+    }
+}
+
+/// `text` with spaces after it up to `width` terminal columns, so a double-width glyph and a
+/// single-width one take the same room. Wider text is left as is.
+pub fn pad_to_width(text: &str, width: usize) -> String {
+    format!("{}{}", text, " ".repeat(width.saturating_sub(text.width())))
+}
+
+/// `text` cut to fit `width` terminal columns, with a `…` when something was dropped.
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let c_width = c.width().unwrap_or(0);
+        if used + c_width + 1 > width {
+            break;
+        }
+        used += c_width;
+        truncated.push(c);
+    }
+    if width > 0 {
+        truncated.push('…');
+    }
+    truncated
+}